@@ -0,0 +1,264 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+declare_id!("3Pbd3pK3K6XQwUkC4PiHQutLy44B1gtg9KuAswpskAw3");
+
+pub const POOL_SEED: &[u8] = b"pool";
+pub const VAULT_A_SEED: &[u8] = b"vault_a";
+pub const VAULT_B_SEED: &[u8] = b"vault_b";
+
+#[program]
+pub mod mock_amm {
+    use super::*;
+
+    /// Create a pool for a token pair with a fee taken from the input
+    pub fn initialize_pool(ctx: Context<InitializePool>, fee_bps: u16) -> Result<()> {
+        require!(fee_bps < 10000, MockAmmError::InvalidFee);
+
+        let pool = &mut ctx.accounts.pool;
+        pool.mint_a = ctx.accounts.mint_a.key();
+        pool.mint_b = ctx.accounts.mint_b.key();
+        pool.vault_a = ctx.accounts.vault_a.key();
+        pool.vault_b = ctx.accounts.vault_b.key();
+        pool.fee_bps = fee_bps;
+        pool.bump = ctx.bumps.pool;
+
+        Ok(())
+    }
+
+    /// Add reserves to both sides of the pool
+    pub fn deposit(ctx: Context<Deposit>, amount_a: u64, amount_b: u64) -> Result<()> {
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+
+        token::transfer(
+            CpiContext::new(
+                cpi_program.clone(),
+                Transfer {
+                    from: ctx.accounts.provider_token_a.to_account_info(),
+                    to: ctx.accounts.vault_a.to_account_info(),
+                    authority: ctx.accounts.provider.to_account_info(),
+                },
+            ),
+            amount_a,
+        )?;
+        token::transfer(
+            CpiContext::new(
+                cpi_program,
+                Transfer {
+                    from: ctx.accounts.provider_token_b.to_account_info(),
+                    to: ctx.accounts.vault_b.to_account_info(),
+                    authority: ctx.accounts.provider.to_account_info(),
+                },
+            ),
+            amount_b,
+        )?;
+
+        Ok(())
+    }
+
+    /// Swap an exact input along x * y = k
+    pub fn swap(ctx: Context<Swap>, amount_in: u64, min_amount_out: u64, a_to_b: bool) -> Result<()> {
+        let pool = &ctx.accounts.pool;
+        let (reserve_in, reserve_out) = if a_to_b {
+            (ctx.accounts.vault_a.amount, ctx.accounts.vault_b.amount)
+        } else {
+            (ctx.accounts.vault_b.amount, ctx.accounts.vault_a.amount)
+        };
+
+        let amount_out = quote_amount_out(reserve_in, reserve_out, amount_in, pool.fee_bps)
+            .ok_or(MockAmmError::InsufficientLiquidity)?;
+        require!(amount_out >= min_amount_out, MockAmmError::SlippageExceeded);
+
+        let (user_in, vault_in, vault_out, user_out) = if a_to_b {
+            (
+                &ctx.accounts.user_token_a,
+                &ctx.accounts.vault_a,
+                &ctx.accounts.vault_b,
+                &ctx.accounts.user_token_b,
+            )
+        } else {
+            (
+                &ctx.accounts.user_token_b,
+                &ctx.accounts.vault_b,
+                &ctx.accounts.vault_a,
+                &ctx.accounts.user_token_a,
+            )
+        };
+
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        token::transfer(
+            CpiContext::new(
+                cpi_program.clone(),
+                Transfer {
+                    from: user_in.to_account_info(),
+                    to: vault_in.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            ),
+            amount_in,
+        )?;
+
+        let mint_a = pool.mint_a;
+        let mint_b = pool.mint_b;
+        let seeds = &[POOL_SEED, mint_a.as_ref(), mint_b.as_ref(), &[pool.bump]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                cpi_program,
+                Transfer {
+                    from: vault_out.to_account_info(),
+                    to: user_out.to_account_info(),
+                    authority: ctx.accounts.pool.to_account_info(),
+                },
+                &[&seeds[..]],
+            ),
+            amount_out,
+        )?;
+
+        Ok(())
+    }
+}
+
+/// Output of an exact-input swap after the fee, or None if the pool cannot fill it
+pub fn quote_amount_out(reserve_in: u64, reserve_out: u64, amount_in: u64, fee_bps: u16) -> Option<u64> {
+    if reserve_in == 0 || reserve_out == 0 {
+        return None;
+    }
+
+    let amount_in_after_fee = amount_in as u128 * (10000 - fee_bps as u128) / 10000;
+    let amount_out = reserve_out as u128 * amount_in_after_fee / (reserve_in as u128 + amount_in_after_fee);
+
+    if amount_out == 0 || amount_out >= reserve_out as u128 {
+        return None;
+    }
+
+    Some(amount_out as u64)
+}
+
+#[account]
+pub struct Pool {
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    pub vault_a: Pubkey,
+    pub vault_b: Pubkey,
+    pub fee_bps: u16,
+    pub bump: u8,
+}
+
+impl Pool {
+    pub const LEN: usize = 8 + 32 * 4 + 2 + 1;
+}
+
+#[derive(Accounts)]
+pub struct InitializePool<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub mint_a: Account<'info, Mint>,
+    pub mint_b: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = Pool::LEN,
+        seeds = [POOL_SEED, mint_a.key().as_ref(), mint_b.key().as_ref()],
+        bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [VAULT_A_SEED, pool.key().as_ref()],
+        bump,
+        token::mint = mint_a,
+        token::authority = pool,
+    )]
+    pub vault_a: Account<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [VAULT_B_SEED, pool.key().as_ref()],
+        bump,
+        token::mint = mint_b,
+        token::authority = pool,
+    )]
+    pub vault_b: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    pub provider: Signer<'info>,
+
+    #[account(has_one = vault_a, has_one = vault_b)]
+    pub pool: Account<'info, Pool>,
+
+    #[account(mut, token::mint = pool.mint_a)]
+    pub provider_token_a: Account<'info, TokenAccount>,
+
+    #[account(mut, token::mint = pool.mint_b)]
+    pub provider_token_b: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub vault_a: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub vault_b: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct Swap<'info> {
+    pub user: Signer<'info>,
+
+    #[account(has_one = vault_a, has_one = vault_b)]
+    pub pool: Account<'info, Pool>,
+
+    #[account(mut, token::mint = pool.mint_a)]
+    pub user_token_a: Account<'info, TokenAccount>,
+
+    #[account(mut, token::mint = pool.mint_b)]
+    pub user_token_b: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub vault_a: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub vault_b: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[error_code]
+pub enum MockAmmError {
+    #[msg("Fee must be below 100%")]
+    InvalidFee,
+
+    #[msg("Pool cannot fill this swap")]
+    InsufficientLiquidity,
+
+    #[msg("Output below the minimum")]
+    SlippageExceeded,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quote_amount_out() {
+        // 1% of a balanced pool with a 30 bps fee
+        assert_eq!(quote_amount_out(1_000_000, 1_000_000, 10_000, 30), Some(9_871));
+        assert_eq!(quote_amount_out(1_000_000, 1_000_000, 10_000, 0), Some(9_900));
+
+        // Never drains the output side
+        assert!(quote_amount_out(1_000, 1_000, u64::MAX, 0).unwrap() < 1_000);
+        assert_eq!(quote_amount_out(0, 1_000, 10, 0), None);
+        assert_eq!(quote_amount_out(1_000_000, 1_000, 1, 30), None);
+    }
+}
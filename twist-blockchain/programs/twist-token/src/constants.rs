@@ -6,9 +6,16 @@ pub const TREASURY_SPLIT_BPS: u64 = 9000; // 90% to floor treasury
 pub const BUYBACK_THRESHOLD_BPS: u64 = 9700; // 97% of floor price
 pub const MIN_STAKE_PERIOD: i64 = 30 * 86400; // 30 days in seconds
 pub const MAX_STAKE_PERIOD: i64 = 365 * 86400; // 365 days
+pub const SECONDS_PER_YEAR: i64 = 365 * 86400;
 pub const DECAY_INTERVAL: i64 = 86400; // 24 hours
 pub const ORACLE_CONFIDENCE_THRESHOLD: u64 = 10000; // $0.01 confidence
 pub const ORACLE_STALENESS_THRESHOLD: i64 = 60; // 60 seconds
+pub const DEX_ORACLE_MIN_INTERVAL: i64 = 30; // Seconds between pool observations
+pub const DEX_ORACLE_TWAP_WINDOW: i64 = 1800; // 30 minute pool TWAP
+pub const DEX_ORACLE_MAX_STALENESS: i64 = 300; // Latest pool observation must be within 5 minutes
+pub const DEX_ORACLE_MAX_TICK_DELTA: i32 = 100; // ~1% price move recorded per observation
+pub const MIN_REPORT_ROUND_DURATION: i64 = 15; // Shortest reporter round
+pub const MIN_REPORTERS_PER_ROUND: u8 = 3; // Fewest reports a median can settle on
 
 // Seeds for PDA derivation
 pub const PROGRAM_STATE_SEED: &[u8] = b"program_state";
@@ -22,20 +29,64 @@ pub const BRIDGE_ESCROW_SEED: &[u8] = b"bridge_escrow";
 pub const LIQUIDITY_POSITION_SEED: &[u8] = b"liquidity_position";
 pub const PID_CONTROLLER_SEED: &[u8] = b"pid_controller";
 pub const FEE_VAULT_SEED: &[u8] = b"fee_vault";
+pub const STAKING_REWARDS_VAULT_SEED: &[u8] = b"staking_rewards_vault";
+pub const MARKET_METRICS_SEED: &[u8] = b"market_metrics";
+pub const CIRCUIT_BREAKER_LOG_SEED: &[u8] = b"circuit_breaker_log";
+pub const GOVERNANCE_SEED: &[u8] = b"governance";
+pub const GOVERNANCE_PROPOSAL_SEED: &[u8] = b"governance_proposal";
+pub const VOTE_RECORD_SEED: &[u8] = b"vote_record";
+pub const COMMIT_REVEAL_SEED: &[u8] = b"commit_reveal";
+pub const BLOCK_RESTRICTION_SEED: &[u8] = b"block_restriction";
+pub const TREASURY_STATE_SEED: &[u8] = b"treasury_state";
+pub const TREASURY_BUDGET_SEED: &[u8] = b"treasury_budget";
+pub const TREASURY_POLICY_SEED: &[u8] = b"treasury_policy";
+pub const INSURANCE_FUND_SEED: &[u8] = b"insurance_fund";
+pub const INSURANCE_VAULT_SEED: &[u8] = b"insurance_vault";
+pub const INSURANCE_CLAIM_SEED: &[u8] = b"insurance_claim";
+pub const RANGE_STRATEGY_SEED: &[u8] = b"range_strategy";
+pub const DEX_REGISTRY_SEED: &[u8] = b"dex_registry";
+pub const LP_VAULT_SEED: &[u8] = b"lp_vault";
+pub const LP_VAULT_SHARE_MINT_SEED: &[u8] = b"lp_vault_share_mint";
+pub const LP_VAULT_TWIST_SEED: &[u8] = b"lp_vault_twist";
+pub const LP_VAULT_USDC_SEED: &[u8] = b"lp_vault_usdc";
+pub const LIQUIDITY_MINING_SEED: &[u8] = b"liquidity_mining";
+pub const MINING_REWARDS_VAULT_SEED: &[u8] = b"mining_rewards_vault";
+pub const MINING_POSITION_SEED: &[u8] = b"mining_position";
+pub const DEX_ORACLE_SEED: &[u8] = b"dex_oracle";
+pub const REPORTER_NETWORK_SEED: &[u8] = b"reporter_network";
+pub const REPORTER_BOND_VAULT_SEED: &[u8] = b"reporter_bond_vault";
+pub const PRICE_REPORTER_SEED: &[u8] = b"price_reporter";
 
 // Staking APY tiers (in basis points)
 pub const APY_30_DAYS: u64 = 1000; // 10%
 pub const APY_90_DAYS: u64 = 2000; // 20%
 pub const APY_180_DAYS: u64 = 3500; // 35%
 pub const APY_365_DAYS: u64 = 6700; // 67%
+pub const FEE_REWARD_PRECISION: u128 = 1_000_000_000_000; // Fee reward accumulator scale
+pub const MINING_REWARD_PRECISION: u128 = 1_000_000_000_000_000_000; // Per-liquidity accumulator scale; pool liquidity runs far larger than stake
+pub const MAX_MINING_EMISSION_RATE: u64 = 100 * 10u64.pow(9); // 100 TWIST per second
 
 // Limits
 pub const MAX_DAILY_BUYBACK_DEFAULT: u64 = 50_000 * 1_000_000; // $50k USDC
 pub const MIN_BUYBACK_AMOUNT: u64 = 100 * 1_000_000; // $100 USDC
 pub const MAX_TRANSACTION_SIZE_EMERGENCY: u64 = 10_000 * 1_000_000; // $10k during emergency
+pub const FEE_SWAP_MAX_SLIPPAGE_BPS: u64 = 100; // 1% below oracle price for fee conversions
+pub const LIQUIDITY_DEFAULT_SLIPPAGE_BPS: u64 = 100; // 1% around computed position amounts
+pub const ROUTE_DEFAULT_PRICE_IMPACT_BPS: u64 = 100; // Split orders that would move a single pool more than 1%
+pub const MAX_ROUTE_PRICE_IMPACT_BPS: u64 = 1000; // Governance cannot accept more than 10% impact
+pub const ROUTE_SPLIT_STEPS: u64 = 10; // Order slices allocated when splitting across pools
+pub const LP_VAULT_MIN_INITIAL_DEPOSIT: u64 = 1_000_000; // $1 of value for the first vault deposit
 
 // Circuit breaker thresholds
 pub const PRICE_VOLATILITY_THRESHOLD: f64 = 0.5; // 50%
 pub const VOLUME_SPIKE_MULTIPLIER: u64 = 10; // 10x normal volume
 pub const SUPPLY_CHANGE_THRESHOLD_BPS: u64 = 200; // 2% daily change
-pub const ORACLE_DIVERGENCE_THRESHOLD_BPS: u64 = 500; // 5% divergence
\ No newline at end of file
+pub const ORACLE_DIVERGENCE_THRESHOLD_BPS: u64 = 500; // 5% divergence
+
+// Commit-reveal
+pub const COMMIT_REVEAL_MIN_DELAY: i64 = 10; // Seconds before any reveal, larger actions wait longer
+pub const COMMIT_REVEAL_WINDOW: i64 = 300; // Reveal must land within 5 minutes of becoming valid
+pub const COMMIT_REVEAL_MIN_SLOT_GAP: u64 = 2; // No two actions by one user in the same or next slot
+
+// Multisig
+pub const MULTISIG_PROPOSAL_LIFETIME: i64 = 7 * 86400; // 7 days to finalize, then 7 days to execute
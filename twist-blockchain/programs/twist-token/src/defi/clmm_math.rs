@@ -0,0 +1,1023 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::TwistError;
+
+// Concentrated liquidity math on Q64.64 sqrt prices, following the Whirlpool program's
+// tick_math and token_math so amounts computed here agree with what the pool charges.
+
+pub const MIN_TICK_INDEX: i32 = -443636;
+pub const MAX_TICK_INDEX: i32 = 443636;
+pub const MIN_SQRT_PRICE_X64: u128 = 4295048016;
+pub const MAX_SQRT_PRICE_X64: u128 = 79226673515401279992447579055;
+
+// Precision of the fractional log2 estimate in tick_index_from_sqrt_price
+const BIT_PRECISION: u32 = 14;
+// log_b(2) as Q32.32, where b = sqrt(1.0001)
+const LOG_B_2_X32: i128 = 59543866431248;
+// Error margins of the log_b estimate as Q64.64: 0.01, and 2^-precision / log2(b) + 0.01
+const LOG_B_P_ERR_MARGIN_LOWER_X64: i128 = 184467440737095516;
+const LOG_B_P_ERR_MARGIN_UPPER_X64: i128 = 15793534762490258745;
+
+/// Q64.64 sqrt price of a tick, sqrt(1.0001)^tick * 2^64
+pub fn sqrt_price_from_tick_index(tick: i32) -> Result<u128> {
+    require!(
+        (MIN_TICK_INDEX..=MAX_TICK_INDEX).contains(&tick),
+        TwistError::InvalidPriceRange
+    );
+    
+    if tick >= 0 {
+        Ok(sqrt_price_positive_tick(tick))
+    } else {
+        Ok(sqrt_price_negative_tick(tick))
+    }
+}
+
+// Products of sqrt(1.0001)^(2^i) as Q32.96, shifted down to Q64.64 at the end
+fn sqrt_price_positive_tick(tick: i32) -> u128 {
+    const FACTORS_X96: [u128; 18] = [
+        79236085330515764027303304731,
+        79244008939048815603706035061,
+        79259858533276714757314932305,
+        79291567232598584799939703904,
+        79355022692464371645785046466,
+        79482085999252804386437311141,
+        79736823300114093921829183326,
+        80248749790819932309965073892,
+        81282483887344747381513967011,
+        83390072131320151908154831281,
+        87770609709833776024991924138,
+        97234110755111693312479820773,
+        119332217159966728226237229890,
+        179736315981702064433883588727,
+        407748233172238350107850275304,
+        2098478828474011932436660412517,
+        55581415166113811149459800483533,
+        38992368544603139932233054999993551,
+    ];
+    
+    let mut ratio: u128 = if tick & 1 != 0 {
+        79232123823359799118286999567
+    } else {
+        79228162514264337593543950336
+    };
+    
+    for (i, factor) in FACTORS_X96.iter().enumerate() {
+        if tick & (2 << i) != 0 {
+            ratio = U256::mul(ratio, *factor).shr(96).lo;
+        }
+    }
+    
+    ratio >> 32
+}
+
+// Products of sqrt(1.0001)^-(2^i) as Q64.64
+fn sqrt_price_negative_tick(tick: i32) -> u128 {
+    const FACTORS_X64: [u128; 18] = [
+        18444899583751176498,
+        18443055278223354162,
+        18439367220385604838,
+        18431993317065449817,
+        18417254355718160513,
+        18387811781193591352,
+        18329067761203520168,
+        18212142134806087854,
+        17980523815641551639,
+        17526086738831147013,
+        16651378430235024244,
+        15030750278693429944,
+        12247334978882834399,
+        8131365268884726200,
+        3584323654723342297,
+        696457651847595233,
+        26294789957452057,
+        37481735321082,
+    ];
+    
+    let abs_tick = tick.abs();
+    let mut ratio: u128 = if abs_tick & 1 != 0 {
+        18445821805675392311
+    } else {
+        18446744073709551616
+    };
+    
+    for (i, factor) in FACTORS_X64.iter().enumerate() {
+        if abs_tick & (2 << i) != 0 {
+            ratio = (ratio * factor) >> 64;
+        }
+    }
+    
+    ratio
+}
+
+/// Greatest tick whose sqrt price does not exceed `sqrt_price_x64`
+pub fn tick_index_from_sqrt_price(sqrt_price_x64: u128) -> Result<i32> {
+    require!(
+        (MIN_SQRT_PRICE_X64..MAX_SQRT_PRICE_X64).contains(&sqrt_price_x64),
+        TwistError::InvalidPriceRange
+    );
+    
+    // Integer part of log2 from the most significant bit
+    let msb = 127 - sqrt_price_x64.leading_zeros();
+    let log2p_integer_x32 = (msb as i128 - 64) << 32;
+    
+    // Fractional part by repeated squaring of the normalized value (Q1.63)
+    let mut bit: i128 = 0x8000_0000_0000_0000;
+    let mut precision = 0;
+    let mut log2p_fraction_x64: i128 = 0;
+    let mut r = if msb >= 64 {
+        sqrt_price_x64 >> (msb - 63)
+    } else {
+        sqrt_price_x64 << (63 - msb)
+    };
+    
+    while bit > 0 && precision < BIT_PRECISION {
+        r *= r;
+        let is_r_more_than_two = (r >> 127) as u32;
+        r >>= 63 + is_r_more_than_two;
+        log2p_fraction_x64 += bit * is_r_more_than_two as i128;
+        bit >>= 1;
+        precision += 1;
+    }
+    
+    let log2p_x32 = log2p_integer_x32 + (log2p_fraction_x64 >> 32);
+    let logbp_x64 = log2p_x32 * LOG_B_2_X32;
+    
+    // The estimate is within the error margins, so at most two ticks are candidates
+    let tick_low = ((logbp_x64 - LOG_B_P_ERR_MARGIN_LOWER_X64) >> 64) as i32;
+    let tick_high = ((logbp_x64 + LOG_B_P_ERR_MARGIN_UPPER_X64) >> 64) as i32;
+    
+    if tick_low == tick_high || sqrt_price_from_tick_index(tick_high)? > sqrt_price_x64 {
+        Ok(tick_low)
+    } else {
+        Ok(tick_high)
+    }
+}
+
+/// Round a tick onto the pool's tick spacing, keeping it inside the valid range
+pub fn round_tick_to_spacing(tick: i32, tick_spacing: u16, round_up: bool) -> Result<i32> {
+    require!(tick_spacing > 0, TwistError::InvalidPriceRange);
+    
+    let spacing = tick_spacing as i32;
+    let mut rounded = tick.div_euclid(spacing) * spacing;
+    if round_up && rounded != tick {
+        rounded += spacing;
+    }
+    
+    let min_tick = MIN_TICK_INDEX + (-MIN_TICK_INDEX).rem_euclid(spacing);
+    let max_tick = MAX_TICK_INDEX - MAX_TICK_INDEX.rem_euclid(spacing);
+    
+    Ok(rounded.clamp(min_tick, max_tick))
+}
+
+/// Q64.64 sqrt price of a USDC price per whole token A (6 decimals), in raw token units
+pub fn sqrt_price_from_price(price: u64, decimals_a: u8) -> Result<u128> {
+    require!(price > 0, TwistError::InvalidAmount);
+    
+    // price_x128 = price * 2^128 / 10^decimals_a, then the square root is Q64.64
+    let scale = 10u128.pow(decimals_a as u32);
+    let (price_x128, _) = U256::from_u128(price as u128)
+        .shl(128)
+        .ok_or(TwistError::MathOverflow)?
+        .div_rem(U256::from_u128(scale));
+    let sqrt_price = price_x128.isqrt();
+    
+    require!(
+        (MIN_SQRT_PRICE_X64..MAX_SQRT_PRICE_X64).contains(&sqrt_price),
+        TwistError::InvalidPriceRange
+    );
+    
+    Ok(sqrt_price)
+}
+
+/// USDC price per whole token A (6 decimals) of a Q64.64 sqrt price, rounded down
+pub fn price_from_sqrt_price(sqrt_price_x64: u128, decimals_a: u8) -> Result<u64> {
+    let price = U256::mul(sqrt_price_x64, sqrt_price_x64)
+        .mul_u128(10u128.pow(decimals_a as u32))
+        .ok_or(TwistError::MathOverflow)?
+        .shr(128)
+        .to_u128()
+        .ok_or(TwistError::MathOverflow)?;
+    
+    u64::try_from(price).map_err(|_| TwistError::MathOverflow.into())
+}
+
+/// Token A owed for `liquidity` between two sqrt prices: L * (upper - lower) / (upper * lower)
+pub fn get_amount_delta_a(
+    sqrt_price_0: u128,
+    sqrt_price_1: u128,
+    liquidity: u128,
+    round_up: bool,
+) -> Result<u64> {
+    let (lower, upper) = increasing_price_order(sqrt_price_0, sqrt_price_1);
+    
+    let numerator = U256::mul(liquidity, upper - lower)
+        .shl(64)
+        .ok_or(TwistError::MathOverflow)?;
+    let denominator = U256::mul(upper, lower);
+    require!(!denominator.is_zero(), TwistError::InvalidPriceRange);
+    
+    let (quotient, remainder) = numerator.div_rem(denominator);
+    let mut amount = quotient.to_u128().ok_or(TwistError::MathOverflow)?;
+    if round_up && !remainder.is_zero() {
+        amount += 1;
+    }
+    
+    u64::try_from(amount).map_err(|_| TwistError::MathOverflow.into())
+}
+
+/// Token B owed for `liquidity` between two sqrt prices: L * (upper - lower)
+pub fn get_amount_delta_b(
+    sqrt_price_0: u128,
+    sqrt_price_1: u128,
+    liquidity: u128,
+    round_up: bool,
+) -> Result<u64> {
+    let (lower, upper) = increasing_price_order(sqrt_price_0, sqrt_price_1);
+    
+    let product = U256::mul(liquidity, upper - lower);
+    let mut amount = product.shr(64).to_u128().ok_or(TwistError::MathOverflow)?;
+    if round_up && product.lo as u64 != 0 {
+        amount += 1;
+    }
+    
+    u64::try_from(amount).map_err(|_| TwistError::MathOverflow.into())
+}
+
+/// Liquidity supplied by `amount_a` of token A between two sqrt prices, rounded down
+pub fn get_liquidity_from_amount_a(
+    sqrt_price_0: u128,
+    sqrt_price_1: u128,
+    amount_a: u64,
+) -> Result<u128> {
+    let (lower, upper) = increasing_price_order(sqrt_price_0, sqrt_price_1);
+    require!(upper > lower, TwistError::InvalidPriceRange);
+    
+    // amount * lower * upper / (upper - lower), with one Q64 factor removed
+    let (liquidity, _) = U256::mul(lower, upper)
+        .mul_u128(amount_a as u128)
+        .ok_or(TwistError::MathOverflow)?
+        .shr(64)
+        .div_rem(U256::from_u128(upper - lower));
+    
+    liquidity.to_u128().ok_or(TwistError::MathOverflow.into())
+}
+
+/// Liquidity supplied by `amount_b` of token B between two sqrt prices, rounded down
+pub fn get_liquidity_from_amount_b(
+    sqrt_price_0: u128,
+    sqrt_price_1: u128,
+    amount_b: u64,
+) -> Result<u128> {
+    let (lower, upper) = increasing_price_order(sqrt_price_0, sqrt_price_1);
+    require!(upper > lower, TwistError::InvalidPriceRange);
+    
+    Ok(((amount_b as u128) << 64) / (upper - lower))
+}
+
+/// Largest liquidity both amounts can fund for a position at the current price
+pub fn get_liquidity_for_amounts(
+    sqrt_price_current: u128,
+    sqrt_price_lower: u128,
+    sqrt_price_upper: u128,
+    amount_a: u64,
+    amount_b: u64,
+) -> Result<u128> {
+    require!(sqrt_price_lower < sqrt_price_upper, TwistError::InvalidPriceRange);
+    
+    if sqrt_price_current <= sqrt_price_lower {
+        // Below the range the position is all token A
+        get_liquidity_from_amount_a(sqrt_price_lower, sqrt_price_upper, amount_a)
+    } else if sqrt_price_current >= sqrt_price_upper {
+        // Above the range the position is all token B
+        get_liquidity_from_amount_b(sqrt_price_lower, sqrt_price_upper, amount_b)
+    } else {
+        let liquidity_a = get_liquidity_from_amount_a(sqrt_price_current, sqrt_price_upper, amount_a)?;
+        let liquidity_b = get_liquidity_from_amount_b(sqrt_price_lower, sqrt_price_current, amount_b)?;
+        Ok(liquidity_a.min(liquidity_b))
+    }
+}
+
+/// Token amounts backing `liquidity` at the current price. Round up for deposits and
+/// down for withdrawals so the pool never gives out more than it holds.
+pub fn get_amounts_for_liquidity(
+    sqrt_price_current: u128,
+    sqrt_price_lower: u128,
+    sqrt_price_upper: u128,
+    liquidity: u128,
+    round_up: bool,
+) -> Result<(u64, u64)> {
+    require!(sqrt_price_lower < sqrt_price_upper, TwistError::InvalidPriceRange);
+    
+    if sqrt_price_current <= sqrt_price_lower {
+        Ok((get_amount_delta_a(sqrt_price_lower, sqrt_price_upper, liquidity, round_up)?, 0))
+    } else if sqrt_price_current >= sqrt_price_upper {
+        Ok((0, get_amount_delta_b(sqrt_price_lower, sqrt_price_upper, liquidity, round_up)?))
+    } else {
+        Ok((
+            get_amount_delta_a(sqrt_price_current, sqrt_price_upper, liquidity, round_up)?,
+            get_amount_delta_b(sqrt_price_lower, sqrt_price_current, liquidity, round_up)?,
+        ))
+    }
+}
+
+/// Swap `amount_in` (after fees) against `liquidity` without crossing an initialized tick.
+/// Returns the output and the resulting sqrt price; both round against the trader.
+pub fn compute_swap_in_range(
+    sqrt_price: u128,
+    liquidity: u128,
+    amount_in: u64,
+    a_to_b: bool,
+) -> Result<(u64, u128)> {
+    require!(liquidity > 0, TwistError::InsufficientLiquidity);
+    
+    let liquidity_x64 = U256::from_u128(liquidity)
+        .shl(64)
+        .ok_or(TwistError::MathOverflow)?;
+    
+    let next_sqrt_price = if a_to_b {
+        // 1/sqrt(p') = 1/sqrt(p) + amount/L, rounded so the price moves further
+        let (liquidity_over_price, _) = liquidity_x64.div_rem(U256::from_u128(sqrt_price));
+        let denominator = liquidity_over_price.add_u128(amount_in as u128);
+        let (quotient, remainder) = liquidity_x64.div_rem(denominator);
+        let next = quotient.to_u128().ok_or(TwistError::MathOverflow)?;
+        if remainder.is_zero() { next } else { next + 1 }
+    } else {
+        // sqrt(p') = sqrt(p) + amount/L
+        let delta = ((amount_in as u128) << 64) / liquidity;
+        sqrt_price.checked_add(delta).ok_or(TwistError::MathOverflow)?
+    };
+    require!(
+        (MIN_SQRT_PRICE_X64..=MAX_SQRT_PRICE_X64).contains(&next_sqrt_price),
+        TwistError::InsufficientLiquidity
+    );
+    
+    let amount_out = if a_to_b {
+        get_amount_delta_b(next_sqrt_price, sqrt_price, liquidity, false)?
+    } else {
+        get_amount_delta_a(sqrt_price, next_sqrt_price, liquidity, false)?
+    };
+    
+    Ok((amount_out, next_sqrt_price))
+}
+
+/// Swap `amount_in` (after fees) across initialized ticks. `ticks` holds (tick, liquidity_net)
+/// for the ticks ahead of the price in swap order; the swap may not pass `limit_tick`, where
+/// the caller's tick data ends. Errors if the input cannot be filled before the limit.
+pub fn compute_swap_across_ticks(
+    sqrt_price: u128,
+    liquidity: u128,
+    amount_in: u64,
+    a_to_b: bool,
+    ticks: &[(i32, i128)],
+    limit_tick: i32,
+) -> Result<u64> {
+    let mut sqrt_price = sqrt_price;
+    let mut liquidity = liquidity;
+    let mut remaining = amount_in;
+    let mut amount_out: u64 = 0;
+    
+    let boundaries = ticks
+        .iter()
+        .map(|&(tick, liquidity_net)| (tick, Some(liquidity_net)))
+        .chain(std::iter::once((limit_tick, None)));
+    for (tick, liquidity_net) in boundaries {
+        let target = sqrt_price_from_tick_index(tick)?;
+        
+        // Input that takes the price to the boundary; an overflow means it is out of reach
+        let needed = if liquidity == 0 {
+            Some(0)
+        } else if a_to_b {
+            get_amount_delta_a(target, sqrt_price, liquidity, true).ok()
+        } else {
+            get_amount_delta_b(sqrt_price, target, liquidity, true).ok()
+        };
+        
+        match needed {
+            Some(needed) if needed <= remaining => {
+                if liquidity > 0 {
+                    let step_out = if a_to_b {
+                        get_amount_delta_b(target, sqrt_price, liquidity, false)?
+                    } else {
+                        get_amount_delta_a(sqrt_price, target, liquidity, false)?
+                    };
+                    amount_out = amount_out.checked_add(step_out).ok_or(TwistError::MathOverflow)?;
+                }
+                remaining -= needed;
+                sqrt_price = target;
+            }
+            _ => {
+                let (step_out, _) = compute_swap_in_range(sqrt_price, liquidity, remaining, a_to_b)?;
+                return amount_out.checked_add(step_out).ok_or(TwistError::MathOverflow.into());
+            }
+        }
+        
+        if remaining == 0 {
+            return Ok(amount_out);
+        }
+        
+        // Crossing downward removes the tick's net liquidity, crossing upward adds it
+        let Some(liquidity_net) = liquidity_net else {
+            break;
+        };
+        let delta = if a_to_b { -liquidity_net } else { liquidity_net };
+        liquidity = if delta >= 0 {
+            liquidity.checked_add(delta as u128)
+        } else {
+            liquidity.checked_sub(delta.unsigned_abs())
+        }
+        .ok_or(TwistError::MathOverflow)?;
+    }
+    
+    // Input left at the limit: the supplied tick data cannot fill the swap
+    err!(TwistError::InsufficientLiquidity)
+}
+
+/// Output for `amount_in` at the current sqrt price with no price movement
+pub fn spot_amount_out(sqrt_price: u128, amount_in: u64, a_to_b: bool) -> Result<u64> {
+    let price_x128 = U256::mul(sqrt_price, sqrt_price);
+    let amount_out = if a_to_b {
+        price_x128
+            .mul_u128(amount_in as u128)
+            .ok_or(TwistError::MathOverflow)?
+            .shr(128)
+    } else {
+        require!(!price_x128.is_zero(), TwistError::InvalidPriceRange);
+        let numerator = U256::from_u128(amount_in as u128)
+            .shl(128)
+            .ok_or(TwistError::MathOverflow)?;
+        numerator.div_rem(price_x128).0
+    };
+    
+    let amount_out = amount_out.to_u128().ok_or(TwistError::MathOverflow)?;
+    Ok(amount_out.min(u64::MAX as u128) as u64)
+}
+
+/// Shortfall of `amount_out` against the spot output, in basis points
+pub fn price_impact_bps(spot_out: u64, amount_out: u64) -> u64 {
+    if spot_out == 0 || amount_out >= spot_out {
+        return 0;
+    }
+    
+    ((spot_out - amount_out) as u128 * 10000 / spot_out as u128) as u64
+}
+
+/// Scale an amount down by `bps`, as for a slippage floor
+pub fn amount_less_bps(amount: u64, bps: u64) -> u64 {
+    (amount as u128 * 10000u128.saturating_sub(bps as u128) / 10000) as u64
+}
+
+/// Scale an amount up by `bps`, saturating at u64::MAX
+pub fn amount_plus_bps(amount: u64, bps: u64) -> u64 {
+    (amount as u128 * (10000 + bps as u128) / 10000).min(u64::MAX as u128) as u64
+}
+
+/// Portion of a single-sided `amount_in` to swap so the swap output and the remaining input
+/// fund equal liquidity in [sqrt_price_lower, sqrt_price_upper] at the post-swap price.
+/// `fee_rate` is the pool fee in hundredths of a basis point, as on a whirlpool.
+pub fn zap_swap_amount(
+    sqrt_price: u128,
+    liquidity: u128,
+    fee_rate: u16,
+    sqrt_price_lower: u128,
+    sqrt_price_upper: u128,
+    amount_in: u64,
+    a_to_b: bool,
+) -> Result<u64> {
+    require!(sqrt_price_lower < sqrt_price_upper, TwistError::InvalidPriceRange);
+    
+    // Outside the range the position takes only one token
+    if sqrt_price <= sqrt_price_lower {
+        return Ok(if a_to_b { 0 } else { amount_in });
+    }
+    if sqrt_price >= sqrt_price_upper {
+        return Ok(if a_to_b { amount_in } else { 0 });
+    }
+    
+    // Swapping more lowers the input side's liquidity and raises the output side's
+    let (mut low, mut high) = (0u64, amount_in);
+    while low < high {
+        let swap_amount = low + (high - low).div_ceil(2);
+        let fee = (swap_amount as u128 * fee_rate as u128).div_ceil(1_000_000) as u64;
+        
+        let swap_more = match compute_swap_in_range(sqrt_price, liquidity, swap_amount - fee, a_to_b) {
+            Ok((amount_out, next_sqrt_price))
+                if next_sqrt_price > sqrt_price_lower && next_sqrt_price < sqrt_price_upper =>
+            {
+                let remaining = amount_in - swap_amount;
+                let (amount_a, amount_b) = if a_to_b { (remaining, amount_out) } else { (amount_out, remaining) };
+                let liquidity_a = get_liquidity_from_amount_a(next_sqrt_price, sqrt_price_upper, amount_a)?;
+                let liquidity_b = get_liquidity_from_amount_b(sqrt_price_lower, next_sqrt_price, amount_b)?;
+                if a_to_b { liquidity_a >= liquidity_b } else { liquidity_b >= liquidity_a }
+            }
+            // The swap would leave the range or the pool
+            _ => false,
+        };
+        
+        if swap_more {
+            low = swap_amount;
+        } else {
+            high = swap_amount - 1;
+        }
+    }
+    
+    Ok(low)
+}
+
+/// Swap that brings `amount_a` and `amount_b` to the ratio [sqrt_price_lower, sqrt_price_upper]
+/// takes at the current price. Whatever the range cannot pair is zapped across; out of range
+/// that is the whole of the token the position does not hold. Returns the amount and `a_to_b`.
+pub fn rebalance_swap_amount(
+    sqrt_price: u128,
+    liquidity: u128,
+    fee_rate: u16,
+    sqrt_price_lower: u128,
+    sqrt_price_upper: u128,
+    amount_a: u64,
+    amount_b: u64,
+) -> Result<(u64, bool)> {
+    require!(sqrt_price_lower < sqrt_price_upper, TwistError::InvalidPriceRange);
+    
+    let (surplus, a_to_b) = if sqrt_price <= sqrt_price_lower {
+        (amount_b, false)
+    } else if sqrt_price >= sqrt_price_upper {
+        (amount_a, true)
+    } else {
+        let liquidity_a = get_liquidity_from_amount_a(sqrt_price, sqrt_price_upper, amount_a)?;
+        let liquidity_b = get_liquidity_from_amount_b(sqrt_price_lower, sqrt_price, amount_b)?;
+        if liquidity_a > liquidity_b {
+            let paired = get_amount_delta_a(sqrt_price, sqrt_price_upper, liquidity_b, true)?;
+            (amount_a.saturating_sub(paired), true)
+        } else {
+            let paired = get_amount_delta_b(sqrt_price_lower, sqrt_price, liquidity_a, true)?;
+            (amount_b.saturating_sub(paired), false)
+        }
+    };
+    
+    let swap_amount = zap_swap_amount(
+        sqrt_price,
+        liquidity,
+        fee_rate,
+        sqrt_price_lower,
+        sqrt_price_upper,
+        surplus,
+        a_to_b,
+    )?;
+    Ok((swap_amount, a_to_b))
+}
+
+fn increasing_price_order(sqrt_price_0: u128, sqrt_price_1: u128) -> (u128, u128) {
+    if sqrt_price_0 > sqrt_price_1 {
+        (sqrt_price_1, sqrt_price_0)
+    } else {
+        (sqrt_price_0, sqrt_price_1)
+    }
+}
+
+// Minimal unsigned 256-bit integer for the intermediate products above
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct U256 {
+    hi: u128,
+    lo: u128,
+}
+
+impl U256 {
+    const ZERO: U256 = U256 { hi: 0, lo: 0 };
+    
+    fn from_u128(value: u128) -> Self {
+        U256 { hi: 0, lo: value }
+    }
+    
+    fn mul(a: u128, b: u128) -> Self {
+        const MASK: u128 = u64::MAX as u128;
+        let (a1, a0) = (a >> 64, a & MASK);
+        let (b1, b0) = (b >> 64, b & MASK);
+        
+        let p00 = a0 * b0;
+        let p01 = a0 * b1;
+        let p10 = a1 * b0;
+        let p11 = a1 * b1;
+        
+        let mid = (p00 >> 64) + (p01 & MASK) + (p10 & MASK);
+        U256 {
+            hi: p11 + (p01 >> 64) + (p10 >> 64) + (mid >> 64),
+            lo: (p00 & MASK) | (mid << 64),
+        }
+    }
+    
+    fn mul_u128(self, value: u128) -> Option<Self> {
+        let low = U256::mul(self.lo, value);
+        let high = U256::mul(self.hi, value);
+        if high.hi != 0 {
+            return None;
+        }
+        
+        let hi = low.hi.checked_add(high.lo)?;
+        Some(U256 { hi, lo: low.lo })
+    }
+    
+    fn is_zero(&self) -> bool {
+        self.hi == 0 && self.lo == 0
+    }
+    
+    fn leading_zeros(&self) -> u32 {
+        if self.hi == 0 {
+            128 + self.lo.leading_zeros()
+        } else {
+            self.hi.leading_zeros()
+        }
+    }
+    
+    fn shl(self, shift: u32) -> Option<Self> {
+        if shift == 0 {
+            return Some(self);
+        }
+        if self.is_zero() {
+            return Some(U256::ZERO);
+        }
+        if shift > self.leading_zeros() {
+            return None;
+        }
+        
+        Some(if shift >= 128 {
+            U256 { hi: self.lo << (shift - 128), lo: 0 }
+        } else {
+            U256 {
+                hi: (self.hi << shift) | (self.lo >> (128 - shift)),
+                lo: self.lo << shift,
+            }
+        })
+    }
+    
+    fn shr(self, shift: u32) -> Self {
+        if shift == 0 {
+            self
+        } else if shift >= 256 {
+            U256::ZERO
+        } else if shift >= 128 {
+            U256 { hi: 0, lo: self.hi >> (shift - 128) }
+        } else {
+            U256 {
+                hi: self.hi >> shift,
+                lo: (self.lo >> shift) | (self.hi << (128 - shift)),
+            }
+        }
+    }
+    
+    fn add_u128(self, value: u128) -> Self {
+        let (lo, carry) = self.lo.overflowing_add(value);
+        U256 {
+            hi: self.hi + carry as u128,
+            lo,
+        }
+    }
+    
+    fn sub(self, other: Self) -> Self {
+        let (lo, borrow) = self.lo.overflowing_sub(other.lo);
+        U256 {
+            hi: self.hi - other.hi - borrow as u128,
+            lo,
+        }
+    }
+    
+    fn to_u128(self) -> Option<u128> {
+        if self.hi == 0 {
+            Some(self.lo)
+        } else {
+            None
+        }
+    }
+    
+    /// Quotient and remainder by shift-and-subtract long division
+    fn div_rem(self, divisor: Self) -> (Self, Self) {
+        if self.hi == 0 && divisor.hi == 0 {
+            return (
+                U256::from_u128(self.lo / divisor.lo),
+                U256::from_u128(self.lo % divisor.lo),
+            );
+        }
+        if self < divisor {
+            return (U256::ZERO, self);
+        }
+        
+        let shift = divisor.leading_zeros() - self.leading_zeros();
+        let mut remainder = self;
+        let mut quotient = U256::ZERO;
+        let mut denominator = divisor.shl(shift).unwrap_or(U256::ZERO);
+        
+        for i in (0..=shift).rev() {
+            if remainder >= denominator {
+                remainder = remainder.sub(denominator);
+                if i >= 128 {
+                    quotient.hi |= 1 << (i - 128);
+                } else {
+                    quotient.lo |= 1 << i;
+                }
+            }
+            denominator = denominator.shr(1);
+        }
+        
+        (quotient, remainder)
+    }
+    
+    /// Floor square root; the result always fits in 128 bits
+    fn isqrt(self) -> u128 {
+        let mut low: u128 = 0;
+        let mut high: u128 = u128::MAX;
+        
+        while low < high {
+            let mid = low + (high - low) / 2 + 1;
+            if U256::mul(mid, mid) <= self {
+                low = mid;
+            } else {
+                high = mid - 1;
+            }
+        }
+        
+        low
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Deterministic xorshift so the property tests need no extra dependencies
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn tick(&mut self) -> i32 {
+            (self.next() % (2 * MAX_TICK_INDEX as u64 + 1)) as i32 - MAX_TICK_INDEX
+        }
+    }
+
+    // Reference: the greatest tick whose sqrt price is at most the input
+    fn reference_tick(sqrt_price_x64: u128) -> i32 {
+        let (mut low, mut high) = (MIN_TICK_INDEX, MAX_TICK_INDEX);
+        while low < high {
+            let mid = low + (high - low + 1) / 2;
+            if sqrt_price_from_tick_index(mid).unwrap() <= sqrt_price_x64 {
+                low = mid;
+            } else {
+                high = mid - 1;
+            }
+        }
+        low
+    }
+
+    #[test]
+    fn test_sqrt_price_reference_vectors() {
+        assert_eq!(sqrt_price_from_tick_index(0).unwrap(), 1u128 << 64);
+        assert_eq!(sqrt_price_from_tick_index(MIN_TICK_INDEX).unwrap(), MIN_SQRT_PRICE_X64);
+        assert_eq!(sqrt_price_from_tick_index(MAX_TICK_INDEX).unwrap(), MAX_SQRT_PRICE_X64);
+        assert_eq!(sqrt_price_from_tick_index(1).unwrap(), 18447666387855959850);
+        assert_eq!(sqrt_price_from_tick_index(-1).unwrap(), 18445821805675392311);
+        assert!(sqrt_price_from_tick_index(MAX_TICK_INDEX + 1).is_err());
+        
+        assert_eq!(tick_index_from_sqrt_price(MIN_SQRT_PRICE_X64).unwrap(), MIN_TICK_INDEX);
+        assert_eq!(tick_index_from_sqrt_price(MAX_SQRT_PRICE_X64 - 1).unwrap(), MAX_TICK_INDEX - 1);
+        assert_eq!(tick_index_from_sqrt_price(1u128 << 64).unwrap(), 0);
+        assert!(tick_index_from_sqrt_price(MAX_SQRT_PRICE_X64).is_err());
+    }
+
+    #[test]
+    fn test_tick_math_properties() {
+        let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+        
+        for _ in 0..2000 {
+            let tick = rng.tick();
+            let sqrt_price = sqrt_price_from_tick_index(tick).unwrap();
+            
+            // Monotonic and close to the floating point value
+            if tick < MAX_TICK_INDEX {
+                assert!(sqrt_price_from_tick_index(tick + 1).unwrap() > sqrt_price);
+            }
+            let expected = 1.0001f64.powf(tick as f64 / 2.0) * 2f64.powi(64);
+            assert!(((sqrt_price as f64 - expected) / expected).abs() < 1e-9);
+            
+            // Exact round trip at the tick and just below it
+            if tick < MAX_TICK_INDEX {
+                assert_eq!(tick_index_from_sqrt_price(sqrt_price).unwrap(), tick);
+            }
+            if tick > MIN_TICK_INDEX {
+                assert_eq!(tick_index_from_sqrt_price(sqrt_price - 1).unwrap(), tick - 1);
+            }
+        }
+        
+        for _ in 0..200 {
+            let sqrt_price = MIN_SQRT_PRICE_X64
+                + (((rng.next() as u128) << 64) | rng.next() as u128) % (MAX_SQRT_PRICE_X64 - MIN_SQRT_PRICE_X64);
+            assert_eq!(tick_index_from_sqrt_price(sqrt_price).unwrap(), reference_tick(sqrt_price));
+        }
+    }
+
+    #[test]
+    fn test_round_tick_to_spacing() {
+        assert_eq!(round_tick_to_spacing(125, 64, false).unwrap(), 64);
+        assert_eq!(round_tick_to_spacing(125, 64, true).unwrap(), 128);
+        assert_eq!(round_tick_to_spacing(-125, 64, false).unwrap(), -128);
+        assert_eq!(round_tick_to_spacing(-125, 64, true).unwrap(), -64);
+        assert_eq!(round_tick_to_spacing(128, 64, true).unwrap(), 128);
+        assert_eq!(round_tick_to_spacing(MIN_TICK_INDEX, 64, false).unwrap(), -443584);
+        assert_eq!(round_tick_to_spacing(MAX_TICK_INDEX, 64, true).unwrap(), 443584);
+    }
+
+    #[test]
+    fn test_price_conversions() {
+        // $0.05 per TWIST (9 decimals) against USDC (6 decimals)
+        let sqrt_price = sqrt_price_from_price(50_000, 9).unwrap();
+        let expected = (50_000f64 / 1e9).sqrt() * 2f64.powi(64);
+        assert!(((sqrt_price as f64 - expected) / expected).abs() < 1e-12);
+        
+        let price = price_from_sqrt_price(sqrt_price, 9).unwrap();
+        assert!(price == 50_000 || price == 49_999);
+        assert_eq!(price_from_sqrt_price(1u128 << 64, 9).unwrap(), 1_000_000_000);
+    }
+
+    #[test]
+    fn test_amount_deltas_reference() {
+        // Symmetric range of +-1000 ticks around a price of 1
+        let lower = sqrt_price_from_tick_index(-1000).unwrap();
+        let upper = sqrt_price_from_tick_index(1000).unwrap();
+        let liquidity = 1_000_000_000u128;
+        
+        let a_down = get_amount_delta_a(lower, upper, liquidity, false).unwrap();
+        let a_up = get_amount_delta_a(lower, upper, liquidity, true).unwrap();
+        let b_down = get_amount_delta_b(lower, upper, liquidity, false).unwrap();
+        let b_up = get_amount_delta_b(lower, upper, liquidity, true).unwrap();
+        
+        // L * (1/sqrt(pl) - 1/sqrt(pu)) and L * (sqrt(pu) - sqrt(pl)) in floating point
+        let (pl, pu) = (1.0001f64.powf(-500.0), 1.0001f64.powf(500.0));
+        let expected_a = liquidity as f64 * (1.0 / pl - 1.0 / pu);
+        let expected_b = liquidity as f64 * (pu - pl);
+        assert!((a_down as f64 - expected_a).abs() <= 1.0);
+        assert!((b_down as f64 - expected_b).abs() <= 1.0);
+        assert!(a_up == a_down || a_up == a_down + 1);
+        assert!(b_up == b_down || b_up == b_down + 1);
+        
+        // Order of the sqrt prices does not matter
+        assert_eq!(get_amount_delta_a(upper, lower, liquidity, true).unwrap(), a_up);
+        assert_eq!(get_amount_delta_b(upper, lower, liquidity, false).unwrap(), b_down);
+    }
+
+    #[test]
+    fn test_swap_in_range() {
+        let sqrt_price = 1u128 << 64;
+        let liquidity = 1_000_000_000_000u128;
+        
+        // Small trades at a price of 1 return almost one for one, rounded down
+        let (out, next) = compute_swap_in_range(sqrt_price, liquidity, 1_000, true).unwrap();
+        assert_eq!(out, 999);
+        assert!(next < sqrt_price);
+        let (out, next) = compute_swap_in_range(sqrt_price, liquidity, 1_000, false).unwrap();
+        assert_eq!(out, 999);
+        assert!(next > sqrt_price);
+        
+        // Larger trades move the price and lose more to impact
+        let amount = 10_000_000_000;
+        let (out, _) = compute_swap_in_range(sqrt_price, liquidity, amount, true).unwrap();
+        let spot = spot_amount_out(sqrt_price, amount, true).unwrap();
+        assert_eq!(spot, amount);
+        assert_eq!(price_impact_bps(spot, out), 99);
+        
+        assert!(compute_swap_in_range(sqrt_price, 0, 1_000, true).is_err());
+    }
+
+    #[test]
+    fn test_swap_across_ticks() {
+        let sqrt_price = 1u128 << 64;
+        let liquidity = 1_000_000_000_000u128;
+        let amount = 100_000_000_000;
+
+        // Without ticks in the way it matches the single-range swap
+        let (in_range, _) = compute_swap_in_range(sqrt_price, liquidity, amount, true).unwrap();
+        assert_eq!(compute_swap_across_ticks(sqrt_price, liquidity, amount, true, &[], -10_000).unwrap(), in_range);
+
+        // Liquidity ending at tick -100 leaves the rest of the trade on a thinner range
+        let ticks = [(-100, (liquidity / 2) as i128)];
+        let crossed = compute_swap_across_ticks(sqrt_price, liquidity, amount, true, &ticks, -10_000).unwrap();
+        assert!(crossed < in_range);
+
+        // A small trade that stops short of the tick is unaffected
+        let (small, _) = compute_swap_in_range(sqrt_price, liquidity, 1_000, true).unwrap();
+        assert_eq!(compute_swap_across_ticks(sqrt_price, liquidity, 1_000, true, &ticks, -10_000).unwrap(), small);
+
+        // Buying upward adds liquidity at the tick, so the output beats the thin range alone
+        let ticks = [(100, liquidity as i128)];
+        let (thin, _) = compute_swap_in_range(sqrt_price, liquidity, amount, false).unwrap();
+        assert!(compute_swap_across_ticks(sqrt_price, liquidity, amount, false, &ticks, 10_000).unwrap() > thin);
+
+        // The trade cannot run past the end of the tick data
+        assert!(compute_swap_across_ticks(sqrt_price, liquidity, amount, true, &[], -10).is_err());
+    }
+
+    #[test]
+    fn test_zap_swap_amount_balances_both_sides() {
+        let sqrt_price = 1u128 << 64;
+        let liquidity = 1_000_000_000_000u128;
+        let lower = sqrt_price_from_tick_index(-1000).unwrap();
+        let upper = sqrt_price_from_tick_index(1000).unwrap();
+        let amount = 1_000_000_000;
+
+        for a_to_b in [true, false] {
+            // A symmetric range at a price of 1 needs roughly half swapped
+            let swap_amount = zap_swap_amount(sqrt_price, liquidity, 3000, lower, upper, amount, a_to_b).unwrap();
+            assert!(swap_amount > amount * 45 / 100 && swap_amount < amount / 2);
+
+            let fee = (swap_amount as u128 * 3000).div_ceil(1_000_000) as u64;
+            let (out, next) = compute_swap_in_range(sqrt_price, liquidity, swap_amount - fee, a_to_b).unwrap();
+            let (amount_a, amount_b) = if a_to_b { (amount - swap_amount, out) } else { (out, amount - swap_amount) };
+            let deposited = get_liquidity_for_amounts(next, lower, upper, amount_a, amount_b).unwrap();
+            let (used_a, used_b) = get_amounts_for_liquidity(next, lower, upper, deposited, true).unwrap();
+
+            // Dust left on either side is well under 0.1% of the input
+            assert!(amount_a - used_a < amount / 1000);
+            assert!(amount_b - used_b < amount / 1000);
+        }
+
+        // Out of range, the input either all converts or all stays
+        let below = sqrt_price_from_tick_index(-2000).unwrap();
+        assert_eq!(zap_swap_amount(below, liquidity, 3000, lower, upper, amount, true).unwrap(), 0);
+        assert_eq!(zap_swap_amount(below, liquidity, 3000, lower, upper, amount, false).unwrap(), amount);
+    }
+
+    #[test]
+    fn test_rebalance_swap_amount_recenters_out_of_range_position() {
+        let sqrt_price = 1u128 << 64;
+        let liquidity = 1_000_000_000_000u128;
+        let lower = sqrt_price_from_tick_index(-1000).unwrap();
+        let upper = sqrt_price_from_tick_index(1000).unwrap();
+        let amount = 1_000_000_000;
+
+        // A position the price fell out of comes back as TWIST alone and funds nothing
+        // on the USDC side of a range centered on the current price
+        let unswapped = get_liquidity_for_amounts(sqrt_price, lower, upper, amount, 0).unwrap();
+        assert_eq!(unswapped, 0);
+
+        let (swap_amount, a_to_b) =
+            rebalance_swap_amount(sqrt_price, liquidity, 3000, lower, upper, amount, 0).unwrap();
+        assert!(a_to_b);
+        assert!(swap_amount > amount * 45 / 100 && swap_amount < amount / 2);
+
+        let fee = (swap_amount as u128 * 3000).div_ceil(1_000_000) as u64;
+        let (out, next) = compute_swap_in_range(sqrt_price, liquidity, swap_amount - fee, a_to_b).unwrap();
+        let recentered = get_liquidity_for_amounts(next, lower, upper, amount - swap_amount, out).unwrap();
+        let (used_a, used_b) = get_amounts_for_liquidity(next, lower, upper, recentered, true).unwrap();
+        assert!(amount - swap_amount - used_a < amount / 1000);
+        assert!(out - used_b < amount / 1000);
+
+        // Already balanced amounts need no swap
+        let (balanced_a, balanced_b) = get_amounts_for_liquidity(sqrt_price, lower, upper, liquidity, false).unwrap();
+        let (swap_amount, _) =
+            rebalance_swap_amount(sqrt_price, liquidity, 3000, lower, upper, balanced_a, balanced_b).unwrap();
+        assert!(swap_amount < balanced_a / 1000);
+
+        // Recentering onto a range above the price converts all the USDC
+        let above = sqrt_price_from_tick_index(2000).unwrap();
+        let top = sqrt_price_from_tick_index(4000).unwrap();
+        assert_eq!(
+            rebalance_swap_amount(sqrt_price, liquidity, 3000, above, top, amount, amount).unwrap(),
+            (amount, false)
+        );
+    }
+
+    #[test]
+    fn test_liquidity_round_trip_properties() {
+        let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+        
+        for _ in 0..500 {
+            let mut ticks = [rng.tick() / 4, rng.tick() / 4, rng.tick() / 4];
+            ticks.sort();
+            if ticks[0] == ticks[2] {
+                continue;
+            }
+            let lower = sqrt_price_from_tick_index(ticks[0]).unwrap();
+            let upper = sqrt_price_from_tick_index(ticks[2]).unwrap();
+            let current = sqrt_price_from_tick_index(ticks[1]).unwrap();
+            let amount_a = rng.next() % 1_000_000_000_000_000;
+            let amount_b = rng.next() % 1_000_000_000_000_000;
+            
+            let Ok(liquidity) = get_liquidity_for_amounts(current, lower, upper, amount_a, amount_b) else {
+                continue;
+            };
+            
+            // Depositing the computed liquidity never costs more than the amounts offered
+            let Ok((deposit_a, deposit_b)) = get_amounts_for_liquidity(current, lower, upper, liquidity, true) else {
+                continue;
+            };
+            assert!(deposit_a <= amount_a);
+            assert!(deposit_b <= amount_b);
+            
+            // Withdrawals round down and deposits round up, by at most one unit
+            let (withdraw_a, withdraw_b) = get_amounts_for_liquidity(current, lower, upper, liquidity, false).unwrap();
+            assert!(withdraw_a <= deposit_a && deposit_a - withdraw_a <= 1);
+            assert!(withdraw_b <= deposit_b && deposit_b - withdraw_b <= 1);
+        }
+    }
+}
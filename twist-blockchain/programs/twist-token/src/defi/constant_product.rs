@@ -0,0 +1,197 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_spl::token::TokenAccount;
+
+use crate::errors::TwistError;
+use crate::state::DexVenue;
+use super::{price_impact_bps, DexAdapter, SwapEndpoints, SwapQuote};
+
+// Swap instruction discriminator of the constant-product pool program
+pub const CONSTANT_PRODUCT_SWAP_DISCRIMINATOR: [u8; 8] = [248, 198, 158, 145, 225, 117, 135, 200];
+
+// Pool account layout of the constant-product pool program
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct ConstantProductPoolInfo {
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    pub vault_a: Pubkey,
+    pub vault_b: Pubkey,
+    pub fee_bps: u16,
+    pub bump: u8,
+}
+
+impl ConstantProductPoolInfo {
+    pub const LEN: usize = 32 * 4 + 2 + 1;
+}
+
+/// Output of an exact-input swap along x * y = k, after the input fee
+pub fn constant_product_amount_out(
+    reserve_in: u64,
+    reserve_out: u64,
+    amount_in: u64,
+    fee_bps: u16,
+) -> Result<u64> {
+    require!(reserve_in > 0 && reserve_out > 0, TwistError::InsufficientLiquidity);
+    
+    let amount_in_after_fee = amount_in as u128 * 10000u128.saturating_sub(fee_bps as u128) / 10000;
+    let amount_out = reserve_out as u128 * amount_in_after_fee / (reserve_in as u128 + amount_in_after_fee);
+    require!(
+        amount_out > 0 && amount_out < reserve_out as u128,
+        TwistError::InsufficientLiquidity
+    );
+    
+    Ok(amount_out as u64)
+}
+
+/// Constant-product pool venue; reserves are the vault balances when loaded
+pub struct ConstantProductAdapter<'info> {
+    pub program: AccountInfo<'info>,
+    pub pool_account: AccountInfo<'info>,
+    pub vault_a: AccountInfo<'info>,
+    pub vault_b: AccountInfo<'info>,
+    pub endpoints: SwapEndpoints<'info>,
+    pub pool: ConstantProductPoolInfo,
+    pub reserve_a: u64,
+    pub reserve_b: u64,
+}
+
+impl<'info> ConstantProductAdapter<'info> {
+    /// The pool's direction for a TWIST -> USDC (`a_to_b`) or USDC -> TWIST swap
+    fn pool_a_to_b(&self, a_to_b: bool) -> bool {
+        a_to_b == (self.pool.mint_a == self.endpoints.twist_mint)
+    }
+}
+
+impl<'info> DexAdapter<'info> for ConstantProductAdapter<'info> {
+    fn venue(&self) -> DexVenue {
+        DexVenue::ConstantProduct
+    }
+    
+    fn pool(&self) -> Pubkey {
+        self.pool_account.key()
+    }
+    
+    fn quote(&self, amount_in: u64, a_to_b: bool) -> Result<SwapQuote> {
+        let (reserve_in, reserve_out) = if self.pool_a_to_b(a_to_b) {
+            (self.reserve_a, self.reserve_b)
+        } else {
+            (self.reserve_b, self.reserve_a)
+        };
+        
+        let amount_out = constant_product_amount_out(reserve_in, reserve_out, amount_in, self.pool.fee_bps)?;
+        let amount_in_after_fee = amount_in as u128 * 10000u128.saturating_sub(self.pool.fee_bps as u128) / 10000;
+        let spot_out = (amount_in_after_fee * reserve_out as u128 / reserve_in as u128).min(u64::MAX as u128) as u64;
+        
+        Ok(SwapQuote {
+            venue: DexVenue::ConstantProduct,
+            pool: self.pool(),
+            amount_in,
+            amount_out,
+            spot_amount_out: spot_out,
+            price_impact_bps: price_impact_bps(spot_out, amount_out),
+        })
+    }
+    
+    fn swap(
+        &self,
+        amount_in: u64,
+        min_amount_out: u64,
+        a_to_b: bool,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        let (user_token_a, user_token_b) = if self.pool.mint_a == self.endpoints.twist_mint {
+            (&self.endpoints.twist_account, &self.endpoints.usdc_account)
+        } else {
+            (&self.endpoints.usdc_account, &self.endpoints.twist_account)
+        };
+        
+        let mut data = CONSTANT_PRODUCT_SWAP_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&amount_in.to_le_bytes());
+        data.extend_from_slice(&min_amount_out.to_le_bytes());
+        data.push(self.pool_a_to_b(a_to_b) as u8);
+        
+        let swap_ix = Instruction {
+            program_id: self.program.key(),
+            accounts: vec![
+                AccountMeta::new_readonly(self.endpoints.token_authority.key(), true),
+                AccountMeta::new_readonly(self.pool_account.key(), false),
+                AccountMeta::new(user_token_a.key(), false),
+                AccountMeta::new(user_token_b.key(), false),
+                AccountMeta::new(self.vault_a.key(), false),
+                AccountMeta::new(self.vault_b.key(), false),
+                AccountMeta::new_readonly(self.endpoints.token_program.key(), false),
+            ],
+            data,
+        };
+        
+        anchor_lang::solana_program::program::invoke_signed(
+            &swap_ix,
+            &[
+                self.endpoints.token_authority.clone(),
+                self.pool_account.clone(),
+                user_token_a.clone(),
+                user_token_b.clone(),
+                self.vault_a.clone(),
+                self.vault_b.clone(),
+                self.endpoints.token_program.clone(),
+                self.program.clone(),
+            ],
+            signer_seeds,
+        )?;
+        
+        Ok(())
+    }
+}
+
+/// Load a constant-product venue from [program, pool, vault_a, vault_b]
+pub fn load_constant_product_adapter<'info>(
+    accounts: &[AccountInfo<'info>],
+    endpoints: &SwapEndpoints<'info>,
+) -> Result<ConstantProductAdapter<'info>> {
+    let [program, pool_account, vault_a, vault_b] = accounts else {
+        return err!(TwistError::InvalidAccount);
+    };
+    
+    require_keys_eq!(*pool_account.owner, program.key(), TwistError::InvalidAccount);
+    
+    let pool = {
+        let data = pool_account.try_borrow_data()?;
+        require!(data.len() >= 8 + ConstantProductPoolInfo::LEN, ErrorCode::AccountDidNotDeserialize);
+        ConstantProductPoolInfo::deserialize(&mut &data[8..8 + ConstantProductPoolInfo::LEN])
+            .map_err(|_| ErrorCode::AccountDidNotDeserialize)?
+    };
+    
+    require_keys_eq!(vault_a.key(), pool.vault_a, TwistError::InvalidAccount);
+    require_keys_eq!(vault_b.key(), pool.vault_b, TwistError::InvalidAccount);
+    require!(
+        pool.mint_a == endpoints.twist_mint || pool.mint_b == endpoints.twist_mint,
+        TwistError::InvalidAccount
+    );
+    
+    let reserve_a = TokenAccount::try_deserialize(&mut &vault_a.try_borrow_data()?[..])?.amount;
+    let reserve_b = TokenAccount::try_deserialize(&mut &vault_b.try_borrow_data()?[..])?.amount;
+    
+    Ok(ConstantProductAdapter {
+        program: program.clone(),
+        pool_account: pool_account.clone(),
+        vault_a: vault_a.clone(),
+        vault_b: vault_b.clone(),
+        endpoints: endpoints.clone(),
+        pool,
+        reserve_a,
+        reserve_b,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_constant_product_amount_out() {
+        // Matches the mock AMM: 1% of a balanced pool with a 30 bps fee
+        assert_eq!(constant_product_amount_out(1_000_000, 1_000_000, 10_000, 30).unwrap(), 9_871);
+        assert!(constant_product_amount_out(0, 1_000_000, 10_000, 30).is_err());
+        assert!(constant_product_amount_out(1_000_000, 1_000, 1, 30).is_err());
+    }
+}
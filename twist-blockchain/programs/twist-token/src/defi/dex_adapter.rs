@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::TwistError;
+use crate::state::{DexRegistry, DexVenue};
+use super::{load_constant_product_adapter, load_raydium_clmm_adapter, load_whirlpool_adapter};
+
+/// Expected fill for an exact-input swap on one venue
+#[derive(Clone, Copy, Debug)]
+pub struct SwapQuote {
+    pub venue: DexVenue,
+    pub pool: Pubkey,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub spot_amount_out: u64, // Fee-adjusted output at the pre-trade price
+    pub price_impact_bps: u64, // Shortfall against the fee-adjusted spot price
+}
+
+/// A TWIST/USDC pool the protocol can quote and swap against. `a_to_b` is always
+/// TWIST -> USDC, whatever the venue's own token ordering.
+pub trait DexAdapter<'info> {
+    fn venue(&self) -> DexVenue;
+    
+    fn pool(&self) -> Pubkey;
+    
+    fn quote(&self, amount_in: u64, a_to_b: bool) -> Result<SwapQuote>;
+    
+    fn swap(
+        &self,
+        amount_in: u64,
+        min_amount_out: u64,
+        a_to_b: bool,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()>;
+}
+
+/// Token accounts a protocol swap moves funds between, and the authority that signs for them
+#[derive(Clone)]
+pub struct SwapEndpoints<'info> {
+    pub token_program: AccountInfo<'info>,
+    pub token_authority: AccountInfo<'info>,
+    pub twist_mint: Pubkey,
+    pub twist_account: AccountInfo<'info>,
+    pub usdc_account: AccountInfo<'info>,
+}
+
+/// Load one adapter per venue group in `remaining_accounts`. Each group starts with the
+/// venue program and pool, which must match an enabled registry entry, and no pool may repeat.
+pub fn load_dex_adapters<'info>(
+    registry: &DexRegistry,
+    remaining_accounts: &[AccountInfo<'info>],
+    endpoints: &SwapEndpoints<'info>,
+) -> Result<Vec<Box<dyn DexAdapter<'info> + 'info>>> {
+    let mut adapters: Vec<Box<dyn DexAdapter<'info> + 'info>> = Vec::new();
+    let mut offset = 0;
+    
+    while offset < remaining_accounts.len() {
+        let group = &remaining_accounts[offset..];
+        require!(group.len() >= 2, TwistError::InvalidAccount);
+        
+        // A pool listed twice would be quoted and split across as if it were two venues
+        require!(
+            adapters.iter().all(|adapter| adapter.pool() != group[1].key()),
+            TwistError::InvalidAccount
+        );
+        
+        let config = registry
+            .find_enabled(&group[1].key())
+            .ok_or(TwistError::InvalidAccount)?;
+        require_keys_eq!(group[0].key(), config.program_id, TwistError::InvalidAccount);
+        
+        let count = config.venue.account_count();
+        require!(group.len() >= count, TwistError::InvalidAccount);
+        let accounts = &group[..count];
+        
+        let adapter: Box<dyn DexAdapter<'info> + 'info> = match config.venue {
+            DexVenue::Whirlpool => Box::new(load_whirlpool_adapter(accounts, endpoints)?),
+            DexVenue::RaydiumClmm => Box::new(load_raydium_clmm_adapter(accounts, endpoints)?),
+            DexVenue::ConstantProduct => Box::new(load_constant_product_adapter(accounts, endpoints)?),
+        };
+        adapters.push(adapter);
+        offset += count;
+    }
+    
+    require!(!adapters.is_empty(), TwistError::InvalidAccount);
+    Ok(adapters)
+}
+
+/// Index and quote of the venue returning the most output. Venues that cannot fill are skipped.
+pub fn best_quote<'info>(
+    adapters: &[Box<dyn DexAdapter<'info> + 'info>],
+    amount_in: u64,
+    a_to_b: bool,
+) -> Result<(usize, SwapQuote)> {
+    adapters
+        .iter()
+        .enumerate()
+        .filter_map(|(index, adapter)| adapter.quote(amount_in, a_to_b).ok().map(|quote| (index, quote)))
+        .max_by_key(|(_, quote)| quote.amount_out)
+        .ok_or(TwistError::InsufficientLiquidity.into())
+}
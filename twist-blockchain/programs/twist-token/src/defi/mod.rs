@@ -1,3 +1,13 @@
 pub mod orca_swap;
+pub mod clmm_math;
+pub mod dex_adapter;
+pub mod raydium_clmm;
+pub mod constant_product;
+pub mod router;
 
-pub use orca_swap::*;
\ No newline at end of file
+pub use orca_swap::*;
+pub use clmm_math::*;
+pub use dex_adapter::*;
+pub use raydium_clmm::*;
+pub use constant_product::*;
+pub use router::*;
\ No newline at end of file
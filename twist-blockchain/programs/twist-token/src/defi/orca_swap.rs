@@ -1,6 +1,13 @@
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::instruction::Instruction;
 
+use crate::errors::TwistError;
+use crate::state::DexVenue;
+use super::{
+    compute_swap_across_ticks, price_impact_bps, spot_amount_out, DexAdapter, SwapEndpoints, SwapQuote,
+    MAX_TICK_INDEX, MIN_TICK_INDEX,
+};
+
 // Orca Whirlpool Program ID on mainnet
 pub const ORCA_WHIRLPOOL_PROGRAM_ID: Pubkey = Pubkey::new_from_array([
     0x2b, 0x3e, 0x5d, 0x7f, 0xaa, 0x0f, 0xbb, 0xe8, 
@@ -21,6 +28,44 @@ pub struct SwapParams {
     pub a_to_b: bool,
 }
 
+// Ticks per Whirlpool tick array
+pub const WHIRLPOOL_TICK_ARRAY_SIZE: i32 = 88;
+
+// Tick array layout: discriminator, start_tick_index, the ticks, then the whirlpool. Each
+// tick starts with its initialized flag and liquidity_net.
+const WHIRLPOOL_TICKS_OFFSET: usize = 8 + 4;
+const WHIRLPOOL_TICK_LEN: usize = 1 + 16 + 16 + 16 + 16 + 16 * 3;
+const WHIRLPOOL_TICK_ARRAY_LEN: usize =
+    WHIRLPOOL_TICKS_OFFSET + WHIRLPOOL_TICK_ARRAY_SIZE as usize * WHIRLPOOL_TICK_LEN + 32;
+
+/// Start index and initialized (tick, liquidity_net) entries of a Whirlpool tick array for `whirlpool`
+pub fn parse_whirlpool_tick_array(
+    data: &[u8],
+    whirlpool: &Pubkey,
+    tick_spacing: u16,
+) -> Result<(i32, Vec<(i32, i128)>)> {
+    require!(data.len() >= WHIRLPOOL_TICK_ARRAY_LEN, ErrorCode::AccountDidNotDeserialize);
+    require!(
+        data[WHIRLPOOL_TICK_ARRAY_LEN - 32..WHIRLPOOL_TICK_ARRAY_LEN] == whirlpool.to_bytes(),
+        TwistError::InvalidAccount
+    );
+    
+    let start_index = i32::from_le_bytes(data[8..12].try_into().unwrap());
+    let ticks = data[WHIRLPOOL_TICKS_OFFSET..WHIRLPOOL_TICK_ARRAY_LEN - 32]
+        .chunks_exact(WHIRLPOOL_TICK_LEN)
+        .enumerate()
+        .filter(|(_, tick)| tick[0] != 0)
+        .map(|(offset, tick)| {
+            (
+                start_index + offset as i32 * tick_spacing as i32,
+                i128::from_le_bytes(tick[1..17].try_into().unwrap()),
+            )
+        })
+        .collect();
+    
+    Ok((start_index, ticks))
+}
+
 #[derive(Clone)]
 pub struct Whirlpool;
 
@@ -30,8 +75,12 @@ impl anchor_lang::Id for Whirlpool {
     }
 }
 
-// Orca Whirlpool account structure (simplified)
-#[account]
+// Anchor discriminator of Orca's `Whirlpool` account, sha256("account:Whirlpool")[..8]
+pub const WHIRLPOOL_DISCRIMINATOR: [u8; 8] = [63, 149, 209, 12, 225, 128, 99, 9];
+
+// Orca Whirlpool account structure (simplified). Orca names the account `Whirlpool`, so
+// the discriminator and owner are implemented by hand rather than derived by #[account]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct WhirlpoolState {
     pub whirlpools_config: Pubkey,
     pub whirlpool_bump: [u8; 1],
@@ -64,7 +113,42 @@ pub struct RewardInfo {
 }
 
 impl WhirlpoolState {
-    pub const LEN: usize = 8 + 653; // discriminator + data
+    pub const LEN: usize = 8 + 261 + 384; // discriminator + pool fields + reward infos
+    
+    /// Deserialize whirlpool account data, checking Orca's discriminator
+    pub fn from_account_data(data: &[u8]) -> Result<Self> {
+        require!(
+            data.len() >= Self::LEN && data[..8] == WHIRLPOOL_DISCRIMINATOR,
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+        Self::deserialize(&mut &data[8..Self::LEN])
+            .map_err(|_| ErrorCode::AccountDidNotDeserialize.into())
+    }
+}
+
+impl anchor_lang::Discriminator for WhirlpoolState {
+    const DISCRIMINATOR: [u8; 8] = WHIRLPOOL_DISCRIMINATOR;
+}
+
+impl anchor_lang::Owner for WhirlpoolState {
+    fn owner() -> Pubkey {
+        ORCA_WHIRLPOOL_PROGRAM_ID
+    }
+}
+
+// Read-only here; the Whirlpool program owns the data
+impl anchor_lang::AccountSerialize for WhirlpoolState {}
+
+impl anchor_lang::AccountDeserialize for WhirlpoolState {
+    fn try_deserialize(buf: &mut &[u8]) -> Result<Self> {
+        Self::from_account_data(buf)
+    }
+    
+    fn try_deserialize_unchecked(buf: &mut &[u8]) -> Result<Self> {
+        require!(buf.len() >= Self::LEN, ErrorCode::AccountDidNotDeserialize);
+        Self::deserialize(&mut &buf[8..Self::LEN])
+            .map_err(|_| ErrorCode::AccountDidNotDeserialize.into())
+    }
 }
 
 // Oracle account for storing pool price
@@ -158,6 +242,601 @@ pub fn build_swap_ix(
     })
 }
 
+/// Accounts for a Whirlpool swap signed by a program PDA. Token A is TWIST, token B is USDC.
+pub struct WhirlpoolSwap<'info> {
+    pub whirlpool_program: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub token_authority: AccountInfo<'info>,
+    pub whirlpool: AccountInfo<'info>,
+    pub token_owner_account_a: AccountInfo<'info>,
+    pub token_vault_a: AccountInfo<'info>,
+    pub token_owner_account_b: AccountInfo<'info>,
+    pub token_vault_b: AccountInfo<'info>,
+    pub tick_arrays: [AccountInfo<'info>; 3],
+    pub oracle: AccountInfo<'info>,
+}
+
+impl<'info> WhirlpoolSwap<'info> {
+    /// Validate the pool accounts against the whirlpool state and invoke the swap
+    pub fn invoke(
+        &self,
+        whirlpool_state: &WhirlpoolState,
+        params: SwapParams,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        require_keys_eq!(self.whirlpool_program.key(), ORCA_WHIRLPOOL_PROGRAM_ID);
+        require_keys_eq!(self.token_vault_a.key(), whirlpool_state.token_vault_a);
+        require_keys_eq!(self.token_vault_b.key(), whirlpool_state.token_vault_b);
+        
+        let tick_arrays = get_tick_array_pubkeys(
+            &self.whirlpool.key(),
+            whirlpool_state.tick_current_index,
+            whirlpool_state.tick_spacing,
+            params.a_to_b,
+            &self.whirlpool_program.key(),
+        )?;
+        for (expected, account) in tick_arrays.iter().zip(self.tick_arrays.iter()) {
+            require_keys_eq!(*expected, account.key());
+        }
+        
+        let swap_ix = build_swap_ix(
+            self.whirlpool_program.key(),
+            self.token_program.key(),
+            self.token_authority.key(),
+            self.whirlpool.key(),
+            self.token_owner_account_a.key(),
+            self.token_vault_a.key(),
+            self.token_owner_account_b.key(),
+            self.token_vault_b.key(),
+            self.tick_arrays[0].key(),
+            self.tick_arrays[1].key(),
+            self.tick_arrays[2].key(),
+            self.oracle.key(),
+            params,
+        )?;
+        
+        anchor_lang::solana_program::program::invoke_signed(
+            &swap_ix,
+            &[
+                self.token_program.clone(),
+                self.token_authority.clone(),
+                self.whirlpool.clone(),
+                self.token_owner_account_a.clone(),
+                self.token_vault_a.clone(),
+                self.token_owner_account_b.clone(),
+                self.token_vault_b.clone(),
+                self.tick_arrays[0].clone(),
+                self.tick_arrays[1].clone(),
+                self.tick_arrays[2].clone(),
+                self.oracle.clone(),
+                self.whirlpool_program.clone(),
+            ],
+            signer_seeds,
+        )?;
+        
+        Ok(())
+    }
+}
+
+/// Whirlpool swap venue with the pool state it was loaded with
+pub struct WhirlpoolAdapter<'info> {
+    pub swap: WhirlpoolSwap<'info>,
+    pub state: Box<WhirlpoolState>,
+}
+
+impl<'info> WhirlpoolAdapter<'info> {
+    /// Initialized ticks ahead of the price in swap order, and the tick where the supplied
+    /// tick arrays run out
+    fn ticks_ahead(&self, a_to_b: bool) -> Result<(Vec<(i32, i128)>, i32)> {
+        let ticks_in_array = WHIRLPOOL_TICK_ARRAY_SIZE * self.state.tick_spacing as i32;
+        let current = self.state.tick_current_index;
+        let mut ticks = Vec::new();
+        let mut limit_tick = current;
+        
+        for tick_array in &self.swap.tick_arrays {
+            require_keys_eq!(*tick_array.owner, ORCA_WHIRLPOOL_PROGRAM_ID, TwistError::InvalidAccount);
+            let (start_index, array_ticks) = parse_whirlpool_tick_array(
+                &tick_array.try_borrow_data()?,
+                &self.swap.whirlpool.key(),
+                self.state.tick_spacing,
+            )?;
+            
+            if a_to_b {
+                limit_tick = limit_tick.min(start_index);
+                ticks.extend(array_ticks.into_iter().filter(|(tick, _)| *tick <= current));
+            } else {
+                limit_tick = limit_tick.max(start_index + ticks_in_array);
+                ticks.extend(array_ticks.into_iter().filter(|(tick, _)| *tick > current));
+            }
+        }
+        
+        if a_to_b {
+            ticks.retain(|(tick, _)| *tick >= limit_tick);
+            ticks.sort_by_key(|(tick, _)| std::cmp::Reverse(*tick));
+        } else {
+            ticks.retain(|(tick, _)| *tick <= limit_tick);
+            ticks.sort_by_key(|(tick, _)| *tick);
+        }
+        ticks.dedup_by_key(|(tick, _)| *tick);
+        
+        Ok((ticks, limit_tick.clamp(MIN_TICK_INDEX, MAX_TICK_INDEX)))
+    }
+}
+
+impl<'info> DexAdapter<'info> for WhirlpoolAdapter<'info> {
+    fn venue(&self) -> DexVenue {
+        DexVenue::Whirlpool
+    }
+    
+    fn pool(&self) -> Pubkey {
+        self.swap.whirlpool.key()
+    }
+    
+    fn quote(&self, amount_in: u64, a_to_b: bool) -> Result<SwapQuote> {
+        // fee_rate is in hundredths of a basis point
+        let fee = (amount_in as u128 * self.state.fee_rate as u128).div_ceil(1_000_000) as u64;
+        let amount_after_fee = amount_in.saturating_sub(fee);
+        
+        // Walk the swap's tick arrays so a large order sees liquidity change as it crosses
+        let (ticks, limit_tick) = self.ticks_ahead(a_to_b)?;
+        let amount_out = compute_swap_across_ticks(
+            self.state.sqrt_price,
+            self.state.liquidity,
+            amount_after_fee,
+            a_to_b,
+            &ticks,
+            limit_tick,
+        )?;
+        let spot_out = spot_amount_out(self.state.sqrt_price, amount_after_fee, a_to_b)?;
+        
+        Ok(SwapQuote {
+            venue: DexVenue::Whirlpool,
+            pool: self.pool(),
+            amount_in,
+            amount_out,
+            spot_amount_out: spot_out,
+            price_impact_bps: price_impact_bps(spot_out, amount_out),
+        })
+    }
+    
+    fn swap(
+        &self,
+        amount_in: u64,
+        min_amount_out: u64,
+        a_to_b: bool,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        self.swap.invoke(
+            &self.state,
+            SwapParams {
+                amount: amount_in,
+                other_amount_threshold: min_amount_out,
+                sqrt_price_limit: 0, // Bounded by other_amount_threshold
+                amount_specified_is_input: true,
+                a_to_b,
+            },
+            signer_seeds,
+        )
+    }
+}
+
+/// Load a Whirlpool venue from
+/// [whirlpool_program, whirlpool, token_vault_a, token_vault_b, tick_array_0, tick_array_1, tick_array_2, oracle]
+pub fn load_whirlpool_adapter<'info>(
+    accounts: &[AccountInfo<'info>],
+    endpoints: &SwapEndpoints<'info>,
+) -> Result<WhirlpoolAdapter<'info>> {
+    let [whirlpool_program, whirlpool, token_vault_a, token_vault_b, tick_array_0, tick_array_1, tick_array_2, oracle] =
+        accounts
+    else {
+        return err!(TwistError::InvalidAccount);
+    };
+    
+    let state = Box::new(load_whirlpool_state(whirlpool)?);
+    require_keys_eq!(state.token_mint_a, endpoints.twist_mint, TwistError::InvalidAccount);
+    
+    let swap = WhirlpoolSwap {
+        whirlpool_program: whirlpool_program.clone(),
+        token_program: endpoints.token_program.clone(),
+        token_authority: endpoints.token_authority.clone(),
+        whirlpool: whirlpool.clone(),
+        token_owner_account_a: endpoints.twist_account.clone(),
+        token_vault_a: token_vault_a.clone(),
+        token_owner_account_b: endpoints.usdc_account.clone(),
+        token_vault_b: token_vault_b.clone(),
+        tick_arrays: [tick_array_0.clone(), tick_array_1.clone(), tick_array_2.clone()],
+        oracle: oracle.clone(),
+    };
+    
+    Ok(WhirlpoolAdapter { swap, state })
+}
+
+/// Deserialize a whirlpool account owned by the Whirlpool program
+pub fn load_whirlpool_state(whirlpool: &AccountInfo) -> Result<WhirlpoolState> {
+    require_keys_eq!(*whirlpool.owner, ORCA_WHIRLPOOL_PROGRAM_ID);
+    WhirlpoolState::from_account_data(&whirlpool.try_borrow_data()?)
+}
+
+// Leading fields of an Orca position account
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct WhirlpoolPositionInfo {
+    pub whirlpool: Pubkey,
+    pub position_mint: Pubkey,
+    pub liquidity: u128,
+    pub tick_lower_index: i32,
+    pub tick_upper_index: i32,
+    pub fee_growth_checkpoint_a: u128,
+    pub fee_owed_a: u64, // As of the last update_fees_and_rewards
+    pub fee_growth_checkpoint_b: u128,
+    pub fee_owed_b: u64,
+}
+
+impl WhirlpoolPositionInfo {
+    pub const LEN: usize = 32 + 32 + 16 + 4 + 4 + 16 + 8 + 16 + 8;
+}
+
+/// Read the range, liquidity and fees owed of a position owned by the Whirlpool program
+pub fn load_whirlpool_position(position: &AccountInfo) -> Result<WhirlpoolPositionInfo> {
+    require_keys_eq!(*position.owner, ORCA_WHIRLPOOL_PROGRAM_ID);
+    let data = position.try_borrow_data()?;
+    require!(data.len() >= 8 + WhirlpoolPositionInfo::LEN, ErrorCode::AccountDidNotDeserialize);
+    WhirlpoolPositionInfo::deserialize(&mut &data[8..8 + WhirlpoolPositionInfo::LEN])
+        .map_err(|_| ErrorCode::AccountDidNotDeserialize.into())
+}
+
+/// Address of the Whirlpool position account for a position NFT mint
+pub fn whirlpool_position_address(position_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"position", position_mint.as_ref()], &ORCA_WHIRLPOOL_PROGRAM_ID)
+}
+
+// Orca liquidity instruction discriminators, sha256("global:<name>")[..8]
+pub const OPEN_POSITION_DISCRIMINATOR: [u8; 8] = [135, 128, 47, 77, 15, 152, 240, 49];
+pub const INCREASE_LIQUIDITY_DISCRIMINATOR: [u8; 8] = [46, 156, 243, 118, 13, 205, 251, 178];
+pub const DECREASE_LIQUIDITY_DISCRIMINATOR: [u8; 8] = [160, 38, 208, 111, 104, 91, 44, 1];
+pub const UPDATE_FEES_AND_REWARDS_DISCRIMINATOR: [u8; 8] = [154, 230, 250, 13, 236, 209, 75, 223];
+pub const COLLECT_FEES_DISCRIMINATOR: [u8; 8] = [164, 152, 207, 99, 30, 186, 19, 182];
+pub const CLOSE_POSITION_DISCRIMINATOR: [u8; 8] = [123, 134, 81, 0, 49, 68, 98, 98];
+
+/// Keys of a Whirlpool position and the pool accounts its liquidity instructions touch
+#[derive(Clone, Copy, Debug)]
+pub struct WhirlpoolPositionKeys {
+    pub whirlpool: Pubkey,
+    pub position_authority: Pubkey,
+    pub position: Pubkey,
+    pub position_token_account: Pubkey,
+    pub token_vault_a: Pubkey,
+    pub token_vault_b: Pubkey,
+    pub tick_array_lower: Pubkey,
+    pub tick_array_upper: Pubkey,
+}
+
+// Whirlpool's ModifyLiquidity accounts, shared by increase and decrease liquidity
+fn modify_liquidity_metas(
+    keys: &WhirlpoolPositionKeys,
+    token_owner_account_a: Pubkey,
+    token_owner_account_b: Pubkey,
+) -> Vec<AccountMeta> {
+    vec![
+        AccountMeta::new(keys.whirlpool, false),
+        AccountMeta::new_readonly(anchor_spl::token::ID, false),
+        AccountMeta::new_readonly(keys.position_authority, true),
+        AccountMeta::new(keys.position, false),
+        AccountMeta::new_readonly(keys.position_token_account, false),
+        AccountMeta::new(token_owner_account_a, false),
+        AccountMeta::new(token_owner_account_b, false),
+        AccountMeta::new(keys.token_vault_a, false),
+        AccountMeta::new(keys.token_vault_b, false),
+        AccountMeta::new(keys.tick_array_lower, false),
+        AccountMeta::new(keys.tick_array_upper, false),
+    ]
+}
+
+fn modify_liquidity_data(discriminator: [u8; 8], liquidity: u128, token_a: u64, token_b: u64) -> Vec<u8> {
+    let mut data = discriminator.to_vec();
+    data.extend_from_slice(&liquidity.to_le_bytes());
+    data.extend_from_slice(&token_a.to_le_bytes());
+    data.extend_from_slice(&token_b.to_le_bytes());
+    data
+}
+
+/// Open a position owned by `owner`, with its NFT in `owner`'s associated token account
+pub fn build_orca_open_position_ix(
+    funder: Pubkey,
+    owner: Pubkey,
+    position_mint: Pubkey,
+    position_token_account: Pubkey,
+    whirlpool: Pubkey,
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+) -> Instruction {
+    let (position, position_bump) = whirlpool_position_address(&position_mint);
+    let accounts = vec![
+        AccountMeta::new(funder, true),
+        AccountMeta::new_readonly(owner, false),
+        AccountMeta::new(position, false),
+        AccountMeta::new(position_mint, true),
+        AccountMeta::new(position_token_account, false),
+        AccountMeta::new_readonly(whirlpool, false),
+        AccountMeta::new_readonly(anchor_spl::token::ID, false),
+        AccountMeta::new_readonly(anchor_lang::system_program::ID, false),
+        AccountMeta::new_readonly(anchor_lang::solana_program::sysvar::rent::ID, false),
+        AccountMeta::new_readonly(anchor_spl::associated_token::ID, false),
+    ];
+    
+    let mut data = OPEN_POSITION_DISCRIMINATOR.to_vec();
+    data.push(position_bump);
+    data.extend_from_slice(&tick_lower_index.to_le_bytes());
+    data.extend_from_slice(&tick_upper_index.to_le_bytes());
+    
+    Instruction {
+        program_id: ORCA_WHIRLPOOL_PROGRAM_ID,
+        accounts,
+        data,
+    }
+}
+
+pub fn build_orca_increase_liquidity_ix(
+    keys: &WhirlpoolPositionKeys,
+    token_owner_account_a: Pubkey,
+    token_owner_account_b: Pubkey,
+    liquidity: u128,
+    token_max_a: u64,
+    token_max_b: u64,
+) -> Instruction {
+    Instruction {
+        program_id: ORCA_WHIRLPOOL_PROGRAM_ID,
+        accounts: modify_liquidity_metas(keys, token_owner_account_a, token_owner_account_b),
+        data: modify_liquidity_data(INCREASE_LIQUIDITY_DISCRIMINATOR, liquidity, token_max_a, token_max_b),
+    }
+}
+
+pub fn build_orca_decrease_liquidity_ix(
+    keys: &WhirlpoolPositionKeys,
+    token_owner_account_a: Pubkey,
+    token_owner_account_b: Pubkey,
+    liquidity: u128,
+    token_min_a: u64,
+    token_min_b: u64,
+) -> Instruction {
+    Instruction {
+        program_id: ORCA_WHIRLPOOL_PROGRAM_ID,
+        accounts: modify_liquidity_metas(keys, token_owner_account_a, token_owner_account_b),
+        data: modify_liquidity_data(DECREASE_LIQUIDITY_DISCRIMINATOR, liquidity, token_min_a, token_min_b),
+    }
+}
+
+/// Bring a position's owed fees up to date; Whirlpool requires it to hold liquidity
+pub fn build_orca_update_fees_ix(keys: &WhirlpoolPositionKeys) -> Instruction {
+    Instruction {
+        program_id: ORCA_WHIRLPOOL_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(keys.whirlpool, false),
+            AccountMeta::new(keys.position, false),
+            AccountMeta::new_readonly(keys.tick_array_lower, false),
+            AccountMeta::new_readonly(keys.tick_array_upper, false),
+        ],
+        data: UPDATE_FEES_AND_REWARDS_DISCRIMINATOR.to_vec(),
+    }
+}
+
+pub fn build_orca_collect_fees_ix(
+    keys: &WhirlpoolPositionKeys,
+    token_owner_account_a: Pubkey,
+    token_owner_account_b: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: ORCA_WHIRLPOOL_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new_readonly(keys.whirlpool, false),
+            AccountMeta::new_readonly(keys.position_authority, true),
+            AccountMeta::new(keys.position, false),
+            AccountMeta::new_readonly(keys.position_token_account, false),
+            AccountMeta::new(token_owner_account_a, false),
+            AccountMeta::new(keys.token_vault_a, false),
+            AccountMeta::new(token_owner_account_b, false),
+            AccountMeta::new(keys.token_vault_b, false),
+            AccountMeta::new_readonly(anchor_spl::token::ID, false),
+        ],
+        data: COLLECT_FEES_DISCRIMINATOR.to_vec(),
+    }
+}
+
+/// Close an empty position, burning its NFT and paying the rent of both to `receiver`
+pub fn build_orca_close_position_ix(
+    keys: &WhirlpoolPositionKeys,
+    receiver: Pubkey,
+    position_mint: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: ORCA_WHIRLPOOL_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new_readonly(keys.position_authority, true),
+            AccountMeta::new(receiver, false),
+            AccountMeta::new(keys.position, false),
+            AccountMeta::new(position_mint, false),
+            AccountMeta::new(keys.position_token_account, false),
+            AccountMeta::new_readonly(anchor_spl::token::ID, false),
+        ],
+        data: CLOSE_POSITION_DISCRIMINATOR.to_vec(),
+    }
+}
+
+/// Accounts for managing a program-owned Whirlpool position. The position authority is a
+/// program PDA and token A is TWIST, token B is USDC.
+pub struct WhirlpoolPositionCpi<'info> {
+    pub whirlpool_program: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub whirlpool: AccountInfo<'info>,
+    pub position_authority: AccountInfo<'info>,
+    pub position: AccountInfo<'info>,
+    pub position_token_account: AccountInfo<'info>,
+    pub token_vault_a: AccountInfo<'info>,
+    pub token_vault_b: AccountInfo<'info>,
+    pub tick_array_lower: AccountInfo<'info>,
+    pub tick_array_upper: AccountInfo<'info>,
+}
+
+impl<'info> WhirlpoolPositionCpi<'info> {
+    pub fn keys(&self) -> WhirlpoolPositionKeys {
+        WhirlpoolPositionKeys {
+            whirlpool: self.whirlpool.key(),
+            position_authority: self.position_authority.key(),
+            position: self.position.key(),
+            position_token_account: self.position_token_account.key(),
+            token_vault_a: self.token_vault_a.key(),
+            token_vault_b: self.token_vault_b.key(),
+            tick_array_lower: self.tick_array_lower.key(),
+            tick_array_upper: self.tick_array_upper.key(),
+        }
+    }
+    
+    pub fn increase_liquidity(
+        &self,
+        token_owner_account_a: &AccountInfo<'info>,
+        token_owner_account_b: &AccountInfo<'info>,
+        liquidity: u128,
+        token_max_a: u64,
+        token_max_b: u64,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        let ix = build_orca_increase_liquidity_ix(
+            &self.keys(),
+            token_owner_account_a.key(),
+            token_owner_account_b.key(),
+            liquidity,
+            token_max_a,
+            token_max_b,
+        );
+        self.invoke_modify_liquidity(&ix, token_owner_account_a, token_owner_account_b, signer_seeds)
+    }
+    
+    pub fn decrease_liquidity(
+        &self,
+        token_owner_account_a: &AccountInfo<'info>,
+        token_owner_account_b: &AccountInfo<'info>,
+        liquidity: u128,
+        token_min_a: u64,
+        token_min_b: u64,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        let ix = build_orca_decrease_liquidity_ix(
+            &self.keys(),
+            token_owner_account_a.key(),
+            token_owner_account_b.key(),
+            liquidity,
+            token_min_a,
+            token_min_b,
+        );
+        self.invoke_modify_liquidity(&ix, token_owner_account_a, token_owner_account_b, signer_seeds)
+    }
+    
+    /// Bring the position's fees owed up to date with the pool
+    pub fn update_fees(&self) -> Result<()> {
+        require_keys_eq!(self.whirlpool_program.key(), ORCA_WHIRLPOOL_PROGRAM_ID);
+        
+        anchor_lang::solana_program::program::invoke(
+            &build_orca_update_fees_ix(&self.keys()),
+            &[
+                self.whirlpool.clone(),
+                self.position.clone(),
+                self.tick_array_lower.clone(),
+                self.tick_array_upper.clone(),
+            ],
+        )?;
+        
+        Ok(())
+    }
+    
+    /// Update the position's owed fees and collect them into the owner accounts
+    pub fn collect_fees(
+        &self,
+        token_owner_account_a: &AccountInfo<'info>,
+        token_owner_account_b: &AccountInfo<'info>,
+        update_first: bool,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        require_keys_eq!(self.whirlpool_program.key(), ORCA_WHIRLPOOL_PROGRAM_ID);
+        let keys = self.keys();
+        
+        if update_first {
+            self.update_fees()?;
+        }
+        
+        anchor_lang::solana_program::program::invoke_signed(
+            &build_orca_collect_fees_ix(&keys, token_owner_account_a.key(), token_owner_account_b.key()),
+            &[
+                self.whirlpool.clone(),
+                self.position_authority.clone(),
+                self.position.clone(),
+                self.position_token_account.clone(),
+                token_owner_account_a.clone(),
+                self.token_vault_a.clone(),
+                token_owner_account_b.clone(),
+                self.token_vault_b.clone(),
+                self.token_program.clone(),
+            ],
+            signer_seeds,
+        )?;
+        
+        Ok(())
+    }
+    
+    /// Close the position once its liquidity and fees are withdrawn
+    pub fn close_position(
+        &self,
+        receiver: &AccountInfo<'info>,
+        position_mint: &AccountInfo<'info>,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        require_keys_eq!(self.whirlpool_program.key(), ORCA_WHIRLPOOL_PROGRAM_ID);
+        
+        anchor_lang::solana_program::program::invoke_signed(
+            &build_orca_close_position_ix(&self.keys(), receiver.key(), position_mint.key()),
+            &[
+                self.position_authority.clone(),
+                receiver.clone(),
+                self.position.clone(),
+                position_mint.clone(),
+                self.position_token_account.clone(),
+                self.token_program.clone(),
+            ],
+            signer_seeds,
+        )?;
+        
+        Ok(())
+    }
+    
+    fn invoke_modify_liquidity(
+        &self,
+        ix: &Instruction,
+        token_owner_account_a: &AccountInfo<'info>,
+        token_owner_account_b: &AccountInfo<'info>,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        require_keys_eq!(self.whirlpool_program.key(), ORCA_WHIRLPOOL_PROGRAM_ID);
+        
+        anchor_lang::solana_program::program::invoke_signed(
+            ix,
+            &[
+                self.whirlpool.clone(),
+                self.token_program.clone(),
+                self.position_authority.clone(),
+                self.position.clone(),
+                self.position_token_account.clone(),
+                token_owner_account_a.clone(),
+                token_owner_account_b.clone(),
+                self.token_vault_a.clone(),
+                self.token_vault_b.clone(),
+                self.tick_array_lower.clone(),
+                self.tick_array_upper.clone(),
+            ],
+            signer_seeds,
+        )?;
+        
+        Ok(())
+    }
+}
+
 // Helper to find tick arrays for a position
 pub fn get_tick_array_pubkeys(
     whirlpool: &Pubkey,
@@ -205,4 +884,237 @@ fn get_tick_array_pda(
         program_id,
     );
     Ok(pda)
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn whirlpool_state() -> WhirlpoolState {
+        WhirlpoolState {
+            whirlpools_config: Pubkey::new_unique(),
+            whirlpool_bump: [255],
+            tick_spacing: 64,
+            tick_spacing_seed: 64u16.to_le_bytes(),
+            fee_rate: 3000,
+            protocol_fee_rate: 300,
+            liquidity: 1_000_000,
+            sqrt_price: 1u128 << 64,
+            tick_current_index: -12,
+            protocol_fee_owed_a: 0,
+            protocol_fee_owed_b: 0,
+            token_mint_a: Pubkey::new_unique(),
+            token_vault_a: Pubkey::new_unique(),
+            fee_growth_global_a: 0,
+            token_mint_b: Pubkey::new_unique(),
+            token_vault_b: Pubkey::new_unique(),
+            fee_growth_global_b: 0,
+            reward_last_updated_timestamp: 0,
+            reward_infos: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_load_whirlpool_state_with_orca_discriminator() {
+        let state = whirlpool_state();
+        let mut data = WHIRLPOOL_DISCRIMINATOR.to_vec();
+        data.extend(state.try_to_vec().unwrap());
+        assert_eq!(data.len(), WhirlpoolState::LEN);
+
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let account = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &ORCA_WHIRLPOOL_PROGRAM_ID, false, 0);
+        let loaded = load_whirlpool_state(&account).unwrap();
+        assert_eq!(loaded.sqrt_price, state.sqrt_price);
+        assert_eq!(loaded.tick_current_index, -12);
+        assert_eq!(loaded.token_vault_b, state.token_vault_b);
+
+        // The Anchor-derived discriminator for our own struct name is not Orca's
+        let mut wrong = data.clone();
+        wrong[..8].copy_from_slice(&anchor_lang::solana_program::hash::hash(b"account:WhirlpoolState").to_bytes()[..8]);
+        assert!(WhirlpoolState::from_account_data(&wrong).is_err());
+        assert!(WhirlpoolState::try_deserialize(&mut &data[..]).is_ok());
+    }
+
+    #[test]
+    fn test_parse_tick_array_keeps_initialized_ticks() {
+        let whirlpool = Pubkey::new_unique();
+        let mut data = vec![0u8; WHIRLPOOL_TICK_ARRAY_LEN];
+        data[8..12].copy_from_slice(&(-5_632i32).to_le_bytes());
+        data[WHIRLPOOL_TICK_ARRAY_LEN - 32..].copy_from_slice(&whirlpool.to_bytes());
+
+        // The third tick is initialized with negative net liquidity: -5632 + 2 * 64
+        let offset = WHIRLPOOL_TICKS_OFFSET + 2 * WHIRLPOOL_TICK_LEN;
+        data[offset] = 1;
+        data[offset + 1..offset + 17].copy_from_slice(&(-5_000i128).to_le_bytes());
+
+        let (start_index, ticks) = parse_whirlpool_tick_array(&data, &whirlpool, 64).unwrap();
+        assert_eq!(start_index, -5_632);
+        assert_eq!(ticks, vec![(-5_504, -5_000)]);
+
+        assert!(parse_whirlpool_tick_array(&data, &Pubkey::new_unique(), 64).is_err());
+        assert!(parse_whirlpool_tick_array(&data[..100], &whirlpool, 64).is_err());
+    }
+
+    #[test]
+    fn test_quote_crosses_initialized_ticks() {
+        let state = whirlpool_state();
+        let key = Pubkey::new_unique();
+        let whirlpool = Pubkey::new_unique();
+
+        // Tick arrays below the price, with the position's lower bound at -128 where its
+        // liquidity leaves the pool
+        let mut arrays: Vec<Vec<u8>> = [-64 * 88, -2 * 64 * 88, -3 * 64 * 88]
+            .iter()
+            .map(|start: &i32| {
+                let mut data = vec![0u8; WHIRLPOOL_TICK_ARRAY_LEN];
+                data[8..12].copy_from_slice(&start.to_le_bytes());
+                data[WHIRLPOOL_TICK_ARRAY_LEN - 32..].copy_from_slice(&whirlpool.to_bytes());
+                data
+            })
+            .collect();
+        let offset = WHIRLPOOL_TICKS_OFFSET + 86 * WHIRLPOOL_TICK_LEN; // -5632 + 86 * 64 = -128
+        arrays[0][offset] = 1;
+        arrays[0][offset + 1..offset + 17].copy_from_slice(&(state.liquidity as i128 * 9 / 10).to_le_bytes());
+
+        let mut lamports = [0u64; 4];
+        let mut empty = Vec::new();
+        let [l0, l1, l2, l3] = &mut lamports;
+        let [a0, a1, a2] = &mut arrays[..] else { unreachable!() };
+        let tick_arrays = [
+            AccountInfo::new(&key, false, false, l0, a0, &ORCA_WHIRLPOOL_PROGRAM_ID, false, 0),
+            AccountInfo::new(&key, false, false, l1, a1, &ORCA_WHIRLPOOL_PROGRAM_ID, false, 0),
+            AccountInfo::new(&key, false, false, l2, a2, &ORCA_WHIRLPOOL_PROGRAM_ID, false, 0),
+        ];
+        let any = AccountInfo::new(&key, false, false, l3, &mut empty, &ORCA_WHIRLPOOL_PROGRAM_ID, false, 0);
+        let mut whirlpool_lamports = 0;
+        let mut whirlpool_data = Vec::new();
+        let whirlpool_info = AccountInfo::new(&whirlpool, false, false, &mut whirlpool_lamports, &mut whirlpool_data, &ORCA_WHIRLPOOL_PROGRAM_ID, false, 0);
+
+        let adapter = WhirlpoolAdapter {
+            swap: WhirlpoolSwap {
+                whirlpool_program: any.clone(),
+                token_program: any.clone(),
+                token_authority: any.clone(),
+                whirlpool: whirlpool_info,
+                token_owner_account_a: any.clone(),
+                token_vault_a: any.clone(),
+                token_owner_account_b: any.clone(),
+                token_vault_b: any.clone(),
+                tick_arrays,
+                oracle: any,
+            },
+            state: Box::new(state.clone()),
+        };
+
+        // A small order stays above the tick and matches the single-range math
+        let fee = |amount: u64| amount - (amount as u128 * 3000).div_ceil(1_000_000) as u64;
+        let small = adapter.quote(1_000, true).unwrap();
+        let (in_range, _) = crate::defi::compute_swap_in_range(state.sqrt_price, state.liquidity, fee(1_000), true).unwrap();
+        assert_eq!(small.amount_out, in_range);
+
+        // A large one crosses it into thin liquidity and gets less than the in-range model
+        let large = adapter.quote(20_000, true).unwrap();
+        let (in_range, _) = crate::defi::compute_swap_in_range(state.sqrt_price, state.liquidity, fee(20_000), true).unwrap();
+        assert!(large.amount_out < in_range);
+    }
+
+    #[test]
+    fn test_load_position_reads_fees_owed() {
+        // Orca's Position: whirlpool, mint, liquidity, range, then a checkpoint and fees owed per token
+        let mut data = vec![0u8; 216];
+        data[72..88].copy_from_slice(&5_000u128.to_le_bytes());
+        data[88..92].copy_from_slice(&(-128i32).to_le_bytes());
+        data[92..96].copy_from_slice(&256i32.to_le_bytes());
+        data[112..120].copy_from_slice(&700u64.to_le_bytes());
+        data[136..144].copy_from_slice(&90u64.to_le_bytes());
+
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let account = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &ORCA_WHIRLPOOL_PROGRAM_ID, false, 0);
+        let position = load_whirlpool_position(&account).unwrap();
+        assert_eq!(position.liquidity, 5_000);
+        assert_eq!((position.tick_lower_index, position.tick_upper_index), (-128, 256));
+        assert_eq!((position.fee_owed_a, position.fee_owed_b), (700, 90));
+    }
+
+    fn discriminator(name: &str) -> [u8; 8] {
+        let hash = anchor_lang::solana_program::hash::hash(format!("global:{}", name).as_bytes());
+        hash.to_bytes()[..8].try_into().unwrap()
+    }
+
+    #[test]
+    fn test_position_instruction_encoding() {
+        assert_eq!(discriminator("open_position"), OPEN_POSITION_DISCRIMINATOR);
+        assert_eq!(discriminator("increase_liquidity"), INCREASE_LIQUIDITY_DISCRIMINATOR);
+        assert_eq!(discriminator("decrease_liquidity"), DECREASE_LIQUIDITY_DISCRIMINATOR);
+        assert_eq!(discriminator("update_fees_and_rewards"), UPDATE_FEES_AND_REWARDS_DISCRIMINATOR);
+        assert_eq!(discriminator("collect_fees"), COLLECT_FEES_DISCRIMINATOR);
+        assert_eq!(discriminator("close_position"), CLOSE_POSITION_DISCRIMINATOR);
+
+        let keys = WhirlpoolPositionKeys {
+            whirlpool: Pubkey::new_unique(),
+            position_authority: Pubkey::new_unique(),
+            position: Pubkey::new_unique(),
+            position_token_account: Pubkey::new_unique(),
+            token_vault_a: Pubkey::new_unique(),
+            token_vault_b: Pubkey::new_unique(),
+            tick_array_lower: Pubkey::new_unique(),
+            tick_array_upper: Pubkey::new_unique(),
+        };
+        let (owner_a, owner_b) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+        let ix = build_orca_decrease_liquidity_ix(&keys, owner_a, owner_b, 1_000, 7, 9);
+        assert_eq!(ix.program_id, ORCA_WHIRLPOOL_PROGRAM_ID);
+        assert_eq!(ix.data.len(), 8 + 16 + 8 + 8);
+        assert_eq!(&ix.data[..8], &DECREASE_LIQUIDITY_DISCRIMINATOR);
+        assert_eq!(u128::from_le_bytes(ix.data[8..24].try_into().unwrap()), 1_000);
+        assert_eq!(u64::from_le_bytes(ix.data[24..32].try_into().unwrap()), 7);
+        assert_eq!(u64::from_le_bytes(ix.data[32..40].try_into().unwrap()), 9);
+        let order: Vec<Pubkey> = ix.accounts.iter().map(|meta| meta.pubkey).collect();
+        assert_eq!(order, vec![
+            keys.whirlpool,
+            anchor_spl::token::ID,
+            keys.position_authority,
+            keys.position,
+            keys.position_token_account,
+            owner_a,
+            owner_b,
+            keys.token_vault_a,
+            keys.token_vault_b,
+            keys.tick_array_lower,
+            keys.tick_array_upper,
+        ]);
+        assert!(ix.accounts[2].is_signer);
+
+        let ix = build_orca_collect_fees_ix(&keys, owner_a, owner_b);
+        assert_eq!(ix.data, COLLECT_FEES_DISCRIMINATOR.to_vec());
+        assert_eq!(ix.accounts[4].pubkey, owner_a);
+        assert_eq!(ix.accounts[5].pubkey, keys.token_vault_a);
+        assert_eq!(ix.accounts[6].pubkey, owner_b);
+        assert!(ix.accounts[1].is_signer);
+
+        let (receiver, position_mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let ix = build_orca_close_position_ix(&keys, receiver, position_mint);
+        assert_eq!(ix.data, CLOSE_POSITION_DISCRIMINATOR.to_vec());
+        let order: Vec<Pubkey> = ix.accounts.iter().map(|meta| meta.pubkey).collect();
+        assert_eq!(order, vec![
+            keys.position_authority,
+            receiver,
+            keys.position,
+            position_mint,
+            keys.position_token_account,
+            anchor_spl::token::ID,
+        ]);
+        assert!(ix.accounts[0].is_signer);
+
+        let mint = Pubkey::new_unique();
+        let ix = build_orca_open_position_ix(Pubkey::new_unique(), keys.position_authority, mint, Pubkey::new_unique(), keys.whirlpool, -128, 256);
+        let (position, bump) = whirlpool_position_address(&mint);
+        assert_eq!(ix.accounts[2].pubkey, position);
+        assert!(ix.accounts[3].is_signer);
+        assert_eq!(ix.data[8], bump);
+        assert_eq!(i32::from_le_bytes(ix.data[9..13].try_into().unwrap()), -128);
+        assert_eq!(i32::from_le_bytes(ix.data[13..17].try_into().unwrap()), 256);
+    }
+}
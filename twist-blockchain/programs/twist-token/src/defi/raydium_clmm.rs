@@ -0,0 +1,367 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+
+use crate::errors::TwistError;
+use crate::state::DexVenue;
+use super::{
+    compute_swap_across_ticks, price_impact_bps, spot_amount_out, DexAdapter, SwapEndpoints, SwapQuote,
+    MAX_TICK_INDEX, MIN_TICK_INDEX,
+};
+
+// Raydium concentrated liquidity program ID on mainnet
+pub const RAYDIUM_CLMM_PROGRAM_ID: Pubkey = Pubkey::new_from_array([
+    0xa5, 0xd5, 0xca, 0x9e, 0x04, 0xcf, 0x5d, 0xb5,
+    0x90, 0xb7, 0x14, 0xba, 0x2f, 0xe3, 0x2c, 0xb1,
+    0x59, 0x13, 0x3f, 0xc1, 0xc1, 0x92, 0xb7, 0x22,
+    0x57, 0xfd, 0x07, 0xd3, 0x9c, 0xb0, 0x40, 0x1e
+]);
+
+// Raydium swap instruction discriminator
+pub const RAYDIUM_SWAP_DISCRIMINATOR: [u8; 8] = [248, 198, 158, 145, 225, 117, 135, 200];
+
+// Ticks per Raydium tick array
+pub const RAYDIUM_TICK_ARRAY_SIZE: i32 = 60;
+
+// Packed tick array layout: discriminator, pool_id, start_tick_index, then the ticks. Each
+// tick starts with its index, liquidity_net and liquidity_gross.
+const RAYDIUM_TICKS_OFFSET: usize = 8 + 32 + 4;
+const RAYDIUM_TICK_STATE_LEN: usize = 168;
+
+// Leading fields of a Raydium pool state account
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct RaydiumPoolInfo {
+    pub bump: [u8; 1],
+    pub amm_config: Pubkey,
+    pub owner: Pubkey,
+    pub token_mint_0: Pubkey,
+    pub token_mint_1: Pubkey,
+    pub token_vault_0: Pubkey,
+    pub token_vault_1: Pubkey,
+    pub observation_key: Pubkey,
+    pub mint_decimals_0: u8,
+    pub mint_decimals_1: u8,
+    pub tick_spacing: u16,
+    pub liquidity: u128,
+    pub sqrt_price_x64: u128,
+    pub tick_current: i32,
+}
+
+impl RaydiumPoolInfo {
+    pub const LEN: usize = 1 + 32 * 7 + 1 + 1 + 2 + 16 + 16 + 4;
+}
+
+// Leading fields of a Raydium AMM config account
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct RaydiumAmmConfigInfo {
+    pub bump: u8,
+    pub index: u16,
+    pub owner: Pubkey,
+    pub protocol_fee_rate: u32,
+    pub trade_fee_rate: u32, // Hundredths of a basis point
+}
+
+impl RaydiumAmmConfigInfo {
+    pub const LEN: usize = 1 + 2 + 32 + 4 + 4;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct RaydiumSwapParams {
+    pub amount: u64,
+    pub other_amount_threshold: u64,
+    pub sqrt_price_limit_x64: u128,
+    pub is_base_input: bool,
+}
+
+fn load_leading_fields<T: AnchorDeserialize>(account: &AccountInfo, len: usize) -> Result<T> {
+    let data = account.try_borrow_data()?;
+    require!(data.len() >= 8 + len, ErrorCode::AccountDidNotDeserialize);
+    T::deserialize(&mut &data[8..8 + len]).map_err(|_| ErrorCode::AccountDidNotDeserialize.into())
+}
+
+/// Start index of the tick array holding `tick`
+pub fn raydium_tick_array_start_index(tick: i32, tick_spacing: u16) -> i32 {
+    let ticks_in_array = RAYDIUM_TICK_ARRAY_SIZE * tick_spacing as i32;
+    tick.div_euclid(ticks_in_array) * ticks_in_array
+}
+
+pub fn get_raydium_tick_array_pda(pool: &Pubkey, start_index: i32, program_id: &Pubkey) -> Pubkey {
+    let (pda, _) = Pubkey::find_program_address(
+        &[b"tick_array", pool.as_ref(), &start_index.to_be_bytes()],
+        program_id,
+    );
+    pda
+}
+
+/// Start index and initialized (tick, liquidity_net) entries of a Raydium tick array for `pool`
+pub fn parse_raydium_tick_array(data: &[u8], pool: &Pubkey) -> Result<(i32, Vec<(i32, i128)>)> {
+    let ticks_len = RAYDIUM_TICK_ARRAY_SIZE as usize * RAYDIUM_TICK_STATE_LEN;
+    require!(data.len() >= RAYDIUM_TICKS_OFFSET + ticks_len, ErrorCode::AccountDidNotDeserialize);
+    require!(data[8..40] == pool.to_bytes(), TwistError::InvalidAccount);
+    
+    let start_index = i32::from_le_bytes(data[40..44].try_into().unwrap());
+    let ticks = data[RAYDIUM_TICKS_OFFSET..RAYDIUM_TICKS_OFFSET + ticks_len]
+        .chunks_exact(RAYDIUM_TICK_STATE_LEN)
+        .filter(|tick| tick[20..36].iter().any(|byte| *byte != 0)) // liquidity_gross > 0
+        .map(|tick| {
+            (
+                i32::from_le_bytes(tick[0..4].try_into().unwrap()),
+                i128::from_le_bytes(tick[4..20].try_into().unwrap()),
+            )
+        })
+        .collect();
+    
+    Ok((start_index, ticks))
+}
+
+/// Raydium CLMM swap venue. The pool orders tokens by mint, so TWIST may be either side.
+pub struct RaydiumClmmAdapter<'info> {
+    pub program: AccountInfo<'info>,
+    pub amm_config: AccountInfo<'info>,
+    pub pool_state: AccountInfo<'info>,
+    pub token_vault_0: AccountInfo<'info>,
+    pub token_vault_1: AccountInfo<'info>,
+    pub observation_state: AccountInfo<'info>,
+    pub tick_arrays: [AccountInfo<'info>; 3],
+    pub endpoints: SwapEndpoints<'info>,
+    pub pool: RaydiumPoolInfo,
+    pub trade_fee_rate: u32,
+}
+
+impl<'info> RaydiumClmmAdapter<'info> {
+    fn twist_is_token_0(&self) -> bool {
+        self.pool.token_mint_0 == self.endpoints.twist_mint
+    }
+    
+    /// Raydium's direction for a TWIST -> USDC (`a_to_b`) or USDC -> TWIST swap
+    fn zero_for_one(&self, a_to_b: bool) -> bool {
+        a_to_b == self.twist_is_token_0()
+    }
+    
+    /// Initialized ticks ahead of the price in swap order, and the tick where the supplied
+    /// tick arrays run out
+    fn ticks_ahead(&self, zero_for_one: bool) -> Result<(Vec<(i32, i128)>, i32)> {
+        let ticks_in_array = RAYDIUM_TICK_ARRAY_SIZE * self.pool.tick_spacing as i32;
+        let current = self.pool.tick_current;
+        let mut ticks = Vec::new();
+        let mut limit_tick = current;
+        
+        for tick_array in &self.tick_arrays {
+            require_keys_eq!(*tick_array.owner, RAYDIUM_CLMM_PROGRAM_ID, TwistError::InvalidAccount);
+            let (start_index, array_ticks) =
+                parse_raydium_tick_array(&tick_array.try_borrow_data()?, &self.pool_state.key())?;
+            
+            if zero_for_one {
+                limit_tick = limit_tick.min(start_index);
+                ticks.extend(array_ticks.into_iter().filter(|(tick, _)| *tick <= current));
+            } else {
+                limit_tick = limit_tick.max(start_index + ticks_in_array);
+                ticks.extend(array_ticks.into_iter().filter(|(tick, _)| *tick > current));
+            }
+        }
+        
+        if zero_for_one {
+            ticks.retain(|(tick, _)| *tick >= limit_tick);
+            ticks.sort_by_key(|(tick, _)| std::cmp::Reverse(*tick));
+        } else {
+            ticks.retain(|(tick, _)| *tick <= limit_tick);
+            ticks.sort_by_key(|(tick, _)| *tick);
+        }
+        ticks.dedup_by_key(|(tick, _)| *tick);
+        
+        Ok((ticks, limit_tick.clamp(MIN_TICK_INDEX, MAX_TICK_INDEX)))
+    }
+}
+
+impl<'info> DexAdapter<'info> for RaydiumClmmAdapter<'info> {
+    fn venue(&self) -> DexVenue {
+        DexVenue::RaydiumClmm
+    }
+    
+    fn pool(&self) -> Pubkey {
+        self.pool_state.key()
+    }
+    
+    fn quote(&self, amount_in: u64, a_to_b: bool) -> Result<SwapQuote> {
+        let fee = (amount_in as u128 * self.trade_fee_rate as u128).div_ceil(1_000_000) as u64;
+        let amount_after_fee = amount_in.saturating_sub(fee);
+        let zero_for_one = self.zero_for_one(a_to_b);
+        
+        // Walk the supplied tick arrays so a large order sees liquidity change as it crosses
+        let (ticks, limit_tick) = self.ticks_ahead(zero_for_one)?;
+        let amount_out = compute_swap_across_ticks(
+            self.pool.sqrt_price_x64,
+            self.pool.liquidity,
+            amount_after_fee,
+            zero_for_one,
+            &ticks,
+            limit_tick,
+        )?;
+        let spot_out = spot_amount_out(self.pool.sqrt_price_x64, amount_after_fee, zero_for_one)?;
+        
+        Ok(SwapQuote {
+            venue: DexVenue::RaydiumClmm,
+            pool: self.pool(),
+            amount_in,
+            amount_out,
+            spot_amount_out: spot_out,
+            price_impact_bps: price_impact_bps(spot_out, amount_out),
+        })
+    }
+    
+    fn swap(
+        &self,
+        amount_in: u64,
+        min_amount_out: u64,
+        a_to_b: bool,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        let zero_for_one = self.zero_for_one(a_to_b);
+        
+        // The first tick array must hold the current tick; the pool program checks the rest
+        let start_index = raydium_tick_array_start_index(self.pool.tick_current, self.pool.tick_spacing);
+        require_keys_eq!(
+            self.tick_arrays[0].key(),
+            get_raydium_tick_array_pda(&self.pool_state.key(), start_index, &self.program.key()),
+            TwistError::InvalidAccount
+        );
+        
+        let (input_account, output_account) = if a_to_b {
+            (&self.endpoints.twist_account, &self.endpoints.usdc_account)
+        } else {
+            (&self.endpoints.usdc_account, &self.endpoints.twist_account)
+        };
+        let (input_vault, output_vault) = if zero_for_one {
+            (&self.token_vault_0, &self.token_vault_1)
+        } else {
+            (&self.token_vault_1, &self.token_vault_0)
+        };
+        
+        let params = RaydiumSwapParams {
+            amount: amount_in,
+            other_amount_threshold: min_amount_out,
+            sqrt_price_limit_x64: 0, // Bounded by other_amount_threshold
+            is_base_input: true,
+        };
+        let mut data = RAYDIUM_SWAP_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&params.try_to_vec()?);
+        
+        let swap_ix = Instruction {
+            program_id: self.program.key(),
+            accounts: vec![
+                AccountMeta::new_readonly(self.endpoints.token_authority.key(), true),
+                AccountMeta::new_readonly(self.amm_config.key(), false),
+                AccountMeta::new(self.pool_state.key(), false),
+                AccountMeta::new(input_account.key(), false),
+                AccountMeta::new(output_account.key(), false),
+                AccountMeta::new(input_vault.key(), false),
+                AccountMeta::new(output_vault.key(), false),
+                AccountMeta::new(self.observation_state.key(), false),
+                AccountMeta::new_readonly(self.endpoints.token_program.key(), false),
+                AccountMeta::new(self.tick_arrays[0].key(), false),
+                AccountMeta::new(self.tick_arrays[1].key(), false),
+                AccountMeta::new(self.tick_arrays[2].key(), false),
+            ],
+            data,
+        };
+        
+        anchor_lang::solana_program::program::invoke_signed(
+            &swap_ix,
+            &[
+                self.endpoints.token_authority.clone(),
+                self.amm_config.clone(),
+                self.pool_state.clone(),
+                input_account.clone(),
+                output_account.clone(),
+                input_vault.clone(),
+                output_vault.clone(),
+                self.observation_state.clone(),
+                self.endpoints.token_program.clone(),
+                self.tick_arrays[0].clone(),
+                self.tick_arrays[1].clone(),
+                self.tick_arrays[2].clone(),
+                self.program.clone(),
+            ],
+            signer_seeds,
+        )?;
+        
+        Ok(())
+    }
+}
+
+/// Load a Raydium CLMM venue from
+/// [program, amm_config, pool_state, token_vault_0, token_vault_1, observation_state, tick_array_0, tick_array_1, tick_array_2]
+pub fn load_raydium_clmm_adapter<'info>(
+    accounts: &[AccountInfo<'info>],
+    endpoints: &SwapEndpoints<'info>,
+) -> Result<RaydiumClmmAdapter<'info>> {
+    let [program, amm_config, pool_state, token_vault_0, token_vault_1, observation_state, tick_array_0, tick_array_1, tick_array_2] =
+        accounts
+    else {
+        return err!(TwistError::InvalidAccount);
+    };
+    
+    require_keys_eq!(program.key(), RAYDIUM_CLMM_PROGRAM_ID, TwistError::InvalidAccount);
+    require_keys_eq!(*pool_state.owner, RAYDIUM_CLMM_PROGRAM_ID, TwistError::InvalidAccount);
+    require_keys_eq!(*amm_config.owner, RAYDIUM_CLMM_PROGRAM_ID, TwistError::InvalidAccount);
+    
+    let pool: RaydiumPoolInfo = load_leading_fields(pool_state, RaydiumPoolInfo::LEN)?;
+    let config: RaydiumAmmConfigInfo = load_leading_fields(amm_config, RaydiumAmmConfigInfo::LEN)?;
+    
+    require_keys_eq!(amm_config.key(), pool.amm_config, TwistError::InvalidAccount);
+    require_keys_eq!(token_vault_0.key(), pool.token_vault_0, TwistError::InvalidAccount);
+    require_keys_eq!(token_vault_1.key(), pool.token_vault_1, TwistError::InvalidAccount);
+    require_keys_eq!(observation_state.key(), pool.observation_key, TwistError::InvalidAccount);
+    require!(
+        pool.token_mint_0 == endpoints.twist_mint || pool.token_mint_1 == endpoints.twist_mint,
+        TwistError::InvalidAccount
+    );
+    
+    Ok(RaydiumClmmAdapter {
+        program: program.clone(),
+        amm_config: amm_config.clone(),
+        pool_state: pool_state.clone(),
+        token_vault_0: token_vault_0.clone(),
+        token_vault_1: token_vault_1.clone(),
+        observation_state: observation_state.clone(),
+        tick_arrays: [tick_array_0.clone(), tick_array_1.clone(), tick_array_2.clone()],
+        endpoints: endpoints.clone(),
+        pool,
+        trade_fee_rate: config.trade_fee_rate,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tick_array_start_index() {
+        // 60 ticks per array at spacing 10 covers 600 ticks
+        assert_eq!(raydium_tick_array_start_index(0, 10), 0);
+        assert_eq!(raydium_tick_array_start_index(599, 10), 0);
+        assert_eq!(raydium_tick_array_start_index(600, 10), 600);
+        assert_eq!(raydium_tick_array_start_index(-1, 10), -600);
+        assert_eq!(raydium_tick_array_start_index(-601, 10), -1200);
+    }
+
+    #[test]
+    fn test_parse_tick_array_keeps_initialized_ticks() {
+        let pool = Pubkey::new_unique();
+        let mut data = vec![0u8; RAYDIUM_TICKS_OFFSET + RAYDIUM_TICK_ARRAY_SIZE as usize * RAYDIUM_TICK_STATE_LEN];
+        data[8..40].copy_from_slice(&pool.to_bytes());
+        data[40..44].copy_from_slice(&(-600i32).to_le_bytes());
+
+        // One initialized tick at -590 with negative net liquidity
+        let offset = RAYDIUM_TICKS_OFFSET + RAYDIUM_TICK_STATE_LEN;
+        data[offset..offset + 4].copy_from_slice(&(-590i32).to_le_bytes());
+        data[offset + 4..offset + 20].copy_from_slice(&(-5_000i128).to_le_bytes());
+        data[offset + 20..offset + 36].copy_from_slice(&5_000u128.to_le_bytes());
+
+        let (start_index, ticks) = parse_raydium_tick_array(&data, &pool).unwrap();
+        assert_eq!(start_index, -600);
+        assert_eq!(ticks, vec![(-590, -5_000)]);
+
+        assert!(parse_raydium_tick_array(&data, &Pubkey::new_unique()).is_err());
+        assert!(parse_raydium_tick_array(&data[..100], &pool).is_err());
+    }
+}
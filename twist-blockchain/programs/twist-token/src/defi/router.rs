@@ -0,0 +1,202 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::TwistError;
+use super::{best_quote, price_impact_bps, DexAdapter, SwapQuote};
+
+/// One venue's share of a routed order
+#[derive(Clone, Copy, Debug)]
+pub struct RouteLeg {
+    pub adapter_index: usize,
+    pub quote: SwapQuote,
+}
+
+/// Order split across venues, with its aggregate expected fill
+#[derive(Clone, Debug)]
+pub struct Route {
+    pub legs: Vec<RouteLeg>,
+    pub amount_in: u64,
+    pub expected_out: u64,
+    pub spot_amount_out: u64,
+    pub price_impact_bps: u64,
+}
+
+impl Route {
+    fn from_legs(legs: Vec<RouteLeg>) -> Self {
+        let amount_in = legs.iter().map(|leg| leg.quote.amount_in).sum();
+        let expected_out = legs.iter().map(|leg| leg.quote.amount_out).sum();
+        let spot_amount_out = legs.iter().map(|leg| leg.quote.spot_amount_out).sum();
+        
+        Self {
+            legs,
+            amount_in,
+            expected_out,
+            spot_amount_out,
+            price_impact_bps: price_impact_bps(spot_amount_out, expected_out),
+        }
+    }
+}
+
+/// Plan an exact-input route. Orders the best single venue fills within
+/// `price_impact_threshold_bps` go there whole; larger orders are split into
+/// `split_steps` slices, each given to the venue with the best marginal output.
+pub fn plan_route<'info>(
+    adapters: &[Box<dyn DexAdapter<'info> + 'info>],
+    amount_in: u64,
+    a_to_b: bool,
+    price_impact_threshold_bps: u64,
+    split_steps: u64,
+) -> Result<Route> {
+    require!(amount_in > 0 && split_steps > 0, TwistError::InvalidAmount);
+    
+    let (best_index, best) = best_quote(adapters, amount_in, a_to_b)?;
+    if best.price_impact_bps <= price_impact_threshold_bps || adapters.len() == 1 {
+        let route = Route::from_legs(vec![RouteLeg { adapter_index: best_index, quote: best }]);
+        require!(
+            route.price_impact_bps <= price_impact_threshold_bps,
+            TwistError::PriceImpactTooHigh
+        );
+        return Ok(route);
+    }
+    
+    // Greedy marginal allocation; optimal while each venue's output is concave in its input
+    let mut allocated: Vec<Option<SwapQuote>> = vec![None; adapters.len()];
+    let slice = amount_in / split_steps;
+    for step in 0..split_steps {
+        let amount = if step == split_steps - 1 {
+            amount_in - slice * (split_steps - 1)
+        } else {
+            slice
+        };
+        if amount == 0 {
+            continue;
+        }
+        
+        let mut best_step: Option<(usize, SwapQuote, u64)> = None;
+        for (index, adapter) in adapters.iter().enumerate() {
+            let (current_in, current_out) = allocated[index]
+                .map(|quote| (quote.amount_in, quote.amount_out))
+                .unwrap_or((0, 0));
+            let Ok(quote) = adapter.quote(current_in + amount, a_to_b) else {
+                continue;
+            };
+            let marginal_out = quote.amount_out.saturating_sub(current_out);
+            if !matches!(best_step, Some((_, _, best_out)) if best_out >= marginal_out) {
+                best_step = Some((index, quote, marginal_out));
+            }
+        }
+        
+        let (index, quote, _) = best_step.ok_or(TwistError::InsufficientLiquidity)?;
+        allocated[index] = Some(quote);
+    }
+    
+    let legs = allocated
+        .into_iter()
+        .enumerate()
+        .filter_map(|(adapter_index, quote)| quote.map(|quote| RouteLeg { adapter_index, quote }))
+        .collect();
+    let route = Route::from_legs(legs);
+    
+    // Never take a split that fills worse than the single best venue
+    let route = if route.expected_out >= best.amount_out {
+        route
+    } else {
+        Route::from_legs(vec![RouteLeg { adapter_index: best_index, quote: best }])
+    };
+    require!(
+        route.price_impact_bps <= price_impact_threshold_bps,
+        TwistError::PriceImpactTooHigh
+    );
+    
+    Ok(route)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::DexVenue;
+    use super::super::constant_product_amount_out;
+
+    struct TestPool {
+        pool: Pubkey,
+        reserve_in: u64,
+        reserve_out: u64,
+        fee_bps: u16,
+    }
+
+    impl DexAdapter<'static> for TestPool {
+        fn venue(&self) -> DexVenue {
+            DexVenue::ConstantProduct
+        }
+
+        fn pool(&self) -> Pubkey {
+            self.pool
+        }
+
+        fn quote(&self, amount_in: u64, _a_to_b: bool) -> Result<SwapQuote> {
+            let amount_out = constant_product_amount_out(self.reserve_in, self.reserve_out, amount_in, self.fee_bps)?;
+            let after_fee = amount_in as u128 * (10000 - self.fee_bps as u128) / 10000;
+            let spot_out = (after_fee * self.reserve_out as u128 / self.reserve_in as u128) as u64;
+            Ok(SwapQuote {
+                venue: DexVenue::ConstantProduct,
+                pool: self.pool,
+                amount_in,
+                amount_out,
+                spot_amount_out: spot_out,
+                price_impact_bps: price_impact_bps(spot_out, amount_out),
+            })
+        }
+
+        fn swap(&self, _: u64, _: u64, _: bool, _: &[&[&[u8]]]) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    fn pools(reserves: &[(u64, u16)]) -> Vec<Box<dyn DexAdapter<'static> + 'static>> {
+        reserves
+            .iter()
+            .map(|&(reserve, fee_bps)| {
+                Box::new(TestPool {
+                    pool: Pubkey::new_unique(),
+                    reserve_in: reserve,
+                    reserve_out: reserve,
+                    fee_bps,
+                }) as Box<dyn DexAdapter<'static>>
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_small_order_uses_single_venue() {
+        let adapters = pools(&[(1_000_000_000, 30), (1_000_000_000, 5)]);
+        let route = plan_route(&adapters, 1_000_000, false, 100, 10).unwrap();
+
+        // The lower fee tier wins outright
+        assert_eq!(route.legs.len(), 1);
+        assert_eq!(route.legs[0].adapter_index, 1);
+        assert_eq!(route.amount_in, 1_000_000);
+    }
+
+    #[test]
+    fn test_large_order_is_split() {
+        let adapters = pools(&[(1_000_000_000, 30), (1_000_000_000, 30)]);
+        let amount = 40_000_000;
+        let single = adapters[0].quote(amount, false).unwrap();
+        assert!(single.price_impact_bps > 200);
+
+        let route = plan_route(&adapters, amount, false, 300, 10).unwrap();
+        assert_eq!(route.legs.len(), 2);
+        assert_eq!(route.amount_in, amount);
+        assert!(route.expected_out > single.amount_out);
+        assert!(route.price_impact_bps < single.price_impact_bps);
+
+        // Equal pools take equal halves
+        assert_eq!(route.legs[0].quote.amount_in, amount / 2);
+    }
+
+    #[test]
+    fn test_route_respects_impact_threshold() {
+        let adapters = pools(&[(1_000_000_000, 30), (1_000_000_000, 30)]);
+        assert!(plan_route(&adapters, 40_000_000, false, 50, 10).is_err());
+        assert!(plan_route(&adapters, 0, false, 100, 10).is_err());
+    }
+}
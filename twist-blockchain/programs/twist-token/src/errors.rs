@@ -127,4 +127,97 @@ pub enum TwistError {
     
     #[msg("Adjustment too soon")]
     AdjustmentTooSoon,
+    
+    #[msg("Bridge halted")]
+    BridgeHalted,
+    
+    #[msg("Transaction size exceeds circuit breaker limit")]
+    TransactionSizeExceeded,
+    
+    #[msg("Multisig approval required")]
+    MultisigRequired,
+    
+    #[msg("Multisig proposal has expired")]
+    ProposalExpired,
+    
+    #[msg("Multisig proposal is still pending")]
+    ProposalNotSettled,
+    
+    #[msg("Upgrade buffer does not match the approved bytecode hash")]
+    BufferHashMismatch,
+    
+    #[msg("Insufficient voting power")]
+    InsufficientVotingPower,
+    
+    #[msg("Voting period has ended")]
+    VotingClosed,
+    
+    #[msg("Voting period is still active")]
+    VotingActive,
+    
+    #[msg("Commitment cannot be revealed yet")]
+    CommitNotReady,
+    
+    #[msg("Commitment reveal window has passed")]
+    CommitExpired,
+    
+    #[msg("Revealed parameters do not match the commitment")]
+    CommitHashMismatch,
+    
+    #[msg("Too few slots since the previous action")]
+    SlotGapNotMet,
+    
+    #[msg("Treasury daily withdrawal limit exceeded")]
+    TreasuryDailyLimitExceeded,
+    
+    #[msg("Budget has nothing available to withdraw")]
+    BudgetExhausted,
+    
+    #[msg("Incident is outside the insurance claim window")]
+    ClaimWindowClosed,
+    
+    #[msg("Range strategy is disabled")]
+    StrategyDisabled,
+    
+    #[msg("Rebalance too soon")]
+    RebalanceTooSoon,
+    
+    #[msg("Price is still inside the recenter band")]
+    RebalanceNotNeeded,
+    
+    #[msg("DEX venue configuration is invalid")]
+    InvalidDexVenue,
+    
+    #[msg("Price impact above the routing threshold")]
+    PriceImpactTooHigh,
+    
+    #[msg("Vault deposits are disabled")]
+    VaultDepositsDisabled,
+    
+    #[msg("Deposit would exceed the vault cap")]
+    VaultDepositCapExceeded,
+    
+    #[msg("DEX oracle history does not cover the TWAP window")]
+    DexOracleNotReady,
+    
+    #[msg("Stake below the reporter minimum")]
+    InsufficientReporterStake,
+    
+    #[msg("Report round is closed")]
+    ReportRoundClosed,
+    
+    #[msg("Report round is still open")]
+    ReportRoundOpen,
+    
+    #[msg("Reporter already submitted this round")]
+    AlreadyReported,
+    
+    #[msg("Report round is full")]
+    ReportRoundFull,
+    
+    #[msg("Reporter bond below the network bond amount")]
+    InsufficientReporterBond,
+    
+    #[msg("Withdrawal is large enough to require commit-reveal")]
+    CommitRevealRequired,
 }
\ No newline at end of file
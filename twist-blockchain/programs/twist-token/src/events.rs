@@ -1,5 +1,8 @@
 use anchor_lang::prelude::*;
 
+use crate::state::{ClaimStatus, IncidentType, ProposalStatus, VoteSide};
+use crate::utils::CommittedAction;
+
 #[event]
 pub struct ProgramInitialized {
     pub authority: Pubkey,
@@ -47,6 +50,14 @@ pub struct RewardsClaimed {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct FeeRewardsClaimed {
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub reward_per_share: u128,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct BuybackExecuted {
     pub usdc_spent: u64,
@@ -208,4 +219,275 @@ pub struct PIDParametersUpdated {
 #[event]
 pub struct PIDControllerReset {
     pub timestamp: i64,
-}
\ No newline at end of file
+}
+
+#[event]
+pub struct GovernanceProposalCreated {
+    pub proposal_index: u64,
+    pub proposer: Pubkey,
+    pub title: String,
+    pub snapshot_total_staked: u128,
+    pub voting_ends_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GovernanceVoteCast {
+    pub proposal_index: u64,
+    pub voter: Pubkey,
+    pub side: VoteSide,
+    pub weight: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GovernanceProposalFinalized {
+    pub proposal_index: u64,
+    pub status: ProposalStatus,
+    pub for_votes: u128,
+    pub against_votes: u128,
+    pub abstain_votes: u128,
+    pub eta: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GovernanceProposalExecuted {
+    pub proposal_index: u64,
+    pub executor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GovernanceProposalVetoed {
+    pub proposal_index: u64,
+    pub vetoed_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ActionCommitted {
+    pub user: Pubkey,
+    pub commit_hash: [u8; 32],
+    pub slot: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ActionRevealed {
+    pub user: Pubkey,
+    pub commit_hash: [u8; 32],
+    pub action: CommittedAction,
+    pub amount: u64,
+    pub usdc_value: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TreasuryBudgetCreated {
+    pub name: String,
+    pub recipient: Pubkey,
+    pub stream_total: u64,
+    pub stream_start: i64,
+    pub stream_end: i64,
+    pub cap_per_epoch: u64,
+    pub epoch_duration: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TreasuryAllocationReport {
+    pub stage: String, // "before" or "after" a rebalance
+    pub twist_price: u64,
+    pub floor_twist_value: u64,
+    pub floor_usdc: u64,
+    pub ops_value: u64,
+    pub reserve_value: u64,
+    pub floor_bps: u64,
+    pub ops_bps: u64,
+    pub reserve_bps: u64,
+    pub floor_usdc_mix_bps: u64,
+    pub floor_target_bps: u64,
+    pub ops_target_bps: u64,
+    pub reserve_target_bps: u64,
+    pub floor_usdc_target_bps: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReservesReconciled {
+    pub floor_liquidity_recorded: u64,
+    pub floor_usdc_balance: u64,
+    pub floor_delta: i128, // balance minus recorded
+    pub floor_divergence_bps: u64,
+    pub floor_twist_balance: u64,
+    pub total_staked_recorded: u128,
+    pub stake_vault_balance: u64,
+    pub stake_delta: i128,
+    pub stake_divergence_bps: u64,
+    pub total_bought_back: u128,
+    pub threshold_bps: u64,
+    pub breaker_tripped: bool,
+    pub reconciled_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct InsuranceClaimFiled {
+    pub claim_index: u64,
+    pub claimant: Pubkey,
+    pub incident_type: IncidentType,
+    pub incident_timestamp: i64,
+    pub amount_requested: u64,
+    pub evidence_hash: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct InsuranceClaimResolved {
+    pub claim_index: u64,
+    pub status: ClaimStatus,
+    pub amount_requested: u64,
+    pub approved_amount: u64,
+    pub resolved_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct InsuranceClaimPaid {
+    pub claim_index: u64,
+    pub recipient: Pubkey,
+    pub approved_amount: u64,
+    pub paid_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct InsuranceCoverageUpdated {
+    pub vault_balance: u64,
+    pub vault_value: u64, // USDC atomic units
+    pub tvl: u64,
+    pub coverage_ratio_bps: u64,
+    pub outstanding_approved: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LpVaultDeposited {
+    pub position_index: u8,
+    pub depositor: Pubkey,
+    pub twist_amount: u64,
+    pub usdc_amount: u64,
+    pub deposit_value: u64, // USDC atomic units
+    pub shares_minted: u64,
+    pub nav_after: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LpVaultWithdrawn {
+    pub position_index: u8,
+    pub owner: Pubkey,
+    pub shares_burned: u64,
+    pub twist_amount: u64,
+    pub usdc_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LpVaultFeeCollected {
+    pub position_index: u8,
+    pub fee_shares: u64,
+    pub twist_amount: u64,
+    pub usdc_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LiquidityMiningFunded {
+    pub funder: Pubkey,
+    pub amount: u64,
+    pub vault_balance: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MiningPositionRegistered {
+    pub position: Pubkey,
+    pub owner: Pubkey,
+    pub lower_tick: i32,
+    pub upper_tick: i32,
+    pub liquidity: u128,
+    pub weighted_liquidity: u128,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MiningPositionRefreshed {
+    pub position: Pubkey,
+    pub liquidity: u128,
+    pub weighted_liquidity: u128,
+    pub unclaimed_rewards: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MiningRewardsClaimed {
+    pub position: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub remaining: u64, // Left unclaimed while the vault is short
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReporterRegistered {
+    pub reporter: Pubkey,
+    pub bond: u64,
+    pub stake: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReporterBondToppedUp {
+    pub reporter: Pubkey,
+    pub amount: u64,
+    pub bond: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PriceReported {
+    pub reporter: Pubkey,
+    pub round: u64,
+    pub price: u64,
+    pub weight: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReporterSlashed {
+    pub reporter: Pubkey,
+    pub round: u64,
+    pub amount: u64,
+    pub remaining_bond: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReportRoundFinalized {
+    pub round: u64,
+    pub price: u64,
+    pub confidence: u64,
+    pub reports: u8,
+    pub outliers: u8,
+    pub slashed: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReporterDeregistered {
+    pub reporter: Pubkey,
+    pub bond_returned: u64,
+    pub timestamp: i64,
+}
@@ -4,6 +4,7 @@ use crate::constants::*;
 use crate::errors::TwistError;
 use crate::events::*;
 use crate::state::*;
+use crate::instructions::circuit_breaker::{lift_circuit_breaker, trip_circuit_breaker};
 
 #[derive(Accounts)]
 pub struct TriggerCircuitBreaker<'info> {
@@ -17,6 +18,20 @@ pub struct TriggerCircuitBreaker<'info> {
         constraint = program_state.authority == authority.key() @ TwistError::Unauthorized,
     )]
     pub program_state: Account<'info, ProgramState>,
+    
+    #[account(
+        mut,
+        seeds = [b"circuit_breaker"],
+        bump,
+    )]
+    pub circuit_breaker: Account<'info, CircuitBreakerState>,
+    
+    #[account(
+        mut,
+        seeds = [CIRCUIT_BREAKER_LOG_SEED],
+        bump = circuit_breaker_log.bump,
+    )]
+    pub circuit_breaker_log: Box<Account<'info, CircuitBreakerLog>>,
 }
 
 #[derive(Accounts)]
@@ -28,9 +43,28 @@ pub struct ResetCircuitBreaker<'info> {
         mut,
         seeds = [PROGRAM_STATE_SEED],
         bump,
-        constraint = program_state.authority == authority.key() @ TwistError::Unauthorized,
     )]
     pub program_state: Account<'info, ProgramState>,
+    
+    #[account(
+        seeds = [b"circuit_breaker"],
+        bump,
+    )]
+    pub circuit_breaker: Account<'info, CircuitBreakerState>,
+    
+    #[account(
+        mut,
+        seeds = [CIRCUIT_BREAKER_LOG_SEED],
+        bump = circuit_breaker_log.bump,
+    )]
+    pub circuit_breaker_log: Box<Account<'info, CircuitBreakerLog>>,
+    
+    /// Signs in place of the admin for High and Critical trips, including the admin's own
+    #[account(
+        seeds = [b"multisig"],
+        bump = multisig_config.bump,
+    )]
+    pub multisig_config: Option<Account<'info, MultisigConfig>>,
 }
 
 #[derive(Accounts)]
@@ -130,23 +164,23 @@ pub fn trigger_circuit_breaker_handler(
         TwistError::InvalidAmount
     );
     
-    // Activate circuit breaker
-    program_state.circuit_breaker_active = true;
-    program_state.buyback_enabled = false;
-    
-    let actions_taken = vec![
-        "Circuit breaker activated".to_string(),
-        "Buyback disabled".to_string(),
-        "High-risk operations restricted".to_string(),
-    ];
-    
-    // Emit event
-    emit!(CircuitBreakerTriggered {
-        trigger_reason: reason.clone(),
-        severity: "High".to_string(),
-        actions_taken,
-        timestamp: clock.unix_timestamp,
-    });
+    // Same logged path as a manual trip, at the severity this instruction always used
+    ctx.accounts.circuit_breaker_log.append(
+        BreakerAction::Trip,
+        CircuitBreakerSeverity::High,
+        TripCondition::ManualTrigger,
+        ctx.accounts.authority.key(),
+        reason.clone(),
+        clock.unix_timestamp,
+    )?;
+    
+    trip_circuit_breaker(
+        program_state,
+        &mut ctx.accounts.circuit_breaker,
+        CircuitBreakerSeverity::High,
+        TripCondition::ManualTrigger,
+        clock.unix_timestamp,
+    )?;
     
     msg!("Circuit breaker triggered by admin: {}", reason);
     
@@ -162,17 +196,24 @@ pub fn reset_circuit_breaker_handler(ctx: Context<ResetCircuitBreaker>) -> Resul
         TwistError::CircuitBreakerActive
     );
     
-    // Reset circuit breaker
-    program_state.circuit_breaker_active = false;
-    program_state.buyback_enabled = true;
-    
-    // Emit event
-    emit!(CircuitBreakerReset {
-        reset_by: ctx.accounts.authority.key(),
-        timestamp: clock.unix_timestamp,
-    });
-    
-    msg!("Circuit breaker reset by admin");
+    // Admin trips are High, so lifting them goes through the multisig signing here
+    let multisig = ctx.accounts.multisig_config.as_ref().map(|multisig| multisig.key());
+    ctx.accounts.circuit_breaker.authorize_reset(
+        &ctx.accounts.authority.key(),
+        &program_state.authority,
+        multisig.as_ref(),
+    )?;
+    
+    let time_since_trip = lift_circuit_breaker(
+        program_state,
+        &ctx.accounts.circuit_breaker,
+        &mut ctx.accounts.circuit_breaker_log,
+        ctx.accounts.authority.key(),
+        "Reset by admin".to_string(),
+        clock.unix_timestamp,
+    )?;
+    
+    msg!("Circuit breaker reset by admin after {} seconds", time_since_trip);
     
     Ok(())
 }
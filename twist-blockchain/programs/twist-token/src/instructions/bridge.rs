@@ -4,7 +4,7 @@ use crate::state::*;
 use crate::constants::*;
 use crate::errors::TwistError;
 use crate::events::*;
-use crate::utils::{validate_amount, safe_sub, safe_div};
+use crate::utils::{validate_amount, safe_sub};
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct BridgeMessage {
@@ -50,6 +50,13 @@ pub struct InitiateBridge<'info> {
     )]
     pub mint: Account<'info, Mint>,
     
+    #[account(
+        mut,
+        seeds = [MARKET_METRICS_SEED],
+        bump = market_metrics.bump,
+    )]
+    pub market_metrics: Box<Account<'info, MarketMetrics>>,
+    
     /// CHECK: Wormhole bridge account - verified in handler
     pub wormhole_bridge: AccountInfo<'info>,
     
@@ -87,6 +94,13 @@ pub fn initiate_handler(
         TwistError::EmergencyPauseActive
     );
     
+    // Check circuit breaker restrictions
+    require!(
+        program_state.can_bridge(),
+        TwistError::BridgeHalted
+    );
+    program_state.check_transaction_size(program_state.twist_to_usdc_value(amount))?;
+    
     // Validate target chain
     const SUPPORTED_CHAINS: [u16; 4] = [
         2,  // Ethereum
@@ -100,8 +114,9 @@ pub fn initiate_handler(
         TwistError::UnsupportedChain
     );
     
-    // Calculate bridge fee (0.1%)
-    let bridge_fee = safe_div(amount, 1000)?;
+    // Calculate bridge fee (0.1% plus any circuit breaker surcharge)
+    let bridge_fee_bps = program_state.apply_fee_surcharge(10);
+    let bridge_fee = (amount as u128 * bridge_fee_bps as u128 / 10000) as u64;
     let transfer_amount = safe_sub(amount, bridge_fee)?;
     
     // Minimum bridge amount check (to cover gas on destination chain)
@@ -125,6 +140,11 @@ pub fn initiate_handler(
     // Update program state
     program_state.total_transactions = program_state.total_transactions.saturating_add(1);
     
+    // Record the outbound flow in the rolling metrics
+    let bridge_value = program_state.twist_to_usdc_value(amount);
+    ctx.accounts.market_metrics.record_volume(clock.unix_timestamp, bridge_value);
+    ctx.accounts.market_metrics.refresh_program_volumes(program_state, clock.unix_timestamp);
+    
     // Create bridge message data
     let _message_data = BridgeMessage {
         amount: transfer_amount,
@@ -193,6 +213,13 @@ pub struct CompleteBridge<'info> {
     )]
     pub mint: Account<'info, Mint>,
     
+    #[account(
+        mut,
+        seeds = [MARKET_METRICS_SEED],
+        bump = market_metrics.bump,
+    )]
+    pub market_metrics: Box<Account<'info, MarketMetrics>>,
+    
     #[account(
         mut,
         token::mint = program_state.mint,
@@ -233,6 +260,11 @@ pub fn complete_handler(
     // Update program state
     program_state.total_transactions = program_state.total_transactions.saturating_add(1);
     
+    // Record the inbound flow in the rolling metrics
+    let bridge_value = program_state.twist_to_usdc_value(amount);
+    ctx.accounts.market_metrics.record_volume(clock.unix_timestamp, bridge_value);
+    ctx.accounts.market_metrics.refresh_program_volumes(program_state, clock.unix_timestamp);
+    
     // Release mutable reference before creating immutable ones
     
     // Mint tokens to recipient
@@ -6,6 +6,9 @@ use crate::errors::TwistError;
 use crate::events::*;
 use crate::processors::get_aggregated_price;
 use crate::defi::*;
+use crate::instructions::fee_collector::protocol_swap_fee;
+use crate::utils::{execute_route, plan_registry_route};
+use crate::instructions::dex_oracle::observe_pool;
 
 #[derive(Accounts)]
 pub struct ExecuteBuyback<'info> {
@@ -25,8 +28,10 @@ pub struct ExecuteBuyback<'info> {
     )]
     pub mint: Account<'info, Mint>,
     
+    // Buybacks spend floor liquidity, so they draw on the floor USDC account it reconciles against
     #[account(
         mut,
+        address = treasury_policy.floor_usdc_account @ TwistError::InvalidAccount,
         constraint = program_usdc_account.mint == usdc_mint.key() @ TwistError::InvalidMintAuthority,
         token::authority = program_state,
     )]
@@ -39,63 +44,75 @@ pub struct ExecuteBuyback<'info> {
     )]
     pub program_twist_account: Account<'info, TokenAccount>,
     
+    #[account(address = fee_collector.usdc_mint @ TwistError::InvalidAccount)]
     pub usdc_mint: Account<'info, Mint>,
     
+    #[account(
+        seeds = [TREASURY_POLICY_SEED],
+        bump = treasury_policy.bump,
+    )]
+    pub treasury_policy: Box<Account<'info, TreasuryPolicy>>,
+    
     /// CHECK: Pyth price feed
     pub pyth_price_account: AccountInfo<'info>,
     
     /// CHECK: Switchboard feed
     pub switchboard_feed: AccountInfo<'info>,
     
-    /// Orca Whirlpool accounts
-    #[account(mut)]
-    pub whirlpool: Box<Account<'info, WhirlpoolState>>,
-    
     #[account(
         mut,
-        constraint = token_vault_a.key() == whirlpool.token_vault_a @ TwistError::InvalidAmount
+        seeds = [MARKET_METRICS_SEED],
+        bump = market_metrics.bump,
     )]
-    pub token_vault_a: Account<'info, TokenAccount>,
+    pub market_metrics: Box<Account<'info, MarketMetrics>>,
     
     #[account(
         mut,
-        constraint = token_vault_b.key() == whirlpool.token_vault_b @ TwistError::InvalidAmount
+        seeds = [b"fee_collector"],
+        bump = fee_collector.bump,
     )]
-    pub token_vault_b: Account<'info, TokenAccount>,
+    pub fee_collector: Box<Account<'info, FeeCollectorState>>,
     
-    /// CHECK: Tick arrays validated in handler
-    pub tick_array_0: AccountInfo<'info>,
+    #[account(
+        mut,
+        address = fee_collector.usdc_fee_vault @ TwistError::InvalidAccount,
+    )]
+    pub usdc_fee_vault: Box<Account<'info, TokenAccount>>,
     
-    /// CHECK: Tick arrays validated in handler
-    pub tick_array_1: AccountInfo<'info>,
+    // Floor spend counts against the floor treasury's daily limit
+    #[account(
+        mut,
+        seeds = [TREASURY_STATE_SEED, TreasuryType::Floor.seed().as_ref()],
+        bump = floor_state.bump,
+    )]
+    pub floor_state: Box<Account<'info, TreasuryState>>,
     
-    /// CHECK: Tick arrays validated in handler
-    pub tick_array_2: AccountInfo<'info>,
+    /// Swap venues the buyback may route through; the venue accounts follow in remaining accounts
+    #[account(
+        seeds = [DEX_REGISTRY_SEED],
+        bump = dex_registry.bump,
+    )]
+    pub dex_registry: Box<Account<'info, DexRegistry>>,
     
     #[account(
         mut,
-        seeds = [b"pool_oracle", whirlpool.key().as_ref()],
-        bump,
+        seeds = [DEX_ORACLE_SEED],
+        bump = dex_oracle.bump,
     )]
-    pub oracle: Account<'info, PoolOracle>,
-    
-    /// CHECK: Whirlpool program
-    pub whirlpool_program: AccountInfo<'info>,
+    pub dex_oracle: Option<Box<Account<'info, DexOracle>>>,
     
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(
-    ctx: Context<ExecuteBuyback>,
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, ExecuteBuyback<'info>>,
     max_usdc_amount: u64,
 ) -> Result<()> {
     let clock = Clock::get()?;
     
-    // Get immutable data first
-    let program_state_key = ctx.accounts.program_state.key();
+    observe_pool(&mut ctx.accounts.dex_oracle, ctx.remaining_accounts, clock.unix_timestamp)?;
     
-    // Now get mutable reference
     let program_state = &mut ctx.accounts.program_state;
     
     // Check if buyback is enabled
@@ -131,111 +148,109 @@ pub fn handler(
     let price_discount = (threshold_price - current_price) * 10000 / threshold_price;
     let buyback_multiplier = std::cmp::min(price_discount / 100 + 100, 300); // Max 3x at 2% discount
     let base_buyback = program_state.floor_liquidity / 50; // 2% of floor liquidity
-    let buyback_amount = std::cmp::min(
+    let mut buyback_amount = std::cmp::min(
         base_buyback * buyback_multiplier / 100,
         max_usdc_amount
     );
     
+    // Respect the circuit breaker transaction cap
+    if program_state.max_transaction_size > 0 {
+        buyback_amount = std::cmp::min(buyback_amount, program_state.max_transaction_size);
+    }
+    
     // Ensure we have enough liquidity
     require!(
         buyback_amount <= program_state.floor_liquidity,
         TwistError::InsufficientLiquidity
     );
     
+    // Treasury limits are kept in TWIST, so the USDC spend is valued at the oracle price
+    let floor_spend = program_state.usdc_to_twist_amount(buyback_amount);
+    ctx.accounts.floor_state.consume_daily_limit(floor_spend, clock.unix_timestamp)?;
+    
     // Get bump before modifying state
     let program_state_bump = program_state.bump;
     
+    // Protocol-routed swaps pay the dynamic trading fee to the fee collector
+    let (fee_bps, fee_amount) = protocol_swap_fee(
+        &ctx.accounts.fee_collector,
+        program_state,
+        &ctx.accounts.market_metrics,
+        buyback_amount,
+        clock.unix_timestamp,
+    )?;
+    let swap_amount = buyback_amount - fee_amount;
+    
     // Determine if we're swapping USDC (token B) for TWIST (token A)
     let a_to_b = false; // USDC -> TWIST
     
     // Calculate minimum output with slippage tolerance (1%)
-    let expected_twist = buyback_amount * 1_000_000 / current_price;
+    let expected_twist = (swap_amount as u128 * 10u128.pow(DECIMALS as u32) / current_price as u128) as u64;
     let min_twist_out = expected_twist * 99 / 100; // 1% slippage
     
-    // Get tick arrays for the swap
-    let tick_arrays = get_tick_array_pubkeys(
-        &ctx.accounts.whirlpool.key(),
-        ctx.accounts.whirlpool.tick_current_index,
-        ctx.accounts.whirlpool.tick_spacing,
+    // Quote every registered venue supplied and split the order if one pool would move too far
+    let endpoints = SwapEndpoints {
+        token_program: ctx.accounts.token_program.to_account_info(),
+        token_authority: ctx.accounts.program_state.to_account_info(),
+        twist_mint: ctx.accounts.program_state.mint,
+        twist_account: ctx.accounts.program_twist_account.to_account_info(),
+        usdc_account: ctx.accounts.program_usdc_account.to_account_info(),
+    };
+    let (adapters, route) = plan_registry_route(
+        &ctx.accounts.dex_registry,
+        ctx.remaining_accounts,
+        &endpoints,
+        swap_amount,
         a_to_b,
-        &ctx.accounts.whirlpool_program.key(),
     )?;
-    
-    // Verify tick arrays match
     require!(
-        tick_arrays[0] == ctx.accounts.tick_array_0.key() &&
-        tick_arrays[1] == ctx.accounts.tick_array_1.key() &&
-        tick_arrays[2] == ctx.accounts.tick_array_2.key(),
-        TwistError::InvalidAmount
+        route.expected_out >= min_twist_out,
+        TwistError::SlippageToleranceExceeded
     );
     
-    // Prepare swap parameters
-    let swap_params = SwapParams {
-        amount: buyback_amount,
-        other_amount_threshold: min_twist_out,
-        sqrt_price_limit: 0, // No price limit
-        amount_specified_is_input: true,
-        a_to_b,
-    };
-    
-    // Build swap instruction
-    let swap_ix = build_swap_ix(
-        ctx.accounts.whirlpool_program.key(),
-        ctx.accounts.token_program.key(),
-        program_state_key,
-        ctx.accounts.whirlpool.key(),
-        ctx.accounts.program_twist_account.key(),
-        ctx.accounts.token_vault_a.key(),
-        ctx.accounts.program_usdc_account.key(),
-        ctx.accounts.token_vault_b.key(),
-        ctx.accounts.tick_array_0.key(),
-        ctx.accounts.tick_array_1.key(),
-        ctx.accounts.tick_array_2.key(),
-        ctx.accounts.oracle.key(),
-        swap_params,
-    )?;
-    
-    // Release mutable reference before CPI
-    
-    // Execute swap via CPI
     let seeds = &[
         PROGRAM_STATE_SEED,
         &[program_state_bump],
     ];
     let signer_seeds = &[&seeds[..]];
     
-    anchor_lang::solana_program::program::invoke_signed(
-        &swap_ix,
-        &[
-            ctx.accounts.token_program.to_account_info(),
-            ctx.accounts.program_state.to_account_info(),
-            ctx.accounts.whirlpool.to_account_info(),
-            ctx.accounts.program_twist_account.to_account_info(),
-            ctx.accounts.token_vault_a.to_account_info(),
-            ctx.accounts.program_usdc_account.to_account_info(),
-            ctx.accounts.token_vault_b.to_account_info(),
-            ctx.accounts.tick_array_0.to_account_info(),
-            ctx.accounts.tick_array_1.to_account_info(),
-            ctx.accounts.tick_array_2.to_account_info(),
-            ctx.accounts.oracle.to_account_info(),
-        ],
+    if fee_amount > 0 {
+        let cpi_accounts = token::Transfer {
+            from: ctx.accounts.program_usdc_account.to_account_info(),
+            to: ctx.accounts.usdc_fee_vault.to_account_info(),
+            authority: ctx.accounts.program_state.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        
+        token::transfer(cpi_ctx, fee_amount)?;
+        
+        ctx.accounts.fee_collector.record_usdc_fee(fee_amount);
+    }
+    
+    let twist_received = execute_route(
+        &adapters,
+        &route,
+        min_twist_out,
+        a_to_b,
         signer_seeds,
+        &mut ctx.accounts.program_twist_account,
     )?;
     
-    // Get actual received amount from account balance change
-    ctx.accounts.program_twist_account.reload()?;
-    let _twist_balance_after = ctx.accounts.program_twist_account.amount;
-    
-    // For now, use expected amount (in production, track balance before/after)
-    let estimated_twist_received = expected_twist;
-    
     // Get mutable reference to program state again
     let program_state = &mut ctx.accounts.program_state;
     
     // Update state
     program_state.daily_buyback_used += buyback_amount;
     program_state.floor_liquidity -= buyback_amount;
-    program_state.total_bought_back = program_state.total_bought_back.saturating_add(estimated_twist_received as u128);
+    program_state.total_bought_back = program_state.total_bought_back.saturating_add(twist_received as u128);
+    
+    // Record the swap in the rolling metrics
+    let market_metrics = &mut ctx.accounts.market_metrics;
+    market_metrics.record_volume(clock.unix_timestamp, buyback_amount);
+    market_metrics.record_price(clock.unix_timestamp, current_price);
+    market_metrics.record_liquidity(clock.unix_timestamp, program_state.floor_liquidity);
+    market_metrics.refresh_program_volumes(program_state, clock.unix_timestamp);
     
     // Update floor price based on new liquidity
     let new_floor_price = if program_state.total_staked > 0 {
@@ -246,7 +261,7 @@ pub fn handler(
     program_state.floor_price = new_floor_price;
     
     // Update total burned before the CPI
-    program_state.total_burned = program_state.total_burned.saturating_add(estimated_twist_received as u128);
+    program_state.total_burned = program_state.total_burned.saturating_add(twist_received as u128);
     
     // Release mutable reference again before burn CPI
     
@@ -266,12 +281,12 @@ pub fn handler(
     let cpi_program = ctx.accounts.token_program.to_account_info();
     let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
     
-    token::burn(cpi_ctx, estimated_twist_received)?;
+    token::burn(cpi_ctx, twist_received)?;
     
     // Emit buyback event
     emit!(BuybackExecuted {
         usdc_spent: buyback_amount,
-        twist_received: estimated_twist_received,
+        twist_received,
         execution_price: current_price,
         new_floor_price,
         timestamp: clock.unix_timestamp,
@@ -279,9 +294,14 @@ pub fn handler(
     
     msg!("Buyback executed: {} USDC for {} TWIST",
         buyback_amount as f64 / 1e6,
-        estimated_twist_received as f64 / 10f64.powf(DECIMALS as f64)
+        twist_received as f64 / 10f64.powf(DECIMALS as f64)
     );
     msg!("New floor price: ${}", new_floor_price as f64 / 1e6);
+    msg!("Dynamic fee: {} bps ({} USDC)", fee_bps, fee_amount as f64 / 1e6);
+    for leg in &route.legs {
+        msg!("Routed {} USDC via {:?} pool {}", leg.quote.amount_in as f64 / 1e6, leg.quote.venue, leg.quote.pool);
+    }
+    msg!("Route price impact: {} bps", route.price_impact_bps);
     
     Ok(())
 }
\ No newline at end of file
@@ -6,7 +6,9 @@ use crate::errors::TwistError;
 use crate::events::*;
 use crate::state::{
     ProgramState, CircuitBreakerState, CircuitBreakerParams, 
-    CircuitBreakerSeverity, TripCondition, PriceSource
+    CircuitBreakerSeverity, TripCondition, PriceSource, SeverityResponse,
+    MarketMetrics, HOUR, DAY, CircuitBreakerLog, BreakerAction, MultisigConfig,
+    DexOracle, OracleType, price_divergence_bps,
 };
 
 #[derive(Accounts)]
@@ -31,6 +33,15 @@ pub struct InitializeCircuitBreaker<'info> {
     )]
     pub circuit_breaker: Account<'info, CircuitBreakerState>,
     
+    #[account(
+        init,
+        payer = authority,
+        space = CircuitBreakerLog::LEN,
+        seeds = [CIRCUIT_BREAKER_LOG_SEED],
+        bump
+    )]
+    pub circuit_breaker_log: Box<Account<'info, CircuitBreakerLog>>,
+    
     pub system_program: Program<'info, System>,
 }
 
@@ -51,6 +62,7 @@ pub fn initialize_handler(
     circuit_breaker.supply_change_threshold_bps = params.supply_change_threshold_bps;
     circuit_breaker.oracle_divergence_threshold_bps = params.oracle_divergence_threshold_bps;
     circuit_breaker.liquidity_drain_threshold_bps = params.liquidity_drain_threshold_bps;
+    circuit_breaker.reserve_divergence_threshold_bps = params.reserve_divergence_threshold_bps;
     
     // Set cooldown periods
     circuit_breaker.low_severity_cooldown = params.low_severity_cooldown;
@@ -58,11 +70,25 @@ pub fn initialize_handler(
     circuit_breaker.high_severity_cooldown = params.high_severity_cooldown;
     circuit_breaker.critical_severity_cooldown = params.critical_severity_cooldown;
     
+    // Set response policies
+    circuit_breaker.low_response = params.low_response;
+    circuit_breaker.medium_response = params.medium_response;
+    circuit_breaker.high_response = params.high_response;
+    circuit_breaker.critical_response = params.critical_response;
+    
     // Initialize state
     circuit_breaker.last_trip_timestamp = 0;
     circuit_breaker.last_trip_severity = CircuitBreakerSeverity::Low;
     circuit_breaker.last_trip_condition = TripCondition::ManualTrigger;
     circuit_breaker.trip_count = 0;
+    circuit_breaker.guardians = Vec::new();
+    
+    // Initialize trip log
+    let circuit_breaker_log = &mut ctx.accounts.circuit_breaker_log;
+    circuit_breaker_log.bump = ctx.bumps.circuit_breaker_log;
+    circuit_breaker_log.total_entries = 0;
+    circuit_breaker_log.next_index = 0;
+    circuit_breaker_log.entries = Vec::new();
     
     msg!("Circuit breaker initialized");
     msg!("Price volatility threshold: {}%", params.price_volatility_threshold_bps as f64 / 100.0);
@@ -72,6 +98,87 @@ pub fn initialize_handler(
     Ok(())
 }
 
+#[derive(Accounts)]
+pub struct MigrateCircuitBreaker<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.authority == authority.key() @ TwistError::Unauthorized
+    )]
+    pub program_state: Account<'info, ProgramState>,
+    
+    /// CHECK: Still in the original layout, so owner and discriminator are checked in handler
+    #[account(
+        mut,
+        seeds = [b"circuit_breaker"],
+        bump,
+    )]
+    pub circuit_breaker: AccountInfo<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn migrate_handler(ctx: Context<MigrateCircuitBreaker>) -> Result<()> {
+    let circuit_breaker = &ctx.accounts.circuit_breaker;
+    let clock = Clock::get()?;
+    
+    require_keys_eq!(*circuit_breaker.owner, crate::ID, TwistError::InvalidAccount);
+    require!(
+        circuit_breaker.data_len() == CircuitBreakerState::ORIGINAL_LEN &&
+        circuit_breaker.try_borrow_data()?[..8] == <CircuitBreakerState as anchor_lang::Discriminator>::DISCRIMINATOR,
+        TwistError::InvalidAccount
+    );
+    
+    // Fund rent for the larger account, then grow it. The appended fields start in the
+    // old reserved bytes, which were never written, so they read as zero until seeded.
+    let rent_due = Rent::get()?
+        .minimum_balance(CircuitBreakerState::LEN)
+        .saturating_sub(circuit_breaker.lamports());
+    if rent_due > 0 {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.authority.to_account_info(),
+                    to: circuit_breaker.to_account_info(),
+                },
+            ),
+            rent_due,
+        )?;
+    }
+    circuit_breaker.realloc(CircuitBreakerState::LEN, true)?;
+    
+    // Zeroed responses would leave every trip alert-only, so a High or Critical trip
+    // would no longer halt buybacks or pause. Start from the defaults; no guardians yet.
+    {
+        let defaults = CircuitBreakerParams::default();
+        let mut data = circuit_breaker.try_borrow_mut_data()?;
+        let mut state = CircuitBreakerState::try_deserialize(&mut &data[..])?;
+        state.reserve_divergence_threshold_bps = defaults.reserve_divergence_threshold_bps;
+        state.low_response = defaults.low_response;
+        state.medium_response = defaults.medium_response;
+        state.high_response = defaults.high_response;
+        state.critical_response = defaults.critical_response;
+        state.try_serialize(&mut &mut data[..])?;
+    }
+    
+    emit!(ParameterUpdated {
+        parameter: "circuit_breaker_account_size".to_string(),
+        old_value: CircuitBreakerState::ORIGINAL_LEN.to_string(),
+        new_value: CircuitBreakerState::LEN.to_string(),
+        updated_by: ctx.accounts.authority.key(),
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Circuit breaker grown to {} bytes", CircuitBreakerState::LEN);
+    msg!("Response policies and the reserve threshold set to their defaults");
+    
+    Ok(())
+}
+
 #[derive(Accounts)]
 pub struct CheckCircuitBreaker<'info> {
     #[account(mut)]
@@ -91,20 +198,54 @@ pub struct CheckCircuitBreaker<'info> {
     )]
     pub circuit_breaker: Account<'info, CircuitBreakerState>,
     
+    #[account(
+        mut,
+        seeds = [MARKET_METRICS_SEED],
+        bump = market_metrics.bump,
+    )]
+    pub market_metrics: Box<Account<'info, MarketMetrics>>,
+    
+    #[account(
+        mut,
+        seeds = [CIRCUIT_BREAKER_LOG_SEED],
+        bump = circuit_breaker_log.bump,
+    )]
+    pub circuit_breaker_log: Box<Account<'info, CircuitBreakerLog>>,
+    
     pub mint: Account<'info, Mint>,
+    
+    #[account(
+        seeds = [DEX_ORACLE_SEED],
+        bump = dex_oracle.bump,
+    )]
+    pub dex_oracle: Option<Box<Account<'info, DexOracle>>>,
 }
 
 pub fn check_conditions_handler(ctx: Context<CheckCircuitBreaker>) -> Result<()> {
     let program_state = &mut ctx.accounts.program_state;
     let circuit_breaker = &mut ctx.accounts.circuit_breaker;
+    let market_metrics = &mut ctx.accounts.market_metrics;
+    let circuit_breaker_log = &mut ctx.accounts.circuit_breaker_log;
     let clock = Clock::get()?;
     
     // Update historical data first
-    update_historical_data(circuit_breaker, program_state, &ctx.accounts.mint, clock.unix_timestamp)?;
+    market_metrics.record_supply(clock.unix_timestamp, ctx.accounts.mint.supply);
+    market_metrics.record_liquidity(clock.unix_timestamp, program_state.floor_liquidity);
+    market_metrics.refresh_program_volumes(program_state, clock.unix_timestamp);
+    update_historical_data(circuit_breaker, market_metrics, clock.unix_timestamp)?;
     
     // Check if we can auto-reset
     if program_state.circuit_breaker_active && circuit_breaker.can_reset(clock.unix_timestamp) {
-        program_state.circuit_breaker_active = false;
+        program_state.lift_breaker_response();
+        
+        circuit_breaker_log.append(
+            BreakerAction::AutoReset,
+            circuit_breaker.last_trip_severity.clone(),
+            circuit_breaker.last_trip_condition.clone(),
+            ctx.accounts.authority.key(),
+            "Cooldown elapsed".to_string(),
+            clock.unix_timestamp,
+        )?;
         
         emit!(CircuitBreakerReset {
             reset_by: ctx.accounts.authority.key(),
@@ -133,7 +274,7 @@ pub fn check_conditions_handler(ctx: Context<CheckCircuitBreaker>) -> Result<()>
     }
     
     // 2. Volume spike check
-    if let Some(severity) = circuit_breaker.check_volume_spike(program_state.volume_24h) {
+    if let Some(severity) = circuit_breaker.check_volume_spike(market_metrics.volume_1h(clock.unix_timestamp)) {
         if max_severity.is_none() || severity_to_u8(&severity) > severity_to_u8(max_severity.as_ref().unwrap()) {
             max_severity = Some(severity);
             triggered_condition = Some(TripCondition::VolumeSpike);
@@ -158,7 +299,7 @@ pub fn check_conditions_handler(ctx: Context<CheckCircuitBreaker>) -> Result<()>
     }
     
     // 5. Oracle divergence check (if we have multiple price sources)
-    if let Some(prices) = get_all_oracle_prices(program_state) {
+    if let Some(prices) = get_all_oracle_prices(program_state, ctx.accounts.dex_oracle.as_ref().map(|oracle| &***oracle), clock.unix_timestamp) {
         if let Some(severity) = check_oracle_divergence(&prices, circuit_breaker.oracle_divergence_threshold_bps) {
             if max_severity.is_none() || severity_to_u8(&severity) > severity_to_u8(max_severity.as_ref().unwrap()) {
                 max_severity = Some(severity);
@@ -169,6 +310,15 @@ pub fn check_conditions_handler(ctx: Context<CheckCircuitBreaker>) -> Result<()>
     
     // Trip circuit breaker if needed
     if let (Some(severity), Some(condition)) = (max_severity, triggered_condition) {
+        circuit_breaker_log.append(
+            BreakerAction::Trip,
+            severity.clone(),
+            condition.clone(),
+            ctx.accounts.authority.key(),
+            format!("{:?}", condition),
+            clock.unix_timestamp,
+        )?;
+        
         trip_circuit_breaker(program_state, circuit_breaker, severity, condition, clock.unix_timestamp)?;
     }
     
@@ -184,7 +334,8 @@ pub struct ManualTripCircuitBreaker<'info> {
         mut,
         seeds = [PROGRAM_STATE_SEED],
         bump,
-        constraint = program_state.authority == authority.key() @ TwistError::Unauthorized
+        constraint = program_state.authority == authority.key() ||
+                    circuit_breaker.is_guardian(&authority.key()) @ TwistError::Unauthorized
     )]
     pub program_state: Account<'info, ProgramState>,
     
@@ -194,6 +345,13 @@ pub struct ManualTripCircuitBreaker<'info> {
         bump,
     )]
     pub circuit_breaker: Account<'info, CircuitBreakerState>,
+    
+    #[account(
+        mut,
+        seeds = [CIRCUIT_BREAKER_LOG_SEED],
+        bump = circuit_breaker_log.bump,
+    )]
+    pub circuit_breaker_log: Box<Account<'info, CircuitBreakerLog>>,
 }
 
 pub fn manual_trip_handler(
@@ -210,6 +368,15 @@ pub fn manual_trip_handler(
         TwistError::CircuitBreakerActive
     );
     
+    ctx.accounts.circuit_breaker_log.append(
+        BreakerAction::Trip,
+        severity.clone(),
+        TripCondition::ManualTrigger,
+        ctx.accounts.authority.key(),
+        reason.clone(),
+        clock.unix_timestamp,
+    )?;
+    
     trip_circuit_breaker(
         program_state,
         circuit_breaker,
@@ -218,7 +385,7 @@ pub fn manual_trip_handler(
         clock.unix_timestamp
     )?;
     
-    msg!("Circuit breaker manually triggered: {}", reason);
+    msg!("Circuit breaker manually triggered by {}: {}", ctx.accounts.authority.key(), reason);
     
     Ok(())
 }
@@ -232,7 +399,6 @@ pub struct ManualResetCircuitBreaker<'info> {
         mut,
         seeds = [PROGRAM_STATE_SEED],
         bump,
-        constraint = program_state.authority == authority.key() @ TwistError::Unauthorized
     )]
     pub program_state: Account<'info, ProgramState>,
     
@@ -240,57 +406,207 @@ pub struct ManualResetCircuitBreaker<'info> {
         mut,
         seeds = [b"circuit_breaker"],
         bump,
+        constraint = !circuit_breaker.is_guardian(&authority.key()) @ TwistError::Unauthorized,
     )]
     pub circuit_breaker: Account<'info, CircuitBreakerState>,
+    
+    #[account(
+        mut,
+        seeds = [CIRCUIT_BREAKER_LOG_SEED],
+        bump = circuit_breaker_log.bump,
+    )]
+    pub circuit_breaker_log: Box<Account<'info, CircuitBreakerLog>>,
+    
+    /// Required when resetting above Medium severity
+    #[account(
+        seeds = [b"multisig"],
+        bump = multisig_config.bump,
+    )]
+    pub multisig_config: Option<Account<'info, MultisigConfig>>,
 }
 
-pub fn manual_reset_handler(ctx: Context<ManualResetCircuitBreaker>) -> Result<()> {
+pub fn manual_reset_handler(
+    ctx: Context<ManualResetCircuitBreaker>,
+    reason: String,
+) -> Result<()> {
     let program_state = &mut ctx.accounts.program_state;
     let circuit_breaker = &mut ctx.accounts.circuit_breaker;
     let clock = Clock::get()?;
+    let actor = ctx.accounts.authority.key();
     
     require!(
         program_state.circuit_breaker_active,
         TwistError::CircuitBreakerActive
     );
     
-    // Check if cooldown has passed
-    let cooldown = circuit_breaker.get_cooldown_duration(&circuit_breaker.last_trip_severity);
-    let time_since_trip = clock.unix_timestamp - circuit_breaker.last_trip_timestamp;
+    // High and Critical trips can only be lifted by a multisig-executed transaction
+    let multisig = ctx.accounts.multisig_config.as_ref().map(|multisig| multisig.key());
+    circuit_breaker.authorize_reset(&actor, &program_state.authority, multisig.as_ref())?;
+    
+    let time_since_trip = lift_circuit_breaker(
+        program_state,
+        circuit_breaker,
+        &mut ctx.accounts.circuit_breaker_log,
+        actor,
+        reason.clone(),
+        clock.unix_timestamp,
+    )?;
+    
+    msg!("Circuit breaker manually reset after {} seconds: {}", time_since_trip, reason);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetCircuitBreakerGuardians<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [PROGRAM_STATE_SEED],
+        bump,
+        constraint = program_state.authority == authority.key() @ TwistError::Unauthorized
+    )]
+    pub program_state: Account<'info, ProgramState>,
+    
+    #[account(
+        mut,
+        seeds = [b"circuit_breaker"],
+        bump,
+    )]
+    pub circuit_breaker: Account<'info, CircuitBreakerState>,
+}
+
+pub fn set_guardians_handler(
+    ctx: Context<SetCircuitBreakerGuardians>,
+    guardians: Vec<Pubkey>,
+) -> Result<()> {
+    let circuit_breaker = &mut ctx.accounts.circuit_breaker;
+    let clock = Clock::get()?;
     
     require!(
-        time_since_trip >= cooldown,
+        guardians.len() <= CircuitBreakerState::MAX_GUARDIANS,
+        TwistError::InvalidAmount
+    );
+    
+    // Guardians are a separate key set from the authority
+    require!(
+        !guardians.contains(&ctx.accounts.program_state.authority),
+        TwistError::InvalidAccount
+    );
+    
+    let mut unique_guardians = guardians.clone();
+    unique_guardians.sort();
+    unique_guardians.dedup();
+    require!(
+        unique_guardians.len() == guardians.len(),
+        TwistError::InvalidAccount
+    );
+    
+    let old_count = circuit_breaker.guardians.len();
+    circuit_breaker.guardians = guardians;
+    
+    emit!(ParameterUpdated {
+        parameter: "circuit_breaker_guardians".to_string(),
+        old_value: format!("count: {}", old_count),
+        new_value: format!("count: {}", circuit_breaker.guardians.len()),
+        updated_by: ctx.accounts.authority.key(),
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Circuit breaker guardians updated: {}", circuit_breaker.guardians.len());
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateResponsePolicy<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [PROGRAM_STATE_SEED],
+        bump,
+        constraint = program_state.authority == authority.key() @ TwistError::Unauthorized
+    )]
+    pub program_state: Account<'info, ProgramState>,
+    
+    #[account(
+        mut,
+        seeds = [b"circuit_breaker"],
+        bump,
+    )]
+    pub circuit_breaker: Account<'info, CircuitBreakerState>,
+}
+
+pub fn update_response_policy_handler(
+    ctx: Context<UpdateResponsePolicy>,
+    severity: CircuitBreakerSeverity,
+    response: SeverityResponse,
+) -> Result<()> {
+    let circuit_breaker = &mut ctx.accounts.circuit_breaker;
+    let clock = Clock::get()?;
+    
+    // A policy cannot change underneath an active trip at the same severity
+    require!(
+        !ctx.accounts.program_state.circuit_breaker_active ||
+            circuit_breaker.last_trip_severity != severity,
         TwistError::CircuitBreakerActive
     );
     
-    // Reset circuit breaker
-    program_state.circuit_breaker_active = false;
+    require!(
+        response.fee_surcharge_bps <= 500, // Max +5%
+        TwistError::InvalidAmount
+    );
     
-    // Re-enable systems based on severity
-    match circuit_breaker.last_trip_severity {
-        CircuitBreakerSeverity::Critical => {
-            program_state.emergency_pause = false;
-            program_state.buyback_enabled = true;
-        },
-        CircuitBreakerSeverity::High => {
-            program_state.buyback_enabled = true;
-        },
-        _ => {}
-    }
+    let old_response = circuit_breaker.get_response(&severity).clone();
+    circuit_breaker.set_response(&severity, response.clone());
     
-    emit!(CircuitBreakerReset {
-        reset_by: ctx.accounts.authority.key(),
+    emit!(ParameterUpdated {
+        parameter: format!("circuit_breaker_response_{:?}", severity),
+        old_value: format!("{:?}", old_response),
+        new_value: format!("{:?}", response),
+        updated_by: ctx.accounts.authority.key(),
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Updated {:?} circuit breaker response", severity);
+    
+    Ok(())
+}
+
+pub fn update_reserve_divergence_threshold_handler(
+    ctx: Context<UpdateResponsePolicy>,
+    threshold_bps: u64,
+) -> Result<()> {
+    let circuit_breaker = &mut ctx.accounts.circuit_breaker;
+    let clock = Clock::get()?;
+    
+    // Zero disables the reserve check
+    require!(
+        threshold_bps <= 10000,
+        TwistError::InvalidAmount
+    );
+    
+    let old_value = circuit_breaker.reserve_divergence_threshold_bps;
+    circuit_breaker.reserve_divergence_threshold_bps = threshold_bps;
+    
+    emit!(ParameterUpdated {
+        parameter: "reserve_divergence_threshold_bps".to_string(),
+        old_value: old_value.to_string(),
+        new_value: threshold_bps.to_string(),
+        updated_by: ctx.accounts.authority.key(),
         timestamp: clock.unix_timestamp,
     });
     
-    msg!("Circuit breaker manually reset after {} seconds", time_since_trip);
+    msg!("Updated reserve divergence threshold to {} bps", threshold_bps);
     
     Ok(())
 }
 
 // Helper functions
 
-fn trip_circuit_breaker(
+pub(crate) fn trip_circuit_breaker(
     program_state: &mut ProgramState,
     circuit_breaker: &mut CircuitBreakerState,
     severity: CircuitBreakerSeverity,
@@ -304,30 +620,10 @@ fn trip_circuit_breaker(
     circuit_breaker.last_trip_condition = condition.clone();
     circuit_breaker.trip_count += 1;
     
-    let mut actions_taken = Vec::new();
-    
-    // Take actions based on severity
-    match severity {
-        CircuitBreakerSeverity::Critical => {
-            program_state.emergency_pause = true;
-            program_state.buyback_enabled = false;
-            actions_taken.push("Emergency pause activated".to_string());
-            actions_taken.push("Buyback disabled".to_string());
-            actions_taken.push("All operations halted".to_string());
-        },
-        CircuitBreakerSeverity::High => {
-            program_state.buyback_enabled = false;
-            actions_taken.push("Buyback disabled".to_string());
-            actions_taken.push("High-risk operations restricted".to_string());
-        },
-        CircuitBreakerSeverity::Medium => {
-            actions_taken.push("Enhanced monitoring enabled".to_string());
-            actions_taken.push("Risk parameters tightened".to_string());
-        },
-        CircuitBreakerSeverity::Low => {
-            actions_taken.push("Alert sent to operators".to_string());
-        }
-    }
+    // Take actions configured for this severity
+    let response = circuit_breaker.get_response(&severity);
+    program_state.apply_breaker_response(response);
+    let actions_taken = response.describe();
     
     emit!(CircuitBreakerTriggered {
         trigger_reason: format!("{:?}", condition),
@@ -341,30 +637,65 @@ fn trip_circuit_breaker(
     Ok(())
 }
 
+/// Lift a trip once its cooldown has passed, logging the reset. Returns the seconds since the trip.
+pub(crate) fn lift_circuit_breaker(
+    program_state: &mut ProgramState,
+    circuit_breaker: &CircuitBreakerState,
+    circuit_breaker_log: &mut CircuitBreakerLog,
+    actor: Pubkey,
+    reason: String,
+    timestamp: i64,
+) -> Result<i64> {
+    // Check if cooldown has passed
+    let cooldown = circuit_breaker.get_cooldown_duration(&circuit_breaker.last_trip_severity);
+    let time_since_trip = timestamp - circuit_breaker.last_trip_timestamp;
+    
+    require!(
+        time_since_trip >= cooldown,
+        TwistError::CircuitBreakerActive
+    );
+    
+    // Reset circuit breaker and re-enable the systems its trip halted
+    program_state.lift_breaker_response();
+    
+    circuit_breaker_log.append(
+        BreakerAction::Reset,
+        circuit_breaker.last_trip_severity.clone(),
+        circuit_breaker.last_trip_condition.clone(),
+        actor,
+        reason,
+        timestamp,
+    )?;
+    
+    emit!(CircuitBreakerReset {
+        reset_by: actor,
+        timestamp,
+    });
+    
+    Ok(time_since_trip)
+}
+
 fn update_historical_data(
     circuit_breaker: &mut CircuitBreakerState,
-    program_state: &ProgramState,
-    mint: &Mint,
+    market_metrics: &MarketMetrics,
     timestamp: i64,
 ) -> Result<()> {
-    // Update 1-hour data every hour
-    if timestamp % 3600 < 60 { // Within first minute of hour
-        circuit_breaker.price_1h_ago = program_state.last_oracle_price;
-        circuit_breaker.volume_1h_ago = program_state.volume_24h; // This would need proper 1h tracking
-        circuit_breaker.liquidity_1h_ago = program_state.floor_liquidity;
-    }
-    
-    // Update 24-hour data every day
-    if timestamp % 86400 < 60 { // Within first minute of day
-        circuit_breaker.price_24h_ago = program_state.last_oracle_price;
-        circuit_breaker.volume_24h_ago = program_state.volume_24h;
-        circuit_breaker.supply_24h_ago = mint.supply as u128;
-    }
+    // 1-hour snapshot from the hourly buckets, volume baseline is the average hour
+    let snapshot_1h = market_metrics.hourly_at(timestamp - HOUR).copied().unwrap_or_default();
+    circuit_breaker.price_1h_ago = snapshot_1h.close_price;
+    circuit_breaker.volume_1h_ago = market_metrics.average_hourly_volume(timestamp);
+    circuit_breaker.liquidity_1h_ago = snapshot_1h.liquidity;
+    
+    // 24-hour snapshot from the daily buckets
+    let snapshot_24h = market_metrics.daily_at(timestamp - DAY).copied().unwrap_or_default();
+    circuit_breaker.price_24h_ago = snapshot_24h.close_price;
+    circuit_breaker.volume_24h_ago = snapshot_24h.volume;
+    circuit_breaker.supply_24h_ago = snapshot_24h.supply as u128;
     
     Ok(())
 }
 
-fn severity_to_u8(severity: &CircuitBreakerSeverity) -> u8 {
+pub(crate) fn severity_to_u8(severity: &CircuitBreakerSeverity) -> u8 {
     match severity {
         CircuitBreakerSeverity::Low => 1,
         CircuitBreakerSeverity::Medium => 2,
@@ -373,10 +704,29 @@ fn severity_to_u8(severity: &CircuitBreakerSeverity) -> u8 {
     }
 }
 
-fn get_all_oracle_prices(_program_state: &ProgramState) -> Option<Vec<PriceSource>> {
-    // This would fetch prices from multiple oracles
-    // For now, returning None as it requires oracle account access
-    None
+fn get_all_oracle_prices(
+    program_state: &ProgramState,
+    dex_oracle: Option<&DexOracle>,
+    current_time: i64,
+) -> Option<Vec<PriceSource>> {
+    // The stored aggregate stands in for the external feeds (labelled with the primary
+    // one); only compare it while fresh
+    if program_state.last_oracle_price == 0 ||
+       current_time - program_state.last_oracle_update > ORACLE_STALENESS_THRESHOLD * 2 {
+        return None;
+    }
+    
+    let dex_source = dex_oracle?.price_source(DEX_ORACLE_TWAP_WINDOW, current_time).ok()?;
+    
+    Some(vec![
+        PriceSource {
+            oracle_type: OracleType::Pyth,
+            price: program_state.last_oracle_price,
+            confidence: 0,
+            timestamp: program_state.last_oracle_update,
+        },
+        dex_source,
+    ])
 }
 
 fn check_oracle_divergence(prices: &[PriceSource], threshold_bps: u64) -> Option<CircuitBreakerSeverity> {
@@ -384,15 +734,14 @@ fn check_oracle_divergence(prices: &[PriceSource], threshold_bps: u64) -> Option
         return None;
     }
     
-    let max_price = prices.iter().map(|p| p.price).max().unwrap();
-    let min_price = prices.iter().map(|p| p.price).min().unwrap();
-    
-    let divergence_bps = ((max_price - min_price) * 10000) / min_price;
+    // A source reporting zero diverges maximally and lands in Critical
+    let prices: Vec<u64> = prices.iter().map(|p| p.price).collect();
+    let divergence_bps = price_divergence_bps(&prices);
     
     if divergence_bps > threshold_bps {
-        if divergence_bps > threshold_bps * 3 {
+        if divergence_bps > threshold_bps.saturating_mul(3) {
             Some(CircuitBreakerSeverity::Critical)
-        } else if divergence_bps > threshold_bps * 2 {
+        } else if divergence_bps > threshold_bps.saturating_mul(2) {
             Some(CircuitBreakerSeverity::High)
         } else {
             Some(CircuitBreakerSeverity::Medium)
@@ -0,0 +1,510 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount};
+
+use crate::constants::*;
+use crate::defi::{load_whirlpool_state, SwapParams, WhirlpoolSwap};
+use crate::errors::TwistError;
+use crate::events::*;
+use crate::instructions::fee_collector::protocol_swap_fee;
+use crate::instructions::treasury_ops::withdraw_from_ops_treasury;
+use crate::state::*;
+use crate::utils::{BlockRestriction, CommittedAction, CommitRevealState, MEVProtection, TransactionType};
+
+#[derive(Accounts)]
+pub struct InitializeBlockRestriction<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    #[account(
+        init,
+        payer = user,
+        space = BlockRestriction::LEN,
+        seeds = [BLOCK_RESTRICTION_SEED, user.key().as_ref()],
+        bump
+    )]
+    pub block_restriction: Account<'info, BlockRestriction>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_block_restriction_handler(ctx: Context<InitializeBlockRestriction>) -> Result<()> {
+    let block_restriction = &mut ctx.accounts.block_restriction;
+    let clock = Clock::get()?;
+    
+    block_restriction.user = ctx.accounts.user.key();
+    block_restriction.last_transaction_slot = 0;
+    block_restriction.transaction_count = 0;
+    block_restriction.daily_volume = 0;
+    block_restriction.last_reset_slot = clock.slot;
+    block_restriction.bump = ctx.bumps.block_restriction;
+    
+    msg!("Block restriction initialized for {}", block_restriction.user);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(commit_hash: [u8; 32])]
+pub struct CommitAction<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    #[account(
+        init,
+        payer = user,
+        space = CommitRevealState::LEN,
+        seeds = [COMMIT_REVEAL_SEED, user.key().as_ref(), commit_hash.as_ref()],
+        bump
+    )]
+    pub commit: Account<'info, CommitRevealState>,
+    
+    #[account(
+        mut,
+        seeds = [BLOCK_RESTRICTION_SEED, user.key().as_ref()],
+        bump = block_restriction.bump,
+    )]
+    pub block_restriction: Account<'info, BlockRestriction>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn commit_action_handler(ctx: Context<CommitAction>, commit_hash: [u8; 32]) -> Result<()> {
+    let commit = &mut ctx.accounts.commit;
+    let clock = Clock::get()?;
+    
+    commit.user = ctx.accounts.user.key();
+    commit.commit_hash = commit_hash;
+    commit.commit_timestamp = clock.unix_timestamp;
+    commit.reveal_timestamp = 0;
+    commit.revealed = false;
+    commit.cancelled = false;
+    commit.bump = ctx.bumps.commit;
+    
+    // The reveal cannot share a block with the commit
+    ctx.accounts.block_restriction.last_transaction_slot = clock.slot;
+    
+    emit!(ActionCommitted {
+        user: commit.user,
+        commit_hash,
+        slot: clock.slot,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Action committed by {} at slot {}", commit.user, clock.slot);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RevealSwap<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [COMMIT_REVEAL_SEED, user.key().as_ref(), commit.commit_hash.as_ref()],
+        bump = commit.bump,
+    )]
+    pub commit: Account<'info, CommitRevealState>,
+    
+    #[account(
+        mut,
+        seeds = [BLOCK_RESTRICTION_SEED, user.key().as_ref()],
+        bump = block_restriction.bump,
+    )]
+    pub block_restriction: Account<'info, BlockRestriction>,
+    
+    #[account(
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = !program_state.emergency_pause @ TwistError::EmergencyPauseActive,
+    )]
+    pub program_state: Box<Account<'info, ProgramState>>,
+    
+    #[account(
+        mut,
+        seeds = [MARKET_METRICS_SEED],
+        bump = market_metrics.bump,
+    )]
+    pub market_metrics: Box<Account<'info, MarketMetrics>>,
+    
+    #[account(
+        mut,
+        seeds = [b"fee_collector"],
+        bump = fee_collector.bump,
+    )]
+    pub fee_collector: Box<Account<'info, FeeCollectorState>>,
+    
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED],
+        bump,
+        token::mint = program_state.mint,
+        token::authority = fee_collector,
+    )]
+    pub fee_vault: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        address = fee_collector.usdc_fee_vault @ TwistError::InvalidAccount,
+    )]
+    pub usdc_fee_vault: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        token::mint = program_state.mint,
+        token::authority = user,
+    )]
+    pub user_twist_account: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        token::mint = fee_collector.usdc_mint,
+        token::authority = user,
+    )]
+    pub user_usdc_account: Box<Account<'info, TokenAccount>>,
+    
+    /// CHECK: Program whirlpool, deserialized in the handler
+    #[account(
+        mut,
+        address = program_state.whirlpool @ TwistError::InvalidAccount,
+    )]
+    pub whirlpool: AccountInfo<'info>,
+    
+    /// CHECK: Validated against the whirlpool in the swap helper
+    #[account(mut)]
+    pub token_vault_a: AccountInfo<'info>,
+    
+    /// CHECK: Validated against the whirlpool in the swap helper
+    #[account(mut)]
+    pub token_vault_b: AccountInfo<'info>,
+    
+    /// CHECK: Tick arrays validated in the swap helper
+    #[account(mut)]
+    pub tick_array_0: AccountInfo<'info>,
+    
+    /// CHECK: Tick arrays validated in the swap helper
+    #[account(mut)]
+    pub tick_array_1: AccountInfo<'info>,
+    
+    /// CHECK: Tick arrays validated in the swap helper
+    #[account(mut)]
+    pub tick_array_2: AccountInfo<'info>,
+    
+    /// CHECK: Whirlpool oracle
+    pub oracle: AccountInfo<'info>,
+    
+    /// CHECK: Whirlpool program, checked in the swap helper
+    pub whirlpool_program: AccountInfo<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn reveal_swap_handler(
+    ctx: Context<RevealSwap>,
+    amount: u64,
+    min_amount_out: u64,
+    a_to_b: bool,
+    nonce: u64,
+    salt: [u8; 32],
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let user = ctx.accounts.user.key();
+    let action = CommittedAction::Swap { a_to_b, min_amount_out };
+    
+    require!(amount > 0, TwistError::InvalidAmount);
+    require!(
+        !ctx.accounts.program_state.circuit_breaker_active,
+        TwistError::CircuitBreakerActive
+    );
+    
+    // TWIST in (a_to_b) is valued at the oracle price, USDC in at face value
+    let usdc_value = if a_to_b {
+        ctx.accounts.program_state.twist_to_usdc_value(amount)
+    } else {
+        amount
+    };
+    ctx.accounts.program_state.check_transaction_size(usdc_value)?;
+    
+    let commit_hash = ctx.accounts.commit.commit_hash;
+    validate_reveal(
+        &ctx.accounts.commit,
+        &ctx.accounts.block_restriction,
+        &clock,
+        &user,
+        amount,
+        nonce,
+        &salt,
+        &action,
+        MEVProtection::should_delay_transaction(usdc_value, TransactionType::Swap).1,
+    )?;
+    
+    // Dynamic fee in the input token, paid straight into the matching fee vault
+    let (fee_bps, fee_usdc) = protocol_swap_fee(
+        &ctx.accounts.fee_collector,
+        &ctx.accounts.program_state,
+        &ctx.accounts.market_metrics,
+        usdc_value,
+        clock.unix_timestamp,
+    )?;
+    let fee_amount = (amount as u128 * fee_bps as u128 / 10000) as u64;
+    let swap_amount = amount - fee_amount;
+    
+    let (fee_from, fee_to) = if a_to_b {
+        (&ctx.accounts.user_twist_account, &ctx.accounts.fee_vault)
+    } else {
+        (&ctx.accounts.user_usdc_account, &ctx.accounts.usdc_fee_vault)
+    };
+    if fee_amount > 0 {
+        let cpi_accounts = token::Transfer {
+            from: fee_from.to_account_info(),
+            to: fee_to.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+        
+        token::transfer(cpi_ctx, fee_amount)?;
+        
+        if a_to_b {
+            ctx.accounts.fee_collector.record_fee(FeeType::Trading, fee_amount);
+        } else {
+            ctx.accounts.fee_collector.record_usdc_fee(fee_amount);
+        }
+    }
+    
+    // Swap the remainder with the user as token authority
+    let whirlpool_state = load_whirlpool_state(&ctx.accounts.whirlpool)?;
+    let swap = WhirlpoolSwap {
+        whirlpool_program: ctx.accounts.whirlpool_program.clone(),
+        token_program: ctx.accounts.token_program.to_account_info(),
+        token_authority: ctx.accounts.user.to_account_info(),
+        whirlpool: ctx.accounts.whirlpool.clone(),
+        token_owner_account_a: ctx.accounts.user_twist_account.to_account_info(),
+        token_vault_a: ctx.accounts.token_vault_a.clone(),
+        token_owner_account_b: ctx.accounts.user_usdc_account.to_account_info(),
+        token_vault_b: ctx.accounts.token_vault_b.clone(),
+        tick_arrays: [
+            ctx.accounts.tick_array_0.clone(),
+            ctx.accounts.tick_array_1.clone(),
+            ctx.accounts.tick_array_2.clone(),
+        ],
+        oracle: ctx.accounts.oracle.clone(),
+    };
+    
+    let destination = if a_to_b {
+        &mut ctx.accounts.user_usdc_account
+    } else {
+        &mut ctx.accounts.user_twist_account
+    };
+    let balance_before = destination.amount;
+    
+    swap.invoke(
+        &whirlpool_state,
+        SwapParams {
+            amount: swap_amount,
+            other_amount_threshold: min_amount_out,
+            sqrt_price_limit: 0, // Bounded by other_amount_threshold
+            amount_specified_is_input: true,
+            a_to_b,
+        },
+        &[],
+    )?;
+    
+    destination.reload()?;
+    let amount_out = destination.amount.saturating_sub(balance_before);
+    require!(
+        amount_out >= min_amount_out,
+        TwistError::SlippageToleranceExceeded
+    );
+    
+    // Record the reveal
+    let commit = &mut ctx.accounts.commit;
+    commit.revealed = true;
+    commit.reveal_timestamp = clock.unix_timestamp;
+    ctx.accounts.block_restriction.update(clock.slot, usdc_value);
+    ctx.accounts.market_metrics.record_volume(clock.unix_timestamp, usdc_value);
+    
+    emit!(ActionRevealed {
+        user,
+        commit_hash,
+        action,
+        amount,
+        usdc_value,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Revealed swap of {} ({} USDC value) for {}", amount, usdc_value, amount_out);
+    msg!("Dynamic fee: {} bps ({} USDC value)", fee_bps, fee_usdc);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RevealTreasuryWithdrawal<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [COMMIT_REVEAL_SEED, authority.key().as_ref(), commit.commit_hash.as_ref()],
+        bump = commit.bump,
+    )]
+    pub commit: Account<'info, CommitRevealState>,
+    
+    #[account(
+        mut,
+        seeds = [BLOCK_RESTRICTION_SEED, authority.key().as_ref()],
+        bump = block_restriction.bump,
+    )]
+    pub block_restriction: Account<'info, BlockRestriction>,
+    
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump,
+        constraint = program_state.authority == authority.key() @ TwistError::Unauthorized,
+        constraint = !program_state.emergency_pause @ TwistError::EmergencyPauseActive,
+    )]
+    pub program_state: Account<'info, ProgramState>,
+    
+    #[account(
+        mut,
+        seeds = [OPS_TREASURY_SEED],
+        bump,
+        token::mint = program_state.mint,
+        token::authority = program_state,
+    )]
+    pub ops_treasury: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = recipient_account.mint == program_state.mint @ TwistError::InvalidAccount,
+    )]
+    pub recipient_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [TREASURY_STATE_SEED, TreasuryType::Operations.seed().as_ref()],
+        bump = treasury_state.bump,
+    )]
+    pub treasury_state: Account<'info, TreasuryState>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn reveal_treasury_withdrawal_handler(
+    ctx: Context<RevealTreasuryWithdrawal>,
+    amount: u64,
+    purpose: String,
+    nonce: u64,
+    salt: [u8; 32],
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let authority = ctx.accounts.authority.key();
+    let recipient = ctx.accounts.recipient_account.owner;
+    let action = CommittedAction::TreasuryWithdrawal { recipient };
+    
+    let usdc_value = ctx.accounts.program_state.twist_to_usdc_value(amount);
+    let commit_hash = ctx.accounts.commit.commit_hash;
+    validate_reveal(
+        &ctx.accounts.commit,
+        &ctx.accounts.block_restriction,
+        &clock,
+        &authority,
+        amount,
+        nonce,
+        &salt,
+        &action,
+        MEVProtection::should_delay_transaction(usdc_value, TransactionType::Withdrawal).1,
+    )?;
+    
+    ctx.accounts.treasury_state.consume_daily_limit(amount, clock.unix_timestamp)?;
+    
+    withdraw_from_ops_treasury(
+        &ctx.accounts.program_state,
+        &ctx.accounts.ops_treasury,
+        &ctx.accounts.recipient_account,
+        &ctx.accounts.token_program,
+        recipient,
+        amount,
+        purpose,
+    )?;
+    
+    let commit = &mut ctx.accounts.commit;
+    commit.revealed = true;
+    commit.reveal_timestamp = clock.unix_timestamp;
+    ctx.accounts.block_restriction.update(clock.slot, usdc_value);
+    
+    emit!(ActionRevealed {
+        user: authority,
+        commit_hash,
+        action,
+        amount,
+        usdc_value,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CloseCommit<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    #[account(
+        mut,
+        close = user,
+        seeds = [COMMIT_REVEAL_SEED, user.key().as_ref(), commit.commit_hash.as_ref()],
+        bump = commit.bump,
+    )]
+    pub commit: Account<'info, CommitRevealState>,
+}
+
+/// Closing an unrevealed commitment cancels it
+pub fn close_commit_handler(ctx: Context<CloseCommit>) -> Result<()> {
+    let commit = &ctx.accounts.commit;
+    
+    if commit.revealed {
+        msg!("Closed revealed commitment");
+    } else {
+        msg!("Cancelled commitment from {}", commit.commit_timestamp);
+    }
+    
+    Ok(())
+}
+
+// Helper functions
+
+/// Check the revealed parameters, the reveal window and the slot gap. `action_delay`
+/// is the extra wait for the action's size; the floor is `COMMIT_REVEAL_MIN_DELAY`.
+#[allow(clippy::too_many_arguments)]
+fn validate_reveal(
+    commit: &CommitRevealState,
+    block_restriction: &BlockRestriction,
+    clock: &Clock,
+    user: &Pubkey,
+    amount: u64,
+    nonce: u64,
+    salt: &[u8; 32],
+    action: &CommittedAction,
+    action_delay: i64,
+) -> Result<()> {
+    MEVProtection::verify_commit_reveal(&commit.commit_hash, user, amount, nonce, salt, action)?;
+    
+    let min_delay = action_delay.max(COMMIT_REVEAL_MIN_DELAY);
+    require!(
+        !commit.is_expired(clock.unix_timestamp, min_delay, COMMIT_REVEAL_WINDOW),
+        TwistError::CommitExpired
+    );
+    require!(
+        commit.can_reveal(clock.unix_timestamp, min_delay),
+        TwistError::CommitNotReady
+    );
+    
+    MEVProtection::validate_transaction_timing(
+        clock,
+        block_restriction.last_transaction_slot,
+        COMMIT_REVEAL_MIN_SLOT_GAP,
+    )
+}
@@ -65,7 +65,7 @@ pub fn handler(ctx: Context<ApplyDecay>) -> Result<()> {
     // Update program state
     program_state.total_decayed = program_state.total_decayed.saturating_add(decay_amount as u128);
     program_state.last_decay_timestamp = clock.unix_timestamp;
-    program_state.floor_liquidity = program_state.floor_liquidity.saturating_add(floor_amount);
+    // floor_liquidity tracks the floor's USDC, so the TWIST share leaves it unchanged
     
     // Emit decay event
     emit!(DecayApplied {
@@ -0,0 +1,104 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::defi::load_whirlpool_state;
+use crate::errors::TwistError;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct InitializeDexOracle<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [PROGRAM_STATE_SEED],
+        bump,
+        constraint = program_state.authority == authority.key() @ TwistError::Unauthorized,
+        constraint = program_state.whirlpool_initialized @ TwistError::InvalidAccount,
+    )]
+    pub program_state: Box<Account<'info, ProgramState>>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = DexOracle::LEN,
+        seeds = [DEX_ORACLE_SEED],
+        bump
+    )]
+    pub dex_oracle: Box<Account<'info, DexOracle>>,
+    
+    /// CHECK: The program's whirlpool, deserialized in handler
+    #[account(address = program_state.whirlpool @ TwistError::InvalidAccount)]
+    pub whirlpool: AccountInfo<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_dex_oracle_handler(ctx: Context<InitializeDexOracle>) -> Result<()> {
+    let dex_oracle = &mut ctx.accounts.dex_oracle;
+    dex_oracle.bump = ctx.bumps.dex_oracle;
+    dex_oracle.whirlpool = ctx.accounts.whirlpool.key();
+    dex_oracle.observation_index = 0;
+    dex_oracle.observation_count = 0;
+    
+    let sqrt_price = load_whirlpool_state(&ctx.accounts.whirlpool)?.sqrt_price;
+    dex_oracle.record(sqrt_price, Clock::get()?.unix_timestamp)?;
+    
+    msg!("DEX oracle initialized for whirlpool {}", dex_oracle.whirlpool);
+    msg!("Ring of {} observations, at least {}s apart", DexOracle::MAX_OBSERVATIONS, DEX_ORACLE_MIN_INTERVAL);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RecordDexObservation<'info> {
+    /// Anyone may keep the observation ring current between protocol trades
+    pub keeper: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [DEX_ORACLE_SEED],
+        bump = dex_oracle.bump,
+    )]
+    pub dex_oracle: Box<Account<'info, DexOracle>>,
+    
+    /// CHECK: The observed whirlpool, deserialized in handler
+    #[account(address = dex_oracle.whirlpool @ TwistError::InvalidAccount)]
+    pub whirlpool: AccountInfo<'info>,
+}
+
+pub fn record_dex_observation_handler(ctx: Context<RecordDexObservation>) -> Result<()> {
+    let dex_oracle = &mut ctx.accounts.dex_oracle;
+    let clock = Clock::get()?;
+    
+    let sqrt_price = load_whirlpool_state(&ctx.accounts.whirlpool)?.sqrt_price;
+    if dex_oracle.record(sqrt_price, clock.unix_timestamp)? {
+        msg!("DEX observation {} recorded at tick {}",
+            dex_oracle.observation_index,
+            dex_oracle.latest().tick
+        );
+    } else {
+        msg!("Last DEX observation is under {}s old, nothing recorded", DEX_ORACLE_MIN_INTERVAL);
+    }
+    
+    Ok(())
+}
+
+/// Record an observation from the oracle's whirlpool if the oracle was passed and the
+/// pool is among `accounts`. Protocol instructions call this before they swap against or
+/// move liquidity in the pool, so the sample is the price before their own effect on it.
+pub(crate) fn observe_pool(
+    dex_oracle: &mut Option<Box<Account<DexOracle>>>,
+    accounts: &[AccountInfo],
+    current_time: i64,
+) -> Result<()> {
+    let Some(dex_oracle) = dex_oracle else {
+        return Ok(());
+    };
+    
+    if let Some(whirlpool) = accounts.iter().find(|account| account.key() == dex_oracle.whirlpool) {
+        dex_oracle.record(load_whirlpool_state(whirlpool)?.sqrt_price, current_time)?;
+    }
+    
+    Ok(())
+}
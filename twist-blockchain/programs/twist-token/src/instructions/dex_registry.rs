@@ -0,0 +1,165 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::TwistError;
+use crate::events::*;
+use crate::defi::{ORCA_WHIRLPOOL_PROGRAM_ID, RAYDIUM_CLMM_PROGRAM_ID};
+
+#[derive(Accounts)]
+pub struct InitializeDexRegistry<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [PROGRAM_STATE_SEED],
+        bump,
+        constraint = program_state.authority == authority.key() @ TwistError::Unauthorized,
+    )]
+    pub program_state: Account<'info, ProgramState>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = DexRegistry::LEN,
+        seeds = [DEX_REGISTRY_SEED],
+        bump
+    )]
+    pub dex_registry: Box<Account<'info, DexRegistry>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_dex_registry_handler(ctx: Context<InitializeDexRegistry>) -> Result<()> {
+    let dex_registry = &mut ctx.accounts.dex_registry;
+    dex_registry.bump = ctx.bumps.dex_registry;
+    dex_registry.venues = [DexVenueConfig::empty(); MAX_DEX_VENUES];
+    dex_registry.price_impact_threshold_bps = ROUTE_DEFAULT_PRICE_IMPACT_BPS;
+    dex_registry.last_updated = Clock::get()?.unix_timestamp;
+    
+    msg!("DEX registry initialized with {} venue slots", MAX_DEX_VENUES);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetDexVenue<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [PROGRAM_STATE_SEED],
+        bump,
+        constraint = program_state.authority == authority.key() @ TwistError::Unauthorized,
+    )]
+    pub program_state: Account<'info, ProgramState>,
+    
+    #[account(
+        mut,
+        seeds = [DEX_REGISTRY_SEED],
+        bump = dex_registry.bump,
+    )]
+    pub dex_registry: Box<Account<'info, DexRegistry>>,
+}
+
+pub fn set_dex_venue_handler(
+    ctx: Context<SetDexVenue>,
+    index: u8,
+    config: DexVenueConfig,
+) -> Result<()> {
+    let dex_registry = &mut ctx.accounts.dex_registry;
+    let clock = Clock::get()?;
+    
+    require!(
+        (index as usize) < MAX_DEX_VENUES,
+        TwistError::InvalidDexVenue
+    );
+    
+    // Concentrated liquidity venues are pinned to their canonical programs
+    let program_matches = match config.venue {
+        DexVenue::Whirlpool => config.program_id == ORCA_WHIRLPOOL_PROGRAM_ID,
+        DexVenue::RaydiumClmm => config.program_id == RAYDIUM_CLMM_PROGRAM_ID,
+        DexVenue::ConstantProduct => config.program_id != Pubkey::default(),
+    };
+    require!(
+        program_matches && config.pool != Pubkey::default(),
+        TwistError::InvalidDexVenue
+    );
+    
+    // A pool may only be registered once
+    require!(
+        dex_registry
+            .venues
+            .iter()
+            .enumerate()
+            .all(|(slot, existing)| slot == index as usize || existing.pool != config.pool),
+        TwistError::InvalidDexVenue
+    );
+    
+    let old_config = dex_registry.venues[index as usize];
+    dex_registry.venues[index as usize] = config;
+    dex_registry.last_updated = clock.unix_timestamp;
+    
+    emit!(ParameterUpdated {
+        parameter: format!("dex_venue_{}", index),
+        old_value: format!("{:?} {} enabled={}", old_config.venue, old_config.pool, old_config.enabled),
+        new_value: format!("{:?} {} enabled={}", config.venue, config.pool, config.enabled),
+        updated_by: ctx.accounts.authority.key(),
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("DEX venue {} set to {:?} pool {} (enabled: {})",
+        index,
+        config.venue,
+        config.pool,
+        config.enabled
+    );
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetRoutingThreshold<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [PROGRAM_STATE_SEED],
+        bump,
+        constraint = program_state.authority == authority.key() @ TwistError::Unauthorized,
+    )]
+    pub program_state: Account<'info, ProgramState>,
+    
+    #[account(
+        mut,
+        seeds = [DEX_REGISTRY_SEED],
+        bump = dex_registry.bump,
+    )]
+    pub dex_registry: Box<Account<'info, DexRegistry>>,
+}
+
+pub fn set_routing_threshold_handler(
+    ctx: Context<SetRoutingThreshold>,
+    price_impact_threshold_bps: u64,
+) -> Result<()> {
+    let dex_registry = &mut ctx.accounts.dex_registry;
+    let clock = Clock::get()?;
+    
+    require!(
+        price_impact_threshold_bps > 0 && price_impact_threshold_bps <= MAX_ROUTE_PRICE_IMPACT_BPS,
+        TwistError::InvalidDexVenue
+    );
+    
+    let old_value = dex_registry.price_impact_threshold_bps;
+    dex_registry.price_impact_threshold_bps = price_impact_threshold_bps;
+    dex_registry.last_updated = clock.unix_timestamp;
+    
+    emit!(ParameterUpdated {
+        parameter: "price_impact_threshold_bps".to_string(),
+        old_value: old_value.to_string(),
+        new_value: price_impact_threshold_bps.to_string(),
+        updated_by: ctx.accounts.authority.key(),
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Routing price impact threshold set to {} bps", price_impact_threshold_bps);
+    
+    Ok(())
+}
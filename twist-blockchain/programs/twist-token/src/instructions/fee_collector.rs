@@ -5,6 +5,9 @@ use crate::constants::*;
 use crate::errors::TwistError;
 use crate::events::*;
 use crate::state::*;
+use crate::utils::{execute_route, oracle_min_swap_output, plan_registry_route, MEVProtection};
+use crate::defi::SwapEndpoints;
+use crate::instructions::dex_oracle::observe_pool;
 
 #[derive(Accounts)]
 pub struct InitializeFeeCollector<'info> {
@@ -28,7 +31,34 @@ pub struct InitializeFeeCollector<'info> {
     )]
     pub fee_collector: Account<'info, FeeCollectorState>,
     
+    #[account(
+        constraint = mint.key() == program_state.mint @ TwistError::InvalidAccount,
+    )]
+    pub mint: Account<'info, token::Mint>,
+    
+    #[account(
+        init,
+        payer = authority,
+        seeds = [FEE_VAULT_SEED],
+        bump,
+        token::mint = mint,
+        token::authority = fee_collector,
+    )]
+    pub fee_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        init,
+        payer = authority,
+        seeds = [STAKING_REWARDS_VAULT_SEED],
+        bump,
+        token::mint = mint,
+        token::authority = fee_collector,
+    )]
+    pub staking_rewards_vault: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
 }
 
 pub fn initialize_fee_collector_handler(
@@ -66,6 +96,15 @@ pub fn initialize_fee_collector_handler(
     
     fee_collector.min_distribution_amount = params.min_distribution_amount;
     
+    // Staking rewards accumulator
+    fee_collector.reward_per_share = 0;
+    fee_collector.unallocated_staking_rewards = 0;
+    
+    fee_collector.dynamic_fee_curve = DynamicFeeCurve::default();
+    fee_collector.insurance_share_bps = 0;
+    
+    ctx.accounts.program_state.fee_collector_initialized = true;
+    
     msg!("Fee collector initialized");
     msg!("Trading fee: {} bps", params.trading_fee_bps);
     msg!("Distribution: Floor {}%, Ops {}%, Staking {}%, Burn {}%",
@@ -78,6 +117,87 @@ pub fn initialize_fee_collector_handler(
     Ok(())
 }
 
+#[derive(Accounts)]
+pub struct MigrateFeeCollector<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.authority == authority.key() @ TwistError::Unauthorized
+    )]
+    pub program_state: Account<'info, ProgramState>,
+    
+    /// CHECK: Still in the original layout, so owner and discriminator are checked in handler
+    #[account(
+        mut,
+        seeds = [b"fee_collector"],
+        bump,
+    )]
+    pub fee_collector: AccountInfo<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn migrate_fee_collector_handler(ctx: Context<MigrateFeeCollector>) -> Result<()> {
+    let fee_collector = &ctx.accounts.fee_collector;
+    let clock = Clock::get()?;
+    
+    require_keys_eq!(*fee_collector.owner, crate::ID, TwistError::InvalidAccount);
+    require!(
+        fee_collector.data_len() == FeeCollectorState::ORIGINAL_LEN &&
+        fee_collector.try_borrow_data()?[..8] == <FeeCollectorState as anchor_lang::Discriminator>::DISCRIMINATOR,
+        TwistError::InvalidAccount
+    );
+    
+    // Fund rent for the larger account, then grow it. The appended fields start in the
+    // old reserved bytes, which were never written, so everything new reads as zero:
+    // an empty staking accumulator, no USDC fee vault until it is initialized, no
+    // insurance share and no swap routing. The fee curve is set below.
+    let rent_due = Rent::get()?
+        .minimum_balance(FeeCollectorState::LEN)
+        .saturating_sub(fee_collector.lamports());
+    if rent_due > 0 {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.authority.to_account_info(),
+                    to: fee_collector.to_account_info(),
+                },
+            ),
+            rent_due,
+        )?;
+    }
+    fee_collector.realloc(FeeCollectorState::LEN, true)?;
+    
+    // A zeroed fee curve would cap every dynamic fee at 0 bps, so start from the default
+    {
+        let mut data = fee_collector.try_borrow_mut_data()?;
+        let mut state = FeeCollectorState::try_deserialize(&mut &data[..])?;
+        state.dynamic_fee_curve = DynamicFeeCurve::default();
+        state.try_serialize(&mut &mut data[..])?;
+    }
+    
+    // The collector predates the flag, so stake and unstake must now require it
+    ctx.accounts.program_state.fee_collector_initialized = true;
+    
+    emit!(ParameterUpdated {
+        parameter: "fee_collector_account_size".to_string(),
+        old_value: FeeCollectorState::ORIGINAL_LEN.to_string(),
+        new_value: FeeCollectorState::LEN.to_string(),
+        updated_by: ctx.accounts.authority.key(),
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Fee collector grown to {} bytes", FeeCollectorState::LEN);
+    msg!("Initialize the USDC fee vault and set the insurance share before relying on them");
+    
+    Ok(())
+}
+
 #[derive(Accounts)]
 pub struct CollectFee<'info> {
     #[account(mut)]
@@ -93,15 +213,16 @@ pub struct CollectFee<'info> {
     #[account(
         mut,
         constraint = fee_account.owner == fee_payer.key() @ TwistError::InvalidAccount,
-        constraint = fee_account.mint == program_state.mint @ TwistError::InvalidAccount,
+        constraint = fee_account.mint == program_state.mint ||
+            (fee_collector.usdc_mint != Pubkey::default() && fee_account.mint == fee_collector.usdc_mint)
+            @ TwistError::InvalidAccount,
     )]
     pub fee_account: Account<'info, TokenAccount>,
     
+    // TWIST or USDC fee vault, matched to the fee mint in the handler
     #[account(
         mut,
-        seeds = [b"fee_vault"],
-        bump,
-        token::mint = program_state.mint,
+        token::mint = fee_account.mint,
         token::authority = fee_collector,
     )]
     pub fee_vault: Account<'info, TokenAccount>,
@@ -128,6 +249,18 @@ pub fn collect_fee_handler(
         TwistError::InvalidAmount
     );
     
+    let is_usdc = ctx.accounts.fee_account.mint != ctx.accounts.program_state.mint;
+    let expected_vault = if is_usdc {
+        fee_collector.usdc_fee_vault
+    } else {
+        Pubkey::find_program_address(&[FEE_VAULT_SEED], ctx.program_id).0
+    };
+    require_keys_eq!(
+        ctx.accounts.fee_vault.key(),
+        expected_vault,
+        TwistError::InvalidAccount
+    );
+    
     // Transfer fee to vault
     let cpi_accounts = token::Transfer {
         from: ctx.accounts.fee_account.to_account_info(),
@@ -141,19 +274,26 @@ pub fn collect_fee_handler(
     token::transfer(cpi_ctx, amount)?;
     
     // Record fee
-    fee_collector.record_fee(fee_type.clone(), amount);
+    let pending = if is_usdc {
+        fee_collector.record_usdc_fee(amount);
+        fee_collector.pending_usdc_distribution
+    } else {
+        fee_collector.record_fee(fee_type.clone(), amount);
+        fee_collector.pending_distribution
+    };
+    let (symbol, prefix) = if is_usdc { ("USDC", "usdc_") } else { ("TWIST", "") };
     
     // Emit event
     emit!(ParameterUpdated {
-        parameter: format!("fee_collected_{:?}", fee_type),
-        old_value: (fee_collector.pending_distribution - amount).to_string(),
-        new_value: fee_collector.pending_distribution.to_string(),
+        parameter: format!("{}fee_collected_{:?}", prefix, fee_type),
+        old_value: (pending - amount).to_string(),
+        new_value: pending.to_string(),
         updated_by: ctx.accounts.fee_payer.key(),
         timestamp: clock.unix_timestamp,
     });
     
-    msg!("Collected {} TWIST in {:?} fees", amount, fee_type);
-    msg!("Total pending distribution: {}", fee_collector.pending_distribution);
+    msg!("Collected {} {} in {:?} fees", amount, symbol, fee_type);
+    msg!("Total pending {} distribution: {}", symbol, pending);
     
     Ok(())
 }
@@ -199,12 +339,12 @@ pub struct DistributeFees<'info> {
     
     #[account(
         mut,
-        seeds = [STAKE_VAULT_SEED],
+        seeds = [STAKING_REWARDS_VAULT_SEED],
         bump,
         token::mint = program_state.mint,
-        token::authority = program_state,
+        token::authority = fee_collector,
     )]
-    pub stake_vault: Account<'info, TokenAccount>,
+    pub staking_rewards_vault: Account<'info, TokenAccount>,
     
     #[account(
         mut,
@@ -213,25 +353,90 @@ pub struct DistributeFees<'info> {
     )]
     pub program_state: Account<'info, ProgramState>,
     
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = mint.key() == program_state.mint @ TwistError::InvalidAccount,
+    )]
     pub mint: Account<'info, token::Mint>,
     
+    // The USDC fee vault, treasury policy and floor USDC account are only required
+    // when the floor share is swapped to USDC
+    #[account(
+        mut,
+        address = fee_collector.usdc_fee_vault @ TwistError::InvalidAccount,
+    )]
+    pub usdc_fee_vault: Option<Box<Account<'info, TokenAccount>>>,
+    
+    #[account(
+        seeds = [TREASURY_POLICY_SEED],
+        bump = treasury_policy.bump,
+    )]
+    pub treasury_policy: Option<Box<Account<'info, TreasuryPolicy>>>,
+    
+    // Checked against the treasury policy in handler
+    #[account(
+        mut,
+        token::mint = fee_collector.usdc_mint,
+    )]
+    pub floor_usdc_account: Option<Box<Account<'info, TokenAccount>>>,
+    
+    // Required once an insurance share is configured
+    #[account(
+        mut,
+        seeds = [INSURANCE_FUND_SEED],
+        bump = insurance_fund.bump,
+    )]
+    pub insurance_fund: Option<Box<Account<'info, InsuranceFund>>>,
+    
+    #[account(
+        mut,
+        seeds = [INSURANCE_VAULT_SEED],
+        bump,
+        token::mint = program_state.mint,
+    )]
+    pub insurance_vault: Option<Box<Account<'info, TokenAccount>>>,
+    
+    // Venues the fee swap may route through, required when swapping
+    #[account(
+        seeds = [DEX_REGISTRY_SEED],
+        bump = dex_registry.bump,
+    )]
+    pub dex_registry: Option<Box<Account<'info, DexRegistry>>>,
+    
+    #[account(
+        mut,
+        seeds = [DEX_ORACLE_SEED],
+        bump = dex_oracle.bump,
+    )]
+    pub dex_oracle: Option<Box<Account<'info, DexOracle>>>,
+    
     pub token_program: Program<'info, Token>,
 }
 
-pub fn distribute_fees_handler(ctx: Context<DistributeFees>) -> Result<()> {
+/// Remaining accounts when swapping: one account group per registered venue to quote,
+/// as for a buyback
+pub fn distribute_fees_handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, DistributeFees<'info>>,
+) -> Result<()> {
     let fee_collector = &mut ctx.accounts.fee_collector;
+    let swap_floor_to_usdc = fee_collector.swap_floor_to_usdc;
     let fee_vault = &ctx.accounts.fee_vault;
     let clock = Clock::get()?;
     
-    // Check if we have enough to distribute
-    require!(
-        fee_vault.amount >= fee_collector.min_distribution_amount,
-        TwistError::InvalidAmount
-    );
+    observe_pool(&mut ctx.accounts.dex_oracle, ctx.remaining_accounts, clock.unix_timestamp)?;
+    
+    // Distribution works off the vault balance, so a repeated call finds nothing left
+    if fee_vault.amount == 0 || fee_vault.amount < fee_collector.min_distribution_amount {
+        msg!("Nothing to distribute: {} below minimum {}",
+            fee_vault.amount,
+            fee_collector.min_distribution_amount
+        );
+        return Ok(());
+    }
     
     let distribution_amount = fee_vault.amount;
-    let distribution = fee_collector.calculate_distribution_amounts(distribution_amount);
+    let insurance_amount = fee_collector.insurance_amount(distribution_amount);
+    let distribution = fee_collector.calculate_distribution_amounts(distribution_amount - insurance_amount);
     
     let seeds = &[
         b"fee_collector".as_ref(),
@@ -239,8 +444,85 @@ pub fn distribute_fees_handler(ctx: Context<DistributeFees>) -> Result<()> {
     ];
     let signer_seeds = &[&seeds[..]];
     
-    // Transfer to floor treasury
-    if distribution.floor_treasury > 0 {
+    // Insurance slice comes off the top
+    if insurance_amount > 0 {
+        let (Some(insurance_fund), Some(insurance_vault)) = (
+            ctx.accounts.insurance_fund.as_mut(),
+            ctx.accounts.insurance_vault.as_ref(),
+        ) else {
+            return err!(TwistError::InvalidAccount);
+        };
+        
+        vault_transfer(
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.fee_vault.to_account_info(),
+            insurance_vault.to_account_info(),
+            fee_collector.to_account_info(),
+            signer_seeds,
+            insurance_amount,
+        )?;
+        
+        insurance_fund.total_deposited = insurance_fund.total_deposited
+            .saturating_add(insurance_amount as u128);
+    }
+    
+    // Transfer to floor treasury, either as TWIST or swapped into its USDC account
+    let mut floor_usdc_received = 0;
+    if distribution.floor_treasury > 0 && swap_floor_to_usdc {
+        let (Some(usdc_fee_vault), Some(treasury_policy), Some(floor_usdc_account), Some(dex_registry)) = (
+            ctx.accounts.usdc_fee_vault.as_mut(),
+            ctx.accounts.treasury_policy.as_ref(),
+            ctx.accounts.floor_usdc_account.as_ref(),
+            ctx.accounts.dex_registry.as_ref(),
+        ) else {
+            return err!(TwistError::InvalidAccount);
+        };
+        require_keys_eq!(
+            floor_usdc_account.key(),
+            treasury_policy.floor_usdc_account,
+            TwistError::InvalidAccount
+        );
+        
+        let min_out = oracle_min_swap_output(
+            &ctx.accounts.program_state,
+            distribution.floor_treasury,
+            true,
+            FEE_SWAP_MAX_SLIPPAGE_BPS,
+            clock.unix_timestamp,
+        )?;
+        let endpoints = SwapEndpoints {
+            token_program: ctx.accounts.token_program.to_account_info(),
+            token_authority: fee_collector.to_account_info(),
+            twist_mint: ctx.accounts.program_state.mint,
+            twist_account: ctx.accounts.fee_vault.to_account_info(),
+            usdc_account: usdc_fee_vault.to_account_info(),
+        };
+        let (adapters, route) = plan_registry_route(
+            dex_registry,
+            ctx.remaining_accounts,
+            &endpoints,
+            distribution.floor_treasury,
+            true,
+        )?;
+        
+        floor_usdc_received = execute_route(
+            &adapters,
+            &route,
+            min_out,
+            true,
+            signer_seeds,
+            usdc_fee_vault,
+        )?;
+        
+        vault_transfer(
+            ctx.accounts.token_program.to_account_info(),
+            usdc_fee_vault.to_account_info(),
+            floor_usdc_account.to_account_info(),
+            fee_collector.to_account_info(),
+            signer_seeds,
+            floor_usdc_received,
+        )?;
+    } else if distribution.floor_treasury > 0 {
         let cpi_accounts = token::Transfer {
             from: ctx.accounts.fee_vault.to_account_info(),
             to: ctx.accounts.floor_treasury.to_account_info(),
@@ -267,11 +549,11 @@ pub fn distribute_fees_handler(ctx: Context<DistributeFees>) -> Result<()> {
         token::transfer(cpi_ctx, distribution.ops_treasury)?;
     }
     
-    // Transfer to staking vault
+    // Deposit into the staking rewards vault and advance the accumulator
     if distribution.staking_rewards > 0 {
         let cpi_accounts = token::Transfer {
             from: ctx.accounts.fee_vault.to_account_info(),
-            to: ctx.accounts.stake_vault.to_account_info(),
+            to: ctx.accounts.staking_rewards_vault.to_account_info(),
             authority: fee_collector.to_account_info(),
         };
         
@@ -279,6 +561,11 @@ pub fn distribute_fees_handler(ctx: Context<DistributeFees>) -> Result<()> {
         let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
         
         token::transfer(cpi_ctx, distribution.staking_rewards)?;
+        
+        fee_collector.allocate_staking_rewards(
+            distribution.staking_rewards,
+            ctx.accounts.program_state.total_staked,
+        );
     }
     
     // Burn tokens
@@ -306,14 +593,18 @@ pub fn distribute_fees_handler(ctx: Context<DistributeFees>) -> Result<()> {
     fee_collector.distributions_count += 1;
     fee_collector.pending_distribution = 0;
     
-    // Update floor liquidity
-    ctx.accounts.program_state.floor_liquidity = ctx.accounts.floor_treasury.amount;
+    // Floor liquidity counts USDC only; a TWIST floor share leaves it unchanged
+    if swap_floor_to_usdc {
+        ctx.accounts.program_state.floor_liquidity = ctx.accounts.program_state.floor_liquidity
+            .saturating_add(floor_usdc_received);
+    }
     
     // Emit event
     emit!(ParameterUpdated {
         parameter: "fee_distribution".to_string(),
         old_value: format!("pending: {}", distribution_amount),
-        new_value: format!("distributed: floor={}, ops={}, stake={}, burn={}",
+        new_value: format!("distributed: insurance={}, floor={}, ops={}, stake={}, burn={}",
+            insurance_amount,
             distribution.floor_treasury,
             distribution.ops_treasury,
             distribution.staking_rewards,
@@ -324,13 +615,454 @@ pub fn distribute_fees_handler(ctx: Context<DistributeFees>) -> Result<()> {
     });
     
     msg!("Distributed {} TWIST in fees", distribution_amount);
-    msg!("Floor: {}, Ops: {}, Staking: {}, Burn: {}",
+    msg!("Insurance: {}, Floor: {}, Ops: {}, Staking: {}, Burn: {}",
+        insurance_amount,
         distribution.floor_treasury,
         distribution.ops_treasury,
         distribution.staking_rewards,
         distribution.burn_amount
     );
     
+    if swap_floor_to_usdc {
+        msg!("Floor share swapped for {} USDC", floor_usdc_received);
+    }
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeUsdcFeeVault<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"fee_collector"],
+        bump = fee_collector.bump,
+        constraint = fee_collector.authority == authority.key() @ TwistError::Unauthorized,
+        constraint = fee_collector.usdc_mint == Pubkey::default() @ TwistError::AlreadyInitialized,
+    )]
+    pub fee_collector: Account<'info, FeeCollectorState>,
+    
+    #[account(
+        seeds = [PROGRAM_STATE_SEED],
+        bump,
+    )]
+    pub program_state: Account<'info, ProgramState>,
+    
+    #[account(
+        constraint = usdc_mint.key() != program_state.mint @ TwistError::InvalidAccount,
+    )]
+    pub usdc_mint: Account<'info, token::Mint>,
+    
+    #[account(
+        init,
+        payer = authority,
+        seeds = [FEE_VAULT_SEED, usdc_mint.key().as_ref()],
+        bump,
+        token::mint = usdc_mint,
+        token::authority = fee_collector,
+    )]
+    pub usdc_fee_vault: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn initialize_usdc_fee_vault_handler(ctx: Context<InitializeUsdcFeeVault>) -> Result<()> {
+    let fee_collector = &mut ctx.accounts.fee_collector;
+    
+    fee_collector.usdc_mint = ctx.accounts.usdc_mint.key();
+    fee_collector.usdc_fee_vault = ctx.accounts.usdc_fee_vault.key();
+    fee_collector.pending_usdc_distribution = 0;
+    
+    msg!("USDC fee vault initialized: {}", fee_collector.usdc_fee_vault);
+    msg!("USDC mint: {}", fee_collector.usdc_mint);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct DistributeUsdcFees<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"fee_collector"],
+        bump = fee_collector.bump,
+    )]
+    pub fee_collector: Box<Account<'info, FeeCollectorState>>,
+    
+    #[account(
+        mut,
+        address = fee_collector.usdc_fee_vault @ TwistError::InvalidAccount,
+    )]
+    pub usdc_fee_vault: Box<Account<'info, TokenAccount>>,
+    
+    // Receives swapped TWIST before it is burned or moved to staking rewards
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED],
+        bump,
+        token::mint = program_state.mint,
+        token::authority = fee_collector,
+    )]
+    pub fee_vault: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        seeds = [TREASURY_POLICY_SEED],
+        bump = treasury_policy.bump,
+    )]
+    pub treasury_policy: Box<Account<'info, TreasuryPolicy>>,
+    
+    #[account(
+        mut,
+        address = treasury_policy.floor_usdc_account @ TwistError::InvalidAccount,
+        token::mint = fee_collector.usdc_mint,
+    )]
+    pub floor_usdc_account: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        seeds = [TREASURY_STATE_SEED, TreasuryType::Operations.seed().as_ref()],
+        bump = ops_treasury_state.bump,
+    )]
+    pub ops_treasury_state: Box<Account<'info, TreasuryState>>,
+    
+    #[account(
+        mut,
+        address = ops_treasury_state.usdc_account @ TwistError::InvalidAccount,
+        token::mint = fee_collector.usdc_mint,
+    )]
+    pub ops_usdc_account: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        seeds = [STAKING_REWARDS_VAULT_SEED],
+        bump,
+        token::mint = program_state.mint,
+        token::authority = fee_collector,
+    )]
+    pub staking_rewards_vault: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump,
+    )]
+    pub program_state: Box<Account<'info, ProgramState>>,
+    
+    #[account(
+        mut,
+        constraint = mint.key() == program_state.mint @ TwistError::InvalidAccount,
+    )]
+    pub mint: Account<'info, token::Mint>,
+    
+    // Required once an insurance share is configured
+    #[account(
+        mut,
+        seeds = [INSURANCE_FUND_SEED],
+        bump = insurance_fund.bump,
+    )]
+    pub insurance_fund: Option<Box<Account<'info, InsuranceFund>>>,
+    
+    #[account(
+        mut,
+        seeds = [INSURANCE_VAULT_SEED],
+        bump,
+        token::mint = program_state.mint,
+    )]
+    pub insurance_vault: Option<Box<Account<'info, TokenAccount>>>,
+    
+    // Venues the fee swap may route through, required when swapping
+    #[account(
+        seeds = [DEX_REGISTRY_SEED],
+        bump = dex_registry.bump,
+    )]
+    pub dex_registry: Option<Box<Account<'info, DexRegistry>>>,
+    
+    #[account(
+        mut,
+        seeds = [DEX_ORACLE_SEED],
+        bump = dex_oracle.bump,
+    )]
+    pub dex_oracle: Option<Box<Account<'info, DexOracle>>>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+/// Without a swap the burn and staking shares go to the floor in USDC. The insurance
+/// slice is always swapped, since the fund pays claims in TWIST.
+/// Remaining accounts when swapping match `distribute_fees_handler`.
+pub fn distribute_usdc_fees_handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, DistributeUsdcFees<'info>>,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    
+    observe_pool(&mut ctx.accounts.dex_oracle, ctx.remaining_accounts, clock.unix_timestamp)?;
+    let fee_collector = &mut ctx.accounts.fee_collector;
+    let swap_to_twist = fee_collector.swap_usdc_to_twist;
+    
+    let distribution_amount = ctx.accounts.usdc_fee_vault.amount;
+    let min_amount = ctx.accounts.program_state.twist_to_usdc_value(fee_collector.min_distribution_amount);
+    if distribution_amount == 0 || distribution_amount < min_amount {
+        msg!("Nothing to distribute: {} USDC below minimum {}",
+            distribution_amount,
+            min_amount
+        );
+        return Ok(());
+    }
+    
+    // Insurance slice comes off the top, as for TWIST fees
+    let insurance_usdc = fee_collector.insurance_amount(distribution_amount);
+    let distribution = fee_collector.calculate_distribution_amounts(distribution_amount - insurance_usdc);
+    let converted = distribution.staking_rewards + distribution.burn_amount;
+    let swapped = if swap_to_twist { converted + insurance_usdc } else { insurance_usdc };
+    
+    let seeds = &[
+        b"fee_collector".as_ref(),
+        &[fee_collector.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+    
+    // Ops treasury keeps its share in USDC
+    if distribution.ops_treasury > 0 {
+        vault_transfer(
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.usdc_fee_vault.to_account_info(),
+            ctx.accounts.ops_usdc_account.to_account_info(),
+            fee_collector.to_account_info(),
+            signer_seeds,
+            distribution.ops_treasury,
+        )?;
+    }
+    
+    // Swap the insurance slice, and the burn and staking shares when routed, to TWIST
+    let mut twist_received = 0;
+    let mut insurance_twist = 0;
+    let mut staking_twist = 0;
+    let mut burn_twist = 0;
+    let mut floor_usdc = distribution.floor_treasury;
+    if swapped > 0 {
+        let dex_registry = ctx.accounts.dex_registry.as_ref().ok_or(TwistError::InvalidAccount)?;
+        let min_out = oracle_min_swap_output(
+            &ctx.accounts.program_state,
+            swapped,
+            false,
+            FEE_SWAP_MAX_SLIPPAGE_BPS,
+            clock.unix_timestamp,
+        )?;
+        let endpoints = SwapEndpoints {
+            token_program: ctx.accounts.token_program.to_account_info(),
+            token_authority: fee_collector.to_account_info(),
+            twist_mint: ctx.accounts.program_state.mint,
+            twist_account: ctx.accounts.fee_vault.to_account_info(),
+            usdc_account: ctx.accounts.usdc_fee_vault.to_account_info(),
+        };
+        let (adapters, route) = plan_registry_route(
+            dex_registry,
+            ctx.remaining_accounts,
+            &endpoints,
+            swapped,
+            false,
+        )?;
+        
+        twist_received = execute_route(
+            &adapters,
+            &route,
+            min_out,
+            false,
+            signer_seeds,
+            &mut ctx.accounts.fee_vault,
+        )?;
+        insurance_twist = (twist_received as u128 * insurance_usdc as u128 / swapped as u128) as u64;
+        if swap_to_twist {
+            (staking_twist, burn_twist) = distribution.split_converted(twist_received - insurance_twist);
+        }
+        
+        if insurance_twist > 0 {
+            let (Some(insurance_fund), Some(insurance_vault)) = (
+                ctx.accounts.insurance_fund.as_mut(),
+                ctx.accounts.insurance_vault.as_ref(),
+            ) else {
+                return err!(TwistError::InvalidAccount);
+            };
+            
+            vault_transfer(
+                ctx.accounts.token_program.to_account_info(),
+                ctx.accounts.fee_vault.to_account_info(),
+                insurance_vault.to_account_info(),
+                fee_collector.to_account_info(),
+                signer_seeds,
+                insurance_twist,
+            )?;
+            
+            insurance_fund.total_deposited = insurance_fund.total_deposited
+                .saturating_add(insurance_twist as u128);
+        }
+        
+        if staking_twist > 0 {
+            vault_transfer(
+                ctx.accounts.token_program.to_account_info(),
+                ctx.accounts.fee_vault.to_account_info(),
+                ctx.accounts.staking_rewards_vault.to_account_info(),
+                fee_collector.to_account_info(),
+                signer_seeds,
+                staking_twist,
+            )?;
+            
+            fee_collector.allocate_staking_rewards(
+                staking_twist,
+                ctx.accounts.program_state.total_staked,
+            );
+        }
+        
+        if burn_twist > 0 {
+            let cpi_accounts = Burn {
+                mint: ctx.accounts.mint.to_account_info(),
+                from: ctx.accounts.fee_vault.to_account_info(),
+                authority: fee_collector.to_account_info(),
+            };
+            
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+            
+            token::burn(cpi_ctx, burn_twist)?;
+            
+            ctx.accounts.program_state.total_burned = ctx.accounts.program_state.total_burned
+                .saturating_add(burn_twist as u128);
+        }
+    }
+    if !swap_to_twist {
+        floor_usdc += converted;
+    }
+    
+    // Floor treasury backs buybacks with its USDC share
+    if floor_usdc > 0 {
+        vault_transfer(
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.usdc_fee_vault.to_account_info(),
+            ctx.accounts.floor_usdc_account.to_account_info(),
+            fee_collector.to_account_info(),
+            signer_seeds,
+            floor_usdc,
+        )?;
+        
+        ctx.accounts.program_state.floor_liquidity = ctx.accounts.program_state.floor_liquidity
+            .saturating_add(floor_usdc);
+    }
+    
+    // Update tracking
+    fee_collector.total_usdc_distributed = fee_collector.total_usdc_distributed
+        .saturating_add(distribution_amount as u128);
+    fee_collector.pending_usdc_distribution = 0;
+    fee_collector.last_distribution_timestamp = clock.unix_timestamp;
+    fee_collector.distributions_count += 1;
+    
+    emit!(ParameterUpdated {
+        parameter: "usdc_fee_distribution".to_string(),
+        old_value: format!("pending: {}", distribution_amount),
+        new_value: format!("distributed: insurance={} TWIST, floor={}, ops={}, stake={} TWIST, burn={} TWIST",
+            insurance_twist,
+            floor_usdc,
+            distribution.ops_treasury,
+            staking_twist,
+            burn_twist
+        ),
+        updated_by: ctx.accounts.authority.key(),
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Distributed {} USDC in fees", distribution_amount);
+    msg!("Floor: {} USDC, Ops: {} USDC", floor_usdc, distribution.ops_treasury);
+    if swapped > 0 {
+        msg!("Swapped {} USDC for {} TWIST: insurance {}, staking {}, burn {}",
+            swapped,
+            twist_received,
+            insurance_twist,
+            staking_twist,
+            burn_twist
+        );
+    }
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClaimFeeRewards<'info> {
+    pub owner: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [STAKE_STATE_SEED, owner.key().as_ref()],
+        bump = stake_state.bump,
+        constraint = stake_state.owner == owner.key() @ TwistError::Unauthorized,
+    )]
+    pub stake_state: Box<Account<'info, StakeState>>,
+    
+    #[account(
+        seeds = [b"fee_collector"],
+        bump = fee_collector.bump,
+    )]
+    pub fee_collector: Account<'info, FeeCollectorState>,
+    
+    #[account(
+        mut,
+        seeds = [STAKING_REWARDS_VAULT_SEED],
+        bump,
+        token::authority = fee_collector,
+    )]
+    pub staking_rewards_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        token::mint = staking_rewards_vault.mint,
+        token::authority = owner,
+    )]
+    pub owner_token_account: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn claim_fee_rewards_handler(ctx: Context<ClaimFeeRewards>) -> Result<()> {
+    let stake_state = &mut ctx.accounts.stake_state;
+    let fee_collector = &ctx.accounts.fee_collector;
+    let clock = Clock::get()?;
+    
+    stake_state.settle_fee_rewards(fee_collector.reward_per_share);
+    
+    let amount = stake_state.unclaimed_fee_rewards;
+    require!(amount > 0, TwistError::NoRewardsToClaim);
+    
+    stake_state.unclaimed_fee_rewards = 0;
+    
+    let seeds = &[
+        b"fee_collector".as_ref(),
+        &[fee_collector.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+    
+    let cpi_accounts = token::Transfer {
+        from: ctx.accounts.staking_rewards_vault.to_account_info(),
+        to: ctx.accounts.owner_token_account.to_account_info(),
+        authority: fee_collector.to_account_info(),
+    };
+    
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+    
+    token::transfer(cpi_ctx, amount)?;
+    
+    emit!(FeeRewardsClaimed {
+        owner: ctx.accounts.owner.key(),
+        amount,
+        reward_per_share: fee_collector.reward_per_share,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Claimed {} TWIST in fee rewards", amount);
+    
     Ok(())
 }
 
@@ -395,4 +1127,113 @@ pub fn update_fee_parameters_handler(
     msg!("Updated fee parameters");
     
     Ok(())
-}
\ No newline at end of file
+}
+
+pub fn update_dynamic_fee_curve_handler(
+    ctx: Context<UpdateFeeParameters>,
+    curve: DynamicFeeCurve,
+) -> Result<()> {
+    let fee_collector = &mut ctx.accounts.fee_collector;
+    let clock = Clock::get()?;
+    
+    require!(
+        curve.is_valid(),
+        TwistError::InvalidAmount
+    );
+    
+    let old_curve = fee_collector.dynamic_fee_curve;
+    fee_collector.dynamic_fee_curve = curve;
+    
+    emit!(ParameterUpdated {
+        parameter: "dynamic_fee_curve".to_string(),
+        old_value: format!("{:?}", old_curve),
+        new_value: format!("{:?}", curve),
+        updated_by: ctx.accounts.authority.key(),
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Updated dynamic fee curve, max fee {} bps", curve.max_fee_bps);
+    
+    Ok(())
+}
+
+pub fn set_fee_swap_routing_handler(
+    ctx: Context<UpdateFeeParameters>,
+    swap_floor_to_usdc: bool,
+    swap_usdc_to_twist: bool,
+) -> Result<()> {
+    let fee_collector = &mut ctx.accounts.fee_collector;
+    let clock = Clock::get()?;
+    
+    let old_value = format!("floor_to_usdc={}, usdc_to_twist={}",
+        fee_collector.swap_floor_to_usdc,
+        fee_collector.swap_usdc_to_twist
+    );
+    fee_collector.swap_floor_to_usdc = swap_floor_to_usdc;
+    fee_collector.swap_usdc_to_twist = swap_usdc_to_twist;
+    
+    emit!(ParameterUpdated {
+        parameter: "fee_swap_routing".to_string(),
+        old_value,
+        new_value: format!("floor_to_usdc={}, usdc_to_twist={}", swap_floor_to_usdc, swap_usdc_to_twist),
+        updated_by: ctx.accounts.authority.key(),
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Updated fee swap routing");
+    
+    Ok(())
+}
+
+/// Fee reward accumulator for a stake change. The fee collector may only be left out
+/// before it exists, otherwise a staker could change weight without settling.
+pub(crate) fn staking_reward_per_share(
+    fee_collector: &Option<Box<Account<FeeCollectorState>>>,
+    program_state: &ProgramState,
+) -> Result<u128> {
+    match fee_collector {
+        Some(fee_collector) => Ok(fee_collector.reward_per_share),
+        None => {
+            require!(!program_state.fee_collector_initialized, TwistError::InvalidAccount);
+            Ok(0)
+        }
+    }
+}
+
+/// Dynamic fee for a protocol-routed swap of `usdc_value`, priced from live market metrics.
+/// Returns the fee rate and the fee amount in USDC atomic units.
+pub(crate) fn protocol_swap_fee(
+    fee_collector: &FeeCollectorState,
+    program_state: &ProgramState,
+    market_metrics: &MarketMetrics,
+    usdc_value: u64,
+    current_time: i64,
+) -> Result<(u64, u64)> {
+    let fee_bps = MEVProtection::calculate_dynamic_fee(
+        &fee_collector.dynamic_fee_curve,
+        program_state.apply_fee_surcharge(fee_collector.trading_fee_bps),
+        usdc_value,
+        market_metrics.volume_1h(current_time),
+        market_metrics.average_hourly_volume(current_time),
+        market_metrics.price_volatility_bps(current_time),
+    )?;
+    let fee_amount = (usdc_value as u128 * fee_bps as u128 / 10000) as u64;
+    
+    Ok((fee_bps, fee_amount))
+}
+
+// Helper functions
+
+fn vault_transfer<'info>(
+    token_program: AccountInfo<'info>,
+    from: AccountInfo<'info>,
+    to: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+    amount: u64,
+) -> Result<()> {
+    let cpi_accounts = token::Transfer { from, to, authority };
+    let cpi_ctx = CpiContext::new_with_signer(token_program, cpi_accounts, signer_seeds);
+    
+    token::transfer(cpi_ctx, amount)
+}
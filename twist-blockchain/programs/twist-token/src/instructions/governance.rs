@@ -0,0 +1,500 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::TwistError;
+use crate::events::*;
+use crate::instructions::multisig::invoke_transaction_instructions;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct InitializeGovernance<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.authority == authority.key() @ TwistError::Unauthorized,
+    )]
+    pub program_state: Account<'info, ProgramState>,
+    
+    #[account(
+        seeds = [b"multisig"],
+        bump = multisig_config.bump,
+        constraint = multisig_config.initialized @ TwistError::InvalidAccount,
+    )]
+    pub multisig_config: Account<'info, MultisigConfig>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = GovernanceConfig::LEN,
+        seeds = [GOVERNANCE_SEED],
+        bump
+    )]
+    pub governance_config: Account<'info, GovernanceConfig>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_governance_handler(
+    ctx: Context<InitializeGovernance>,
+    params: GovernanceParams,
+) -> Result<()> {
+    validate_params(&params)?;
+    
+    let governance_config = &mut ctx.accounts.governance_config;
+    governance_config.bump = ctx.bumps.governance_config;
+    governance_config.proposal_count = 0;
+    governance_config.veto_authority = ctx.accounts.multisig_config.key();
+    apply_params(governance_config, &params);
+    
+    msg!("Governance initialized, veto authority: {}", governance_config.veto_authority);
+    msg!("Quorum: {} bps, approval: {} bps", params.quorum_bps, params.approval_threshold_bps);
+    msg!("Voting period: {}s, timelock: {}s", params.voting_period, params.timelock_delay);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(instructions: Vec<TransactionInstruction>)]
+pub struct CreateGovernanceProposal<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [GOVERNANCE_SEED],
+        bump = governance_config.bump,
+    )]
+    pub governance_config: Account<'info, GovernanceConfig>,
+    
+    #[account(
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+    )]
+    pub program_state: Account<'info, ProgramState>,
+    
+    #[account(
+        seeds = [STAKE_STATE_SEED, proposer.key().as_ref()],
+        bump = stake_state.bump,
+    )]
+    pub stake_state: Account<'info, StakeState>,
+    
+    #[account(
+        init,
+        payer = proposer,
+        space = GovernanceProposal::space(&instructions),
+        seeds = [
+            GOVERNANCE_PROPOSAL_SEED,
+            &governance_config.proposal_count.to_le_bytes()
+        ],
+        bump
+    )]
+    pub proposal: Account<'info, GovernanceProposal>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_governance_proposal_handler(
+    ctx: Context<CreateGovernanceProposal>,
+    instructions: Vec<TransactionInstruction>,
+    title: String,
+    description: String,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let governance_config = &mut ctx.accounts.governance_config;
+    
+    // Validate inputs
+    require!(
+        !instructions.is_empty() && instructions.len() <= MultisigTransaction::MAX_INSTRUCTIONS,
+        TwistError::InvalidAmount
+    );
+    
+    require!(
+        GovernanceProposal::space(&instructions) <= MultisigTransaction::MAX_ACCOUNT_SIZE,
+        TwistError::InvalidAmount
+    );
+    
+    for instruction in &instructions {
+        require!(
+            instruction.accounts.len() <= MultisigTransaction::MAX_ACCOUNTS_PER_INSTRUCTION,
+            TwistError::InvalidAmount
+        );
+        
+        // Governance payloads are submitted in full so voters see the final bytes
+        require!(
+            instruction.is_complete(),
+            TwistError::InvalidAmount
+        );
+    }
+    
+    require!(
+        title.len() <= 64 && !title.is_empty(),
+        TwistError::InvalidAmount
+    );
+    
+    require!(
+        description.len() <= 256,
+        TwistError::InvalidAmount
+    );
+    
+    let proposer_weight = snapshot_weight(&ctx.accounts.stake_state, clock.unix_timestamp);
+    require!(
+        proposer_weight >= governance_config.proposal_threshold,
+        TwistError::InsufficientVotingPower
+    );
+    
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.proposal_index = governance_config.proposal_count;
+    proposal.proposer = ctx.accounts.proposer.key();
+    proposal.instructions = instructions;
+    proposal.status = ProposalStatus::Voting;
+    proposal.snapshot_timestamp = clock.unix_timestamp;
+    proposal.snapshot_total_staked = ctx.accounts.program_state.total_staked;
+    proposal.for_votes = 0;
+    proposal.against_votes = 0;
+    proposal.abstain_votes = 0;
+    proposal.voting_ends_at = clock.unix_timestamp + governance_config.voting_period;
+    proposal.eta = i64::MAX; // Set when queued
+    proposal.expires_at = i64::MAX;
+    proposal.executed_at = 0;
+    proposal.bump = ctx.bumps.proposal;
+    proposal.title = title.clone();
+    proposal.description = description;
+    
+    governance_config.proposal_count += 1;
+    
+    emit!(GovernanceProposalCreated {
+        proposal_index: proposal.proposal_index,
+        proposer: proposal.proposer,
+        title,
+        snapshot_total_staked: proposal.snapshot_total_staked,
+        voting_ends_at: proposal.voting_ends_at,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Governance proposal {} created", proposal.proposal_index);
+    msg!("Voting ends at {}", proposal.voting_ends_at);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CastVote<'info> {
+    #[account(mut)]
+    pub voter: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [
+            GOVERNANCE_PROPOSAL_SEED,
+            &proposal.proposal_index.to_le_bytes()
+        ],
+        bump = proposal.bump,
+        constraint = proposal.status == ProposalStatus::Voting @ TwistError::VotingClosed,
+    )]
+    pub proposal: Account<'info, GovernanceProposal>,
+    
+    #[account(
+        seeds = [STAKE_STATE_SEED, voter.key().as_ref()],
+        bump = stake_state.bump,
+    )]
+    pub stake_state: Account<'info, StakeState>,
+    
+    // One vote per staker per proposal
+    #[account(
+        init,
+        payer = voter,
+        space = VoteRecord::LEN,
+        seeds = [VOTE_RECORD_SEED, proposal.key().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn cast_vote_handler(ctx: Context<CastVote>, side: VoteSide) -> Result<()> {
+    let clock = Clock::get()?;
+    let proposal = &mut ctx.accounts.proposal;
+    
+    require!(
+        clock.unix_timestamp < proposal.voting_ends_at,
+        TwistError::VotingClosed
+    );
+    
+    // Stakes opened after the snapshot carry no weight, so moved tokens cannot vote twice
+    let weight = snapshot_weight(&ctx.accounts.stake_state, proposal.snapshot_timestamp);
+    require!(weight > 0, TwistError::InsufficientVotingPower);
+    
+    match side {
+        VoteSide::For => proposal.for_votes += weight as u128,
+        VoteSide::Against => proposal.against_votes += weight as u128,
+        VoteSide::Abstain => proposal.abstain_votes += weight as u128,
+    }
+    
+    let vote_record = &mut ctx.accounts.vote_record;
+    vote_record.proposal = proposal.key();
+    vote_record.voter = ctx.accounts.voter.key();
+    vote_record.side = side;
+    vote_record.weight = weight;
+    vote_record.timestamp = clock.unix_timestamp;
+    vote_record.bump = ctx.bumps.vote_record;
+    
+    emit!(GovernanceVoteCast {
+        proposal_index: proposal.proposal_index,
+        voter: vote_record.voter,
+        side,
+        weight,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Vote cast on proposal {}: {:?} with weight {}", proposal.proposal_index, side, weight);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct FinalizeGovernanceVote<'info> {
+    pub caller: Signer<'info>,
+    
+    #[account(
+        seeds = [GOVERNANCE_SEED],
+        bump = governance_config.bump,
+    )]
+    pub governance_config: Account<'info, GovernanceConfig>,
+    
+    #[account(
+        mut,
+        seeds = [
+            GOVERNANCE_PROPOSAL_SEED,
+            &proposal.proposal_index.to_le_bytes()
+        ],
+        bump = proposal.bump,
+        constraint = proposal.status == ProposalStatus::Voting @ TwistError::InvalidAccount,
+    )]
+    pub proposal: Account<'info, GovernanceProposal>,
+}
+
+pub fn finalize_governance_vote_handler(ctx: Context<FinalizeGovernanceVote>) -> Result<()> {
+    let clock = Clock::get()?;
+    let governance_config = &ctx.accounts.governance_config;
+    let proposal = &mut ctx.accounts.proposal;
+    
+    require!(
+        clock.unix_timestamp >= proposal.voting_ends_at,
+        TwistError::VotingActive
+    );
+    
+    let passed = proposal.quorum_reached(governance_config.quorum_bps) &&
+        proposal.approved(governance_config.approval_threshold_bps);
+    
+    if passed {
+        proposal.status = ProposalStatus::Queued;
+        proposal.eta = clock.unix_timestamp + governance_config.timelock_delay;
+        proposal.expires_at = proposal.eta + governance_config.execution_window;
+    } else {
+        proposal.status = ProposalStatus::Defeated;
+    }
+    
+    emit!(GovernanceProposalFinalized {
+        proposal_index: proposal.proposal_index,
+        status: proposal.status,
+        for_votes: proposal.for_votes,
+        against_votes: proposal.against_votes,
+        abstain_votes: proposal.abstain_votes,
+        eta: proposal.eta,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Governance proposal {} {:?}", proposal.proposal_index, proposal.status);
+    msg!("For: {}, against: {}, abstain: {}",
+        proposal.for_votes,
+        proposal.against_votes,
+        proposal.abstain_votes
+    );
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ExecuteGovernanceProposal<'info> {
+    pub executor: Signer<'info>,
+    
+    #[account(
+        seeds = [GOVERNANCE_SEED],
+        bump = governance_config.bump,
+    )]
+    pub governance_config: Account<'info, GovernanceConfig>,
+    
+    #[account(
+        mut,
+        seeds = [
+            GOVERNANCE_PROPOSAL_SEED,
+            &proposal.proposal_index.to_le_bytes()
+        ],
+        bump = proposal.bump,
+        constraint = proposal.can_execute(Clock::get()?.unix_timestamp) @ TwistError::InvalidAccount,
+    )]
+    pub proposal: Account<'info, GovernanceProposal>,
+}
+
+pub fn execute_governance_proposal_handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, ExecuteGovernanceProposal<'info>>,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let proposal = &mut ctx.accounts.proposal;
+    
+    // The governance PDA signs; program upgrades stay with the multisig
+    let seeds = &[
+        GOVERNANCE_SEED,
+        &[ctx.accounts.governance_config.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+    
+    invoke_transaction_instructions(
+        &proposal.instructions,
+        ctx.remaining_accounts,
+        signer_seeds,
+        None,
+    )?;
+    
+    proposal.status = ProposalStatus::Executed;
+    proposal.executed_at = clock.unix_timestamp;
+    
+    emit!(GovernanceProposalExecuted {
+        proposal_index: proposal.proposal_index,
+        executor: ctx.accounts.executor.key(),
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Governance proposal {} executed ({} instructions)",
+        proposal.proposal_index,
+        proposal.instructions.len()
+    );
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct VetoGovernanceProposal<'info> {
+    /// The multisig PDA, signing through execute_transaction
+    #[account(
+        constraint = veto_authority.key() == governance_config.veto_authority @ TwistError::MultisigRequired,
+    )]
+    pub veto_authority: Signer<'info>,
+    
+    #[account(
+        seeds = [GOVERNANCE_SEED],
+        bump = governance_config.bump,
+    )]
+    pub governance_config: Account<'info, GovernanceConfig>,
+    
+    #[account(
+        mut,
+        seeds = [
+            GOVERNANCE_PROPOSAL_SEED,
+            &proposal.proposal_index.to_le_bytes()
+        ],
+        bump = proposal.bump,
+        constraint = proposal.status == ProposalStatus::Voting ||
+                    proposal.status == ProposalStatus::Queued @ TwistError::InvalidAccount,
+    )]
+    pub proposal: Account<'info, GovernanceProposal>,
+}
+
+pub fn veto_governance_proposal_handler(ctx: Context<VetoGovernanceProposal>) -> Result<()> {
+    let clock = Clock::get()?;
+    let proposal = &mut ctx.accounts.proposal;
+    
+    proposal.status = ProposalStatus::Vetoed;
+    
+    emit!(GovernanceProposalVetoed {
+        proposal_index: proposal.proposal_index,
+        vetoed_by: ctx.accounts.veto_authority.key(),
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Governance proposal {} vetoed", proposal.proposal_index);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateGovernanceConfig<'info> {
+    /// The governance PDA, signing through an executed proposal
+    #[account(
+        constraint = governance_signer.key() == governance_config.key() @ TwistError::Unauthorized,
+    )]
+    pub governance_signer: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [GOVERNANCE_SEED],
+        bump = governance_config.bump,
+    )]
+    pub governance_config: Account<'info, GovernanceConfig>,
+}
+
+pub fn update_governance_config_handler(
+    ctx: Context<UpdateGovernanceConfig>,
+    params: GovernanceParams,
+) -> Result<()> {
+    validate_params(&params)?;
+    
+    let clock = Clock::get()?;
+    let governance_config = &mut ctx.accounts.governance_config;
+    
+    let old_value = format!("quorum: {}, approval: {}",
+        governance_config.quorum_bps,
+        governance_config.approval_threshold_bps
+    );
+    
+    apply_params(governance_config, &params);
+    
+    emit!(ParameterUpdated {
+        parameter: "governance_config".to_string(),
+        old_value,
+        new_value: format!("quorum: {}, approval: {}", params.quorum_bps, params.approval_threshold_bps),
+        updated_by: ctx.accounts.governance_signer.key(),
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Governance config updated");
+    
+    Ok(())
+}
+
+// Helper functions
+
+fn validate_params(params: &GovernanceParams) -> Result<()> {
+    require!(
+        params.quorum_bps > 0 && params.quorum_bps <= 10000,
+        TwistError::InvalidAmount
+    );
+    
+    // At least a simple majority of decisive votes
+    require!(
+        params.approval_threshold_bps >= 5000 && params.approval_threshold_bps <= 10000,
+        TwistError::InvalidAmount
+    );
+    
+    require!(
+        params.voting_period > 0 &&
+        params.timelock_delay >= 0 &&
+        params.execution_window > 0,
+        TwistError::InvalidAmount
+    );
+    
+    Ok(())
+}
+
+fn apply_params(governance_config: &mut GovernanceConfig, params: &GovernanceParams) {
+    governance_config.proposal_threshold = params.proposal_threshold;
+    governance_config.quorum_bps = params.quorum_bps;
+    governance_config.approval_threshold_bps = params.approval_threshold_bps;
+    governance_config.voting_period = params.voting_period;
+    governance_config.timelock_delay = params.timelock_delay;
+    governance_config.execution_window = params.execution_window;
+}
@@ -0,0 +1,566 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+use crate::constants::*;
+use crate::errors::TwistError;
+use crate::events::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct InitializeInsuranceFund<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [PROGRAM_STATE_SEED],
+        bump,
+        constraint = program_state.authority == authority.key() @ TwistError::Unauthorized,
+    )]
+    pub program_state: Box<Account<'info, ProgramState>>,
+    
+    #[account(
+        mut,
+        seeds = [b"fee_collector"],
+        bump = fee_collector.bump,
+    )]
+    pub fee_collector: Box<Account<'info, FeeCollectorState>>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = InsuranceFund::LEN,
+        seeds = [INSURANCE_FUND_SEED],
+        bump
+    )]
+    pub insurance_fund: Box<Account<'info, InsuranceFund>>,
+    
+    #[account(
+        init,
+        payer = authority,
+        seeds = [INSURANCE_VAULT_SEED],
+        bump,
+        token::mint = mint,
+        token::authority = insurance_fund,
+    )]
+    pub insurance_vault: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        constraint = mint.key() == program_state.mint @ TwistError::InvalidAccount,
+    )]
+    pub mint: Account<'info, Mint>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn initialize_insurance_fund_handler(
+    ctx: Context<InitializeInsuranceFund>,
+    params: InsuranceParams,
+) -> Result<()> {
+    require!(
+        params.is_valid(),
+        TwistError::InvalidAmount
+    );
+    
+    let insurance_fund = &mut ctx.accounts.insurance_fund;
+    insurance_fund.bump = ctx.bumps.insurance_fund;
+    insurance_fund.vault = ctx.accounts.insurance_vault.key();
+    insurance_fund.apply(&params);
+    insurance_fund.total_deposited = 0;
+    insurance_fund.total_paid = 0;
+    insurance_fund.outstanding_approved = 0;
+    insurance_fund.claim_count = 0;
+    insurance_fund.claims_paid = 0;
+    
+    ctx.accounts.fee_collector.insurance_share_bps = params.fee_share_bps;
+    
+    msg!("Insurance fund initialized, vault: {}", insurance_fund.vault);
+    msg!("Fee share: {}%, claim cap: {} TWIST or {}% of the vault",
+        params.fee_share_bps as f64 / 100.0,
+        params.max_claim_amount / 10u64.pow(9),
+        params.max_claim_vault_bps as f64 / 100.0
+    );
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateInsuranceParameters<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [PROGRAM_STATE_SEED],
+        bump,
+        constraint = program_state.authority == authority.key() @ TwistError::Unauthorized,
+    )]
+    pub program_state: Box<Account<'info, ProgramState>>,
+    
+    #[account(
+        mut,
+        seeds = [b"fee_collector"],
+        bump = fee_collector.bump,
+    )]
+    pub fee_collector: Box<Account<'info, FeeCollectorState>>,
+    
+    #[account(
+        mut,
+        seeds = [INSURANCE_FUND_SEED],
+        bump = insurance_fund.bump,
+    )]
+    pub insurance_fund: Box<Account<'info, InsuranceFund>>,
+}
+
+pub fn update_insurance_parameters_handler(
+    ctx: Context<UpdateInsuranceParameters>,
+    params: InsuranceParams,
+) -> Result<()> {
+    let insurance_fund = &mut ctx.accounts.insurance_fund;
+    let clock = Clock::get()?;
+    
+    require!(
+        params.is_valid(),
+        TwistError::InvalidAmount
+    );
+    
+    let old_value = format!("fee_share={}, max_claim={}, max_claim_vault={}, window={}",
+        ctx.accounts.fee_collector.insurance_share_bps,
+        insurance_fund.max_claim_amount,
+        insurance_fund.max_claim_vault_bps,
+        insurance_fund.claim_window
+    );
+    
+    insurance_fund.apply(&params);
+    ctx.accounts.fee_collector.insurance_share_bps = params.fee_share_bps;
+    
+    emit!(ParameterUpdated {
+        parameter: "insurance_parameters".to_string(),
+        old_value,
+        new_value: format!("fee_share={}, max_claim={}, max_claim_vault={}, window={}",
+            params.fee_share_bps,
+            params.max_claim_amount,
+            params.max_claim_vault_bps,
+            params.claim_window
+        ),
+        updated_by: ctx.accounts.authority.key(),
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Updated insurance parameters");
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct FileInsuranceClaim<'info> {
+    #[account(mut)]
+    pub claimant: Signer<'info>,
+    
+    #[account(
+        seeds = [PROGRAM_STATE_SEED],
+        bump,
+    )]
+    pub program_state: Box<Account<'info, ProgramState>>,
+    
+    #[account(
+        mut,
+        seeds = [INSURANCE_FUND_SEED],
+        bump = insurance_fund.bump,
+    )]
+    pub insurance_fund: Box<Account<'info, InsuranceFund>>,
+    
+    #[account(
+        init,
+        payer = claimant,
+        space = InsuranceClaim::LEN,
+        seeds = [
+            INSURANCE_CLAIM_SEED,
+            &insurance_fund.claim_count.to_le_bytes()
+        ],
+        bump
+    )]
+    pub claim: Box<Account<'info, InsuranceClaim>>,
+    
+    #[account(
+        token::mint = program_state.mint,
+        token::authority = claimant,
+    )]
+    pub recipient_account: Box<Account<'info, TokenAccount>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn file_insurance_claim_handler(
+    ctx: Context<FileInsuranceClaim>,
+    incident_type: IncidentType,
+    incident_timestamp: i64,
+    amount_requested: u64,
+    evidence_hash: [u8; 32],
+) -> Result<()> {
+    let insurance_fund = &mut ctx.accounts.insurance_fund;
+    let claim = &mut ctx.accounts.claim;
+    let clock = Clock::get()?;
+    
+    require!(
+        amount_requested > 0,
+        TwistError::InvalidAmount
+    );
+    require!(
+        incident_timestamp <= clock.unix_timestamp &&
+            clock.unix_timestamp - incident_timestamp <= insurance_fund.claim_window,
+        TwistError::ClaimWindowClosed
+    );
+    
+    claim.claim_index = insurance_fund.claim_count;
+    claim.claimant = ctx.accounts.claimant.key();
+    claim.recipient_account = ctx.accounts.recipient_account.key();
+    claim.incident_type = incident_type;
+    claim.incident_timestamp = incident_timestamp;
+    claim.evidence_hash = evidence_hash;
+    claim.amount_requested = amount_requested;
+    claim.approved_amount = 0;
+    claim.paid_amount = 0;
+    claim.status = ClaimStatus::Pending;
+    claim.filed_at = clock.unix_timestamp;
+    claim.resolved_at = 0;
+    claim.resolved_by = Pubkey::default();
+    claim.bump = ctx.bumps.claim;
+    
+    insurance_fund.claim_count += 1;
+    
+    emit!(InsuranceClaimFiled {
+        claim_index: claim.claim_index,
+        claimant: claim.claimant,
+        incident_type,
+        incident_timestamp,
+        amount_requested,
+        evidence_hash,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Insurance claim {} filed for {} TWIST ({:?})",
+        claim.claim_index,
+        amount_requested,
+        incident_type
+    );
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ResolveInsuranceClaim<'info> {
+    /// The multisig PDA through execute_transaction, or the governance PDA through an
+    /// executed proposal
+    pub approver: Signer<'info>,
+    
+    #[account(
+        seeds = [b"multisig"],
+        bump = multisig_config.bump,
+    )]
+    pub multisig_config: Option<Box<Account<'info, MultisigConfig>>>,
+    
+    #[account(
+        seeds = [GOVERNANCE_SEED],
+        bump = governance_config.bump,
+    )]
+    pub governance_config: Option<Box<Account<'info, GovernanceConfig>>>,
+    
+    #[account(
+        mut,
+        seeds = [INSURANCE_FUND_SEED],
+        bump = insurance_fund.bump,
+    )]
+    pub insurance_fund: Box<Account<'info, InsuranceFund>>,
+    
+    #[account(
+        address = insurance_fund.vault @ TwistError::InvalidAccount,
+    )]
+    pub insurance_vault: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        seeds = [
+            INSURANCE_CLAIM_SEED,
+            &claim.claim_index.to_le_bytes()
+        ],
+        bump = claim.bump,
+        constraint = claim.status == ClaimStatus::Pending @ TwistError::InvalidAccount,
+    )]
+    pub claim: Box<Account<'info, InsuranceClaim>>,
+}
+
+pub fn approve_insurance_claim_handler(
+    ctx: Context<ResolveInsuranceClaim>,
+    approved_amount: u64,
+) -> Result<()> {
+    require_claims_authority(ctx.accounts)?;
+    
+    let insurance_fund = &mut ctx.accounts.insurance_fund;
+    let claim = &mut ctx.accounts.claim;
+    let clock = Clock::get()?;
+    
+    require!(
+        approved_amount > 0 && approved_amount <= claim.amount_requested,
+        TwistError::InvalidAmount
+    );
+    
+    let capped = insurance_fund.cap_claim(approved_amount, ctx.accounts.insurance_vault.amount);
+    require!(
+        capped > 0,
+        TwistError::InsufficientLiquidity
+    );
+    
+    claim.approved_amount = capped;
+    claim.status = ClaimStatus::Approved;
+    claim.resolved_at = clock.unix_timestamp;
+    claim.resolved_by = ctx.accounts.approver.key();
+    
+    insurance_fund.outstanding_approved = insurance_fund.outstanding_approved.saturating_add(capped);
+    
+    emit!(InsuranceClaimResolved {
+        claim_index: claim.claim_index,
+        status: claim.status,
+        amount_requested: claim.amount_requested,
+        approved_amount: capped,
+        resolved_by: claim.resolved_by,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Insurance claim {} approved for {} TWIST", claim.claim_index, capped);
+    if capped < approved_amount {
+        msg!("Capped from {} by the per-claim limits", approved_amount);
+    }
+    
+    Ok(())
+}
+
+pub fn reject_insurance_claim_handler(ctx: Context<ResolveInsuranceClaim>) -> Result<()> {
+    require_claims_authority(ctx.accounts)?;
+    
+    let claim = &mut ctx.accounts.claim;
+    let clock = Clock::get()?;
+    
+    claim.status = ClaimStatus::Rejected;
+    claim.resolved_at = clock.unix_timestamp;
+    claim.resolved_by = ctx.accounts.approver.key();
+    
+    emit!(InsuranceClaimResolved {
+        claim_index: claim.claim_index,
+        status: claim.status,
+        amount_requested: claim.amount_requested,
+        approved_amount: 0,
+        resolved_by: claim.resolved_by,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Insurance claim {} rejected", claim.claim_index);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct PayInsuranceClaim<'info> {
+    pub payer: Signer<'info>,
+    
+    #[account(
+        seeds = [PROGRAM_STATE_SEED],
+        bump,
+    )]
+    pub program_state: Box<Account<'info, ProgramState>>,
+    
+    #[account(
+        mut,
+        seeds = [INSURANCE_FUND_SEED],
+        bump = insurance_fund.bump,
+    )]
+    pub insurance_fund: Box<Account<'info, InsuranceFund>>,
+    
+    #[account(
+        mut,
+        address = insurance_fund.vault @ TwistError::InvalidAccount,
+    )]
+    pub insurance_vault: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        seeds = [
+            INSURANCE_CLAIM_SEED,
+            &claim.claim_index.to_le_bytes()
+        ],
+        bump = claim.bump,
+        constraint = claim.status == ClaimStatus::Approved @ TwistError::InvalidAccount,
+        // The payout is fixed at settlement, so only the claimant or approver picks when
+        constraint = payer.key() == claim.claimant || payer.key() == claim.resolved_by @ TwistError::Unauthorized,
+    )]
+    pub claim: Box<Account<'info, InsuranceClaim>>,
+    
+    #[account(
+        mut,
+        address = claim.recipient_account @ TwistError::InvalidAccount,
+    )]
+    pub recipient_account: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        seeds = [FLOOR_TREASURY_SEED],
+        bump,
+        token::mint = program_state.mint,
+        token::authority = program_state,
+    )]
+    pub floor_treasury: Box<Account<'info, TokenAccount>>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+/// The claimant or approver settles an approved claim; the payout is pro-rated against
+/// every approved claim
+pub fn pay_insurance_claim_handler(ctx: Context<PayInsuranceClaim>) -> Result<()> {
+    let clock = Clock::get()?;
+    
+    let approved_amount = ctx.accounts.claim.approved_amount;
+    let payout = ctx.accounts.insurance_fund.pro_rated_payout(
+        approved_amount,
+        ctx.accounts.insurance_vault.amount,
+    );
+    
+    if payout > 0 {
+        let seeds = &[
+            INSURANCE_FUND_SEED,
+            &[ctx.accounts.insurance_fund.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+        
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.insurance_vault.to_account_info(),
+            to: ctx.accounts.recipient_account.to_account_info(),
+            authority: ctx.accounts.insurance_fund.to_account_info(),
+        };
+        
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        
+        token::transfer(cpi_ctx, payout)?;
+    }
+    
+    let claim = &mut ctx.accounts.claim;
+    claim.paid_amount = payout;
+    claim.status = ClaimStatus::Paid;
+    
+    ctx.accounts.insurance_fund.record_payout(approved_amount, payout);
+    
+    emit!(InsuranceClaimPaid {
+        claim_index: claim.claim_index,
+        recipient: claim.recipient_account,
+        approved_amount,
+        paid_amount: payout,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Insurance claim {} paid {} of {} approved TWIST",
+        claim.claim_index,
+        payout,
+        approved_amount
+    );
+    
+    ctx.accounts.insurance_vault.reload()?;
+    refresh_coverage(
+        &ctx.accounts.program_state,
+        &mut ctx.accounts.insurance_fund,
+        ctx.accounts.insurance_vault.amount,
+        ctx.accounts.floor_treasury.amount,
+        clock.unix_timestamp,
+    );
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RefreshInsuranceCoverage<'info> {
+    #[account(
+        seeds = [PROGRAM_STATE_SEED],
+        bump,
+    )]
+    pub program_state: Box<Account<'info, ProgramState>>,
+    
+    #[account(
+        mut,
+        seeds = [INSURANCE_FUND_SEED],
+        bump = insurance_fund.bump,
+    )]
+    pub insurance_fund: Box<Account<'info, InsuranceFund>>,
+    
+    #[account(
+        address = insurance_fund.vault @ TwistError::InvalidAccount,
+    )]
+    pub insurance_vault: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        seeds = [FLOOR_TREASURY_SEED],
+        bump,
+        token::mint = program_state.mint,
+        token::authority = program_state,
+    )]
+    pub floor_treasury: Box<Account<'info, TokenAccount>>,
+}
+
+pub fn refresh_insurance_coverage_handler(ctx: Context<RefreshInsuranceCoverage>) -> Result<()> {
+    let clock = Clock::get()?;
+    
+    refresh_coverage(
+        &ctx.accounts.program_state,
+        &mut ctx.accounts.insurance_fund,
+        ctx.accounts.insurance_vault.amount,
+        ctx.accounts.floor_treasury.amount,
+        clock.unix_timestamp,
+    );
+    
+    Ok(())
+}
+
+// Helper functions
+
+fn require_claims_authority(accounts: &ResolveInsuranceClaim) -> Result<()> {
+    let approver = accounts.approver.key();
+    let signed_by_multisig = accounts.multisig_config
+        .as_ref()
+        .map(|multisig| multisig.key() == approver)
+        .unwrap_or(false);
+    let signed_by_governance = accounts.governance_config
+        .as_ref()
+        .map(|governance| governance.key() == approver)
+        .unwrap_or(false);
+    
+    require!(
+        signed_by_multisig || signed_by_governance,
+        TwistError::MultisigRequired
+    );
+    
+    Ok(())
+}
+
+/// TVL covered by the fund: staked TWIST plus the floor's TWIST and USDC, in USDC
+fn refresh_coverage(
+    program_state: &ProgramState,
+    insurance_fund: &mut InsuranceFund,
+    vault_balance: u64,
+    floor_twist_balance: u64,
+    timestamp: i64,
+) {
+    let staked = program_state.total_staked.min(u64::MAX as u128) as u64;
+    let tvl = program_state.twist_to_usdc_value(staked)
+        .saturating_add(program_state.twist_to_usdc_value(floor_twist_balance))
+        .saturating_add(program_state.floor_liquidity);
+    let vault_value = program_state.twist_to_usdc_value(vault_balance);
+    
+    insurance_fund.update_coverage(vault_value, tvl, timestamp);
+    
+    emit!(InsuranceCoverageUpdated {
+        vault_balance,
+        vault_value,
+        tvl,
+        coverage_ratio_bps: insurance_fund.coverage_ratio_bps,
+        outstanding_approved: insurance_fund.outstanding_approved,
+        timestamp,
+    });
+    
+    msg!("Insurance coverage: {}% of TVL", insurance_fund.coverage_ratio_bps as f64 / 100.0);
+}
@@ -1,10 +1,13 @@
 use anchor_lang::prelude::*;
+use anchor_spl::associated_token::{get_associated_token_address, AssociatedToken};
 use anchor_spl::token::{Token, TokenAccount, Mint};
 use crate::state::*;
 use crate::constants::*;
 use crate::errors::TwistError;
 use crate::defi::*;
-use crate::utils::{validate_amount, safe_mul, safe_div, safe_add};
+use crate::utils::{validate_amount, safe_add, execute_route, oracle_min_swap_output, plan_registry_route, transfer_tokens};
+use crate::instructions::dex_oracle::observe_pool;
+use crate::instructions::fee_collector::protocol_swap_fee;
 
 #[derive(Accounts)]
 #[instruction(params: PoolParams)]
@@ -141,6 +144,13 @@ pub struct AddLiquidity<'info> {
     /// CHECK: Whirlpool program
     pub whirlpool_program: AccountInfo<'info>,
     
+    #[account(
+        mut,
+        seeds = [DEX_ORACLE_SEED],
+        bump = dex_oracle.bump,
+    )]
+    pub dex_oracle: Option<Box<Account<'info, DexOracle>>>,
+    
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
@@ -188,6 +198,9 @@ pub struct RemoveLiquidity<'info> {
     /// CHECK: Position mint - verified in handler
     pub position_mint: AccountInfo<'info>,
     
+    /// CHECK: Whirlpool position for position_mint - verified in handler
+    pub position: AccountInfo<'info>,
+    
     /// CHECK: Position token account - verified in handler
     #[account(mut)]
     pub position_token_account: AccountInfo<'info>,
@@ -211,6 +224,13 @@ pub struct RemoveLiquidity<'info> {
     /// CHECK: Whirlpool program
     pub whirlpool_program: AccountInfo<'info>,
     
+    #[account(
+        mut,
+        seeds = [DEX_ORACLE_SEED],
+        bump = dex_oracle.bump,
+    )]
+    pub dex_oracle: Option<Box<Account<'info, DexOracle>>>,
+    
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
@@ -266,9 +286,8 @@ pub fn initialize_pool_handler(
     
     // Calculate initial sqrt price
     // Price = TWIST/USDC, so if TWIST = $0.05, then 1 TWIST = 0.05 USDC
-    // sqrt_price = sqrt(price * 2^64)
-    let price_ratio = (params.initial_price as u128) * (1u128 << 64) / 1_000_000u128; // Adjust for USDC decimals
-    let sqrt_price = (price_ratio as f64).sqrt() as u128;
+    // sqrt_price is Q64.64 of the raw unit ratio, USDC atoms per TWIST atom
+    let sqrt_price = sqrt_price_from_price(params.initial_price, DECIMALS)?;
     
     // Store pool info in program state
     program_state.whirlpool = ctx.accounts.whirlpool.key();
@@ -346,6 +365,8 @@ pub fn add_liquidity_handler(
 ) -> Result<()> {
     let clock = Clock::get()?;
     
+    observe_pool(&mut ctx.accounts.dex_oracle, &[ctx.accounts.whirlpool.to_account_info()], clock.unix_timestamp)?;
+    
     // Validate amounts
     validate_amount(twist_amount)?;
     validate_amount(usdc_amount)?;
@@ -355,17 +376,29 @@ pub fn add_liquidity_handler(
     );
     
     // Calculate price range for concentrated liquidity
-    // For now, we'll use a ±10% range around current price
-    let oracle = &ctx.accounts.oracle;
-    let current_sqrt_price = oracle.sqrt_price;
+    let whirlpool = load_whirlpool_state(&ctx.accounts.whirlpool)?;
+    let current_sqrt_price = whirlpool.sqrt_price;
+    let (lower_tick, upper_tick) = default_position_ticks(&whirlpool)?;
     
-    // Calculate lower and upper sqrt prices (±10% range)
-    let lower_sqrt_price = current_sqrt_price * 9000 / 10000;
-    let upper_sqrt_price = current_sqrt_price * 11000 / 10000;
+    // Liquidity the offered amounts can fund, and what depositing it will cost
+    let lower_sqrt_price = sqrt_price_from_tick_index(lower_tick)?;
+    let upper_sqrt_price = sqrt_price_from_tick_index(upper_tick)?;
+    let liquidity = get_liquidity_for_amounts(
+        current_sqrt_price,
+        lower_sqrt_price,
+        upper_sqrt_price,
+        twist_amount,
+        usdc_amount,
+    )?;
+    require!(liquidity > 0, TwistError::InvalidAmount);
     
-    // Convert sqrt prices to tick indices
-    let lower_tick = sqrt_price_to_tick(lower_sqrt_price)?;
-    let upper_tick = sqrt_price_to_tick(upper_sqrt_price)?;
+    let (expected_twist, expected_usdc) = get_amounts_for_liquidity(
+        current_sqrt_price,
+        lower_sqrt_price,
+        upper_sqrt_price,
+        liquidity,
+        true,
+    )?;
     
     // Build open position instruction
     let open_position_ix = build_open_position_ix(
@@ -391,9 +424,9 @@ pub fn add_liquidity_handler(
         ],
     )?;
     
-    // Calculate minimum amounts with slippage
-    let min_twist = safe_div(safe_mul(twist_amount, 10000 - slippage_bps)?, 10000)?;
-    let min_usdc = safe_div(safe_mul(usdc_amount, 10000 - slippage_bps)?, 10000)?;
+    // Cap what the pool may pull if the price moves before execution
+    let max_twist = amount_plus_bps(expected_twist, slippage_bps).min(twist_amount);
+    let max_usdc = amount_plus_bps(expected_usdc, slippage_bps).min(usdc_amount);
     
     // Build increase liquidity instruction
     let increase_liquidity_ix = build_increase_liquidity_ix(
@@ -408,10 +441,9 @@ pub fn add_liquidity_handler(
         ctx.accounts.tick_array_lower.key(),
         ctx.accounts.tick_array_upper.key(),
         ctx.accounts.liquidity_provider.key(),
-        twist_amount,
-        usdc_amount,
-        min_twist,
-        min_usdc,
+        liquidity,
+        max_twist,
+        max_usdc,
     )?;
     
     // Execute CPI to add liquidity
@@ -444,16 +476,17 @@ pub fn add_liquidity_handler(
     emit!(LiquidityAdded {
         provider: ctx.accounts.liquidity_provider.key(),
         position_mint: ctx.accounts.position_mint.key(),
-        twist_amount,
-        usdc_amount,
+        twist_amount: expected_twist,
+        usdc_amount: expected_usdc,
         lower_tick,
         upper_tick,
         timestamp: clock.unix_timestamp,
     });
     
     msg!("Liquidity added successfully");
-    msg!("TWIST: {} tokens", twist_amount as f64 / 10f64.powf(DECIMALS as f64));
-    msg!("USDC: {} tokens", usdc_amount as f64 / 1_000_000.0);
+    msg!("Liquidity: {}", liquidity);
+    msg!("TWIST: {} tokens", expected_twist as f64 / 10f64.powf(DECIMALS as f64));
+    msg!("USDC: {} tokens", expected_usdc as f64 / 1_000_000.0);
     msg!("Position mint: {}", ctx.accounts.position_mint.key());
     
     Ok(())
@@ -467,9 +500,45 @@ pub fn remove_liquidity_handler(
 ) -> Result<()> {
     let clock = Clock::get()?;
     
+    observe_pool(&mut ctx.accounts.dex_oracle, &[ctx.accounts.whirlpool.to_account_info()], clock.unix_timestamp)?;
+    
     // Validate amounts
     validate_amount(liquidity_amount)?;
     
+    let position = load_whirlpool_position(&ctx.accounts.position)?;
+    require_keys_eq!(position.whirlpool, ctx.accounts.whirlpool.key(), TwistError::InvalidAccount);
+    require_keys_eq!(position.position_mint, ctx.accounts.position_mint.key(), TwistError::InvalidAccount);
+    require!(
+        liquidity_amount as u128 <= position.liquidity,
+        TwistError::InsufficientLiquidity
+    );
+    
+    // Withdrawals round down, so these are the least the pool pays out at the current price
+    let whirlpool = load_whirlpool_state(&ctx.accounts.whirlpool)?;
+    let (expected_twist, expected_usdc) = get_amounts_for_liquidity(
+        whirlpool.sqrt_price,
+        sqrt_price_from_tick_index(position.tick_lower_index)?,
+        sqrt_price_from_tick_index(position.tick_upper_index)?,
+        liquidity_amount as u128,
+        false,
+    )?;
+    require!(
+        min_twist <= expected_twist && min_usdc <= expected_usdc,
+        TwistError::SlippageToleranceExceeded
+    );
+    
+    // A zero minimum falls back to the expected amount less the default slippage
+    let min_twist = if min_twist == 0 {
+        amount_less_bps(expected_twist, LIQUIDITY_DEFAULT_SLIPPAGE_BPS)
+    } else {
+        min_twist
+    };
+    let min_usdc = if min_usdc == 0 {
+        amount_less_bps(expected_usdc, LIQUIDITY_DEFAULT_SLIPPAGE_BPS)
+    } else {
+        min_usdc
+    };
+    
     // Build decrease liquidity instruction
     let decrease_liquidity_ix = build_decrease_liquidity_ix(
         ctx.accounts.whirlpool_program.key(),
@@ -524,14 +593,398 @@ pub fn remove_liquidity_handler(
     
     msg!("Liquidity removed successfully");
     msg!("Liquidity amount: {}", liquidity_amount);
+    msg!("Expected TWIST: {} USDC: {}", expected_twist, expected_usdc);
     msg!("Min TWIST: {} tokens", min_twist as f64 / 10f64.powf(DECIMALS as f64));
     msg!("Min USDC: {} tokens", min_usdc as f64 / 1_000_000.0);
     
     Ok(())
 }
 
+#[derive(Accounts)]
+pub struct ZapLiquidity<'info> {
+    #[account(mut)]
+    pub liquidity_provider: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = !program_state.emergency_pause @ TwistError::EmergencyPauseActive,
+        constraint = !program_state.circuit_breaker_active @ TwistError::CircuitBreakerActive,
+    )]
+    pub program_state: Account<'info, ProgramState>,
+    
+    #[account(
+        mut,
+        constraint = user_twist_account.owner == liquidity_provider.key() @ TwistError::Unauthorized,
+        constraint = user_twist_account.mint == program_state.mint @ TwistError::InvalidMintAuthority
+    )]
+    pub user_twist_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = user_usdc_account.owner == liquidity_provider.key() @ TwistError::Unauthorized,
+        constraint = user_usdc_account.mint == usdc_mint.key() @ TwistError::InvalidMintAuthority
+    )]
+    pub user_usdc_account: Account<'info, TokenAccount>,
+    
+    pub usdc_mint: Account<'info, Mint>,
+    
+    /// CHECK: The program's TWIST/USDC whirlpool, deserialized in handler
+    #[account(
+        mut,
+        constraint = whirlpool.key() == program_state.whirlpool @ TwistError::InvalidAccount
+    )]
+    pub whirlpool: AccountInfo<'info>,
+    
+    /// CHECK: Token vault A - verified against the whirlpool in handler
+    #[account(mut)]
+    pub token_vault_a: AccountInfo<'info>,
+    
+    /// CHECK: Token vault B - verified against the whirlpool in handler
+    #[account(mut)]
+    pub token_vault_b: AccountInfo<'info>,
+    
+    /// CHECK: Position mint - created by CPI
+    #[account(mut)]
+    pub position_mint: AccountInfo<'info>,
+    
+    /// CHECK: Position token account - created by CPI
+    #[account(mut)]
+    pub position_token_account: AccountInfo<'info>,
+    
+    /// CHECK: Tick array lower - verified by CPI
+    #[account(mut)]
+    pub tick_array_lower: AccountInfo<'info>,
+    
+    /// CHECK: Tick array upper - verified by CPI
+    #[account(mut)]
+    pub tick_array_upper: AccountInfo<'info>,
+    
+    /// Pool oracle for price updates
+    #[account(
+        mut,
+        seeds = [b"pool_oracle", whirlpool.key().as_ref()],
+        bump,
+    )]
+    pub oracle: Account<'info, PoolOracle>,
+    
+    /// CHECK: Whirlpool program - verified in handler
+    pub whirlpool_program: AccountInfo<'info>,
+    
+    // Venues the swap leg may route through
+    #[account(
+        seeds = [DEX_REGISTRY_SEED],
+        bump = dex_registry.bump,
+    )]
+    pub dex_registry: Box<Account<'info, DexRegistry>>,
+    
+    #[account(
+        mut,
+        seeds = [DEX_ORACLE_SEED],
+        bump = dex_oracle.bump,
+    )]
+    pub dex_oracle: Option<Box<Account<'info, DexOracle>>>,
+    
+    // The swap leg pays the dynamic protocol fee in the input token
+    #[account(
+        mut,
+        seeds = [b"fee_collector"],
+        bump = fee_collector.bump,
+    )]
+    pub fee_collector: Box<Account<'info, FeeCollectorState>>,
+    
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED],
+        bump,
+        token::mint = program_state.mint,
+        token::authority = fee_collector,
+    )]
+    pub fee_vault: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        address = fee_collector.usdc_fee_vault @ TwistError::InvalidAccount,
+    )]
+    pub usdc_fee_vault: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        seeds = [MARKET_METRICS_SEED],
+        bump = market_metrics.bump,
+    )]
+    pub market_metrics: Box<Account<'info, MarketMetrics>>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Add liquidity from TWIST alone (`twist_in`) or USDC alone. Swaps the share of the input
+/// the position needs of the other token, then deposits both into a new position. Leftover
+/// dust never leaves the provider's accounts. `min_swap_out` is the caller's own floor for
+/// the swap leg, quoted off-chain before the transaction. Remaining accounts: one account
+/// group per registered venue the swap leg may route through.
+pub fn zap_liquidity_handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, ZapLiquidity<'info>>,
+    amount_in: u64,
+    twist_in: bool,
+    min_swap_out: u64,
+    min_liquidity: u128,
+    slippage_bps: u64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    
+    observe_pool(&mut ctx.accounts.dex_oracle, &[ctx.accounts.whirlpool.to_account_info()], clock.unix_timestamp)?;
+    
+    validate_amount(amount_in)?;
+    require!(
+        slippage_bps <= 1000, // Max 10% slippage
+        TwistError::InvalidAmount
+    );
+    let input_balance = if twist_in {
+        ctx.accounts.user_twist_account.amount
+    } else {
+        ctx.accounts.user_usdc_account.amount
+    };
+    require!(input_balance >= amount_in, TwistError::InsufficientLiquidity);
+    
+    let whirlpool = load_whirlpool_state(&ctx.accounts.whirlpool)?;
+    let (lower_tick, upper_tick) = default_position_ticks(&whirlpool)?;
+    let lower_sqrt_price = sqrt_price_from_tick_index(lower_tick)?;
+    let upper_sqrt_price = sqrt_price_from_tick_index(upper_tick)?;
+    
+    // Swap leg: TWIST in sells toward USDC, USDC in buys TWIST
+    let a_to_b = twist_in;
+    let swap_amount = zap_swap_amount(
+        whirlpool.sqrt_price,
+        whirlpool.liquidity,
+        whirlpool.fee_rate,
+        lower_sqrt_price,
+        upper_sqrt_price,
+        amount_in,
+        a_to_b,
+    )?;
+    
+    // Dynamic fee on the swap leg, paid in the input token like any protocol-routed swap
+    let swap_value = if twist_in {
+        ctx.accounts.program_state.twist_to_usdc_value(swap_amount)
+    } else {
+        swap_amount
+    };
+    ctx.accounts.program_state.check_transaction_size(swap_value)?;
+    let (fee_bps, _) = protocol_swap_fee(
+        &ctx.accounts.fee_collector,
+        &ctx.accounts.program_state,
+        &ctx.accounts.market_metrics,
+        swap_value,
+        clock.unix_timestamp,
+    )?;
+    let swap_fee = (swap_amount as u128 * fee_bps as u128 / 10000) as u64;
+    if swap_fee > 0 {
+        if twist_in {
+            transfer_tokens(
+                &ctx.accounts.user_twist_account,
+                &ctx.accounts.fee_vault,
+                &ctx.accounts.liquidity_provider,
+                &ctx.accounts.token_program,
+                swap_fee,
+            )?;
+            ctx.accounts.fee_collector.record_fee(FeeType::Trading, swap_fee);
+        } else {
+            transfer_tokens(
+                &ctx.accounts.user_usdc_account,
+                &ctx.accounts.usdc_fee_vault,
+                &ctx.accounts.liquidity_provider,
+                &ctx.accounts.token_program,
+                swap_fee,
+            )?;
+            ctx.accounts.fee_collector.record_usdc_fee(swap_fee);
+        }
+    }
+    
+    let mut swap_out = 0;
+    if swap_amount > swap_fee {
+        let endpoints = SwapEndpoints {
+            token_program: ctx.accounts.token_program.to_account_info(),
+            token_authority: ctx.accounts.liquidity_provider.to_account_info(),
+            twist_mint: ctx.accounts.program_state.mint,
+            twist_account: ctx.accounts.user_twist_account.to_account_info(),
+            usdc_account: ctx.accounts.user_usdc_account.to_account_info(),
+        };
+        let (adapters, route) = plan_registry_route(
+            &ctx.accounts.dex_registry,
+            ctx.remaining_accounts,
+            &endpoints,
+            swap_amount - swap_fee,
+            a_to_b,
+        )?;
+        
+        let destination = if a_to_b {
+            &mut ctx.accounts.user_usdc_account
+        } else {
+            &mut ctx.accounts.user_twist_account
+        };
+        swap_out = execute_route(&adapters, &route, min_swap_out, a_to_b, &[], destination)?;
+        
+        // Zap flow counts toward the volume the breaker and dynamic fee see
+        ctx.accounts.market_metrics.record_volume(clock.unix_timestamp, swap_value);
+    }
+    
+    // Deposit leg, priced at the pool as the swap left it
+    let remaining = amount_in - swap_amount;
+    let (twist_available, usdc_available) = if twist_in {
+        (remaining, swap_out)
+    } else {
+        (swap_out, remaining)
+    };
+    let current_sqrt_price = load_whirlpool_state(&ctx.accounts.whirlpool)?.sqrt_price;
+    let liquidity = get_liquidity_for_amounts(
+        current_sqrt_price,
+        lower_sqrt_price,
+        upper_sqrt_price,
+        twist_available,
+        usdc_available,
+    )?;
+    require!(
+        liquidity > 0 && liquidity >= min_liquidity,
+        TwistError::SlippageToleranceExceeded
+    );
+    
+    let (expected_twist, expected_usdc) = get_amounts_for_liquidity(
+        current_sqrt_price,
+        lower_sqrt_price,
+        upper_sqrt_price,
+        liquidity,
+        true,
+    )?;
+    
+    let open_position_ix = build_open_position_ix(
+        ctx.accounts.whirlpool_program.key(),
+        ctx.accounts.whirlpool.key(),
+        ctx.accounts.position_mint.key(),
+        ctx.accounts.position_token_account.key(),
+        ctx.accounts.liquidity_provider.key(),
+        lower_tick,
+        upper_tick,
+    )?;
+    
+    anchor_lang::solana_program::program::invoke(
+        &open_position_ix,
+        &[
+            ctx.accounts.whirlpool.to_account_info(),
+            ctx.accounts.position_mint.to_account_info(),
+            ctx.accounts.position_token_account.to_account_info(),
+            ctx.accounts.liquidity_provider.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+        ],
+    )?;
+    
+    // The pool may never pull more than the zap produced
+    let max_twist = amount_plus_bps(expected_twist, slippage_bps).min(twist_available);
+    let max_usdc = amount_plus_bps(expected_usdc, slippage_bps).min(usdc_available);
+    
+    let increase_liquidity_ix = build_increase_liquidity_ix(
+        ctx.accounts.whirlpool_program.key(),
+        ctx.accounts.whirlpool.key(),
+        ctx.accounts.position_mint.key(),
+        ctx.accounts.position_token_account.key(),
+        ctx.accounts.user_twist_account.key(),
+        ctx.accounts.user_usdc_account.key(),
+        ctx.accounts.token_vault_a.key(),
+        ctx.accounts.token_vault_b.key(),
+        ctx.accounts.tick_array_lower.key(),
+        ctx.accounts.tick_array_upper.key(),
+        ctx.accounts.liquidity_provider.key(),
+        liquidity,
+        max_twist,
+        max_usdc,
+    )?;
+    
+    anchor_lang::solana_program::program::invoke(
+        &increase_liquidity_ix,
+        &[
+            ctx.accounts.whirlpool.to_account_info(),
+            ctx.accounts.position_mint.to_account_info(),
+            ctx.accounts.position_token_account.to_account_info(),
+            ctx.accounts.user_twist_account.to_account_info(),
+            ctx.accounts.user_usdc_account.to_account_info(),
+            ctx.accounts.token_vault_a.to_account_info(),
+            ctx.accounts.token_vault_b.to_account_info(),
+            ctx.accounts.tick_array_lower.to_account_info(),
+            ctx.accounts.tick_array_upper.to_account_info(),
+            ctx.accounts.liquidity_provider.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+        ],
+    )?;
+    
+    let oracle = &mut ctx.accounts.oracle;
+    oracle.last_update_timestamp = clock.unix_timestamp;
+    
+    let program_state = &mut ctx.accounts.program_state;
+    program_state.total_transactions = program_state.total_transactions.saturating_add(1);
+    
+    emit!(LiquidityAdded {
+        provider: ctx.accounts.liquidity_provider.key(),
+        position_mint: ctx.accounts.position_mint.key(),
+        twist_amount: expected_twist,
+        usdc_amount: expected_usdc,
+        lower_tick,
+        upper_tick,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    emit!(LiquidityZapped {
+        provider: ctx.accounts.liquidity_provider.key(),
+        position_mint: ctx.accounts.position_mint.key(),
+        twist_in,
+        amount_in,
+        swap_amount,
+        swap_fee,
+        swap_out,
+        liquidity,
+        twist_dust: twist_available.saturating_sub(expected_twist),
+        usdc_dust: usdc_available.saturating_sub(expected_usdc),
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Zapped {} {} into liquidity {}",
+        if twist_in {
+            amount_in as f64 / 10f64.powf(DECIMALS as f64)
+        } else {
+            amount_in as f64 / 1_000_000.0
+        },
+        if twist_in { "TWIST" } else { "USDC" },
+        liquidity
+    );
+    msg!("Swapped {} for {} after a {} bps fee", swap_amount, swap_out, fee_bps);
+    msg!("Position mint: {}", ctx.accounts.position_mint.key());
+    
+    Ok(())
+}
+
 // Helper functions for Orca integration
 
+/// User positions span ±10% around the live pool price, widened outward onto the pool's tick spacing
+fn default_position_ticks(whirlpool: &WhirlpoolState) -> Result<(i32, i32)> {
+    let current_price = price_from_sqrt_price(whirlpool.sqrt_price, DECIMALS)?;
+    
+    let lower_tick = round_tick_to_spacing(
+        tick_index_from_sqrt_price(sqrt_price_from_price(amount_less_bps(current_price, 1000), DECIMALS)?)?,
+        whirlpool.tick_spacing,
+        false,
+    )?;
+    let upper_tick = round_tick_to_spacing(
+        tick_index_from_sqrt_price(sqrt_price_from_price(amount_plus_bps(current_price, 1000), DECIMALS)?)?,
+        whirlpool.tick_spacing,
+        true,
+    )?;
+    require!(lower_tick < upper_tick, TwistError::InvalidPriceRange);
+    
+    Ok((lower_tick, upper_tick))
+}
+
 fn build_initialize_pool_ix(
     whirlpool_program: Pubkey,
     whirlpools_config: Pubkey,
@@ -616,10 +1069,9 @@ fn build_increase_liquidity_ix(
     tick_array_lower: Pubkey,
     tick_array_upper: Pubkey,
     position_authority: Pubkey,
-    amount_a: u64,
-    amount_b: u64,
-    _min_amount_a: u64,
-    _min_amount_b: u64,
+    liquidity_amount: u128,
+    token_max_a: u64,
+    token_max_b: u64,
 ) -> Result<anchor_lang::solana_program::instruction::Instruction> {
     // Orca increase liquidity instruction discriminator
     const INCREASE_LIQUIDITY_DISCRIMINATOR: [u8; 8] = [46, 156, 243, 118, 13, 205, 251, 178];
@@ -645,14 +1097,10 @@ fn build_increase_liquidity_ix(
         token_max_b: u64,
     }
     
-    // Calculate liquidity amount from token amounts
-    // This is simplified - real calculation would use Orca's liquidity math
-    let liquidity_amount = std::cmp::min(amount_a as u128, amount_b as u128);
-    
     let params = IncreaseLiquidityParams {
         liquidity_amount,
-        token_max_a: amount_a,
-        token_max_b: amount_b,
+        token_max_a,
+        token_max_b,
     };
     
     let mut data = INCREASE_LIQUIDITY_DISCRIMINATOR.to_vec();
@@ -721,15 +1169,6 @@ fn build_decrease_liquidity_ix(
     })
 }
 
-// Convert sqrt price to tick index
-fn sqrt_price_to_tick(sqrt_price: u128) -> Result<i32> {
-    // Simplified conversion - in production would use full tick math
-    let log_base = 1.0001f64;
-    let price = (sqrt_price as f64 / (1u64 << 32) as f64).powi(2);
-    let tick = (price.ln() / log_base.ln()) as i32;
-    Ok(tick)
-}
-
 // New events for liquidity operations
 #[event]
 pub struct PoolInitialized {
@@ -762,6 +1201,21 @@ pub struct LiquidityRemoved {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct LiquidityZapped {
+    pub provider: Pubkey,
+    pub position_mint: Pubkey,
+    pub twist_in: bool,
+    pub amount_in: u64,
+    pub swap_amount: u64,
+    pub swap_fee: u64,
+    pub swap_out: u64,
+    pub liquidity: u128,
+    pub twist_dust: u64,
+    pub usdc_dust: u64,
+    pub timestamp: i64,
+}
+
 // Advanced Liquidity Management Instructions
 
 #[derive(Accounts)]
@@ -792,164 +1246,825 @@ pub struct RebalancePosition<'info> {
     )]
     pub whirlpool: Box<Account<'info, WhirlpoolState>>,
     
+    /// CHECK: Pool TWIST vault, checked against the whirlpool
+    #[account(mut, address = whirlpool.token_vault_a @ TwistError::InvalidAccount)]
+    pub token_vault_a: AccountInfo<'info>,
+    
+    /// CHECK: Pool USDC vault, checked against the whirlpool
+    #[account(mut, address = whirlpool.token_vault_b @ TwistError::InvalidAccount)]
+    pub token_vault_b: AccountInfo<'info>,
+    
+    // Program-state-owned accounts the position pays into and is funded from
     #[account(
         mut,
-        constraint = twist_vault.key() == whirlpool.token_vault_a @ TwistError::InvalidAccount
+        token::mint = whirlpool.token_mint_a,
+        token::authority = program_state,
     )]
-    pub twist_vault: Account<'info, TokenAccount>,
+    pub twist_account: Box<Account<'info, TokenAccount>>,
     
     #[account(
         mut,
-        constraint = usdc_vault.key() == whirlpool.token_vault_b @ TwistError::InvalidAccount
+        token::mint = whirlpool.token_mint_b,
+        token::authority = program_state,
     )]
-    pub usdc_vault: Account<'info, TokenAccount>,
+    pub usdc_account: Box<Account<'info, TokenAccount>>,
     
-    /// CHECK: Old tick array lower
+    // Required when the position belongs to its LP vault, whose idle accounts must then
+    // be the TWIST and USDC accounts above
+    #[account(
+        seeds = [LP_VAULT_SEED, &position_index.to_le_bytes()],
+        bump = lp_vault.bump,
+    )]
+    pub lp_vault: Option<Box<Account<'info, LpVault>>>,
+    
+    /// CHECK: The Whirlpool position of `liquidity_position`, checked by address
+    #[account(
+        mut,
+        address = whirlpool_position_address(&liquidity_position.position_mint).0 @ TwistError::InvalidAccount
+    )]
+    pub position: AccountInfo<'info>,
+    
+    /// CHECK: NFT mint of `liquidity_position`, burned when the emptied position is closed
+    #[account(mut, address = liquidity_position.position_mint @ TwistError::InvalidAccount)]
+    pub position_mint: AccountInfo<'info>,
+    
+    #[account(
+        mut,
+        constraint = position_token_account.mint == liquidity_position.position_mint @ TwistError::InvalidAccount,
+        constraint = position_token_account.owner == program_state.key() @ TwistError::InvalidAccount,
+        constraint = position_token_account.amount == 1 @ TwistError::InvalidAccount,
+    )]
+    pub position_token_account: Box<Account<'info, TokenAccount>>,
+    
+    /// CHECK: Old tick array lower
+    #[account(mut)]
     pub old_tick_array_lower: AccountInfo<'info>,
     
     /// CHECK: Old tick array upper
+    #[account(mut)]
     pub old_tick_array_upper: AccountInfo<'info>,
     
+    /// CHECK: Whirlpool position for the new range, created by CPI
+    #[account(
+        mut,
+        address = whirlpool_position_address(&new_position_mint.key()).0 @ TwistError::InvalidAccount
+    )]
+    pub new_position: AccountInfo<'info>,
+    
+    /// Fresh mint for the new position NFT
+    #[account(mut)]
+    pub new_position_mint: Signer<'info>,
+    
+    /// CHECK: Program state's token account for the new position NFT, created by CPI
+    #[account(
+        mut,
+        address = get_associated_token_address(&program_state.key(), &new_position_mint.key()) @ TwistError::InvalidAccount
+    )]
+    pub new_position_token_account: AccountInfo<'info>,
+    
     /// CHECK: New tick array lower
+    #[account(mut)]
     pub new_tick_array_lower: AccountInfo<'info>,
     
-    /// CHECK: New tick array upper  
+    /// CHECK: New tick array upper
+    #[account(mut)]
     pub new_tick_array_upper: AccountInfo<'info>,
     
     /// CHECK: Orca Whirlpool program
+    #[account(address = ORCA_WHIRLPOOL_PROGRAM_ID @ TwistError::InvalidAccount)]
     pub whirlpool_program: AccountInfo<'info>,
     
+    // Venues the swap leg may route through
+    #[account(
+        seeds = [DEX_REGISTRY_SEED],
+        bump = dex_registry.bump,
+    )]
+    pub dex_registry: Box<Account<'info, DexRegistry>>,
+    
+    #[account(
+        mut,
+        seeds = [DEX_ORACLE_SEED],
+        bump = dex_oracle.bump,
+    )]
+    pub dex_oracle: Option<Box<Account<'info, DexOracle>>>,
+    
     pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
 }
 
-pub fn rebalance_position_handler(
-    ctx: Context<RebalancePosition>,
+/// Remaining accounts: one account group per registered venue the recentering swap may
+/// route through
+pub fn rebalance_position_handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, RebalancePosition<'info>>,
     position_index: u8,
     params: RebalanceParams,
 ) -> Result<()> {
-    let liquidity_position = &mut ctx.accounts.liquidity_position;
     let whirlpool = &ctx.accounts.whirlpool;
     let clock = Clock::get()?;
     
+    check_position_funding_accounts(
+        position_index,
+        &ctx.accounts.liquidity_position,
+        &ctx.accounts.lp_vault,
+        ctx.accounts.twist_account.key(),
+        ctx.accounts.usdc_account.key(),
+    )?;
+    
+    observe_pool(&mut ctx.accounts.dex_oracle, &[ctx.accounts.whirlpool.to_account_info()], clock.unix_timestamp)?;
+    
     // Validate new price range
     require!(
         params.new_lower_price < params.new_upper_price,
         TwistError::InvalidPriceRange
     );
     
-    // Check if rebalancing is needed (price moved out of range)
-    let current_sqrt_price = whirlpool.sqrt_price;
-    let _current_price = (current_sqrt_price as u128).pow(2) / (1u128 << 64);
+    require!(
+        params.max_slippage_bps <= 1000, // Max 10% slippage
+        TwistError::InvalidAmount
+    );
     
-    // Calculate ticks for new range
-    let new_lower_tick = price_to_tick(params.new_lower_price, whirlpool.tick_spacing)?;
-    let new_upper_tick = price_to_tick(params.new_upper_price, whirlpool.tick_spacing)?;
+    // Calculate ticks for new range, widened outward onto the tick spacing
+    let new_lower_tick = round_tick_to_spacing(
+        tick_index_from_sqrt_price(sqrt_price_from_price(params.new_lower_price, DECIMALS)?)?,
+        whirlpool.tick_spacing,
+        false,
+    )?;
+    let new_upper_tick = round_tick_to_spacing(
+        tick_index_from_sqrt_price(sqrt_price_from_price(params.new_upper_price, DECIMALS)?)?,
+        whirlpool.tick_spacing,
+        true,
+    )?;
     
-    // Get current liquidity amount
-    let old_liquidity = liquidity_position.liquidity;
+    let new_liquidity = RangeRebalance {
+        position_index,
+        program_state: &ctx.accounts.program_state,
+        liquidity_position: &mut ctx.accounts.liquidity_position,
+        whirlpool: &ctx.accounts.whirlpool,
+        token_vault_a: &ctx.accounts.token_vault_a,
+        token_vault_b: &ctx.accounts.token_vault_b,
+        twist_account: &mut ctx.accounts.twist_account,
+        usdc_account: &mut ctx.accounts.usdc_account,
+        position: &ctx.accounts.position,
+        position_token_account: &ctx.accounts.position_token_account,
+        position_mint: &ctx.accounts.position_mint,
+        old_tick_array_lower: &ctx.accounts.old_tick_array_lower,
+        old_tick_array_upper: &ctx.accounts.old_tick_array_upper,
+        new_position: &ctx.accounts.new_position,
+        new_position_mint: &ctx.accounts.new_position_mint,
+        new_position_token_account: &ctx.accounts.new_position_token_account,
+        new_tick_array_lower: &ctx.accounts.new_tick_array_lower,
+        new_tick_array_upper: &ctx.accounts.new_tick_array_upper,
+        dex_registry: &ctx.accounts.dex_registry,
+        swap_accounts: ctx.remaining_accounts,
+        funder: &ctx.accounts.authority,
+        whirlpool_program: &ctx.accounts.whirlpool_program,
+        token_program: &ctx.accounts.token_program,
+        associated_token_program: &ctx.accounts.associated_token_program,
+        system_program: &ctx.accounts.system_program,
+        rent: &ctx.accounts.rent,
+    }.execute(
+        new_lower_tick,
+        new_upper_tick,
+        params.max_slippage_bps,
+        clock.unix_timestamp,
+    )?;
     
-    // Step 1: Remove liquidity from old position
-    let remove_liquidity_ix = build_orca_decrease_liquidity_ix(
-        &ctx.accounts.whirlpool_program.key(),
-        &ctx.accounts.whirlpool.key(),
-        liquidity_position.position_mint,
-        &ctx.accounts.old_tick_array_lower.key(),
-        &ctx.accounts.old_tick_array_upper.key(),
-        old_liquidity,
-        0, // Min amount A (we'll check slippage separately)
-        0, // Min amount B
+    msg!("Position {} rebalanced successfully", position_index);
+    msg!("New range: ${} - ${}", 
+        params.new_lower_price as f64 / 1e6,
+        params.new_upper_price as f64 / 1e6
     );
+    msg!("New liquidity: {}", new_liquidity);
     
-    // Execute remove liquidity CPI
-    let seeds = &[
-        PROGRAM_STATE_SEED,
-        &[ctx.accounts.program_state.bump],
-    ];
-    let signer_seeds = &[&seeds[..]];   
+    Ok(())
+}
+
+// A position owned by its LP vault is funded from and pays into the vault's idle
+// accounts, so withdrawn and collected tokens stay in the vault's NAV
+fn check_position_funding_accounts(
+    position_index: u8,
+    liquidity_position: &LiquidityPosition,
+    lp_vault: &Option<Box<Account<LpVault>>>,
+    twist_account: Pubkey,
+    usdc_account: Pubkey,
+) -> Result<()> {
+    let Some(lp_vault) = lp_vault else {
+        let vault_address = Pubkey::find_program_address(
+            &[LP_VAULT_SEED, &position_index.to_le_bytes()],
+            &crate::ID,
+        ).0;
+        require_keys_neq!(liquidity_position.owner, vault_address, TwistError::InvalidAccount);
+        return Ok(());
+    };
     
-    anchor_lang::solana_program::program::invoke_signed(
-        &remove_liquidity_ix,
-        &[
-            ctx.accounts.whirlpool_program.to_account_info(),
-            ctx.accounts.whirlpool.to_account_info(),
-            ctx.accounts.program_state.to_account_info(),
-            ctx.accounts.twist_vault.to_account_info(),
-            ctx.accounts.usdc_vault.to_account_info(),
-            ctx.accounts.old_tick_array_lower.to_account_info(),
-            ctx.accounts.old_tick_array_upper.to_account_info(),
-            ctx.accounts.token_program.to_account_info(),
-        ],
-        signer_seeds,
+    require_keys_eq!(liquidity_position.owner, lp_vault.key(), TwistError::InvalidAccount);
+    require_keys_eq!(twist_account, lp_vault.twist_account, TwistError::InvalidAccount);
+    require_keys_eq!(usdc_account, lp_vault.usdc_account, TwistError::InvalidAccount);
+    Ok(())
+}
+
+// Accounts for moving a protocol-owned position to a new range
+struct RangeRebalance<'a, 'info> {
+    position_index: u8,
+    program_state: &'a Account<'info, ProgramState>,
+    liquidity_position: &'a mut Account<'info, LiquidityPosition>,
+    whirlpool: &'a Account<'info, WhirlpoolState>,
+    token_vault_a: &'a AccountInfo<'info>,
+    token_vault_b: &'a AccountInfo<'info>,
+    twist_account: &'a mut Account<'info, TokenAccount>,
+    usdc_account: &'a mut Account<'info, TokenAccount>,
+    position: &'a AccountInfo<'info>,
+    position_token_account: &'a Account<'info, TokenAccount>,
+    position_mint: &'a AccountInfo<'info>,
+    old_tick_array_lower: &'a AccountInfo<'info>,
+    old_tick_array_upper: &'a AccountInfo<'info>,
+    new_position: &'a AccountInfo<'info>,
+    new_position_mint: &'a AccountInfo<'info>,
+    new_position_token_account: &'a AccountInfo<'info>,
+    new_tick_array_lower: &'a AccountInfo<'info>,
+    new_tick_array_upper: &'a AccountInfo<'info>,
+    dex_registry: &'a DexRegistry,
+    swap_accounts: &'a [AccountInfo<'info>],
+    funder: &'a AccountInfo<'info>,
+    whirlpool_program: &'a AccountInfo<'info>,
+    token_program: &'a Program<'info, Token>,
+    associated_token_program: &'a Program<'info, AssociatedToken>,
+    system_program: &'a Program<'info, System>,
+    rent: &'a Sysvar<'info, Rent>,
+}
+
+impl<'a, 'info> RangeRebalance<'a, 'info> {
+    fn position_cpi(
+        &self,
+        position: &AccountInfo<'info>,
+        position_token_account: AccountInfo<'info>,
+        tick_array_lower: &AccountInfo<'info>,
+        tick_array_upper: &AccountInfo<'info>,
+    ) -> WhirlpoolPositionCpi<'info> {
+        WhirlpoolPositionCpi {
+            whirlpool_program: self.whirlpool_program.clone(),
+            token_program: self.token_program.to_account_info(),
+            whirlpool: self.whirlpool.to_account_info(),
+            position_authority: self.program_state.to_account_info(),
+            position: position.clone(),
+            position_token_account,
+            token_vault_a: self.token_vault_a.clone(),
+            token_vault_b: self.token_vault_b.clone(),
+            tick_array_lower: tick_array_lower.clone(),
+            tick_array_upper: tick_array_upper.clone(),
+        }
+    }
+    
+    /// Withdraw and close the position, swap the withdrawn tokens to the new range's ratio,
+    /// open a position between the new ticks, redeposit and return the new liquidity
+    fn execute(
+        self,
+        new_lower_tick: i32,
+        new_upper_tick: i32,
+        max_slippage_bps: u16,
+        timestamp: i64,
+    ) -> Result<u128> {
+        require!(new_lower_tick < new_upper_tick, TwistError::InvalidPriceRange);
+        
+        let current_sqrt_price = self.whirlpool.sqrt_price;
+        
+        // Get current liquidity amount and range
+        let old_liquidity = self.liquidity_position.liquidity;
+        let old_lower_tick = self.liquidity_position.lower_tick;
+        let old_upper_tick = self.liquidity_position.upper_tick;
+        
+        // Minimums for the withdrawal from what the old range holds at the current price
+        let (expected_twist, expected_usdc) = get_amounts_for_liquidity(
+            current_sqrt_price,
+            sqrt_price_from_tick_index(old_lower_tick)?,
+            sqrt_price_from_tick_index(old_upper_tick)?,
+            old_liquidity,
+            false,
+        )?;
+        let min_twist = amount_less_bps(expected_twist, max_slippage_bps as u64);
+        let min_usdc = amount_less_bps(expected_usdc, max_slippage_bps as u64);
+        
+        let seeds = &[
+            PROGRAM_STATE_SEED,
+            &[self.program_state.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+        
+        let twist_account = self.twist_account.to_account_info();
+        let usdc_account = self.usdc_account.to_account_info();
+        let twist_before = self.twist_account.amount;
+        let usdc_before = self.usdc_account.amount;
+        
+        // Step 1: Withdraw the old position and its fees into the protocol accounts
+        let old_position = self.position_cpi(
+            self.position,
+            self.position_token_account.to_account_info(),
+            self.old_tick_array_lower,
+            self.old_tick_array_upper,
+        );
+        old_position.decrease_liquidity(
+            &twist_account,
+            &usdc_account,
+            old_liquidity,
+            min_twist,
+            min_usdc,
+            signer_seeds,
+        )?;
+        old_position.collect_fees(&twist_account, &usdc_account, false, signer_seeds)?;
+        
+        // The emptied position and its NFT are closed, returning their rent to the funder
+        old_position.close_position(self.funder, self.position_mint, signer_seeds)?;
+        
+        // Only what came out of the position goes back in
+        self.twist_account.reload()?;
+        self.usdc_account.reload()?;
+        let mut twist_amount = self.twist_account.amount.saturating_sub(twist_before);
+        let mut usdc_amount = self.usdc_account.amount.saturating_sub(usdc_before);
+        
+        // Step 2: Swap to the new range's ratio. A position the price has left comes out
+        // as one token, which would fund next to no liquidity around the current price.
+        // Sized against the position's pool; a fill routed elsewhere leaves only dust behind.
+        let new_lower_sqrt_price = sqrt_price_from_tick_index(new_lower_tick)?;
+        let new_upper_sqrt_price = sqrt_price_from_tick_index(new_upper_tick)?;
+        let pool = load_whirlpool_state(&self.whirlpool.to_account_info())?;
+        let (swap_amount, a_to_b) = rebalance_swap_amount(
+            pool.sqrt_price,
+            pool.liquidity,
+            pool.fee_rate,
+            new_lower_sqrt_price,
+            new_upper_sqrt_price,
+            twist_amount,
+            usdc_amount,
+        )?;
+        
+        if swap_amount > 0 {
+            let min_out = oracle_min_swap_output(
+                self.program_state,
+                swap_amount,
+                a_to_b,
+                max_slippage_bps as u64,
+                timestamp,
+            )?;
+            let endpoints = SwapEndpoints {
+                token_program: self.token_program.to_account_info(),
+                token_authority: self.program_state.to_account_info(),
+                twist_mint: self.program_state.mint,
+                twist_account: twist_account.clone(),
+                usdc_account: usdc_account.clone(),
+            };
+            let (adapters, route) = plan_registry_route(
+                self.dex_registry,
+                self.swap_accounts,
+                &endpoints,
+                swap_amount,
+                a_to_b,
+            )?;
+            
+            if a_to_b {
+                let received = execute_route(&adapters, &route, min_out, a_to_b, signer_seeds, self.usdc_account)?;
+                twist_amount -= swap_amount;
+                usdc_amount = safe_add(usdc_amount, received)?;
+            } else {
+                let received = execute_route(&adapters, &route, min_out, a_to_b, signer_seeds, self.twist_account)?;
+                usdc_amount -= swap_amount;
+                twist_amount = safe_add(twist_amount, received)?;
+            }
+        }
+        
+        // Step 3: Open a position for the new range, its NFT held by the program state
+        anchor_lang::solana_program::program::invoke(
+            &build_orca_open_position_ix(
+                self.funder.key(),
+                self.program_state.key(),
+                self.new_position_mint.key(),
+                self.new_position_token_account.key(),
+                self.whirlpool.key(),
+                new_lower_tick,
+                new_upper_tick,
+            ),
+            &[
+                self.funder.clone(),
+                self.program_state.to_account_info(),
+                self.new_position.clone(),
+                self.new_position_mint.clone(),
+                self.new_position_token_account.clone(),
+                self.whirlpool.to_account_info(),
+                self.token_program.to_account_info(),
+                self.system_program.to_account_info(),
+                self.rent.to_account_info(),
+                self.associated_token_program.to_account_info(),
+            ],
+        )?;
+        
+        // Liquidity the withdrawn amounts fund in the new range, at the price the swap left
+        let new_liquidity = get_liquidity_for_amounts(
+            load_whirlpool_state(&self.whirlpool.to_account_info())?.sqrt_price,
+            new_lower_sqrt_price,
+            new_upper_sqrt_price,
+            twist_amount,
+            usdc_amount,
+        )?;
+        require!(new_liquidity > 0, TwistError::InsufficientLiquidity);
+        
+        // Step 4: Redeposit into the new position
+        let new_position = self.position_cpi(
+            self.new_position,
+            self.new_position_token_account.clone(),
+            self.new_tick_array_lower,
+            self.new_tick_array_upper,
+        );
+        new_position.increase_liquidity(
+            &twist_account,
+            &usdc_account,
+            new_liquidity,
+            twist_amount,
+            usdc_amount,
+            signer_seeds,
+        )?;
+        
+        // Update position state
+        let liquidity_position = self.liquidity_position;
+        liquidity_position.position_mint = self.new_position_mint.key();
+        liquidity_position.lower_tick = new_lower_tick;
+        liquidity_position.upper_tick = new_upper_tick;
+        liquidity_position.last_rebalance_timestamp = timestamp;
+        liquidity_position.rebalance_count = liquidity_position.rebalance_count.saturating_add(1);
+        liquidity_position.liquidity = new_liquidity;
+        
+        // Emit event
+        emit!(PositionRebalanced {
+            position_index: self.position_index,
+            old_lower_tick,
+            old_upper_tick,
+            new_lower_tick,
+            new_upper_tick,
+            old_liquidity,
+            new_liquidity,
+            timestamp,
+        });
+        
+        Ok(new_liquidity)
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(position_index: u8)]
+pub struct InitializeRangeStrategy<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.authority == authority.key() @ TwistError::Unauthorized
+    )]
+    pub program_state: Box<Account<'info, ProgramState>>,
+    
+    #[account(
+        seeds = [LIQUIDITY_POSITION_SEED, &position_index.to_le_bytes()],
+        bump = liquidity_position.bump,
+        constraint = liquidity_position.whirlpool == program_state.whirlpool @ TwistError::InvalidAccount
+    )]
+    pub liquidity_position: Account<'info, LiquidityPosition>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = RangeStrategy::LEN,
+        seeds = [RANGE_STRATEGY_SEED, &position_index.to_le_bytes()],
+        bump
+    )]
+    pub range_strategy: Account<'info, RangeStrategy>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_range_strategy_handler(
+    ctx: Context<InitializeRangeStrategy>,
+    position_index: u8,
+    params: RangeStrategyParams,
+) -> Result<()> {
+    require!(
+        params.is_valid(),
+        TwistError::InvalidAmount
+    );
+    
+    let range_strategy = &mut ctx.accounts.range_strategy;
+    range_strategy.position_index = position_index;
+    range_strategy.liquidity_position = ctx.accounts.liquidity_position.key();
+    range_strategy.last_rebalance_timestamp = ctx.accounts.liquidity_position.last_rebalance_timestamp;
+    range_strategy.keeper_rebalance_count = 0;
+    range_strategy.bump = ctx.bumps.range_strategy;
+    range_strategy.apply(&params);
+    
+    msg!("Range strategy initialized for position {}", position_index);
+    msg!("Width: {} ticks, recenter past {} bps of half-width",
+        params.range_width_ticks,
+        params.recenter_threshold_bps
+    );
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(position_index: u8)]
+pub struct UpdateRangeStrategy<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.authority == authority.key() @ TwistError::Unauthorized
+    )]
+    pub program_state: Box<Account<'info, ProgramState>>,
+    
+    #[account(
+        mut,
+        seeds = [RANGE_STRATEGY_SEED, &position_index.to_le_bytes()],
+        bump = range_strategy.bump,
+    )]
+    pub range_strategy: Account<'info, RangeStrategy>,
+}
+
+pub fn update_range_strategy_handler(
+    ctx: Context<UpdateRangeStrategy>,
+    position_index: u8,
+    params: RangeStrategyParams,
+) -> Result<()> {
+    require!(
+        params.is_valid(),
+        TwistError::InvalidAmount
+    );
+    
+    let range_strategy = &mut ctx.accounts.range_strategy;
+    range_strategy.apply(&params);
+    
+    msg!("Range strategy for position {} updated", position_index);
+    msg!("Enabled: {}, width: {} ticks, interval: {}s",
+        params.enabled,
+        params.range_width_ticks,
+        params.min_rebalance_interval
+    );
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(position_index: u8)]
+pub struct KeeperRebalancePosition<'info> {
+    /// Anyone may rebalance once the strategy's triggers are met; the keeper funds
+    /// rent for the new position
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = !program_state.emergency_pause @ TwistError::EmergencyPauseActive,
+        constraint = !program_state.circuit_breaker_active @ TwistError::CircuitBreakerActive,
+    )]
+    pub program_state: Box<Account<'info, ProgramState>>,
+    
+    #[account(
+        mut,
+        seeds = [RANGE_STRATEGY_SEED, &position_index.to_le_bytes()],
+        bump = range_strategy.bump,
+        constraint = range_strategy.liquidity_position == liquidity_position.key() @ TwistError::InvalidAccount
+    )]
+    pub range_strategy: Account<'info, RangeStrategy>,
+    
+    #[account(
+        mut,
+        seeds = [LIQUIDITY_POSITION_SEED, &position_index.to_le_bytes()],
+        bump = liquidity_position.bump,
+        constraint = liquidity_position.whirlpool == whirlpool.key() @ TwistError::InvalidAccount
+    )]
+    pub liquidity_position: Account<'info, LiquidityPosition>,
+    
+    #[account(
+        mut,
+        constraint = whirlpool.key() == program_state.whirlpool @ TwistError::InvalidAccount
+    )]
+    pub whirlpool: Box<Account<'info, WhirlpoolState>>,
+    
+    /// CHECK: Pool TWIST vault, checked against the whirlpool
+    #[account(mut, address = whirlpool.token_vault_a @ TwistError::InvalidAccount)]
+    pub token_vault_a: AccountInfo<'info>,
+    
+    /// CHECK: Pool USDC vault, checked against the whirlpool
+    #[account(mut, address = whirlpool.token_vault_b @ TwistError::InvalidAccount)]
+    pub token_vault_b: AccountInfo<'info>,
+    
+    // Program-state-owned accounts the position pays into and is funded from
+    #[account(
+        mut,
+        token::mint = whirlpool.token_mint_a,
+        token::authority = program_state,
+    )]
+    pub twist_account: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        token::mint = whirlpool.token_mint_b,
+        token::authority = program_state,
+    )]
+    pub usdc_account: Box<Account<'info, TokenAccount>>,
+    
+    // Required when the position belongs to its LP vault, whose idle accounts must then
+    // be the TWIST and USDC accounts above
+    #[account(
+        seeds = [LP_VAULT_SEED, &position_index.to_le_bytes()],
+        bump = lp_vault.bump,
+    )]
+    pub lp_vault: Option<Box<Account<'info, LpVault>>>,
+    
+    /// CHECK: The Whirlpool position of `liquidity_position`, checked by address
+    #[account(
+        mut,
+        address = whirlpool_position_address(&liquidity_position.position_mint).0 @ TwistError::InvalidAccount
+    )]
+    pub position: AccountInfo<'info>,
+    
+    /// CHECK: NFT mint of `liquidity_position`, burned when the emptied position is closed
+    #[account(mut, address = liquidity_position.position_mint @ TwistError::InvalidAccount)]
+    pub position_mint: AccountInfo<'info>,
+    
+    #[account(
+        mut,
+        constraint = position_token_account.mint == liquidity_position.position_mint @ TwistError::InvalidAccount,
+        constraint = position_token_account.owner == program_state.key() @ TwistError::InvalidAccount,
+        constraint = position_token_account.amount == 1 @ TwistError::InvalidAccount,
+    )]
+    pub position_token_account: Box<Account<'info, TokenAccount>>,
+    
+    /// CHECK: Old tick array lower
+    #[account(mut)]
+    pub old_tick_array_lower: AccountInfo<'info>,
+    
+    /// CHECK: Old tick array upper
+    #[account(mut)]
+    pub old_tick_array_upper: AccountInfo<'info>,
+    
+    /// CHECK: Whirlpool position for the new range, created by CPI
+    #[account(
+        mut,
+        address = whirlpool_position_address(&new_position_mint.key()).0 @ TwistError::InvalidAccount
+    )]
+    pub new_position: AccountInfo<'info>,
+    
+    /// Fresh mint for the new position NFT
+    #[account(mut)]
+    pub new_position_mint: Signer<'info>,
+    
+    /// CHECK: Program state's token account for the new position NFT, created by CPI
+    #[account(
+        mut,
+        address = get_associated_token_address(&program_state.key(), &new_position_mint.key()) @ TwistError::InvalidAccount
+    )]
+    pub new_position_token_account: AccountInfo<'info>,
+    
+    /// CHECK: New tick array lower
+    #[account(mut)]
+    pub new_tick_array_lower: AccountInfo<'info>,
+    
+    /// CHECK: New tick array upper
+    #[account(mut)]
+    pub new_tick_array_upper: AccountInfo<'info>,
+    
+    /// CHECK: Orca Whirlpool program
+    #[account(address = ORCA_WHIRLPOOL_PROGRAM_ID @ TwistError::InvalidAccount)]
+    pub whirlpool_program: AccountInfo<'info>,
+    
+    // Venues the swap leg may route through
+    #[account(
+        seeds = [DEX_REGISTRY_SEED],
+        bump = dex_registry.bump,
+    )]
+    pub dex_registry: Box<Account<'info, DexRegistry>>,
+    
+    #[account(
+        mut,
+        seeds = [DEX_ORACLE_SEED],
+        bump = dex_oracle.bump,
+    )]
+    pub dex_oracle: Option<Box<Account<'info, DexOracle>>>,
+    
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// Remaining accounts: one account group per registered venue the recentering swap may
+/// route through
+pub fn keeper_rebalance_position_handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, KeeperRebalancePosition<'info>>,
+    position_index: u8,
+) -> Result<()> {
+    let range_strategy = &ctx.accounts.range_strategy;
+    let liquidity_position = &ctx.accounts.liquidity_position;
+    let whirlpool = &ctx.accounts.whirlpool;
+    let clock = Clock::get()?;
+    
+    check_position_funding_accounts(
+        position_index,
+        liquidity_position,
+        &ctx.accounts.lp_vault,
+        ctx.accounts.twist_account.key(),
+        ctx.accounts.usdc_account.key(),
     )?;
     
-    // Get amounts after removal
-    let twist_amount = ctx.accounts.twist_vault.amount;
-    let usdc_amount = ctx.accounts.usdc_vault.amount;
-    
-    // Step 2: Add liquidity to new position
-    let add_liquidity_ix = build_orca_increase_liquidity_ix(
-        &ctx.accounts.whirlpool_program.key(),
-        &ctx.accounts.whirlpool.key(),
-        &ctx.accounts.authority.key(),
-        liquidity_position.position_mint,
-        &ctx.accounts.new_tick_array_lower.key(),
-        &ctx.accounts.new_tick_array_upper.key(),
-        new_lower_tick,
-        new_upper_tick,
-        twist_amount,
-        usdc_amount,
-        params.max_slippage_bps,
+    observe_pool(&mut ctx.accounts.dex_oracle, &[ctx.accounts.whirlpool.to_account_info()], clock.unix_timestamp)?;
+    
+    require!(range_strategy.enabled, TwistError::StrategyDisabled);
+    require!(
+        range_strategy.can_rebalance(clock.unix_timestamp),
+        TwistError::RebalanceTooSoon
     );
     
-    anchor_lang::solana_program::program::invoke_signed(
-        &add_liquidity_ix,
-        &[
-            ctx.accounts.whirlpool_program.to_account_info(),
-            ctx.accounts.whirlpool.to_account_info(),
-            ctx.accounts.program_state.to_account_info(),
-            ctx.accounts.twist_vault.to_account_info(),
-            ctx.accounts.usdc_vault.to_account_info(),
-            ctx.accounts.new_tick_array_lower.to_account_info(),
-            ctx.accounts.new_tick_array_upper.to_account_info(),
-            ctx.accounts.token_program.to_account_info(),
-        ],
-        signer_seeds,
+    // Anyone can call this, so refuse to recenter on a pool pushed away from the oracle
+    ctx.accounts.program_state.check_pool_price(
+        price_from_sqrt_price(whirlpool.sqrt_price, DECIMALS)?,
+        clock.unix_timestamp,
     )?;
     
-    // Update position state
-    liquidity_position.lower_tick = new_lower_tick;
-    liquidity_position.upper_tick = new_upper_tick;
-    liquidity_position.last_rebalance_timestamp = clock.unix_timestamp;
-    liquidity_position.rebalance_count = liquidity_position.rebalance_count.saturating_add(1);
+    let current_tick = whirlpool.tick_current_index;
+    require!(
+        range_strategy.needs_recenter(
+            current_tick,
+            liquidity_position.lower_tick,
+            liquidity_position.upper_tick,
+        ),
+        TwistError::RebalanceNotNeeded
+    );
     
-    // Calculate new liquidity (would come from Orca response)
-    let new_liquidity = calculate_liquidity_from_amounts(
-        twist_amount,
-        usdc_amount,
+    // Recenter on the current tick, widened outward onto the tick spacing
+    let half_width = range_strategy.range_width_ticks / 2;
+    let new_lower_tick = round_tick_to_spacing(
+        current_tick.saturating_sub(half_width),
+        whirlpool.tick_spacing,
+        false,
+    )?;
+    let new_upper_tick = round_tick_to_spacing(
+        current_tick.saturating_add(half_width),
+        whirlpool.tick_spacing,
+        true,
+    )?;
+    let max_slippage_bps = range_strategy.max_slippage_bps;
+    
+    let new_liquidity = RangeRebalance {
+        position_index,
+        program_state: &ctx.accounts.program_state,
+        liquidity_position: &mut ctx.accounts.liquidity_position,
+        whirlpool: &ctx.accounts.whirlpool,
+        token_vault_a: &ctx.accounts.token_vault_a,
+        token_vault_b: &ctx.accounts.token_vault_b,
+        twist_account: &mut ctx.accounts.twist_account,
+        usdc_account: &mut ctx.accounts.usdc_account,
+        position: &ctx.accounts.position,
+        position_token_account: &ctx.accounts.position_token_account,
+        position_mint: &ctx.accounts.position_mint,
+        old_tick_array_lower: &ctx.accounts.old_tick_array_lower,
+        old_tick_array_upper: &ctx.accounts.old_tick_array_upper,
+        new_position: &ctx.accounts.new_position,
+        new_position_mint: &ctx.accounts.new_position_mint,
+        new_position_token_account: &ctx.accounts.new_position_token_account,
+        new_tick_array_lower: &ctx.accounts.new_tick_array_lower,
+        new_tick_array_upper: &ctx.accounts.new_tick_array_upper,
+        dex_registry: &ctx.accounts.dex_registry,
+        swap_accounts: ctx.remaining_accounts,
+        funder: &ctx.accounts.keeper,
+        whirlpool_program: &ctx.accounts.whirlpool_program,
+        token_program: &ctx.accounts.token_program,
+        associated_token_program: &ctx.accounts.associated_token_program,
+        system_program: &ctx.accounts.system_program,
+        rent: &ctx.accounts.rent,
+    }.execute(
         new_lower_tick,
         new_upper_tick,
-        current_sqrt_price,
+        max_slippage_bps,
+        clock.unix_timestamp,
     )?;
-    liquidity_position.liquidity = new_liquidity;
     
-    // Emit event
-    emit!(PositionRebalanced {
+    let range_strategy = &mut ctx.accounts.range_strategy;
+    range_strategy.last_rebalance_timestamp = clock.unix_timestamp;
+    range_strategy.keeper_rebalance_count = range_strategy.keeper_rebalance_count.saturating_add(1);
+    
+    emit!(KeeperRebalanceExecuted {
         position_index,
-        old_lower_tick: liquidity_position.lower_tick,
-        old_upper_tick: liquidity_position.upper_tick,
+        keeper: ctx.accounts.keeper.key(),
+        current_tick,
         new_lower_tick,
         new_upper_tick,
-        old_liquidity,
         new_liquidity,
         timestamp: clock.unix_timestamp,
     });
     
-    msg!("Position {} rebalanced successfully", position_index);
-    msg!("New range: ${} - ${}", 
-        params.new_lower_price as f64 / 1e6,
-        params.new_upper_price as f64 / 1e6
-    );
-    msg!("New liquidity: {}", new_liquidity);
+    msg!("Keeper recentered position {} on tick {}", position_index, current_tick);
+    msg!("New range: ticks {} - {}", new_lower_tick, new_upper_tick);
     
     Ok(())
 }
@@ -980,33 +2095,70 @@ pub struct AutoCompound<'info> {
     )]
     pub whirlpool: Box<Account<'info, WhirlpoolState>>,
     
+    /// CHECK: Pool TWIST vault, checked against the whirlpool
+    #[account(mut, address = whirlpool.token_vault_a @ TwistError::InvalidAccount)]
+    pub token_vault_a: AccountInfo<'info>,
+    
+    /// CHECK: Pool USDC vault, checked against the whirlpool
+    #[account(mut, address = whirlpool.token_vault_b @ TwistError::InvalidAccount)]
+    pub token_vault_b: AccountInfo<'info>,
+    
+    // Program-state-owned accounts the fees are collected into and compounded from
     #[account(
         mut,
-        constraint = twist_vault.key() == whirlpool.token_vault_a @ TwistError::InvalidAccount
+        token::mint = whirlpool.token_mint_a,
+        token::authority = program_state,
     )]
-    pub twist_vault: Account<'info, TokenAccount>,
+    pub twist_account: Box<Account<'info, TokenAccount>>,
     
     #[account(
         mut,
-        constraint = usdc_vault.key() == whirlpool.token_vault_b @ TwistError::InvalidAccount
+        token::mint = whirlpool.token_mint_b,
+        token::authority = program_state,
+    )]
+    pub usdc_account: Box<Account<'info, TokenAccount>>,
+    
+    // Required when the position belongs to its LP vault, whose idle accounts must then
+    // be the TWIST and USDC accounts above
+    #[account(
+        seeds = [LP_VAULT_SEED, &position_index.to_le_bytes()],
+        bump = lp_vault.bump,
     )]
-    pub usdc_vault: Account<'info, TokenAccount>,
+    pub lp_vault: Option<Box<Account<'info, LpVault>>>,
     
+    /// CHECK: The Whirlpool position of `liquidity_position`, checked by address
     #[account(
         mut,
-        constraint = fee_account.mint == program_state.mint @ TwistError::InvalidMintAuthority
+        address = whirlpool_position_address(&liquidity_position.position_mint).0 @ TwistError::InvalidAccount
+    )]
+    pub position: AccountInfo<'info>,
+    
+    #[account(
+        constraint = position_token_account.mint == liquidity_position.position_mint @ TwistError::InvalidAccount,
+        constraint = position_token_account.owner == program_state.key() @ TwistError::InvalidAccount,
+        constraint = position_token_account.amount == 1 @ TwistError::InvalidAccount,
     )]
-    pub fee_account: Account<'info, TokenAccount>,
+    pub position_token_account: Box<Account<'info, TokenAccount>>,
     
     /// CHECK: Tick array lower
+    #[account(mut)]
     pub tick_array_lower: AccountInfo<'info>,
     
     /// CHECK: Tick array upper
+    #[account(mut)]
     pub tick_array_upper: AccountInfo<'info>,
     
     /// CHECK: Orca Whirlpool program
+    #[account(address = ORCA_WHIRLPOOL_PROGRAM_ID @ TwistError::InvalidAccount)]
     pub whirlpool_program: AccountInfo<'info>,
     
+    #[account(
+        mut,
+        seeds = [DEX_ORACLE_SEED],
+        bump = dex_oracle.bump,
+    )]
+    pub dex_oracle: Option<Box<Account<'info, DexOracle>>>,
+    
     pub token_program: Program<'info, Token>,
 }
 
@@ -1018,6 +2170,16 @@ pub fn auto_compound_handler(
     let whirlpool = &ctx.accounts.whirlpool;
     let clock = Clock::get()?;
     
+    check_position_funding_accounts(
+        position_index,
+        liquidity_position,
+        &ctx.accounts.lp_vault,
+        ctx.accounts.twist_account.key(),
+        ctx.accounts.usdc_account.key(),
+    )?;
+    
+    observe_pool(&mut ctx.accounts.dex_oracle, &[ctx.accounts.whirlpool.to_account_info()], clock.unix_timestamp)?;
+    
     // Check if enough time has passed since last compound (minimum 1 hour)
     let time_since_last_compound = clock.unix_timestamp - liquidity_position.last_compound_timestamp;
     require!(
@@ -1025,44 +2187,39 @@ pub fn auto_compound_handler(
         TwistError::CompoundTooSoon
     );
     
-    // Step 1: Collect fees from position
-    let collect_fees_ix = build_orca_collect_fees_ix(
-        &ctx.accounts.whirlpool_program.key(),
-        &ctx.accounts.whirlpool.key(),
-        &ctx.accounts.authority.key(),
-        liquidity_position.position_mint,
-        &ctx.accounts.tick_array_lower.key(),
-        &ctx.accounts.tick_array_upper.key(),
-    );
-    
     let seeds = &[
         PROGRAM_STATE_SEED,
         &[ctx.accounts.program_state.bump],
     ];
-    let signer_seeds = &[&seeds[..]];   
+    let signer_seeds = &[&seeds[..]];
+    
+    let position_cpi = WhirlpoolPositionCpi {
+        whirlpool_program: ctx.accounts.whirlpool_program.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info(),
+        whirlpool: ctx.accounts.whirlpool.to_account_info(),
+        position_authority: ctx.accounts.program_state.to_account_info(),
+        position: ctx.accounts.position.to_account_info(),
+        position_token_account: ctx.accounts.position_token_account.to_account_info(),
+        token_vault_a: ctx.accounts.token_vault_a.to_account_info(),
+        token_vault_b: ctx.accounts.token_vault_b.to_account_info(),
+        tick_array_lower: ctx.accounts.tick_array_lower.to_account_info(),
+        tick_array_upper: ctx.accounts.tick_array_upper.to_account_info(),
+    };
+    let twist_account = ctx.accounts.twist_account.to_account_info();
+    let usdc_account = ctx.accounts.usdc_account.to_account_info();
     
     // Get balances before collection
-    let twist_before = ctx.accounts.fee_account.amount;
-    let usdc_before = ctx.accounts.usdc_vault.amount;
+    let twist_before = ctx.accounts.twist_account.amount;
+    let usdc_before = ctx.accounts.usdc_account.amount;
     
-    anchor_lang::solana_program::program::invoke_signed(
-        &collect_fees_ix,
-        &[
-            ctx.accounts.whirlpool_program.to_account_info(),
-            ctx.accounts.whirlpool.to_account_info(),
-            ctx.accounts.program_state.to_account_info(),
-            ctx.accounts.fee_account.to_account_info(),
-            ctx.accounts.usdc_vault.to_account_info(),
-            ctx.accounts.tick_array_lower.to_account_info(),
-            ctx.accounts.tick_array_upper.to_account_info(),
-            ctx.accounts.token_program.to_account_info(),
-        ],
-        signer_seeds,
-    )?;
+    // Step 1: Collect fees from position
+    position_cpi.collect_fees(&twist_account, &usdc_account, true, signer_seeds)?;
     
     // Calculate collected fees
-    let twist_fees = ctx.accounts.fee_account.amount - twist_before;
-    let usdc_fees = ctx.accounts.usdc_vault.amount - usdc_before;
+    ctx.accounts.twist_account.reload()?;
+    ctx.accounts.usdc_account.reload()?;
+    let twist_fees = ctx.accounts.twist_account.amount.saturating_sub(twist_before);
+    let usdc_fees = ctx.accounts.usdc_account.amount.saturating_sub(usdc_before);
     
     // Only compound if we collected meaningful fees
     require!(
@@ -1070,43 +2227,23 @@ pub fn auto_compound_handler(
         TwistError::NoFeesToCompound  
     );
     
-    // Step 2: Add collected fees back to position as liquidity
-    let add_liquidity_ix = build_orca_increase_liquidity_ix(
-        &ctx.accounts.whirlpool_program.key(),
-        &ctx.accounts.whirlpool.key(),
-        &ctx.accounts.authority.key(),
-        liquidity_position.position_mint,
-        &ctx.accounts.tick_array_lower.key(),
-        &ctx.accounts.tick_array_upper.key(),
-        liquidity_position.lower_tick,
-        liquidity_position.upper_tick,
+    // Liquidity the collected fees fund in the existing range
+    let additional_liquidity = get_liquidity_for_amounts(
+        whirlpool.sqrt_price,
+        sqrt_price_from_tick_index(liquidity_position.lower_tick)?,
+        sqrt_price_from_tick_index(liquidity_position.upper_tick)?,
         twist_fees,
         usdc_fees,
-        100, // 1% slippage tolerance
-    );
-    
-    anchor_lang::solana_program::program::invoke_signed(
-        &add_liquidity_ix,
-        &[
-            ctx.accounts.whirlpool_program.to_account_info(),
-            ctx.accounts.whirlpool.to_account_info(),
-            ctx.accounts.program_state.to_account_info(),
-            ctx.accounts.fee_account.to_account_info(),
-            ctx.accounts.usdc_vault.to_account_info(),
-            ctx.accounts.tick_array_lower.to_account_info(),
-            ctx.accounts.tick_array_upper.to_account_info(),
-            ctx.accounts.token_program.to_account_info(),
-        ],
-        signer_seeds,
     )?;
     
-    // Calculate additional liquidity from fees
-    let additional_liquidity = calculate_liquidity_from_amounts(
+    // Step 2: Add collected fees back to position as liquidity
+    position_cpi.increase_liquidity(
+        &twist_account,
+        &usdc_account,
+        additional_liquidity,
         twist_fees,
         usdc_fees,
-        liquidity_position.lower_tick,
-        liquidity_position.upper_tick,
-        whirlpool.sqrt_price,
+        signer_seeds,
     )?;
     
     // Update position state
@@ -1148,91 +2285,6 @@ pub fn auto_compound_handler(
     Ok(())
 }
 
-// Helper functions
-
-fn price_to_tick(price: u64, tick_spacing: u16) -> Result<i32> {
-    // Convert price to tick index
-    // This is a simplified version - real implementation would use Orca's math
-    let price_f64 = price as f64 / 1e6;
-    let tick_f64 = (price_f64.ln() / 1.0001_f64.ln()) as i32;
-    
-    // Round to nearest valid tick
-    let rounded_tick = (tick_f64 / tick_spacing as i32) * tick_spacing as i32;
-    
-    Ok(rounded_tick)
-}
-
-fn calculate_liquidity_from_amounts(
-    amount_a: u64,
-    amount_b: u64,
-    _lower_tick: i32,
-    _upper_tick: i32,
-    _sqrt_price: u128,
-) -> Result<u128> {
-    // Simplified liquidity calculation
-    // Real implementation would use Orca's liquidity math
-    let sqrt_product = ((amount_a as u128).saturating_mul(amount_b as u128) as f64).sqrt();
-    let liquidity = (sqrt_product as u128).saturating_mul(100); // Scale factor
-    
-    Ok(liquidity)
-}
-
-fn build_orca_decrease_liquidity_ix(
-    _program_id: &Pubkey,
-    _whirlpool: &Pubkey,
-    _position_mint: Pubkey,
-    _tick_array_lower: &Pubkey,
-    _tick_array_upper: &Pubkey,
-    _liquidity: u128,
-    _min_amount_a: u64,
-    _min_amount_b: u64,
-) -> anchor_lang::solana_program::instruction::Instruction {
-    // Build Orca decrease liquidity instruction
-    // This would use actual Orca instruction builder
-    anchor_lang::solana_program::instruction::Instruction {
-        program_id: crate::defi::ORCA_WHIRLPOOL_PROGRAM_ID,
-        accounts: vec![],
-        data: vec![],
-    }
-}
-
-fn build_orca_collect_fees_ix(
-    _program_id: &Pubkey,
-    _whirlpool: &Pubkey,
-    _authority: &Pubkey,
-    _position_mint: Pubkey,
-    _tick_array_lower: &Pubkey,
-    _tick_array_upper: &Pubkey,
-) -> anchor_lang::solana_program::instruction::Instruction {
-    // Build Orca collect fees instruction
-    anchor_lang::solana_program::instruction::Instruction {
-        program_id: crate::defi::ORCA_WHIRLPOOL_PROGRAM_ID,
-        accounts: vec![],
-        data: vec![],
-    }
-}
-
-fn build_orca_increase_liquidity_ix(
-    _program_id: &Pubkey,
-    _whirlpool: &Pubkey,
-    _authority: &Pubkey,
-    _position_mint: Pubkey,
-    _tick_array_lower: &Pubkey,
-    _tick_array_upper: &Pubkey,
-    _lower_tick: i32,
-    _upper_tick: i32,
-    _twist_amount: u64,
-    _usdc_amount: u64,
-    _max_slippage_bps: u16,
-) -> anchor_lang::solana_program::instruction::Instruction {
-    // Build Orca increase liquidity instruction
-    anchor_lang::solana_program::instruction::Instruction {
-        program_id: crate::defi::ORCA_WHIRLPOOL_PROGRAM_ID,
-        accounts: vec![],
-        data: vec![],
-    }
-}
-
 // New events for advanced liquidity management
 #[event]
 pub struct PositionRebalanced {
@@ -1254,4 +2306,15 @@ pub struct FeesCompounded {
     pub additional_liquidity: u128,
     pub new_total_liquidity: u128,
     pub timestamp: i64,
+}
+
+#[event]
+pub struct KeeperRebalanceExecuted {
+    pub position_index: u8,
+    pub keeper: Pubkey,
+    pub current_tick: i32,
+    pub new_lower_tick: i32,
+    pub new_upper_tick: i32,
+    pub new_liquidity: u128,
+    pub timestamp: i64,
 }
\ No newline at end of file
@@ -0,0 +1,732 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::constants::*;
+use crate::defi::{load_whirlpool_position, load_whirlpool_state};
+use crate::errors::TwistError;
+use crate::events::*;
+use crate::state::*;
+use crate::utils::{transfer_tokens, transfer_tokens_with_signer};
+
+#[derive(Accounts)]
+pub struct InitializeLiquidityMining<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [PROGRAM_STATE_SEED],
+        bump,
+        constraint = program_state.authority == authority.key() @ TwistError::Unauthorized,
+        constraint = program_state.whirlpool_initialized @ TwistError::InvalidAccount,
+    )]
+    pub program_state: Box<Account<'info, ProgramState>>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = LiquidityMining::LEN,
+        seeds = [LIQUIDITY_MINING_SEED],
+        bump
+    )]
+    pub liquidity_mining: Box<Account<'info, LiquidityMining>>,
+    
+    #[account(
+        init,
+        payer = authority,
+        seeds = [MINING_REWARDS_VAULT_SEED],
+        bump,
+        token::mint = mint,
+        token::authority = liquidity_mining,
+    )]
+    pub rewards_vault: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        constraint = mint.key() == program_state.mint @ TwistError::InvalidAccount,
+    )]
+    pub mint: Account<'info, Mint>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn initialize_liquidity_mining_handler(
+    ctx: Context<InitializeLiquidityMining>,
+    params: MiningScheduleParams,
+) -> Result<()> {
+    require!(
+        params.is_valid(),
+        TwistError::InvalidAmount
+    );
+    
+    let liquidity_mining = &mut ctx.accounts.liquidity_mining;
+    liquidity_mining.bump = ctx.bumps.liquidity_mining;
+    liquidity_mining.whirlpool = ctx.accounts.program_state.whirlpool;
+    liquidity_mining.rewards_vault = ctx.accounts.rewards_vault.key();
+    liquidity_mining.apply(&params);
+    liquidity_mining.reward_per_liquidity = 0;
+    liquidity_mining.total_weighted_liquidity = 0;
+    liquidity_mining.last_update_time = Clock::get()?.unix_timestamp;
+    liquidity_mining.total_emitted = 0;
+    liquidity_mining.total_claimed = 0;
+    liquidity_mining.registered_positions = 0;
+    
+    msg!("Liquidity mining initialized, rewards vault: {}", liquidity_mining.rewards_vault);
+    msg!("Emitting {} TWIST per second from {} to {}",
+        params.emission_rate as f64 / 10f64.powf(DECIMALS as f64),
+        params.start_time,
+        params.end_time
+    );
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetMiningSchedule<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [PROGRAM_STATE_SEED],
+        bump,
+        constraint = program_state.authority == authority.key() @ TwistError::Unauthorized,
+    )]
+    pub program_state: Box<Account<'info, ProgramState>>,
+    
+    #[account(
+        mut,
+        seeds = [LIQUIDITY_MINING_SEED],
+        bump = liquidity_mining.bump,
+    )]
+    pub liquidity_mining: Box<Account<'info, LiquidityMining>>,
+}
+
+pub fn set_mining_schedule_handler(
+    ctx: Context<SetMiningSchedule>,
+    params: MiningScheduleParams,
+) -> Result<()> {
+    let liquidity_mining = &mut ctx.accounts.liquidity_mining;
+    let clock = Clock::get()?;
+    
+    require!(
+        params.is_valid(),
+        TwistError::InvalidAmount
+    );
+    
+    // Emit under the old schedule up to now before switching
+    liquidity_mining.update(clock.unix_timestamp);
+    
+    let old_value = format!("rate={}, start={}, end={}",
+        liquidity_mining.emission_rate,
+        liquidity_mining.start_time,
+        liquidity_mining.end_time
+    );
+    liquidity_mining.apply(&params);
+    
+    emit!(ParameterUpdated {
+        parameter: "mining_schedule".to_string(),
+        old_value,
+        new_value: format!("rate={}, start={}, end={}",
+            params.emission_rate,
+            params.start_time,
+            params.end_time
+        ),
+        updated_by: ctx.accounts.authority.key(),
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct FundLiquidityMining<'info> {
+    pub funder: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = funder_token_account.owner == funder.key() @ TwistError::Unauthorized,
+    )]
+    pub funder_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        seeds = [LIQUIDITY_MINING_SEED],
+        bump = liquidity_mining.bump,
+    )]
+    pub liquidity_mining: Box<Account<'info, LiquidityMining>>,
+    
+    #[account(
+        mut,
+        address = liquidity_mining.rewards_vault @ TwistError::InvalidAccount,
+    )]
+    pub rewards_vault: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn fund_liquidity_mining_handler(ctx: Context<FundLiquidityMining>, amount: u64) -> Result<()> {
+    require!(amount > 0, TwistError::InvalidAmount);
+    
+    transfer_tokens(
+        &ctx.accounts.funder_token_account,
+        &ctx.accounts.rewards_vault,
+        &ctx.accounts.funder,
+        &ctx.accounts.token_program,
+        amount,
+    )?;
+    ctx.accounts.rewards_vault.reload()?;
+    
+    emit!(LiquidityMiningFunded {
+        funder: ctx.accounts.funder.key(),
+        amount,
+        vault_balance: ctx.accounts.rewards_vault.amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    msg!("Liquidity mining funded with {} TWIST", amount as f64 / 10f64.powf(DECIMALS as f64));
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RegisterMiningPosition<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [LIQUIDITY_MINING_SEED],
+        bump = liquidity_mining.bump,
+    )]
+    pub liquidity_mining: Box<Account<'info, LiquidityMining>>,
+    
+    #[account(
+        init,
+        payer = owner,
+        space = MiningPosition::LEN,
+        seeds = [MINING_POSITION_SEED, position.key().as_ref()],
+        bump
+    )]
+    pub mining_position: Box<Account<'info, MiningPosition>>,
+    
+    /// CHECK: Whirlpool position account - deserialized and verified in handler
+    pub position: AccountInfo<'info>,
+    
+    // Only the holder of the position NFT may register it
+    #[account(
+        constraint = position_token_account.owner == owner.key() @ TwistError::Unauthorized,
+        constraint = position_token_account.amount == 1 @ TwistError::Unauthorized,
+    )]
+    pub position_token_account: Account<'info, TokenAccount>,
+    
+    /// CHECK: The mined whirlpool, deserialized in handler
+    #[account(address = liquidity_mining.whirlpool @ TwistError::InvalidAccount)]
+    pub whirlpool: AccountInfo<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn register_mining_position_handler(ctx: Context<RegisterMiningPosition>) -> Result<()> {
+    let clock = Clock::get()?;
+    
+    let position_info = load_whirlpool_position(&ctx.accounts.position)?;
+    require_keys_eq!(position_info.whirlpool, ctx.accounts.liquidity_mining.whirlpool, TwistError::InvalidAccount);
+    require_keys_eq!(
+        ctx.accounts.position_token_account.mint,
+        position_info.position_mint,
+        TwistError::Unauthorized
+    );
+    let tick_current = load_whirlpool_state(&ctx.accounts.whirlpool)?.tick_current_index;
+    
+    let mining_position = &mut ctx.accounts.mining_position;
+    mining_position.owner = ctx.accounts.owner.key();
+    mining_position.position = ctx.accounts.position.key();
+    mining_position.position_mint = position_info.position_mint;
+    mining_position.lp_vault = Pubkey::default();
+    mining_position.lower_tick = position_info.tick_lower_index;
+    mining_position.upper_tick = position_info.tick_upper_index;
+    mining_position.liquidity = 0;
+    mining_position.weighted_liquidity = 0;
+    mining_position.reward_debt = 0;
+    mining_position.unclaimed_rewards = 0;
+    mining_position.total_claimed = 0;
+    mining_position.bump = ctx.bumps.mining_position;
+    
+    let liquidity_mining = &mut ctx.accounts.liquidity_mining;
+    liquidity_mining.sync_position(mining_position, position_info.liquidity, tick_current, clock.unix_timestamp);
+    liquidity_mining.registered_positions += 1;
+    
+    emit!(MiningPositionRegistered {
+        position: mining_position.position,
+        owner: mining_position.owner,
+        lower_tick: mining_position.lower_tick,
+        upper_tick: mining_position.upper_tick,
+        liquidity: mining_position.liquidity,
+        weighted_liquidity: mining_position.weighted_liquidity,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Mining position {} registered, liquidity {} ({} in range)",
+        mining_position.position,
+        mining_position.liquidity,
+        mining_position.weighted_liquidity
+    );
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RefreshMiningPosition<'info> {
+    /// Anyone may re-weight a position as the pool price moves
+    pub keeper: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [LIQUIDITY_MINING_SEED],
+        bump = liquidity_mining.bump,
+    )]
+    pub liquidity_mining: Box<Account<'info, LiquidityMining>>,
+    
+    #[account(
+        mut,
+        seeds = [MINING_POSITION_SEED, position.key().as_ref()],
+        bump = mining_position.bump,
+        constraint = !mining_position.is_vault_share() @ TwistError::InvalidAccount,
+    )]
+    pub mining_position: Box<Account<'info, MiningPosition>>,
+    
+    /// CHECK: Whirlpool position account, read in handler; may have been closed
+    #[account(address = mining_position.position @ TwistError::InvalidAccount)]
+    pub position: AccountInfo<'info>,
+    
+    /// CHECK: The mined whirlpool, deserialized in handler
+    #[account(address = liquidity_mining.whirlpool @ TwistError::InvalidAccount)]
+    pub whirlpool: AccountInfo<'info>,
+}
+
+/// Positions keep their cached weight until refreshed, and a refresh that finds the price
+/// outside the range pays nothing for the interval since the last one. Keepers should
+/// refresh positions as the price crosses their bounds so emissions go to in-range
+/// liquidity; owners should refresh while in range to lock in what they have earned.
+pub fn refresh_mining_position_handler(ctx: Context<RefreshMiningPosition>) -> Result<()> {
+    let clock = Clock::get()?;
+    
+    let liquidity = live_position_liquidity(&ctx.accounts.position, &ctx.accounts.mining_position)?;
+    let tick_current = load_whirlpool_state(&ctx.accounts.whirlpool)?.tick_current_index;
+    
+    let mining_position = &mut ctx.accounts.mining_position;
+    ctx.accounts.liquidity_mining.sync_position(mining_position, liquidity, tick_current, clock.unix_timestamp);
+    
+    emit!(MiningPositionRefreshed {
+        position: mining_position.position,
+        liquidity: mining_position.liquidity,
+        weighted_liquidity: mining_position.weighted_liquidity,
+        unclaimed_rewards: mining_position.unclaimed_rewards,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClaimMiningRewards<'info> {
+    pub owner: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [LIQUIDITY_MINING_SEED],
+        bump = liquidity_mining.bump,
+    )]
+    pub liquidity_mining: Box<Account<'info, LiquidityMining>>,
+    
+    #[account(
+        mut,
+        seeds = [MINING_POSITION_SEED, position.key().as_ref()],
+        bump = mining_position.bump,
+        constraint = !mining_position.is_vault_share() @ TwistError::InvalidAccount,
+    )]
+    pub mining_position: Box<Account<'info, MiningPosition>>,
+    
+    /// CHECK: Whirlpool position account, read in handler
+    #[account(address = mining_position.position @ TwistError::InvalidAccount)]
+    pub position: AccountInfo<'info>,
+    
+    // Rewards go to whoever holds the position NFT now
+    #[account(
+        constraint = position_token_account.owner == owner.key() @ TwistError::Unauthorized,
+        constraint = position_token_account.mint == mining_position.position_mint @ TwistError::Unauthorized,
+        constraint = position_token_account.amount == 1 @ TwistError::Unauthorized,
+    )]
+    pub position_token_account: Account<'info, TokenAccount>,
+    
+    /// CHECK: The mined whirlpool, deserialized in handler
+    #[account(address = liquidity_mining.whirlpool @ TwistError::InvalidAccount)]
+    pub whirlpool: AccountInfo<'info>,
+    
+    #[account(
+        mut,
+        address = liquidity_mining.rewards_vault @ TwistError::InvalidAccount,
+    )]
+    pub rewards_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        token::mint = rewards_vault.mint,
+    )]
+    pub owner_token_account: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn claim_mining_rewards_handler(ctx: Context<ClaimMiningRewards>) -> Result<()> {
+    let clock = Clock::get()?;
+    
+    let liquidity = live_position_liquidity(&ctx.accounts.position, &ctx.accounts.mining_position)?;
+    let tick_current = load_whirlpool_state(&ctx.accounts.whirlpool)?.tick_current_index;
+    ctx.accounts.liquidity_mining.sync_position(
+        &mut ctx.accounts.mining_position,
+        liquidity,
+        tick_current,
+        clock.unix_timestamp,
+    );
+    
+    pay_mining_rewards(
+        &mut ctx.accounts.liquidity_mining,
+        &mut ctx.accounts.mining_position,
+        &ctx.accounts.rewards_vault,
+        &ctx.accounts.owner_token_account,
+        &ctx.accounts.token_program,
+        ctx.accounts.owner.key(),
+        clock.unix_timestamp,
+    )
+}
+
+#[derive(Accounts)]
+pub struct RegisterVaultMiningPosition<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [LIQUIDITY_MINING_SEED],
+        bump = liquidity_mining.bump,
+    )]
+    pub liquidity_mining: Box<Account<'info, LiquidityMining>>,
+    
+    #[account(
+        init,
+        payer = owner,
+        space = MiningPosition::LEN,
+        seeds = [MINING_POSITION_SEED, share_account.key().as_ref()],
+        bump
+    )]
+    pub mining_position: Box<Account<'info, MiningPosition>>,
+    
+    #[account(
+        seeds = [LP_VAULT_SEED, &lp_vault.position_index.to_le_bytes()],
+        bump = lp_vault.bump,
+    )]
+    pub lp_vault: Box<Account<'info, LpVault>>,
+    
+    #[account(
+        address = lp_vault.liquidity_position @ TwistError::InvalidAccount,
+        constraint = liquidity_position.whirlpool == liquidity_mining.whirlpool @ TwistError::InvalidAccount,
+    )]
+    pub liquidity_position: Box<Account<'info, LiquidityPosition>>,
+    
+    #[account(address = lp_vault.share_mint @ TwistError::InvalidAccount)]
+    pub share_mint: Box<Account<'info, Mint>>,
+    
+    // Shares are mined per holding account, registered by its owner
+    #[account(
+        token::mint = share_mint,
+        constraint = share_account.owner == owner.key() @ TwistError::Unauthorized,
+    )]
+    pub share_account: Box<Account<'info, TokenAccount>>,
+    
+    /// CHECK: The mined whirlpool, deserialized in handler
+    #[account(address = liquidity_mining.whirlpool @ TwistError::InvalidAccount)]
+    pub whirlpool: AccountInfo<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn register_vault_mining_position_handler(ctx: Context<RegisterVaultMiningPosition>) -> Result<()> {
+    let clock = Clock::get()?;
+    
+    let tick_current = load_whirlpool_state(&ctx.accounts.whirlpool)?.tick_current_index;
+    
+    let mining_position = &mut ctx.accounts.mining_position;
+    mining_position.owner = ctx.accounts.owner.key();
+    mining_position.position = ctx.accounts.share_account.key();
+    mining_position.position_mint = ctx.accounts.share_mint.key();
+    mining_position.lp_vault = ctx.accounts.lp_vault.key();
+    mining_position.liquidity = 0;
+    mining_position.weighted_liquidity = 0;
+    mining_position.reward_debt = 0;
+    mining_position.unclaimed_rewards = 0;
+    mining_position.total_claimed = 0;
+    mining_position.bump = ctx.bumps.mining_position;
+    
+    let liquidity = vault_share_liquidity(
+        mining_position,
+        &ctx.accounts.lp_vault,
+        &ctx.accounts.liquidity_position,
+        &ctx.accounts.share_mint,
+        ctx.accounts.share_account.amount,
+    );
+    
+    let liquidity_mining = &mut ctx.accounts.liquidity_mining;
+    liquidity_mining.sync_position(mining_position, liquidity, tick_current, clock.unix_timestamp);
+    liquidity_mining.registered_positions += 1;
+    
+    emit!(MiningPositionRegistered {
+        position: mining_position.position,
+        owner: mining_position.owner,
+        lower_tick: mining_position.lower_tick,
+        upper_tick: mining_position.upper_tick,
+        liquidity: mining_position.liquidity,
+        weighted_liquidity: mining_position.weighted_liquidity,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Vault shares {} registered for mining, liquidity {} ({} in range)",
+        mining_position.position,
+        mining_position.liquidity,
+        mining_position.weighted_liquidity
+    );
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RefreshVaultMiningPosition<'info> {
+    /// Anyone may re-weight vault shares as the pool price or share balance moves
+    pub keeper: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [LIQUIDITY_MINING_SEED],
+        bump = liquidity_mining.bump,
+    )]
+    pub liquidity_mining: Box<Account<'info, LiquidityMining>>,
+    
+    #[account(
+        mut,
+        seeds = [MINING_POSITION_SEED, share_account.key().as_ref()],
+        bump = mining_position.bump,
+        constraint = mining_position.lp_vault == lp_vault.key() @ TwistError::InvalidAccount,
+    )]
+    pub mining_position: Box<Account<'info, MiningPosition>>,
+    
+    pub lp_vault: Box<Account<'info, LpVault>>,
+    
+    #[account(address = lp_vault.liquidity_position @ TwistError::InvalidAccount)]
+    pub liquidity_position: Box<Account<'info, LiquidityPosition>>,
+    
+    #[account(address = lp_vault.share_mint @ TwistError::InvalidAccount)]
+    pub share_mint: Box<Account<'info, Mint>>,
+    
+    /// CHECK: Registered share account, read in handler; may have been closed
+    #[account(address = mining_position.position @ TwistError::InvalidAccount)]
+    pub share_account: AccountInfo<'info>,
+    
+    /// CHECK: The mined whirlpool, deserialized in handler
+    #[account(address = liquidity_mining.whirlpool @ TwistError::InvalidAccount)]
+    pub whirlpool: AccountInfo<'info>,
+}
+
+/// Vault shares are weighted like a direct position and need the same keeper refreshes
+/// as the price crosses the vault's range; see `refresh_mining_position_handler`.
+pub fn refresh_vault_mining_position_handler(ctx: Context<RefreshVaultMiningPosition>) -> Result<()> {
+    let clock = Clock::get()?;
+    
+    let shares = live_share_balance(&ctx.accounts.share_account)?;
+    let tick_current = load_whirlpool_state(&ctx.accounts.whirlpool)?.tick_current_index;
+    
+    let mining_position = &mut ctx.accounts.mining_position;
+    let liquidity = vault_share_liquidity(
+        mining_position,
+        &ctx.accounts.lp_vault,
+        &ctx.accounts.liquidity_position,
+        &ctx.accounts.share_mint,
+        shares,
+    );
+    ctx.accounts.liquidity_mining.sync_position(mining_position, liquidity, tick_current, clock.unix_timestamp);
+    
+    emit!(MiningPositionRefreshed {
+        position: mining_position.position,
+        liquidity: mining_position.liquidity,
+        weighted_liquidity: mining_position.weighted_liquidity,
+        unclaimed_rewards: mining_position.unclaimed_rewards,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClaimVaultMiningRewards<'info> {
+    pub owner: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [LIQUIDITY_MINING_SEED],
+        bump = liquidity_mining.bump,
+    )]
+    pub liquidity_mining: Box<Account<'info, LiquidityMining>>,
+    
+    #[account(
+        mut,
+        seeds = [MINING_POSITION_SEED, share_account.key().as_ref()],
+        bump = mining_position.bump,
+        constraint = mining_position.lp_vault == lp_vault.key() @ TwistError::InvalidAccount,
+    )]
+    pub mining_position: Box<Account<'info, MiningPosition>>,
+    
+    pub lp_vault: Box<Account<'info, LpVault>>,
+    
+    #[account(address = lp_vault.liquidity_position @ TwistError::InvalidAccount)]
+    pub liquidity_position: Box<Account<'info, LiquidityPosition>>,
+    
+    #[account(address = lp_vault.share_mint @ TwistError::InvalidAccount)]
+    pub share_mint: Box<Account<'info, Mint>>,
+    
+    // Rewards go to whoever owns the share account now
+    #[account(
+        address = mining_position.position @ TwistError::InvalidAccount,
+        constraint = share_account.owner == owner.key() @ TwistError::Unauthorized,
+    )]
+    pub share_account: Box<Account<'info, TokenAccount>>,
+    
+    /// CHECK: The mined whirlpool, deserialized in handler
+    #[account(address = liquidity_mining.whirlpool @ TwistError::InvalidAccount)]
+    pub whirlpool: AccountInfo<'info>,
+    
+    #[account(
+        mut,
+        address = liquidity_mining.rewards_vault @ TwistError::InvalidAccount,
+    )]
+    pub rewards_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        token::mint = rewards_vault.mint,
+    )]
+    pub owner_token_account: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn claim_vault_mining_rewards_handler(ctx: Context<ClaimVaultMiningRewards>) -> Result<()> {
+    let clock = Clock::get()?;
+    
+    let tick_current = load_whirlpool_state(&ctx.accounts.whirlpool)?.tick_current_index;
+    let liquidity = vault_share_liquidity(
+        &mut ctx.accounts.mining_position,
+        &ctx.accounts.lp_vault,
+        &ctx.accounts.liquidity_position,
+        &ctx.accounts.share_mint,
+        ctx.accounts.share_account.amount,
+    );
+    ctx.accounts.liquidity_mining.sync_position(
+        &mut ctx.accounts.mining_position,
+        liquidity,
+        tick_current,
+        clock.unix_timestamp,
+    );
+    
+    pay_mining_rewards(
+        &mut ctx.accounts.liquidity_mining,
+        &mut ctx.accounts.mining_position,
+        &ctx.accounts.rewards_vault,
+        &ctx.accounts.owner_token_account,
+        &ctx.accounts.token_program,
+        ctx.accounts.owner.key(),
+        clock.unix_timestamp,
+    )
+}
+
+// Transfer a synced position's unclaimed rewards to its current holder
+fn pay_mining_rewards<'info>(
+    liquidity_mining: &mut Account<'info, LiquidityMining>,
+    mining_position: &mut Account<'info, MiningPosition>,
+    rewards_vault: &Account<'info, TokenAccount>,
+    owner_token_account: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+    owner: Pubkey,
+    now: i64,
+) -> Result<()> {
+    // An underfunded vault pays what it holds; the rest stays claimable
+    let amount = mining_position.unclaimed_rewards.min(rewards_vault.amount);
+    require!(amount > 0, TwistError::NoRewardsToClaim);
+    
+    let seeds = &[
+        LIQUIDITY_MINING_SEED,
+        &[liquidity_mining.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+    
+    transfer_tokens_with_signer(
+        rewards_vault,
+        owner_token_account,
+        &liquidity_mining.to_account_info(),
+        token_program,
+        amount,
+        signer_seeds,
+    )?;
+    
+    mining_position.unclaimed_rewards -= amount;
+    mining_position.total_claimed = mining_position.total_claimed.saturating_add(amount);
+    mining_position.owner = owner;
+    
+    liquidity_mining.total_claimed = liquidity_mining.total_claimed.saturating_add(amount);
+    
+    emit!(MiningRewardsClaimed {
+        position: mining_position.position,
+        owner: mining_position.owner,
+        amount,
+        remaining: mining_position.unclaimed_rewards,
+        timestamp: now,
+    });
+    
+    msg!("Claimed {} TWIST of mining rewards for position {}",
+        amount as f64 / 10f64.powf(DECIMALS as f64),
+        mining_position.position
+    );
+    
+    Ok(())
+}
+
+// Live liquidity of a registered position; a closed position counts as empty
+fn live_position_liquidity(position: &AccountInfo, mining_position: &MiningPosition) -> Result<u128> {
+    if position.data_is_empty() {
+        return Ok(0);
+    }
+    
+    let position_info = load_whirlpool_position(position)?;
+    require_keys_eq!(position_info.position_mint, mining_position.position_mint, TwistError::InvalidAccount);
+    Ok(position_info.liquidity)
+}
+
+// Share balance of a registered share account; a closed account holds nothing
+fn live_share_balance(share_account: &AccountInfo) -> Result<u64> {
+    if share_account.data_is_empty() {
+        return Ok(0);
+    }
+    
+    let data = share_account.try_borrow_data()?;
+    Ok(TokenAccount::try_deserialize(&mut &data[..])?.amount)
+}
+
+// Vault position liquidity behind `shares`. The vault's range moves when it is
+// rebalanced, so the mined range follows it.
+fn vault_share_liquidity(
+    mining_position: &mut MiningPosition,
+    lp_vault: &LpVault,
+    liquidity_position: &LiquidityPosition,
+    share_mint: &Mint,
+    shares: u64,
+) -> u128 {
+    mining_position.lower_tick = liquidity_position.lower_tick;
+    mining_position.upper_tick = liquidity_position.upper_tick;
+    pro_rata_liquidity(liquidity_position.liquidity, shares, lp_vault.total_shares(share_mint.supply))
+}
@@ -0,0 +1,948 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Burn, Mint, MintTo, Token, TokenAccount, Transfer};
+
+use crate::constants::*;
+use crate::defi::*;
+use crate::errors::TwistError;
+use crate::events::*;
+use crate::state::*;
+use crate::utils::transfer_tokens_with_signer;
+use crate::instructions::dex_oracle::observe_pool;
+
+#[derive(Accounts)]
+#[instruction(position_index: u8)]
+pub struct InitializeLpVault<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [PROGRAM_STATE_SEED],
+        bump,
+        constraint = program_state.authority == authority.key() @ TwistError::Unauthorized,
+    )]
+    pub program_state: Box<Account<'info, ProgramState>>,
+    
+    // The vault takes over an empty position so every unit of liquidity in it is backed by shares
+    #[account(
+        mut,
+        seeds = [LIQUIDITY_POSITION_SEED, &position_index.to_le_bytes()],
+        bump = liquidity_position.bump,
+        constraint = liquidity_position.whirlpool == whirlpool.key() @ TwistError::InvalidAccount,
+        constraint = liquidity_position.liquidity == 0 @ TwistError::InvalidAccount,
+        constraint = liquidity_position.lower_tick < liquidity_position.upper_tick @ TwistError::InvalidPriceRange,
+    )]
+    pub liquidity_position: Box<Account<'info, LiquidityPosition>>,
+    
+    #[account(
+        constraint = whirlpool.key() == program_state.whirlpool @ TwistError::InvalidAccount
+    )]
+    pub whirlpool: Box<Account<'info, WhirlpoolState>>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = LpVault::LEN,
+        seeds = [LP_VAULT_SEED, &position_index.to_le_bytes()],
+        bump
+    )]
+    pub lp_vault: Box<Account<'info, LpVault>>,
+    
+    #[account(
+        init,
+        payer = authority,
+        seeds = [LP_VAULT_SHARE_MINT_SEED, lp_vault.key().as_ref()],
+        bump,
+        mint::decimals = 6,
+        mint::authority = program_state,
+    )]
+    pub share_mint: Box<Account<'info, Mint>>,
+    
+    #[account(
+        init,
+        payer = authority,
+        seeds = [LP_VAULT_TWIST_SEED, lp_vault.key().as_ref()],
+        bump,
+        token::mint = twist_mint,
+        token::authority = program_state,
+    )]
+    pub vault_twist_account: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        init,
+        payer = authority,
+        seeds = [LP_VAULT_USDC_SEED, lp_vault.key().as_ref()],
+        bump,
+        token::mint = usdc_mint,
+        token::authority = program_state,
+    )]
+    pub vault_usdc_account: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        constraint = twist_mint.key() == whirlpool.token_mint_a @ TwistError::InvalidAccount
+    )]
+    pub twist_mint: Box<Account<'info, Mint>>,
+    
+    #[account(
+        constraint = usdc_mint.key() == whirlpool.token_mint_b @ TwistError::InvalidAccount
+    )]
+    pub usdc_mint: Box<Account<'info, Mint>>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn initialize_lp_vault_handler(
+    ctx: Context<InitializeLpVault>,
+    position_index: u8,
+    params: LpVaultParams,
+) -> Result<()> {
+    require!(
+        params.is_valid(),
+        TwistError::InvalidAmount
+    );
+    
+    let lp_vault = &mut ctx.accounts.lp_vault;
+    lp_vault.bump = ctx.bumps.lp_vault;
+    lp_vault.position_index = position_index;
+    lp_vault.liquidity_position = ctx.accounts.liquidity_position.key();
+    lp_vault.share_mint = ctx.accounts.share_mint.key();
+    lp_vault.twist_account = ctx.accounts.vault_twist_account.key();
+    lp_vault.usdc_account = ctx.accounts.vault_usdc_account.key();
+    lp_vault.apply(&params);
+    lp_vault.fee_shares = 0;
+    lp_vault.last_fee_accrual = Clock::get()?.unix_timestamp;
+    lp_vault.total_fees_paid_twist = 0;
+    lp_vault.total_fees_paid_usdc = 0;
+    
+    ctx.accounts.liquidity_position.owner = lp_vault.key();
+    
+    msg!("LP vault initialized over position {}", position_index);
+    msg!("Deposit cap: {} USDC, management fee: {}% a year",
+        params.deposit_cap as f64 / 1e6,
+        params.management_fee_bps as f64 / 100.0
+    );
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(position_index: u8)]
+pub struct UpdateLpVault<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [PROGRAM_STATE_SEED],
+        bump,
+        constraint = program_state.authority == authority.key() @ TwistError::Unauthorized,
+    )]
+    pub program_state: Box<Account<'info, ProgramState>>,
+    
+    #[account(
+        mut,
+        seeds = [LP_VAULT_SEED, &position_index.to_le_bytes()],
+        bump = lp_vault.bump,
+    )]
+    pub lp_vault: Box<Account<'info, LpVault>>,
+    
+    #[account(address = lp_vault.share_mint @ TwistError::InvalidAccount)]
+    pub share_mint: Box<Account<'info, Mint>>,
+}
+
+pub fn update_lp_vault_handler(
+    ctx: Context<UpdateLpVault>,
+    _position_index: u8,
+    params: LpVaultParams,
+) -> Result<()> {
+    let lp_vault = &mut ctx.accounts.lp_vault;
+    let clock = Clock::get()?;
+    
+    require!(
+        params.is_valid(),
+        TwistError::InvalidAmount
+    );
+    
+    // Charge the old rate up to now before switching
+    lp_vault.accrue_management_fee(ctx.accounts.share_mint.supply, clock.unix_timestamp);
+    
+    let old_value = format!("enabled={}, cap={}, fee={}",
+        lp_vault.deposits_enabled,
+        lp_vault.deposit_cap,
+        lp_vault.management_fee_bps
+    );
+    lp_vault.apply(&params);
+    
+    emit!(ParameterUpdated {
+        parameter: format!("lp_vault_{}", lp_vault.position_index),
+        old_value,
+        new_value: format!("enabled={}, cap={}, fee={}",
+            params.deposits_enabled,
+            params.deposit_cap,
+            params.management_fee_bps
+        ),
+        updated_by: ctx.accounts.authority.key(),
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(position_index: u8)]
+pub struct DepositLpVault<'info> {
+    pub depositor: Signer<'info>,
+    
+    #[account(
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = !program_state.emergency_pause @ TwistError::EmergencyPauseActive,
+        constraint = !program_state.circuit_breaker_active @ TwistError::CircuitBreakerActive,
+    )]
+    pub program_state: Box<Account<'info, ProgramState>>,
+    
+    #[account(
+        mut,
+        seeds = [LP_VAULT_SEED, &position_index.to_le_bytes()],
+        bump = lp_vault.bump,
+    )]
+    pub lp_vault: Box<Account<'info, LpVault>>,
+    
+    #[account(
+        mut,
+        address = lp_vault.liquidity_position @ TwistError::InvalidAccount,
+        constraint = liquidity_position.whirlpool == whirlpool.key() @ TwistError::InvalidAccount
+    )]
+    pub liquidity_position: Box<Account<'info, LiquidityPosition>>,
+    
+    #[account(
+        mut,
+        constraint = whirlpool.key() == program_state.whirlpool @ TwistError::InvalidAccount
+    )]
+    pub whirlpool: Box<Account<'info, WhirlpoolState>>,
+    
+    #[account(mut, address = lp_vault.twist_account @ TwistError::InvalidAccount)]
+    pub vault_twist_account: Box<Account<'info, TokenAccount>>,
+    
+    #[account(mut, address = lp_vault.usdc_account @ TwistError::InvalidAccount)]
+    pub vault_usdc_account: Box<Account<'info, TokenAccount>>,
+    
+    #[account(mut, address = lp_vault.share_mint @ TwistError::InvalidAccount)]
+    pub share_mint: Box<Account<'info, Mint>>,
+    
+    #[account(
+        mut,
+        constraint = user_twist_account.owner == depositor.key() @ TwistError::Unauthorized,
+        constraint = user_twist_account.mint == vault_twist_account.mint @ TwistError::InvalidAccount,
+    )]
+    pub user_twist_account: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        constraint = user_usdc_account.owner == depositor.key() @ TwistError::Unauthorized,
+        constraint = user_usdc_account.mint == vault_usdc_account.mint @ TwistError::InvalidAccount,
+    )]
+    pub user_usdc_account: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        token::mint = share_mint,
+    )]
+    pub user_share_account: Box<Account<'info, TokenAccount>>,
+    
+    /// CHECK: Pool TWIST vault, checked against the whirlpool
+    #[account(mut, address = whirlpool.token_vault_a @ TwistError::InvalidAccount)]
+    pub token_vault_a: AccountInfo<'info>,
+    
+    /// CHECK: Pool USDC vault, checked against the whirlpool
+    #[account(mut, address = whirlpool.token_vault_b @ TwistError::InvalidAccount)]
+    pub token_vault_b: AccountInfo<'info>,
+    
+    /// CHECK: The vault's Whirlpool position, checked by address
+    #[account(
+        mut,
+        address = whirlpool_position_address(&liquidity_position.position_mint).0 @ TwistError::InvalidAccount
+    )]
+    pub position: AccountInfo<'info>,
+    
+    #[account(
+        constraint = position_token_account.mint == liquidity_position.position_mint @ TwistError::InvalidAccount,
+        constraint = position_token_account.owner == program_state.key() @ TwistError::InvalidAccount,
+        constraint = position_token_account.amount == 1 @ TwistError::InvalidAccount,
+    )]
+    pub position_token_account: Box<Account<'info, TokenAccount>>,
+    
+    /// CHECK: Tick array lower
+    #[account(mut)]
+    pub tick_array_lower: AccountInfo<'info>,
+    
+    /// CHECK: Tick array upper
+    #[account(mut)]
+    pub tick_array_upper: AccountInfo<'info>,
+    
+    /// CHECK: Orca Whirlpool program
+    #[account(address = ORCA_WHIRLPOOL_PROGRAM_ID @ TwistError::InvalidAccount)]
+    pub whirlpool_program: AccountInfo<'info>,
+    
+    #[account(
+        mut,
+        seeds = [DEX_ORACLE_SEED],
+        bump = dex_oracle.bump,
+    )]
+    pub dex_oracle: Option<Box<Account<'info, DexOracle>>>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn deposit_lp_vault_handler(
+    ctx: Context<DepositLpVault>,
+    position_index: u8,
+    twist_amount: u64,
+    usdc_amount: u64,
+    min_shares: u64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    
+    observe_pool(&mut ctx.accounts.dex_oracle, &[ctx.accounts.whirlpool.to_account_info()], clock.unix_timestamp)?;
+    
+    require!(ctx.accounts.lp_vault.deposits_enabled, TwistError::VaultDepositsDisabled);
+    require!(
+        twist_amount > 0 || usdc_amount > 0,
+        TwistError::InvalidAmount
+    );
+    
+    let share_supply = ctx.accounts.share_mint.supply;
+    ctx.accounts.lp_vault.accrue_management_fee(share_supply, clock.unix_timestamp);
+    
+    let position_cpi = WhirlpoolPositionCpi {
+        whirlpool_program: ctx.accounts.whirlpool_program.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info(),
+        whirlpool: ctx.accounts.whirlpool.to_account_info(),
+        position_authority: ctx.accounts.program_state.to_account_info(),
+        position: ctx.accounts.position.to_account_info(),
+        position_token_account: ctx.accounts.position_token_account.to_account_info(),
+        token_vault_a: ctx.accounts.token_vault_a.to_account_info(),
+        token_vault_b: ctx.accounts.token_vault_b.to_account_info(),
+        tick_array_lower: ctx.accounts.tick_array_lower.to_account_info(),
+        tick_array_upper: ctx.accounts.tick_array_upper.to_account_info(),
+    };
+    
+    // Price the deposit against the vault as it stands, before any tokens move. Fees the
+    // position has earned belong to the existing holders, so they count toward NAV.
+    position_cpi.update_fees()?;
+    let position_info = load_whirlpool_position(&ctx.accounts.position)?;
+    let nav = vault_nav(
+        &ctx.accounts.program_state,
+        &ctx.accounts.whirlpool,
+        &ctx.accounts.liquidity_position,
+        ctx.accounts.vault_twist_account.amount.saturating_add(position_info.fee_owed_a),
+        ctx.accounts.vault_usdc_account.amount.saturating_add(position_info.fee_owed_b),
+        clock.unix_timestamp,
+    )?;
+    let deposit_value = usdc_amount.saturating_add(ctx.accounts.program_state.twist_to_usdc_value(twist_amount));
+    require!(
+        nav.saturating_add(deposit_value) <= ctx.accounts.lp_vault.deposit_cap,
+        TwistError::VaultDepositCapExceeded
+    );
+    
+    let lp_vault = &ctx.accounts.lp_vault;
+    if lp_vault.total_shares(share_supply) == 0 {
+        require!(
+            deposit_value >= LP_VAULT_MIN_INITIAL_DEPOSIT,
+            TwistError::InvalidAmount
+        );
+    }
+    let shares = lp_vault
+        .shares_for_deposit(deposit_value, nav, share_supply)
+        .ok_or(TwistError::MathOverflow)?;
+    require!(
+        shares > 0 && shares >= min_shares,
+        TwistError::SlippageToleranceExceeded
+    );
+    
+    for (from, to, amount) in [
+        (&ctx.accounts.user_twist_account, &ctx.accounts.vault_twist_account, twist_amount),
+        (&ctx.accounts.user_usdc_account, &ctx.accounts.vault_usdc_account, usdc_amount),
+    ] {
+        if amount > 0 {
+            token::transfer(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: from.to_account_info(),
+                        to: to.to_account_info(),
+                        authority: ctx.accounts.depositor.to_account_info(),
+                    },
+                ),
+                amount,
+            )?;
+        }
+    }
+    
+    let seeds = &[
+        PROGRAM_STATE_SEED,
+        &[ctx.accounts.program_state.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+    
+    // Put the idle balances to work in the position; any unmatched remainder waits in the vault
+    ctx.accounts.vault_twist_account.reload()?;
+    ctx.accounts.vault_usdc_account.reload()?;
+    let idle_twist = ctx.accounts.vault_twist_account.amount;
+    let idle_usdc = ctx.accounts.vault_usdc_account.amount;
+    let liquidity_position = &ctx.accounts.liquidity_position;
+    let added_liquidity = get_liquidity_for_amounts(
+        ctx.accounts.whirlpool.sqrt_price,
+        sqrt_price_from_tick_index(liquidity_position.lower_tick)?,
+        sqrt_price_from_tick_index(liquidity_position.upper_tick)?,
+        idle_twist,
+        idle_usdc,
+    )?;
+    
+    if added_liquidity > 0 {
+        position_cpi.increase_liquidity(
+            &ctx.accounts.vault_twist_account.to_account_info(),
+            &ctx.accounts.vault_usdc_account.to_account_info(),
+            added_liquidity,
+            idle_twist,
+            idle_usdc,
+            signer_seeds,
+        )?;
+        
+        let liquidity_position = &mut ctx.accounts.liquidity_position;
+        liquidity_position.liquidity = liquidity_position.liquidity.saturating_add(added_liquidity);
+    }
+    
+    token::mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.share_mint.to_account_info(),
+                to: ctx.accounts.user_share_account.to_account_info(),
+                authority: ctx.accounts.program_state.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        shares,
+    )?;
+    
+    emit!(LpVaultDeposited {
+        position_index,
+        depositor: ctx.accounts.depositor.key(),
+        twist_amount,
+        usdc_amount,
+        deposit_value,
+        shares_minted: shares,
+        nav_after: nav.saturating_add(deposit_value),
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Deposited {} USDC of value into LP vault {} for {} shares",
+        deposit_value as f64 / 1e6,
+        position_index,
+        shares
+    );
+    msg!("Liquidity added: {}", added_liquidity);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(position_index: u8)]
+pub struct WithdrawLpVault<'info> {
+    pub owner: Signer<'info>,
+    
+    #[account(
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+    )]
+    pub program_state: Box<Account<'info, ProgramState>>,
+    
+    #[account(
+        mut,
+        seeds = [LP_VAULT_SEED, &position_index.to_le_bytes()],
+        bump = lp_vault.bump,
+    )]
+    pub lp_vault: Box<Account<'info, LpVault>>,
+    
+    #[account(
+        mut,
+        address = lp_vault.liquidity_position @ TwistError::InvalidAccount,
+        constraint = liquidity_position.whirlpool == whirlpool.key() @ TwistError::InvalidAccount
+    )]
+    pub liquidity_position: Box<Account<'info, LiquidityPosition>>,
+    
+    #[account(mut)]
+    pub whirlpool: Box<Account<'info, WhirlpoolState>>,
+    
+    #[account(mut, address = lp_vault.twist_account @ TwistError::InvalidAccount)]
+    pub vault_twist_account: Box<Account<'info, TokenAccount>>,
+    
+    #[account(mut, address = lp_vault.usdc_account @ TwistError::InvalidAccount)]
+    pub vault_usdc_account: Box<Account<'info, TokenAccount>>,
+    
+    #[account(mut, address = lp_vault.share_mint @ TwistError::InvalidAccount)]
+    pub share_mint: Box<Account<'info, Mint>>,
+    
+    #[account(
+        mut,
+        constraint = user_twist_account.mint == vault_twist_account.mint @ TwistError::InvalidAccount,
+    )]
+    pub user_twist_account: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        constraint = user_usdc_account.mint == vault_usdc_account.mint @ TwistError::InvalidAccount,
+    )]
+    pub user_usdc_account: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        token::mint = share_mint,
+        token::authority = owner,
+    )]
+    pub user_share_account: Box<Account<'info, TokenAccount>>,
+    
+    /// CHECK: Pool TWIST vault, checked against the whirlpool
+    #[account(mut, address = whirlpool.token_vault_a @ TwistError::InvalidAccount)]
+    pub token_vault_a: AccountInfo<'info>,
+    
+    /// CHECK: Pool USDC vault, checked against the whirlpool
+    #[account(mut, address = whirlpool.token_vault_b @ TwistError::InvalidAccount)]
+    pub token_vault_b: AccountInfo<'info>,
+    
+    /// CHECK: The vault's Whirlpool position, checked by address
+    #[account(
+        mut,
+        address = whirlpool_position_address(&liquidity_position.position_mint).0 @ TwistError::InvalidAccount
+    )]
+    pub position: AccountInfo<'info>,
+    
+    #[account(
+        constraint = position_token_account.mint == liquidity_position.position_mint @ TwistError::InvalidAccount,
+        constraint = position_token_account.owner == program_state.key() @ TwistError::InvalidAccount,
+        constraint = position_token_account.amount == 1 @ TwistError::InvalidAccount,
+    )]
+    pub position_token_account: Box<Account<'info, TokenAccount>>,
+    
+    /// CHECK: Tick array lower
+    #[account(mut)]
+    pub tick_array_lower: AccountInfo<'info>,
+    
+    /// CHECK: Tick array upper
+    #[account(mut)]
+    pub tick_array_upper: AccountInfo<'info>,
+    
+    /// CHECK: Orca Whirlpool program
+    #[account(address = ORCA_WHIRLPOOL_PROGRAM_ID @ TwistError::InvalidAccount)]
+    pub whirlpool_program: AccountInfo<'info>,
+    
+    #[account(
+        mut,
+        seeds = [DEX_ORACLE_SEED],
+        bump = dex_oracle.bump,
+    )]
+    pub dex_oracle: Option<Box<Account<'info, DexOracle>>>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn withdraw_lp_vault_handler(
+    ctx: Context<WithdrawLpVault>,
+    position_index: u8,
+    shares: u64,
+    min_twist: u64,
+    min_usdc: u64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    
+    observe_pool(&mut ctx.accounts.dex_oracle, &[ctx.accounts.whirlpool.to_account_info()], clock.unix_timestamp)?;
+    
+    require!(
+        shares > 0 && shares <= ctx.accounts.user_share_account.amount,
+        TwistError::InvalidAmount
+    );
+    
+    let share_supply = ctx.accounts.share_mint.supply;
+    ctx.accounts.lp_vault.accrue_management_fee(share_supply, clock.unix_timestamp);
+    let total_shares = ctx.accounts.lp_vault.total_shares(share_supply);
+    
+    let (twist_out, usdc_out) = VaultRedemption {
+        program_state: &ctx.accounts.program_state,
+        liquidity_position: &mut ctx.accounts.liquidity_position,
+        whirlpool: &ctx.accounts.whirlpool,
+        vault_twist_account: &mut ctx.accounts.vault_twist_account,
+        vault_usdc_account: &mut ctx.accounts.vault_usdc_account,
+        token_vault_a: &ctx.accounts.token_vault_a,
+        token_vault_b: &ctx.accounts.token_vault_b,
+        position: &ctx.accounts.position,
+        position_token_account: &ctx.accounts.position_token_account,
+        tick_array_lower: &ctx.accounts.tick_array_lower,
+        tick_array_upper: &ctx.accounts.tick_array_upper,
+        whirlpool_program: &ctx.accounts.whirlpool_program,
+        token_program: &ctx.accounts.token_program,
+    }.execute(shares, total_shares)?;
+    require!(
+        twist_out >= min_twist && usdc_out >= min_usdc,
+        TwistError::SlippageToleranceExceeded
+    );
+    
+    token::burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.share_mint.to_account_info(),
+                from: ctx.accounts.user_share_account.to_account_info(),
+                authority: ctx.accounts.owner.to_account_info(),
+            },
+        ),
+        shares,
+    )?;
+    
+    let seeds = &[
+        PROGRAM_STATE_SEED,
+        &[ctx.accounts.program_state.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+    let program_state_info = ctx.accounts.program_state.to_account_info();
+    
+    if twist_out > 0 {
+        transfer_tokens_with_signer(
+            &ctx.accounts.vault_twist_account,
+            &ctx.accounts.user_twist_account,
+            &program_state_info,
+            &ctx.accounts.token_program,
+            twist_out,
+            signer_seeds,
+        )?;
+    }
+    if usdc_out > 0 {
+        transfer_tokens_with_signer(
+            &ctx.accounts.vault_usdc_account,
+            &ctx.accounts.user_usdc_account,
+            &program_state_info,
+            &ctx.accounts.token_program,
+            usdc_out,
+            signer_seeds,
+        )?;
+    }
+    
+    emit!(LpVaultWithdrawn {
+        position_index,
+        owner: ctx.accounts.owner.key(),
+        shares_burned: shares,
+        twist_amount: twist_out,
+        usdc_amount: usdc_out,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Redeemed {} LP vault {} shares for {} TWIST and {} USDC",
+        shares,
+        position_index,
+        twist_out as f64 / 10f64.powf(DECIMALS as f64),
+        usdc_out as f64 / 1e6
+    );
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(position_index: u8)]
+pub struct CollectLpVaultFee<'info> {
+    /// Anyone may push accrued management fees to the fee collector
+    pub payer: Signer<'info>,
+    
+    #[account(
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+    )]
+    pub program_state: Box<Account<'info, ProgramState>>,
+    
+    #[account(
+        mut,
+        seeds = [LP_VAULT_SEED, &position_index.to_le_bytes()],
+        bump = lp_vault.bump,
+    )]
+    pub lp_vault: Box<Account<'info, LpVault>>,
+    
+    #[account(
+        mut,
+        address = lp_vault.liquidity_position @ TwistError::InvalidAccount,
+        constraint = liquidity_position.whirlpool == whirlpool.key() @ TwistError::InvalidAccount
+    )]
+    pub liquidity_position: Box<Account<'info, LiquidityPosition>>,
+    
+    #[account(mut)]
+    pub whirlpool: Box<Account<'info, WhirlpoolState>>,
+    
+    #[account(mut, address = lp_vault.twist_account @ TwistError::InvalidAccount)]
+    pub vault_twist_account: Box<Account<'info, TokenAccount>>,
+    
+    #[account(mut, address = lp_vault.usdc_account @ TwistError::InvalidAccount)]
+    pub vault_usdc_account: Box<Account<'info, TokenAccount>>,
+    
+    #[account(address = lp_vault.share_mint @ TwistError::InvalidAccount)]
+    pub share_mint: Box<Account<'info, Mint>>,
+    
+    #[account(
+        mut,
+        seeds = [b"fee_collector"],
+        bump = fee_collector.bump,
+    )]
+    pub fee_collector: Box<Account<'info, FeeCollectorState>>,
+    
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED],
+        bump,
+        token::authority = fee_collector,
+    )]
+    pub fee_vault: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        address = fee_collector.usdc_fee_vault @ TwistError::InvalidAccount,
+    )]
+    pub usdc_fee_vault: Box<Account<'info, TokenAccount>>,
+    
+    /// CHECK: Pool TWIST vault, checked against the whirlpool
+    #[account(mut, address = whirlpool.token_vault_a @ TwistError::InvalidAccount)]
+    pub token_vault_a: AccountInfo<'info>,
+    
+    /// CHECK: Pool USDC vault, checked against the whirlpool
+    #[account(mut, address = whirlpool.token_vault_b @ TwistError::InvalidAccount)]
+    pub token_vault_b: AccountInfo<'info>,
+    
+    /// CHECK: The vault's Whirlpool position, checked by address
+    #[account(
+        mut,
+        address = whirlpool_position_address(&liquidity_position.position_mint).0 @ TwistError::InvalidAccount
+    )]
+    pub position: AccountInfo<'info>,
+    
+    #[account(
+        constraint = position_token_account.mint == liquidity_position.position_mint @ TwistError::InvalidAccount,
+        constraint = position_token_account.owner == program_state.key() @ TwistError::InvalidAccount,
+        constraint = position_token_account.amount == 1 @ TwistError::InvalidAccount,
+    )]
+    pub position_token_account: Box<Account<'info, TokenAccount>>,
+    
+    /// CHECK: Tick array lower
+    #[account(mut)]
+    pub tick_array_lower: AccountInfo<'info>,
+    
+    /// CHECK: Tick array upper
+    #[account(mut)]
+    pub tick_array_upper: AccountInfo<'info>,
+    
+    /// CHECK: Orca Whirlpool program
+    #[account(address = ORCA_WHIRLPOOL_PROGRAM_ID @ TwistError::InvalidAccount)]
+    pub whirlpool_program: AccountInfo<'info>,
+    
+    #[account(
+        mut,
+        seeds = [DEX_ORACLE_SEED],
+        bump = dex_oracle.bump,
+    )]
+    pub dex_oracle: Option<Box<Account<'info, DexOracle>>>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn collect_lp_vault_fee_handler(
+    ctx: Context<CollectLpVaultFee>,
+    position_index: u8,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    
+    observe_pool(&mut ctx.accounts.dex_oracle, &[ctx.accounts.whirlpool.to_account_info()], clock.unix_timestamp)?;
+    
+    let share_supply = ctx.accounts.share_mint.supply;
+    ctx.accounts.lp_vault.accrue_management_fee(share_supply, clock.unix_timestamp);
+    let fee_shares = ctx.accounts.lp_vault.fee_shares;
+    let total_shares = ctx.accounts.lp_vault.total_shares(share_supply);
+    require!(fee_shares > 0, TwistError::NoFeesToCompound);
+    
+    let (twist_fee, usdc_fee) = VaultRedemption {
+        program_state: &ctx.accounts.program_state,
+        liquidity_position: &mut ctx.accounts.liquidity_position,
+        whirlpool: &ctx.accounts.whirlpool,
+        vault_twist_account: &mut ctx.accounts.vault_twist_account,
+        vault_usdc_account: &mut ctx.accounts.vault_usdc_account,
+        token_vault_a: &ctx.accounts.token_vault_a,
+        token_vault_b: &ctx.accounts.token_vault_b,
+        position: &ctx.accounts.position,
+        position_token_account: &ctx.accounts.position_token_account,
+        tick_array_lower: &ctx.accounts.tick_array_lower,
+        tick_array_upper: &ctx.accounts.tick_array_upper,
+        whirlpool_program: &ctx.accounts.whirlpool_program,
+        token_program: &ctx.accounts.token_program,
+    }.execute(fee_shares, total_shares)?;
+    
+    let seeds = &[
+        PROGRAM_STATE_SEED,
+        &[ctx.accounts.program_state.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+    let program_state_info = ctx.accounts.program_state.to_account_info();
+    
+    if twist_fee > 0 {
+        transfer_tokens_with_signer(
+            &ctx.accounts.vault_twist_account,
+            &ctx.accounts.fee_vault,
+            &program_state_info,
+            &ctx.accounts.token_program,
+            twist_fee,
+            signer_seeds,
+        )?;
+        ctx.accounts.fee_collector.record_fee(FeeType::Liquidity, twist_fee);
+    }
+    if usdc_fee > 0 {
+        transfer_tokens_with_signer(
+            &ctx.accounts.vault_usdc_account,
+            &ctx.accounts.usdc_fee_vault,
+            &program_state_info,
+            &ctx.accounts.token_program,
+            usdc_fee,
+            signer_seeds,
+        )?;
+        ctx.accounts.fee_collector.record_usdc_fee(usdc_fee);
+    }
+    
+    let lp_vault = &mut ctx.accounts.lp_vault;
+    lp_vault.fee_shares = 0;
+    lp_vault.total_fees_paid_twist = lp_vault.total_fees_paid_twist.saturating_add(twist_fee);
+    lp_vault.total_fees_paid_usdc = lp_vault.total_fees_paid_usdc.saturating_add(usdc_fee);
+    
+    emit!(LpVaultFeeCollected {
+        position_index,
+        fee_shares,
+        twist_amount: twist_fee,
+        usdc_amount: usdc_fee,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("LP vault {} management fee: {} TWIST and {} USDC for {} shares",
+        position_index,
+        twist_fee as f64 / 10f64.powf(DECIMALS as f64),
+        usdc_fee as f64 / 1e6,
+        fee_shares
+    );
+    
+    Ok(())
+}
+
+/// Vault holdings at the pool price, valued at the oracle price in USDC atomic units
+fn vault_nav(
+    program_state: &ProgramState,
+    whirlpool: &WhirlpoolState,
+    liquidity_position: &LiquidityPosition,
+    idle_twist: u64,
+    idle_usdc: u64,
+    current_time: i64,
+) -> Result<u64> {
+    // A pool pushed away from the oracle would misprice the position's token mix
+    program_state.check_pool_price(price_from_sqrt_price(whirlpool.sqrt_price, DECIMALS)?, current_time)?;
+    
+    let (twist, usdc) = vault_holdings(
+        whirlpool.sqrt_price,
+        liquidity_position.lower_tick,
+        liquidity_position.upper_tick,
+        liquidity_position.liquidity,
+        idle_twist,
+        idle_usdc,
+    )?;
+    Ok(usdc.saturating_add(program_state.twist_to_usdc_value(twist)))
+}
+
+// Accounts for paying out a share of a vault's position and idle balances
+struct VaultRedemption<'a, 'info> {
+    program_state: &'a Account<'info, ProgramState>,
+    liquidity_position: &'a mut Account<'info, LiquidityPosition>,
+    whirlpool: &'a Account<'info, WhirlpoolState>,
+    vault_twist_account: &'a mut Account<'info, TokenAccount>,
+    vault_usdc_account: &'a mut Account<'info, TokenAccount>,
+    token_vault_a: &'a AccountInfo<'info>,
+    token_vault_b: &'a AccountInfo<'info>,
+    position: &'a AccountInfo<'info>,
+    position_token_account: &'a Account<'info, TokenAccount>,
+    tick_array_lower: &'a AccountInfo<'info>,
+    tick_array_upper: &'a AccountInfo<'info>,
+    whirlpool_program: &'a AccountInfo<'info>,
+    token_program: &'a Program<'info, Token>,
+}
+
+impl<'a, 'info> VaultRedemption<'a, 'info> {
+    /// Withdraw the shares' slice of the position into the vault and return the TWIST and
+    /// USDC they are owed, including their slice of the idle balances
+    fn execute(self, shares: u64, total_shares: u64) -> Result<(u64, u64)> {
+        require!(shares <= total_shares, TwistError::InvalidAmount);
+        
+        let seeds = &[
+            PROGRAM_STATE_SEED,
+            &[self.program_state.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+        
+        let position_cpi = WhirlpoolPositionCpi {
+            whirlpool_program: self.whirlpool_program.clone(),
+            token_program: self.token_program.to_account_info(),
+            whirlpool: self.whirlpool.to_account_info(),
+            position_authority: self.program_state.to_account_info(),
+            position: self.position.clone(),
+            position_token_account: self.position_token_account.to_account_info(),
+            token_vault_a: self.token_vault_a.clone(),
+            token_vault_b: self.token_vault_b.clone(),
+            tick_array_lower: self.tick_array_lower.clone(),
+            tick_array_upper: self.tick_array_upper.clone(),
+        };
+        
+        // Deposits are priced with the position's pending fees, so redemptions pay them out too
+        position_cpi.collect_fees(
+            &self.vault_twist_account.to_account_info(),
+            &self.vault_usdc_account.to_account_info(),
+            true,
+            signer_seeds,
+        )?;
+        self.vault_twist_account.reload()?;
+        self.vault_usdc_account.reload()?;
+        
+        let liquidity_position = self.liquidity_position;
+        let idle_twist = self.vault_twist_account.amount;
+        let idle_usdc = self.vault_usdc_account.amount;
+        let (liquidity, mut twist_out, mut usdc_out) = redemption_for_shares(
+            liquidity_position.liquidity,
+            idle_twist,
+            idle_usdc,
+            shares,
+            total_shares,
+        );
+        
+        if liquidity > 0 {
+            let (expected_twist, expected_usdc) = get_amounts_for_liquidity(
+                self.whirlpool.sqrt_price,
+                sqrt_price_from_tick_index(liquidity_position.lower_tick)?,
+                sqrt_price_from_tick_index(liquidity_position.upper_tick)?,
+                liquidity,
+                false,
+            )?;
+            
+            position_cpi.decrease_liquidity(
+                &self.vault_twist_account.to_account_info(),
+                &self.vault_usdc_account.to_account_info(),
+                liquidity,
+                amount_less_bps(expected_twist, LIQUIDITY_DEFAULT_SLIPPAGE_BPS),
+                amount_less_bps(expected_usdc, LIQUIDITY_DEFAULT_SLIPPAGE_BPS),
+                signer_seeds,
+            )?;
+            
+            self.vault_twist_account.reload()?;
+            self.vault_usdc_account.reload()?;
+            twist_out = twist_out.saturating_add(self.vault_twist_account.amount.saturating_sub(idle_twist));
+            usdc_out = usdc_out.saturating_add(self.vault_usdc_account.amount.saturating_sub(idle_usdc));
+            
+            liquidity_position.liquidity = liquidity_position.liquidity.saturating_sub(liquidity);
+        }
+        
+        Ok((twist_out, usdc_out))
+    }
+}
@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::TwistError;
+use crate::state::{ProgramState, MarketMetrics};
+
+#[derive(Accounts)]
+pub struct InitializeMarketMetrics<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [PROGRAM_STATE_SEED],
+        bump,
+        constraint = program_state.authority == authority.key() @ TwistError::Unauthorized
+    )]
+    pub program_state: Account<'info, ProgramState>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = MarketMetrics::LEN,
+        seeds = [MARKET_METRICS_SEED],
+        bump
+    )]
+    pub market_metrics: Box<Account<'info, MarketMetrics>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_market_metrics_handler(ctx: Context<InitializeMarketMetrics>) -> Result<()> {
+    let program_state = &ctx.accounts.program_state;
+    let market_metrics = &mut ctx.accounts.market_metrics;
+    let clock = Clock::get()?;
+    
+    market_metrics.bump = ctx.bumps.market_metrics;
+    
+    // Seed the first buckets with the current state so deltas have a baseline
+    market_metrics.record_price(clock.unix_timestamp, program_state.last_oracle_price);
+    market_metrics.record_liquidity(clock.unix_timestamp, program_state.floor_liquidity);
+    
+    msg!("Market metrics initialized");
+    msg!("Hourly buckets: {}, daily buckets: {}",
+        MarketMetrics::HOURLY_BUCKETS,
+        MarketMetrics::DAILY_BUCKETS
+    );
+    
+    Ok(())
+}
@@ -17,6 +17,18 @@ pub mod liquidity;
 pub mod circuit_breaker;
 pub mod fee_collector;
 pub mod multisig;
+pub mod market_metrics;
+pub mod program_upgrade;
+pub mod governance;
+pub mod commit_reveal;
+pub mod treasury_budget;
+pub mod proof_of_reserves;
+pub mod insurance;
+pub mod dex_registry;
+pub mod lp_vault;
+pub mod liquidity_mining;
+pub mod dex_oracle;
+pub mod price_reporter;
 
 pub use initialize::*;
 pub use mint::*;
@@ -36,4 +48,16 @@ pub use treasury_ops::*;
 pub use liquidity::*;
 pub use circuit_breaker::*;
 pub use fee_collector::*;
-pub use multisig::*;
\ No newline at end of file
+pub use multisig::*;
+pub use market_metrics::*;
+pub use program_upgrade::*;
+pub use governance::*;
+pub use commit_reveal::*;
+pub use treasury_budget::*;
+pub use proof_of_reserves::*;
+pub use insurance::*;
+pub use dex_registry::*;
+pub use lp_vault::*;
+pub use liquidity_mining::*;
+pub use dex_oracle::*;
+pub use price_reporter::*;
\ No newline at end of file
@@ -1,4 +1,6 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::bpf_loader_upgradeable::UpgradeableLoaderState;
+use anchor_lang::solana_program::hash::hash;
 use anchor_lang::solana_program::instruction::Instruction;
 
 use crate::constants::*;
@@ -90,12 +92,12 @@ pub fn initialize_multisig_handler(
 }
 
 #[derive(Accounts)]
-#[instruction(transaction_data: Vec<u8>)]
 pub struct ProposeTransaction<'info> {
     #[account(mut)]
     pub proposer: Signer<'info>,
     
     #[account(
+        mut,
         seeds = [b"multisig"],
         bump,
         constraint = multisig_config.initialized @ TwistError::InvalidAccount,
@@ -128,10 +130,6 @@ pub fn propose_transaction_handler(
     description: String,
     delay_seconds: i64,
 ) -> Result<()> {
-    let multisig_config = &mut ctx.accounts.multisig_config;
-    let transaction = &mut ctx.accounts.transaction;
-    let clock = Clock::get()?;
-    
     // Validate inputs
     require!(
         instruction_data.len() <= 1024,
@@ -143,49 +141,183 @@ pub fn propose_transaction_handler(
         TwistError::InvalidAmount
     );
     
+    let instruction = TransactionInstruction {
+        program_id: instruction_program_id,
+        accounts: instruction_accounts,
+        data_len: instruction_data.len() as u32,
+        data: instruction_data,
+    };
+    
+    let bump = ctx.bumps.transaction;
+    init_transaction(
+        &mut ctx.accounts.multisig_config,
+        &mut ctx.accounts.transaction,
+        ctx.accounts.proposer.key(),
+        vec![instruction],
+        title,
+        description,
+        delay_seconds,
+        bump,
+    )
+}
+
+#[derive(Accounts)]
+#[instruction(instructions: Vec<TransactionInstruction>)]
+pub struct ProposeBatchTransaction<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"multisig"],
+        bump,
+        constraint = multisig_config.initialized @ TwistError::InvalidAccount,
+        constraint = multisig_config.is_member(&proposer.key()) @ TwistError::Unauthorized,
+    )]
+    pub multisig_config: Account<'info, MultisigConfig>,
+    
+    #[account(
+        init,
+        payer = proposer,
+        space = MultisigTransaction::space(&instructions),
+        seeds = [
+            b"multisig_transaction",
+            multisig_config.key().as_ref(),
+            &multisig_config.transaction_count.to_le_bytes()
+        ],
+        bump
+    )]
+    pub transaction: Account<'info, MultisigTransaction>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn propose_batch_transaction_handler(
+    ctx: Context<ProposeBatchTransaction>,
+    instructions: Vec<TransactionInstruction>,
+    title: String,
+    description: String,
+    delay_seconds: i64,
+) -> Result<()> {
+    // Validate inputs
     require!(
-        title.len() <= 64 && !title.is_empty(),
+        !instructions.is_empty() && instructions.len() <= MultisigTransaction::MAX_INSTRUCTIONS,
         TwistError::InvalidAmount
     );
     
     require!(
-        description.len() <= 256,
+        MultisigTransaction::space(&instructions) <= MultisigTransaction::MAX_ACCOUNT_SIZE,
         TwistError::InvalidAmount
     );
     
-    // Initialize transaction
-    transaction.multisig = multisig_config.key();
-    transaction.transaction_index = multisig_config.transaction_count;
-    transaction.proposer = ctx.accounts.proposer.key();
-    transaction.instruction_data = instruction_data;
-    transaction.instruction_program_id = instruction_program_id;
-    transaction.instruction_accounts = instruction_accounts;
-    transaction.signers = vec![ctx.accounts.proposer.key()]; // Proposer auto-signs
-    transaction.executed = false;
-    transaction.cancelled = false;
-    transaction.created_at = clock.unix_timestamp;
-    transaction.executed_at = 0;
-    transaction.eta = clock.unix_timestamp + delay_seconds;
-    transaction.bump = ctx.bumps.transaction;
-    transaction.title = title.clone();
-    transaction.description = description.clone();
+    for instruction in &instructions {
+        require!(
+            instruction.accounts.len() <= MultisigTransaction::MAX_ACCOUNTS_PER_INSTRUCTION,
+            TwistError::InvalidAmount
+        );
+        
+        // Data beyond the initial chunk is uploaded with upload_transaction_chunk
+        require!(
+            instruction.data.len() <= instruction.data_len as usize,
+            TwistError::InvalidAmount
+        );
+    }
     
-    // Update multisig state
-    multisig_config.transaction_count += 1;
-    multisig_config.pending_transactions += 1;
+    let bump = ctx.bumps.transaction;
+    init_transaction(
+        &mut ctx.accounts.multisig_config,
+        &mut ctx.accounts.transaction,
+        ctx.accounts.proposer.key(),
+        instructions,
+        title,
+        description,
+        delay_seconds,
+        bump,
+    )
+}
+
+#[derive(Accounts)]
+pub struct UploadTransactionChunk<'info> {
+    pub proposer: Signer<'info>,
     
-    // Emit event
-    emit!(ParameterUpdated {
-        parameter: "multisig_proposal".to_string(),
-        old_value: format!("pending: {}", multisig_config.pending_transactions - 1),
-        new_value: format!("pending: {}", multisig_config.pending_transactions),
-        updated_by: ctx.accounts.proposer.key(),
-        timestamp: clock.unix_timestamp,
-    });
+    #[account(
+        seeds = [b"multisig"],
+        bump,
+    )]
+    pub multisig_config: Account<'info, MultisigConfig>,
     
-    msg!("Transaction proposed: {}", title);
-    msg!("Index: {}, ETA: {}", transaction.transaction_index, transaction.eta);
-    msg!("Signatures: 1/{}", multisig_config.threshold);
+    #[account(
+        mut,
+        seeds = [
+            b"multisig_transaction",
+            multisig_config.key().as_ref(),
+            &transaction.transaction_index.to_le_bytes()
+        ],
+        bump,
+        constraint = transaction.proposer == proposer.key() @ TwistError::Unauthorized,
+        constraint = !transaction.finalized @ TwistError::InvalidAccount,
+        constraint = !transaction.cancelled @ TwistError::InvalidAccount,
+        constraint = !transaction.is_expired(Clock::get()?.unix_timestamp) @ TwistError::ProposalExpired,
+    )]
+    pub transaction: Account<'info, MultisigTransaction>,
+}
+
+pub fn upload_transaction_chunk_handler(
+    ctx: Context<UploadTransactionChunk>,
+    instruction_index: u8,
+    offset: u32,
+    chunk: Vec<u8>,
+) -> Result<()> {
+    let transaction = &mut ctx.accounts.transaction;
+    transaction.append_chunk(instruction_index, offset, &chunk)?;
+    let instruction = &transaction.instructions[instruction_index as usize];
+    
+    msg!("Uploaded {} bytes to instruction {} of transaction {}",
+        chunk.len(),
+        instruction_index,
+        transaction.transaction_index
+    );
+    msg!("Progress: {}/{} bytes", instruction.data.len(), instruction.data_len);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct FinalizeTransaction<'info> {
+    pub proposer: Signer<'info>,
+    
+    #[account(
+        seeds = [b"multisig"],
+        bump,
+    )]
+    pub multisig_config: Account<'info, MultisigConfig>,
+    
+    #[account(
+        mut,
+        seeds = [
+            b"multisig_transaction",
+            multisig_config.key().as_ref(),
+            &transaction.transaction_index.to_le_bytes()
+        ],
+        bump,
+        constraint = transaction.proposer == proposer.key() @ TwistError::Unauthorized,
+        constraint = !transaction.finalized @ TwistError::InvalidAccount,
+        constraint = !transaction.cancelled @ TwistError::InvalidAccount,
+    )]
+    pub transaction: Account<'info, MultisigTransaction>,
+}
+
+pub fn finalize_transaction_handler(ctx: Context<FinalizeTransaction>) -> Result<()> {
+    let transaction = &mut ctx.accounts.transaction;
+    let clock = Clock::get()?;
+    
+    transaction.finalize(&ctx.accounts.multisig_config, clock.unix_timestamp)?;
+    
+    msg!("Transaction {} finalized with {} instructions", 
+        transaction.transaction_index,
+        transaction.instructions.len()
+    );
+    msg!("Class: {:?}, ETA: {}", transaction.operation_class, transaction.eta);
     
     Ok(())
 }
@@ -212,6 +344,8 @@ pub struct ApproveTransaction<'info> {
         bump,
         constraint = !transaction.executed @ TwistError::InvalidAccount,
         constraint = !transaction.cancelled @ TwistError::InvalidAccount,
+        constraint = !transaction.is_expired(Clock::get()?.unix_timestamp) @ TwistError::ProposalExpired,
+        constraint = transaction.finalized @ TwistError::InvalidAccount,
     )]
     pub transaction: Account<'info, MultisigTransaction>,
 }
@@ -274,13 +408,10 @@ pub struct ExecuteTransaction<'info> {
             &transaction.transaction_index.to_le_bytes()
         ],
         bump,
-        constraint = transaction.can_execute(multisig_config.threshold, clock.unix_timestamp) @ TwistError::InvalidAccount,
+        constraint = transaction.can_execute(&multisig_config, clock.unix_timestamp) @ TwistError::InvalidAccount,
     )]
     pub transaction: Account<'info, MultisigTransaction>,
     
-    /// CHECK: The program to invoke
-    pub target_program: AccountInfo<'info>,
-    
     pub clock: Sysvar<'info, Clock>,
 }
 
@@ -290,48 +421,24 @@ pub fn execute_transaction_handler<'info>(
     let transaction = &mut ctx.accounts.transaction;
     let multisig_config = &mut ctx.accounts.multisig_config;
     let clock = &ctx.accounts.clock;
-    
-    // Build the instruction
-    let mut account_infos = vec![];
     let remaining_accounts = ctx.remaining_accounts;
     
-    // Match accounts from the transaction to remaining accounts
-    for (i, _tx_account) in transaction.instruction_accounts.iter().enumerate() {
-        if i < remaining_accounts.len() {
-            account_infos.push(remaining_accounts[i].clone());
-        } else {
-            return Err(TwistError::InvalidAccount.into());
-        }
-    }
-    
-    // Create the instruction
-    let ix = Instruction {
-        program_id: transaction.instruction_program_id,
-        accounts: transaction.instruction_accounts
-            .iter()
-            .enumerate()
-            .map(|(i, acc)| AccountMeta {
-                pubkey: remaining_accounts[i].key(),
-                is_signer: acc.is_signer,
-                is_writable: acc.is_writable,
-            })
-            .collect(),
-        data: transaction.instruction_data.clone(),
-    };
-    
-    // Execute via CPI
     let seeds = &[
         b"multisig".as_ref(),
         &[multisig_config.bump],
     ];
     let signer_seeds = &[&seeds[..]];
     
-    anchor_lang::solana_program::program::invoke_signed(
-        &ix,
-        &account_infos,
+    invoke_transaction_instructions(
+        &transaction.instructions,
+        remaining_accounts,
         signer_seeds,
+        transaction.buffer_hash,
     )?;
     
+    // Config changes executed against the multisig itself must not be overwritten
+    multisig_config.reload()?;
+    
     // Update transaction state
     transaction.executed = true;
     transaction.executed_at = clock.unix_timestamp;
@@ -348,7 +455,10 @@ pub fn execute_transaction_handler<'info>(
         timestamp: clock.unix_timestamp,
     });
     
-    msg!("Transaction {} executed successfully", transaction.transaction_index);
+    msg!("Transaction {} executed successfully ({} instructions)", 
+        transaction.transaction_index,
+        transaction.instructions.len()
+    );
     msg!("Title: {}", transaction.title);
     
     Ok(())
@@ -378,7 +488,7 @@ pub struct CancelTransaction<'info> {
         constraint = !transaction.executed @ TwistError::InvalidAccount,
         constraint = !transaction.cancelled @ TwistError::InvalidAccount,
         constraint = transaction.proposer == canceller.key() || 
-                    transaction.signature_count() < multisig_config.threshold @ TwistError::Unauthorized,
+                    transaction.valid_signature_count(&multisig_config) < multisig_config.threshold @ TwistError::Unauthorized,
     )]
     pub transaction: Account<'info, MultisigTransaction>,
 }
@@ -406,4 +516,535 @@ pub fn cancel_transaction_handler(ctx: Context<CancelTransaction>) -> Result<()>
     msg!("Transaction {} cancelled", transaction.transaction_index);
     
     Ok(())
-}
\ No newline at end of file
+}
+
+#[derive(Accounts)]
+pub struct CloseTransaction<'info> {
+    pub closer: Signer<'info>,
+    
+    /// CHECK: Receives the rent, must be the original proposer
+    #[account(
+        mut,
+        constraint = proposer.key() == transaction.proposer @ TwistError::InvalidAccount,
+    )]
+    pub proposer: AccountInfo<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"multisig"],
+        bump,
+    )]
+    pub multisig_config: Account<'info, MultisigConfig>,
+    
+    #[account(
+        mut,
+        close = proposer,
+        seeds = [
+            b"multisig_transaction",
+            multisig_config.key().as_ref(),
+            &transaction.transaction_index.to_le_bytes()
+        ],
+        bump,
+        constraint = transaction.is_settled(Clock::get()?.unix_timestamp) @ TwistError::ProposalNotSettled,
+    )]
+    pub transaction: Account<'info, MultisigTransaction>,
+}
+
+pub fn close_transaction_handler(ctx: Context<CloseTransaction>) -> Result<()> {
+    let transaction = &ctx.accounts.transaction;
+    let multisig_config = &mut ctx.accounts.multisig_config;
+    let clock = Clock::get()?;
+    
+    // Executed and cancelled proposals were already removed from the pending count
+    let status = if transaction.executed {
+        "executed"
+    } else if transaction.cancelled {
+        "cancelled"
+    } else {
+        multisig_config.pending_transactions = multisig_config.pending_transactions.saturating_sub(1);
+        "expired"
+    };
+    
+    // Emit event
+    emit!(ParameterUpdated {
+        parameter: format!("multisig_close_{}", transaction.transaction_index),
+        old_value: status.to_string(),
+        new_value: "closed".to_string(),
+        updated_by: ctx.accounts.closer.key(),
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Transaction {} ({}) closed, rent returned to {}", 
+        transaction.transaction_index,
+        status,
+        transaction.proposer
+    );
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateMultisigConfig<'info> {
+    /// The multisig PDA, signing through execute_transaction
+    #[account(
+        constraint = multisig_signer.key() == multisig_config.key() @ TwistError::MultisigRequired,
+    )]
+    pub multisig_signer: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"multisig"],
+        bump = multisig_config.bump,
+        constraint = multisig_config.initialized @ TwistError::InvalidAccount,
+    )]
+    pub multisig_config: Account<'info, MultisigConfig>,
+}
+
+pub fn add_member_handler(ctx: Context<UpdateMultisigConfig>, member: Pubkey) -> Result<()> {
+    let multisig_config = &mut ctx.accounts.multisig_config;
+    
+    require!(
+        !multisig_config.is_member(&member),
+        TwistError::InvalidAccount
+    );
+    
+    require!(
+        multisig_config.members.len() < 10,
+        TwistError::InvalidAmount
+    );
+    
+    let old_count = multisig_config.members.len();
+    multisig_config.members.push(member);
+    
+    emit_config_change(
+        multisig_config,
+        "multisig_members",
+        format!("members: {}", old_count),
+        format!("members: {}", multisig_config.members.len()),
+    )?;
+    
+    msg!("Multisig member added: {}", member);
+    
+    Ok(())
+}
+
+pub fn remove_member_handler(ctx: Context<UpdateMultisigConfig>, member: Pubkey) -> Result<()> {
+    let multisig_config = &mut ctx.accounts.multisig_config;
+    
+    let position = multisig_config.members
+        .iter()
+        .position(|m| *m == member)
+        .ok_or(TwistError::InvalidAccount)?;
+    
+    let old_count = multisig_config.members.len();
+    multisig_config.members.remove(position);
+    
+    // Removing a member must not leave the threshold unreachable
+    require!(
+        multisig_config.validate_threshold(),
+        TwistError::InvalidAmount
+    );
+    
+    emit_config_change(
+        multisig_config,
+        "multisig_members",
+        format!("members: {}", old_count),
+        format!("members: {}", multisig_config.members.len()),
+    )?;
+    
+    msg!("Multisig member removed: {}", member);
+    
+    Ok(())
+}
+
+pub fn change_threshold_handler(ctx: Context<UpdateMultisigConfig>, threshold: u64) -> Result<()> {
+    let multisig_config = &mut ctx.accounts.multisig_config;
+    
+    let old_threshold = multisig_config.threshold;
+    multisig_config.threshold = threshold;
+    
+    require!(
+        threshold >= 2 && multisig_config.validate_threshold(),
+        TwistError::InvalidAmount
+    );
+    
+    emit_config_change(
+        multisig_config,
+        "multisig_threshold",
+        old_threshold.to_string(),
+        threshold.to_string(),
+    )?;
+    
+    msg!("Multisig threshold changed: {} -> {}", old_threshold, threshold);
+    
+    Ok(())
+}
+
+pub fn update_delays_handler(
+    ctx: Context<UpdateMultisigConfig>,
+    parameter_update_delay: i64,
+    treasury_withdrawal_delay: i64,
+    authority_transfer_delay: i64,
+) -> Result<()> {
+    let multisig_config = &mut ctx.accounts.multisig_config;
+    
+    require!(
+        parameter_update_delay >= 0 &&
+        treasury_withdrawal_delay >= 0 &&
+        authority_transfer_delay >= 0,
+        TwistError::InvalidAmount
+    );
+    
+    let old_value = format!("{}/{}/{}",
+        multisig_config.parameter_update_delay,
+        multisig_config.treasury_withdrawal_delay,
+        multisig_config.authority_transfer_delay
+    );
+    
+    multisig_config.parameter_update_delay = parameter_update_delay;
+    multisig_config.treasury_withdrawal_delay = treasury_withdrawal_delay;
+    multisig_config.authority_transfer_delay = authority_transfer_delay;
+    
+    emit_config_change(
+        multisig_config,
+        "multisig_delays",
+        old_value,
+        format!("{}/{}/{}", parameter_update_delay, treasury_withdrawal_delay, authority_transfer_delay),
+    )?;
+    
+    msg!("Multisig delays updated");
+    
+    Ok(())
+}
+
+// Helper functions
+
+/// Invoke each instruction in order with the given PDA signer. Remaining accounts are
+/// verified against the stored keys so executors cannot substitute accounts.
+pub(crate) fn invoke_transaction_instructions<'info>(
+    instructions: &[TransactionInstruction],
+    remaining_accounts: &[AccountInfo<'info>],
+    signer_seeds: &[&[&[u8]]],
+    buffer_hash: Option<[u8; 32]>,
+) -> Result<()> {
+    // Remaining accounts are laid out per instruction: program, then its accounts
+    let total_accounts: usize = instructions.iter().map(|ix| ix.accounts.len() + 1).sum();
+    require!(
+        remaining_accounts.len() == total_accounts,
+        TwistError::InvalidAccount
+    );
+    
+    // Execute every instruction in order; any failure reverts the whole batch
+    let mut cursor = 0;
+    for instruction in instructions.iter() {
+        let program_info = &remaining_accounts[cursor];
+        require!(
+            program_info.key() == instruction.program_id,
+            TwistError::InvalidAccount
+        );
+        cursor += 1;
+        
+        // Upgrades must deploy exactly the bytecode signers approved
+        if let Some(index) = instruction.upgrade_buffer_index() {
+            let buffer = remaining_accounts
+                .get(cursor + index)
+                .ok_or(TwistError::InvalidAccount)?;
+            require!(
+                buffer_hash == Some(hash_buffer(buffer)?),
+                TwistError::BufferHashMismatch
+            );
+        }
+        
+        let mut account_infos = Vec::with_capacity(instruction.accounts.len() + 1);
+        let mut account_metas = Vec::with_capacity(instruction.accounts.len());
+        
+        for tx_account in instruction.accounts.iter() {
+            let account_info = &remaining_accounts[cursor];
+            require!(
+                account_info.key() == tx_account.pubkey,
+                TwistError::InvalidAccount
+            );
+            
+            account_metas.push(AccountMeta {
+                pubkey: tx_account.pubkey,
+                is_signer: tx_account.is_signer,
+                is_writable: tx_account.is_writable,
+            });
+            account_infos.push(account_info.clone());
+            cursor += 1;
+        }
+        account_infos.push(program_info.clone());
+        
+        let ix = Instruction {
+            program_id: instruction.program_id,
+            accounts: account_metas,
+            data: instruction.data.clone(),
+        };
+        
+        anchor_lang::solana_program::program::invoke_signed(
+            &ix,
+            &account_infos,
+            signer_seeds,
+        )?;
+    }
+    
+    Ok(())
+}
+
+/// SHA-256 of a loader buffer's bytecode, excluding the buffer metadata header
+pub(crate) fn hash_buffer(buffer: &AccountInfo) -> Result<[u8; 32]> {
+    let data = buffer.try_borrow_data()?;
+    let bytecode = data
+        .get(UpgradeableLoaderState::size_of_buffer_metadata()..)
+        .ok_or(TwistError::InvalidAccount)?;
+    Ok(hash(bytecode).to_bytes())
+}
+
+fn emit_config_change(
+    multisig_config: &MultisigConfig,
+    parameter: &str,
+    old_value: String,
+    new_value: String,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    
+    emit!(ParameterUpdated {
+        parameter: parameter.to_string(),
+        old_value,
+        new_value,
+        updated_by: multisig_config.multisig_address,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn init_transaction(
+    multisig_config: &mut MultisigConfig,
+    transaction: &mut MultisigTransaction,
+    proposer: Pubkey,
+    instructions: Vec<TransactionInstruction>,
+    title: String,
+    description: String,
+    delay_seconds: i64,
+    bump: u8,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    
+    require!(
+        title.len() <= 64 && !title.is_empty(),
+        TwistError::InvalidAmount
+    );
+    
+    require!(
+        description.len() <= 256,
+        TwistError::InvalidAmount
+    );
+    
+    require!(
+        delay_seconds >= 0,
+        TwistError::InvalidAmount
+    );
+    
+    // Initialize transaction
+    transaction.multisig = multisig_config.multisig_address;
+    transaction.transaction_index = multisig_config.transaction_count;
+    transaction.proposer = proposer;
+    transaction.instructions = instructions;
+    transaction.signers = vec![proposer]; // Proposer auto-signs
+    transaction.executed = false;
+    transaction.cancelled = false;
+    transaction.finalized = transaction.is_fully_uploaded();
+    transaction.created_at = clock.unix_timestamp;
+    transaction.executed_at = 0;
+    transaction.delay_seconds = delay_seconds;
+    transaction.operation_class = OperationClass::AuthorityTransfer;
+    transaction.eta = i64::MAX; // Set once the payload is complete
+    transaction.expires_at = clock.unix_timestamp + MULTISIG_PROPOSAL_LIFETIME;
+    transaction.buffer_hash = None;
+    if transaction.finalized {
+        transaction.schedule(multisig_config, clock.unix_timestamp)?;
+    }
+    transaction.bump = bump;
+    transaction.title = title.clone();
+    transaction.description = description;
+    
+    // Update multisig state
+    multisig_config.transaction_count += 1;
+    multisig_config.pending_transactions += 1;
+    
+    // Emit event
+    emit!(ParameterUpdated {
+        parameter: "multisig_proposal".to_string(),
+        old_value: format!("pending: {}", multisig_config.pending_transactions - 1),
+        new_value: format!("pending: {}", multisig_config.pending_transactions),
+        updated_by: proposer,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Transaction proposed: {}", title);
+    msg!("Index: {}, Class: {:?}, ETA: {}", 
+        transaction.transaction_index,
+        transaction.operation_class,
+        transaction.eta
+    );
+    msg!("Instructions: {}, finalized: {}", transaction.instructions.len(), transaction.finalized);
+    msg!("Signatures: 1/{}", multisig_config.threshold);
+    
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::solana_program::bpf_loader_upgradeable;
+    use anchor_lang::solana_program::entrypoint::ProgramResult;
+    use anchor_lang::solana_program::program_stubs::{set_syscall_stubs, SyscallStubs};
+    use anchor_lang::Discriminator;
+    use std::sync::Once;
+
+    // Stands in for the runtime's CPI: adds a multisig member the way a self-invoked
+    // add_multisig_member would, writing straight into the shared account data
+    struct SelfCpiStubs;
+
+    impl SyscallStubs for SelfCpiStubs {
+        fn sol_invoke_signed(
+            &self,
+            instruction: &Instruction,
+            account_infos: &[AccountInfo],
+            _signers_seeds: &[&[&[u8]]],
+        ) -> ProgramResult {
+            if instruction.program_id == crate::ID &&
+                instruction.data[..8] == crate::instruction::AddMultisigMember::DISCRIMINATOR {
+                let member = Pubkey::try_from(&instruction.data[8..40]).unwrap();
+                let info = &account_infos[0];
+                let mut config = MultisigConfig::try_deserialize(&mut &info.try_borrow_data()?[..]).unwrap();
+                config.members.push(member);
+                config.try_serialize(&mut &mut info.try_borrow_mut_data()?[..]).unwrap();
+            }
+            Ok(())
+        }
+    }
+
+    fn install_stubs() {
+        static STUBS: Once = Once::new();
+        STUBS.call_once(|| {
+            set_syscall_stubs(Box::new(SelfCpiStubs));
+        });
+    }
+
+    fn add_member(multisig: Pubkey, member: Pubkey) -> TransactionInstruction {
+        let mut data = crate::instruction::AddMultisigMember::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(member.as_ref());
+        TransactionInstruction {
+            program_id: crate::ID,
+            accounts: vec![TransactionAccount { pubkey: multisig, is_signer: true, is_writable: true }],
+            data_len: data.len() as u32,
+            data,
+        }
+    }
+
+    #[test]
+    fn test_batch_executes_in_order_and_reloads_config() {
+        install_stubs();
+
+        let multisig_key = Pubkey::new_unique();
+        let original = Pubkey::new_unique();
+        let (first, second) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+        let mut data = vec![0u8; MultisigConfig::LEN];
+        MultisigConfig {
+            multisig_address: multisig_key,
+            threshold: 1,
+            members: vec![original],
+            transaction_count: 0,
+            initialized: true,
+            bump: 0,
+            pending_transactions: 0,
+            parameter_update_delay: 86400,
+            treasury_withdrawal_delay: 172800,
+            authority_transfer_delay: 259200,
+            _reserved: [0; 64],
+        }.try_serialize(&mut &mut data[..]).unwrap();
+        let mut lamports = 0;
+        let multisig_info = AccountInfo::new(
+            &multisig_key, true, true, &mut lamports, &mut data, &crate::ID, false, 0,
+        );
+        let mut program_lamports = 0;
+        let mut program_data = vec![];
+        let program_info = AccountInfo::new(
+            &crate::ID, false, false, &mut program_lamports, &mut program_data, &crate::ID, true, 0,
+        );
+
+        let mut multisig_config: Account<MultisigConfig> = Account::try_from(&multisig_info).unwrap();
+        let instructions = vec![add_member(multisig_key, first), add_member(multisig_key, second)];
+
+        // Accounts must match the stored keys, program first for each instruction
+        let swapped = [multisig_info.clone(), program_info.clone(), program_info.clone(), multisig_info.clone()];
+        assert!(invoke_transaction_instructions(&instructions, &swapped, &[], None).is_err());
+        assert!(invoke_transaction_instructions(&instructions, &swapped[..2], &[], None).is_err());
+
+        let remaining = [program_info.clone(), multisig_info.clone(), program_info, multisig_info.clone()];
+        invoke_transaction_instructions(&instructions, &remaining, &[], None).unwrap();
+
+        // The cached config predates the batch until it is reloaded
+        assert_eq!(multisig_config.members, vec![original]);
+        multisig_config.reload().unwrap();
+        assert_eq!(multisig_config.members, vec![original, first, second]);
+    }
+
+    #[test]
+    fn test_upgrade_rejects_buffer_that_does_not_match_approved_hash() {
+        install_stubs();
+
+        let (program, buffer, authority, spill) =
+            (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let upgrade = bpf_loader_upgradeable::upgrade(&program, &buffer, &authority, &spill);
+        let instructions = vec![TransactionInstruction {
+            program_id: upgrade.program_id,
+            accounts: upgrade.accounts.iter().map(|meta| TransactionAccount {
+                pubkey: meta.pubkey,
+                is_signer: meta.is_signer,
+                is_writable: meta.is_writable,
+            }).collect(),
+            data_len: upgrade.data.len() as u32,
+            data: upgrade.data.clone(),
+        }];
+
+        let header = UpgradeableLoaderState::size_of_buffer_metadata();
+        let approved = [[0u8; 37].as_slice(), b"approved bytecode"].concat();
+        let approved_hash = hash(&approved[header..]).to_bytes();
+
+        let run = |bytecode: &[u8], buffer_hash: Option<[u8; 32]>| {
+            let mut lamports = vec![0u64; upgrade.accounts.len() + 1];
+            let mut account_data: Vec<Vec<u8>> = upgrade.accounts.iter().map(|meta| {
+                if meta.pubkey == buffer { [&approved[..header], bytecode].concat() } else { vec![] }
+            }).collect();
+            let mut loader_data = vec![];
+            let (loader_lamports, account_lamports) = lamports.split_first_mut().unwrap();
+
+            let mut remaining = vec![AccountInfo::new(
+                &upgrade.program_id, false, false, loader_lamports, &mut loader_data,
+                &upgrade.program_id, true, 0,
+            )];
+            for ((meta, lamports), data) in upgrade.accounts.iter().zip(account_lamports).zip(account_data.iter_mut()) {
+                remaining.push(AccountInfo::new(
+                    &meta.pubkey, meta.is_signer, meta.is_writable, lamports, data,
+                    &upgrade.program_id, false, 0,
+                ));
+            }
+            invoke_transaction_instructions(&instructions, &remaining, &[], buffer_hash)
+        };
+
+        run(b"approved bytecode", Some(approved_hash)).unwrap();
+        assert_eq!(
+            run(b"swapped bytecode", Some(approved_hash)).unwrap_err(),
+            TwistError::BufferHashMismatch.into()
+        );
+        assert_eq!(
+            run(b"approved bytecode", None).unwrap_err(),
+            TwistError::BufferHashMismatch.into()
+        );
+    }
+}
@@ -15,6 +15,13 @@ pub struct UpdateOracle<'info> {
     )]
     pub program_state: Account<'info, ProgramState>,
     
+    #[account(
+        mut,
+        seeds = [MARKET_METRICS_SEED],
+        bump = market_metrics.bump,
+    )]
+    pub market_metrics: Box<Account<'info, MarketMetrics>>,
+    
     /// CHECK: Pyth price feed account
     #[account(
         constraint = pyth_price_account.key() == program_state.pyth_price_feed @ TwistError::InvalidOracle
@@ -68,6 +75,11 @@ pub fn handler(ctx: Context<UpdateOracle>) -> Result<()> {
     program_state.last_oracle_price = mock_price;
     program_state.last_oracle_update = clock.unix_timestamp;
     
+    // Record the observation in the rolling metrics
+    let market_metrics = &mut ctx.accounts.market_metrics;
+    market_metrics.record_price(clock.unix_timestamp, mock_price);
+    market_metrics.record_liquidity(clock.unix_timestamp, program_state.floor_liquidity);
+    
     // Calculate price change for monitoring
     let price_change_bps = if old_price > 0 {
         let change = if mock_price > old_price {
@@ -0,0 +1,566 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Burn, Mint, Token, TokenAccount};
+
+use crate::constants::*;
+use crate::errors::TwistError;
+use crate::events::*;
+use crate::state::*;
+use crate::utils::{transfer_tokens, transfer_tokens_with_signer};
+
+#[derive(Accounts)]
+pub struct InitializeReporterNetwork<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [PROGRAM_STATE_SEED],
+        bump,
+        constraint = program_state.authority == authority.key() @ TwistError::Unauthorized,
+    )]
+    pub program_state: Box<Account<'info, ProgramState>>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = ReporterNetwork::LEN,
+        seeds = [REPORTER_NETWORK_SEED],
+        bump
+    )]
+    pub reporter_network: Box<Account<'info, ReporterNetwork>>,
+    
+    #[account(
+        init,
+        payer = authority,
+        seeds = [REPORTER_BOND_VAULT_SEED],
+        bump,
+        token::mint = mint,
+        token::authority = reporter_network,
+    )]
+    pub bond_vault: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        constraint = mint.key() == program_state.mint @ TwistError::InvalidAccount,
+    )]
+    pub mint: Account<'info, Mint>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn initialize_reporter_network_handler(
+    ctx: Context<InitializeReporterNetwork>,
+    params: ReporterNetworkParams,
+) -> Result<()> {
+    require!(
+        params.is_valid(),
+        TwistError::InvalidAmount
+    );
+    
+    let reporter_network = &mut ctx.accounts.reporter_network;
+    reporter_network.bump = ctx.bumps.reporter_network;
+    reporter_network.bond_vault = ctx.accounts.bond_vault.key();
+    reporter_network.apply(&params);
+    reporter_network.round = 1;
+    reporter_network.round_start = Clock::get()?.unix_timestamp;
+    reporter_network.submissions = Vec::new();
+    reporter_network.last_price = 0;
+    reporter_network.last_confidence = 0;
+    reporter_network.last_finalized = 0;
+    reporter_network.active_reporters = 0;
+    reporter_network.total_bonded = 0;
+    reporter_network.total_slashed = 0;
+    
+    msg!("Reporter network initialized, bond vault: {}", reporter_network.bond_vault);
+    msg!("Rounds of {}s, {} TWIST bond, {} bps outlier threshold",
+        params.round_duration,
+        params.bond_amount as f64 / 10f64.powf(DECIMALS as f64),
+        params.outlier_threshold_bps
+    );
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateReporterNetwork<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [PROGRAM_STATE_SEED],
+        bump,
+        constraint = program_state.authority == authority.key() @ TwistError::Unauthorized,
+    )]
+    pub program_state: Box<Account<'info, ProgramState>>,
+    
+    #[account(
+        mut,
+        seeds = [REPORTER_NETWORK_SEED],
+        bump = reporter_network.bump,
+    )]
+    pub reporter_network: Box<Account<'info, ReporterNetwork>>,
+}
+
+pub fn update_reporter_network_handler(
+    ctx: Context<UpdateReporterNetwork>,
+    params: ReporterNetworkParams,
+) -> Result<()> {
+    require!(
+        params.is_valid(),
+        TwistError::InvalidAmount
+    );
+    
+    // Existing bonds are kept as posted; reporters below a raised amount top up before reporting
+    let reporter_network = &mut ctx.accounts.reporter_network;
+    let old_value = format!("stake={}, bond={}, round={}s, min_reporters={}, outlier={}bps, slash={}bps",
+        reporter_network.min_stake,
+        reporter_network.bond_amount,
+        reporter_network.round_duration,
+        reporter_network.min_reporters,
+        reporter_network.outlier_threshold_bps,
+        reporter_network.slash_bps
+    );
+    reporter_network.apply(&params);
+    
+    emit!(ParameterUpdated {
+        parameter: "reporter_network".to_string(),
+        old_value,
+        new_value: format!("stake={}, bond={}, round={}s, min_reporters={}, outlier={}bps, slash={}bps",
+            params.min_stake,
+            params.bond_amount,
+            params.round_duration,
+            params.min_reporters,
+            params.outlier_threshold_bps,
+            params.slash_bps
+        ),
+        updated_by: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RegisterReporter<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [REPORTER_NETWORK_SEED],
+        bump = reporter_network.bump,
+    )]
+    pub reporter_network: Box<Account<'info, ReporterNetwork>>,
+    
+    #[account(
+        init,
+        payer = owner,
+        space = PriceReporter::LEN,
+        seeds = [PRICE_REPORTER_SEED, owner.key().as_ref()],
+        bump
+    )]
+    pub price_reporter: Box<Account<'info, PriceReporter>>,
+    
+    #[account(
+        seeds = [STAKE_STATE_SEED, owner.key().as_ref()],
+        bump = stake_state.bump,
+        constraint = stake_state.total_staked >= reporter_network.min_stake @ TwistError::InsufficientReporterStake,
+    )]
+    pub stake_state: Box<Account<'info, StakeState>>,
+    
+    #[account(
+        mut,
+        constraint = owner_token_account.owner == owner.key() @ TwistError::Unauthorized,
+    )]
+    pub owner_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        address = reporter_network.bond_vault @ TwistError::InvalidAccount,
+    )]
+    pub bond_vault: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn register_reporter_handler(ctx: Context<RegisterReporter>) -> Result<()> {
+    let clock = Clock::get()?;
+    let bond = ctx.accounts.reporter_network.bond_amount;
+    
+    transfer_tokens(
+        &ctx.accounts.owner_token_account,
+        &ctx.accounts.bond_vault,
+        &ctx.accounts.owner,
+        &ctx.accounts.token_program,
+        bond,
+    )?;
+    
+    let price_reporter = &mut ctx.accounts.price_reporter;
+    price_reporter.owner = ctx.accounts.owner.key();
+    price_reporter.bond = bond;
+    price_reporter.last_round = 0;
+    price_reporter.reports_submitted = 0;
+    price_reporter.times_slashed = 0;
+    price_reporter.total_slashed = 0;
+    price_reporter.registered_at = clock.unix_timestamp;
+    price_reporter.bump = ctx.bumps.price_reporter;
+    
+    let reporter_network = &mut ctx.accounts.reporter_network;
+    reporter_network.active_reporters += 1;
+    reporter_network.total_bonded = reporter_network.total_bonded.saturating_add(bond);
+    
+    emit!(ReporterRegistered {
+        reporter: price_reporter.owner,
+        bond,
+        stake: ctx.accounts.stake_state.total_staked,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Reporter {} registered with a {} TWIST bond",
+        price_reporter.owner,
+        bond as f64 / 10f64.powf(DECIMALS as f64)
+    );
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct TopUpReporterBond<'info> {
+    pub owner: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [REPORTER_NETWORK_SEED],
+        bump = reporter_network.bump,
+    )]
+    pub reporter_network: Box<Account<'info, ReporterNetwork>>,
+    
+    #[account(
+        mut,
+        seeds = [PRICE_REPORTER_SEED, owner.key().as_ref()],
+        bump = price_reporter.bump,
+    )]
+    pub price_reporter: Box<Account<'info, PriceReporter>>,
+    
+    #[account(
+        mut,
+        constraint = owner_token_account.owner == owner.key() @ TwistError::Unauthorized,
+    )]
+    pub owner_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        address = reporter_network.bond_vault @ TwistError::InvalidAccount,
+    )]
+    pub bond_vault: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+/// Restore the bond to the network's current bond amount after slashing or a raise
+pub fn top_up_reporter_bond_handler(ctx: Context<TopUpReporterBond>) -> Result<()> {
+    let clock = Clock::get()?;
+    let shortfall = ctx.accounts.reporter_network.bond_amount
+        .saturating_sub(ctx.accounts.price_reporter.bond);
+    
+    require!(shortfall > 0, TwistError::InvalidAmount);
+    
+    transfer_tokens(
+        &ctx.accounts.owner_token_account,
+        &ctx.accounts.bond_vault,
+        &ctx.accounts.owner,
+        &ctx.accounts.token_program,
+        shortfall,
+    )?;
+    
+    let price_reporter = &mut ctx.accounts.price_reporter;
+    price_reporter.bond += shortfall;
+    
+    let reporter_network = &mut ctx.accounts.reporter_network;
+    reporter_network.total_bonded = reporter_network.total_bonded.saturating_add(shortfall);
+    
+    emit!(ReporterBondToppedUp {
+        reporter: price_reporter.owner,
+        amount: shortfall,
+        bond: price_reporter.bond,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Reporter {} bond topped up by {} TWIST",
+        price_reporter.owner,
+        shortfall as f64 / 10f64.powf(DECIMALS as f64)
+    );
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SubmitPrice<'info> {
+    pub owner: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [REPORTER_NETWORK_SEED],
+        bump = reporter_network.bump,
+    )]
+    pub reporter_network: Box<Account<'info, ReporterNetwork>>,
+    
+    // A bond slashed below the current amount must be topped up before reporting again
+    #[account(
+        mut,
+        seeds = [PRICE_REPORTER_SEED, owner.key().as_ref()],
+        bump = price_reporter.bump,
+        constraint = price_reporter.bond >= reporter_network.bond_amount @ TwistError::InsufficientReporterBond,
+    )]
+    pub price_reporter: Box<Account<'info, PriceReporter>>,
+    
+    // Weight is the stake held at submission
+    #[account(
+        seeds = [STAKE_STATE_SEED, owner.key().as_ref()],
+        bump = stake_state.bump,
+        constraint = stake_state.total_staked > 0 @ TwistError::InsufficientReporterStake,
+        constraint = stake_state.total_staked >= reporter_network.min_stake @ TwistError::InsufficientReporterStake,
+    )]
+    pub stake_state: Box<Account<'info, StakeState>>,
+}
+
+pub fn submit_price_handler(ctx: Context<SubmitPrice>, price: u64) -> Result<()> {
+    let reporter_network = &mut ctx.accounts.reporter_network;
+    let price_reporter = &mut ctx.accounts.price_reporter;
+    let clock = Clock::get()?;
+    
+    require!(price > 0, TwistError::InvalidAmount);
+    require!(
+        reporter_network.round_open(clock.unix_timestamp),
+        TwistError::ReportRoundClosed
+    );
+    require!(
+        price_reporter.last_round < reporter_network.round,
+        TwistError::AlreadyReported
+    );
+    
+    let weight = ctx.accounts.stake_state.total_staked;
+    reporter_network.submit(price_reporter.owner, price, weight)?;
+    price_reporter.last_round = reporter_network.round;
+    price_reporter.reports_submitted += 1;
+    
+    emit!(PriceReported {
+        reporter: price_reporter.owner,
+        round: reporter_network.round,
+        price,
+        weight,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct FinalizeReportRound<'info> {
+    /// Anyone may close a round once its submission window has passed
+    pub keeper: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+    )]
+    pub program_state: Box<Account<'info, ProgramState>>,
+    
+    #[account(
+        mut,
+        seeds = [REPORTER_NETWORK_SEED],
+        bump = reporter_network.bump,
+    )]
+    pub reporter_network: Box<Account<'info, ReporterNetwork>>,
+    
+    #[account(
+        mut,
+        address = reporter_network.bond_vault @ TwistError::InvalidAccount,
+    )]
+    pub bond_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = mint.key() == program_state.mint @ TwistError::InvalidAccount,
+    )]
+    pub mint: Account<'info, Mint>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+/// Settle the round on its stake-weighted median. The `PriceReporter` account of every
+/// outlier must be passed in remaining accounts; their slashed bonds are burned.
+pub fn finalize_report_round_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, FinalizeReportRound<'info>>,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    
+    require!(
+        !ctx.accounts.reporter_network.round_open(clock.unix_timestamp),
+        TwistError::ReportRoundOpen
+    );
+    
+    let round = ctx.accounts.reporter_network.round;
+    let submissions = ctx.accounts.reporter_network.submissions.len();
+    let Some(result) = ctx.accounts.reporter_network.settle() else {
+        msg!("Round {} closed with {} of {} required reports, no price set",
+            round,
+            submissions,
+            ctx.accounts.reporter_network.min_reporters
+        );
+        ctx.accounts.reporter_network.start_next_round(clock.unix_timestamp);
+        return Ok(());
+    };
+    
+    // Slash each outlier exactly once
+    let slash_bps = ctx.accounts.reporter_network.slash_bps;
+    let mut slashed_reporters: Vec<Pubkey> = Vec::new();
+    let mut slashed_total: u64 = 0;
+    for account in ctx.remaining_accounts {
+        let mut price_reporter = Account::<PriceReporter>::try_from(account)?;
+        require!(
+            result.outliers.contains(&price_reporter.owner) && !slashed_reporters.contains(&price_reporter.owner),
+            TwistError::InvalidAccount
+        );
+        
+        let amount = price_reporter.slash(slash_bps);
+        price_reporter.exit(ctx.program_id)?;
+        slashed_reporters.push(price_reporter.owner);
+        slashed_total = slashed_total.saturating_add(amount);
+        
+        emit!(ReporterSlashed {
+            reporter: price_reporter.owner,
+            round,
+            amount,
+            remaining_bond: price_reporter.bond,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+    require!(
+        slashed_reporters.len() == result.outliers.len(),
+        TwistError::InvalidAccount
+    );
+    
+    if slashed_total > 0 {
+        let seeds = &[
+            REPORTER_NETWORK_SEED,
+            &[ctx.accounts.reporter_network.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+        
+        let cpi_accounts = Burn {
+            mint: ctx.accounts.mint.to_account_info(),
+            from: ctx.accounts.bond_vault.to_account_info(),
+            authority: ctx.accounts.reporter_network.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        
+        token::burn(cpi_ctx, slashed_total)?;
+        
+        let program_state = &mut ctx.accounts.program_state;
+        program_state.total_burned = program_state.total_burned.saturating_add(slashed_total as u128);
+    }
+    
+    let reporter_network = &mut ctx.accounts.reporter_network;
+    reporter_network.last_price = result.price;
+    reporter_network.last_confidence = result.confidence;
+    reporter_network.last_finalized = clock.unix_timestamp;
+    reporter_network.total_bonded = reporter_network.total_bonded.saturating_sub(slashed_total);
+    reporter_network.total_slashed = reporter_network.total_slashed.saturating_add(slashed_total);
+    reporter_network.start_next_round(clock.unix_timestamp);
+    
+    emit!(ReportRoundFinalized {
+        round,
+        price: result.price,
+        confidence: result.confidence,
+        reports: submissions as u8,
+        outliers: result.outliers.len() as u8,
+        slashed: slashed_total,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Round {} settled at ${} from {} reports",
+        round,
+        result.price as f64 / 1e6,
+        submissions
+    );
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct DeregisterReporter<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [REPORTER_NETWORK_SEED],
+        bump = reporter_network.bump,
+    )]
+    pub reporter_network: Box<Account<'info, ReporterNetwork>>,
+    
+    // A report still awaiting settlement keeps the bond at stake
+    #[account(
+        mut,
+        close = owner,
+        seeds = [PRICE_REPORTER_SEED, owner.key().as_ref()],
+        bump = price_reporter.bump,
+        constraint = price_reporter.last_round < reporter_network.round @ TwistError::ReportRoundOpen,
+    )]
+    pub price_reporter: Box<Account<'info, PriceReporter>>,
+    
+    #[account(
+        mut,
+        address = reporter_network.bond_vault @ TwistError::InvalidAccount,
+    )]
+    pub bond_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        token::mint = bond_vault.mint,
+    )]
+    pub owner_token_account: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn deregister_reporter_handler(ctx: Context<DeregisterReporter>) -> Result<()> {
+    let bond = ctx.accounts.price_reporter.bond;
+    
+    if bond > 0 {
+        let seeds = &[
+            REPORTER_NETWORK_SEED,
+            &[ctx.accounts.reporter_network.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+        
+        transfer_tokens_with_signer(
+            &ctx.accounts.bond_vault,
+            &ctx.accounts.owner_token_account,
+            &ctx.accounts.reporter_network.to_account_info(),
+            &ctx.accounts.token_program,
+            bond,
+            signer_seeds,
+        )?;
+    }
+    
+    let reporter_network = &mut ctx.accounts.reporter_network;
+    reporter_network.active_reporters = reporter_network.active_reporters.saturating_sub(1);
+    reporter_network.total_bonded = reporter_network.total_bonded.saturating_sub(bond);
+    
+    emit!(ReporterDeregistered {
+        reporter: ctx.accounts.owner.key(),
+        bond_returned: bond,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    msg!("Reporter {} deregistered, {} TWIST bond returned",
+        ctx.accounts.owner.key(),
+        bond as f64 / 10f64.powf(DECIMALS as f64)
+    );
+    
+    Ok(())
+}
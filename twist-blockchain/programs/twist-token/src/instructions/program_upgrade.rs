@@ -0,0 +1,212 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::bpf_loader_upgradeable::{self, UpgradeableLoaderState};
+use anchor_lang::solana_program::instruction::Instruction;
+
+use crate::errors::TwistError;
+use crate::instructions::multisig::{hash_buffer, init_transaction};
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct ProposeProgramUpgrade<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"multisig"],
+        bump,
+        constraint = multisig_config.initialized @ TwistError::InvalidAccount,
+        constraint = multisig_config.is_member(&proposer.key()) @ TwistError::Unauthorized,
+    )]
+    pub multisig_config: Account<'info, MultisigConfig>,
+    
+    #[account(
+        init,
+        payer = proposer,
+        space = MultisigTransaction::PROGRAM_UPGRADE_LEN,
+        seeds = [
+            b"multisig_transaction",
+            multisig_config.key().as_ref(),
+            &multisig_config.transaction_count.to_le_bytes()
+        ],
+        bump
+    )]
+    pub transaction: Account<'info, MultisigTransaction>,
+    
+    /// CHECK: Upgradeable program, validated through its program data account
+    #[account(
+        executable,
+        owner = bpf_loader_upgradeable::ID @ TwistError::InvalidAccount,
+    )]
+    pub program: AccountInfo<'info>,
+    
+    #[account(
+        seeds = [program.key().as_ref()],
+        bump,
+        seeds::program = bpf_loader_upgradeable::ID,
+        constraint = program_data.upgrade_authority_address == Some(multisig_config.key()) @ TwistError::MultisigRequired,
+    )]
+    pub program_data: Account<'info, ProgramData>,
+    
+    /// CHECK: Loader buffer holding the new bytecode, validated in the handler
+    #[account(
+        owner = bpf_loader_upgradeable::ID @ TwistError::InvalidAccount,
+    )]
+    pub buffer: AccountInfo<'info>,
+    
+    /// CHECK: Receives the buffer's lamports after the upgrade
+    pub spill: AccountInfo<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn propose_program_upgrade_handler(
+    ctx: Context<ProposeProgramUpgrade>,
+    title: String,
+    description: String,
+) -> Result<()> {
+    let multisig_key = ctx.accounts.multisig_config.key();
+    
+    // Only the multisig may write to the buffer, so the hash cannot change before execution
+    let buffer_state = UpgradeableLoaderState::try_deserialize(
+        &mut &ctx.accounts.buffer.try_borrow_data()?[..]
+    )?;
+    match buffer_state {
+        UpgradeableLoaderState::Buffer { authority_address } => {
+            require!(
+                authority_address == Some(multisig_key),
+                TwistError::MultisigRequired
+            );
+        }
+        _ => return err!(TwistError::InvalidAccount),
+    }
+    
+    let buffer_hash = hash_buffer(&ctx.accounts.buffer)?;
+    
+    let upgrade_ix = bpf_loader_upgradeable::upgrade(
+        &ctx.accounts.program.key(),
+        &ctx.accounts.buffer.key(),
+        &multisig_key,
+        &ctx.accounts.spill.key(),
+    );
+    
+    let delay_seconds = ctx.accounts.multisig_config.authority_transfer_delay;
+    let bump = ctx.bumps.transaction;
+    init_transaction(
+        &mut ctx.accounts.multisig_config,
+        &mut ctx.accounts.transaction,
+        ctx.accounts.proposer.key(),
+        vec![to_transaction_instruction(upgrade_ix)],
+        title,
+        description,
+        delay_seconds,
+        bump,
+    )?;
+    
+    ctx.accounts.transaction.buffer_hash = Some(buffer_hash);
+    
+    msg!("Program upgrade proposed for {}", ctx.accounts.program.key());
+    msg!("Buffer: {}, hash: {:?}", ctx.accounts.buffer.key(), buffer_hash);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ProposeUpgradeAuthority<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"multisig"],
+        bump,
+        constraint = multisig_config.initialized @ TwistError::InvalidAccount,
+        constraint = multisig_config.is_member(&proposer.key()) @ TwistError::Unauthorized,
+    )]
+    pub multisig_config: Account<'info, MultisigConfig>,
+    
+    #[account(
+        init,
+        payer = proposer,
+        space = MultisigTransaction::PROGRAM_UPGRADE_LEN,
+        seeds = [
+            b"multisig_transaction",
+            multisig_config.key().as_ref(),
+            &multisig_config.transaction_count.to_le_bytes()
+        ],
+        bump
+    )]
+    pub transaction: Account<'info, MultisigTransaction>,
+    
+    /// CHECK: Upgradeable program, validated through its program data account
+    #[account(
+        executable,
+        owner = bpf_loader_upgradeable::ID @ TwistError::InvalidAccount,
+    )]
+    pub program: AccountInfo<'info>,
+    
+    #[account(
+        seeds = [program.key().as_ref()],
+        bump,
+        seeds::program = bpf_loader_upgradeable::ID,
+        constraint = program_data.upgrade_authority_address == Some(multisig_config.key()) @ TwistError::MultisigRequired,
+    )]
+    pub program_data: Account<'info, ProgramData>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn propose_upgrade_authority_handler(
+    ctx: Context<ProposeUpgradeAuthority>,
+    new_authority: Option<Pubkey>,
+    title: String,
+    description: String,
+) -> Result<()> {
+    let multisig_key = ctx.accounts.multisig_config.key();
+    
+    // A None authority makes the program immutable
+    let set_authority_ix = bpf_loader_upgradeable::set_upgrade_authority(
+        &ctx.accounts.program.key(),
+        &multisig_key,
+        new_authority.as_ref(),
+    );
+    
+    let delay_seconds = ctx.accounts.multisig_config.authority_transfer_delay;
+    let bump = ctx.bumps.transaction;
+    init_transaction(
+        &mut ctx.accounts.multisig_config,
+        &mut ctx.accounts.transaction,
+        ctx.accounts.proposer.key(),
+        vec![to_transaction_instruction(set_authority_ix)],
+        title,
+        description,
+        delay_seconds,
+        bump,
+    )?;
+    
+    msg!("Upgrade authority change proposed for {}", ctx.accounts.program.key());
+    match new_authority {
+        Some(authority) => msg!("New authority: {}", authority),
+        None => msg!("New authority: none (program becomes immutable)"),
+    }
+    
+    Ok(())
+}
+
+// Helper functions
+
+fn to_transaction_instruction(ix: Instruction) -> TransactionInstruction {
+    TransactionInstruction {
+        program_id: ix.program_id,
+        accounts: ix.accounts
+            .into_iter()
+            .map(|meta| TransactionAccount {
+                pubkey: meta.pubkey,
+                is_signer: meta.is_signer,
+                is_writable: meta.is_writable,
+            })
+            .collect(),
+        data_len: ix.data.len() as u32,
+        data: ix.data,
+    }
+}
@@ -0,0 +1,147 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+use crate::constants::*;
+use crate::events::*;
+use crate::errors::TwistError;
+use crate::instructions::circuit_breaker::{severity_to_u8, trip_circuit_breaker};
+use crate::state::{
+    ProgramState, CircuitBreakerState, CircuitBreakerLog, BreakerAction,
+    TripCondition, TreasuryPolicy, reserve_divergence_bps,
+};
+
+#[derive(Accounts)]
+pub struct ReconcileReserves<'info> {
+    /// Anyone may reconcile
+    pub reconciler: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump,
+    )]
+    pub program_state: Box<Account<'info, ProgramState>>,
+    
+    #[account(
+        mut,
+        seeds = [b"circuit_breaker"],
+        bump,
+    )]
+    pub circuit_breaker: Box<Account<'info, CircuitBreakerState>>,
+    
+    #[account(
+        mut,
+        seeds = [CIRCUIT_BREAKER_LOG_SEED],
+        bump = circuit_breaker_log.bump,
+    )]
+    pub circuit_breaker_log: Box<Account<'info, CircuitBreakerLog>>,
+    
+    #[account(
+        seeds = [TREASURY_POLICY_SEED],
+        bump = treasury_policy.bump,
+    )]
+    pub treasury_policy: Box<Account<'info, TreasuryPolicy>>,
+    
+    #[account(
+        address = treasury_policy.floor_usdc_account @ TwistError::InvalidAccount,
+    )]
+    pub floor_usdc_account: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        seeds = [FLOOR_TREASURY_SEED],
+        bump,
+        token::mint = program_state.mint,
+        token::authority = program_state,
+    )]
+    pub floor_treasury: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        seeds = [STAKE_VAULT_SEED],
+        bump,
+        token::mint = program_state.mint,
+        token::authority = program_state,
+    )]
+    pub stake_vault: Box<Account<'info, TokenAccount>>,
+}
+
+pub fn reconcile_reserves_handler(ctx: Context<ReconcileReserves>) -> Result<()> {
+    let program_state = &mut ctx.accounts.program_state;
+    let circuit_breaker = &mut ctx.accounts.circuit_breaker;
+    let clock = Clock::get()?;
+    
+    // Floor liquidity is USDC and must match the floor USDC account in both directions
+    let floor_usdc_balance = ctx.accounts.floor_usdc_account.amount;
+    let floor_divergence_bps = reserve_divergence_bps(
+        program_state.floor_liquidity as u128,
+        floor_usdc_balance as u128,
+    );
+    
+    // The stake vault also receives fee allocations, so only a shortfall diverges
+    let stake_vault_balance = ctx.accounts.stake_vault.amount;
+    let stake_divergence_bps = if stake_vault_balance as u128 >= program_state.total_staked {
+        0
+    } else {
+        reserve_divergence_bps(program_state.total_staked, stake_vault_balance as u128)
+    };
+    
+    let floor_severity = circuit_breaker.check_reserve_divergence(floor_divergence_bps);
+    let stake_severity = circuit_breaker.check_reserve_divergence(stake_divergence_bps);
+    let (severity, reason) = match (floor_severity, stake_severity) {
+        (Some(floor), Some(stake)) if severity_to_u8(&stake) > severity_to_u8(&floor) => {
+            (Some(stake), format!("stake vault short {} bps", stake_divergence_bps))
+        },
+        (Some(floor), _) => (Some(floor), format!("floor liquidity off {} bps", floor_divergence_bps)),
+        (None, Some(stake)) => (Some(stake), format!("stake vault short {} bps", stake_divergence_bps)),
+        (None, None) => (None, String::new()),
+    };
+    
+    let breaker_tripped = severity.is_some() && !program_state.circuit_breaker_active;
+    
+    emit!(ReservesReconciled {
+        floor_liquidity_recorded: program_state.floor_liquidity,
+        floor_usdc_balance,
+        floor_delta: floor_usdc_balance as i128 - program_state.floor_liquidity as i128,
+        floor_divergence_bps,
+        floor_twist_balance: ctx.accounts.floor_treasury.amount,
+        total_staked_recorded: program_state.total_staked,
+        stake_vault_balance,
+        stake_delta: stake_vault_balance as i128 - program_state.total_staked as i128,
+        stake_divergence_bps,
+        total_bought_back: program_state.total_bought_back,
+        threshold_bps: circuit_breaker.reserve_divergence_threshold_bps,
+        breaker_tripped,
+        reconciled_by: ctx.accounts.reconciler.key(),
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Reserves reconciled: floor {} bps, stake vault {} bps",
+        floor_divergence_bps,
+        stake_divergence_bps
+    );
+    
+    if let Some(severity) = severity {
+        if !breaker_tripped {
+            msg!("Reserve divergence ({}) while circuit breaker already active", reason);
+            return Ok(());
+        }
+        
+        ctx.accounts.circuit_breaker_log.append(
+            BreakerAction::Trip,
+            severity.clone(),
+            TripCondition::ReserveDivergence,
+            ctx.accounts.reconciler.key(),
+            reason,
+            clock.unix_timestamp,
+        )?;
+        
+        trip_circuit_breaker(
+            program_state,
+            circuit_breaker,
+            severity,
+            TripCondition::ReserveDivergence,
+            clock.unix_timestamp,
+        )?;
+    }
+    
+    Ok(())
+}
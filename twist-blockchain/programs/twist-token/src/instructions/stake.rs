@@ -4,6 +4,7 @@ use crate::state::*;
 use crate::constants::*;
 use crate::errors::TwistError;
 use crate::events::*;
+use crate::instructions::fee_collector::staking_reward_per_share;
 use crate::utils::validate_amount;
 
 #[derive(Accounts)]
@@ -11,10 +12,14 @@ pub struct Stake<'info> {
     #[account(mut)]
     pub owner: Signer<'info>,
     
+    // Accounts from before the fee reward fields are grown on first use
     #[account(
         mut,
         seeds = [STAKE_STATE_SEED, owner.key().as_ref()],
-        bump
+        bump,
+        realloc = StakeState::LEN,
+        realloc::payer = owner,
+        realloc::zero = false,
     )]
     pub stake_state: Box<Account<'info, StakeState>>,
     
@@ -41,6 +46,13 @@ pub struct Stake<'info> {
     )]
     pub stake_vault: Account<'info, TokenAccount>,
     
+    // Required once the fee collector is initialized
+    #[account(
+        seeds = [b"fee_collector"],
+        bump = fee_collector.bump,
+    )]
+    pub fee_collector: Option<Box<Account<'info, FeeCollectorState>>>,
+    
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
@@ -76,6 +88,8 @@ pub fn handler(
         stake_state.total_earned = 0;
         stake_state.stakes = Vec::new();
         stake_state.is_initialized = true;
+        stake_state.fee_reward_debt = 0;
+        stake_state.unclaimed_fee_rewards = 0;
         
         // Increment total users
         program_state.total_users += 1;
@@ -100,9 +114,14 @@ pub fn handler(
         withdrawn: false,
     };
     
+    // Settle fee rewards on the existing stake before its weight changes
+    let reward_per_share = staking_reward_per_share(&ctx.accounts.fee_collector, program_state)?;
+    stake_state.settle_fee_rewards(reward_per_share);
+    
     // Add to user's stakes
     stake_state.stakes.push(stake_entry);
     stake_state.total_staked = stake_state.total_staked.saturating_add(amount);
+    stake_state.reset_fee_reward_debt(reward_per_share);
     
     // Update global metrics
     program_state.total_staked = program_state.total_staked.saturating_add(amount as u128);
@@ -135,4 +154,56 @@ pub fn handler(
     );
     
     Ok(())
-}
\ No newline at end of file
+}
+
+#[derive(Accounts)]
+pub struct MigrateStakeState<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    /// CHECK: Still in the original layout and possibly too full to load, so owner and
+    /// discriminator are checked in handler
+    #[account(
+        mut,
+        seeds = [STAKE_STATE_SEED, owner.key().as_ref()],
+        bump,
+    )]
+    pub stake_state: AccountInfo<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn migrate_stake_state_handler(ctx: Context<MigrateStakeState>) -> Result<()> {
+    let stake_state = &ctx.accounts.stake_state;
+    
+    require_keys_eq!(*stake_state.owner, crate::ID, TwistError::InvalidAccount);
+    require!(
+        stake_state.data_len() == StakeState::ORIGINAL_LEN &&
+        stake_state.try_borrow_data()?[..8] == <StakeState as anchor_lang::Discriminator>::DISCRIMINATOR,
+        TwistError::InvalidAccount
+    );
+    
+    // A full account has no room left for the fee reward fields. They are appended
+    // zeroed: no debt, so the stake is credited everything the accumulator has
+    // allocated to it since fee rewards began.
+    let rent_due = Rent::get()?
+        .minimum_balance(StakeState::LEN)
+        .saturating_sub(stake_state.lamports());
+    if rent_due > 0 {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.owner.to_account_info(),
+                    to: stake_state.to_account_info(),
+                },
+            ),
+            rent_due,
+        )?;
+    }
+    stake_state.realloc(StakeState::LEN, true)?;
+    
+    msg!("Stake state grown to {} bytes", StakeState::LEN);
+    
+    Ok(())
+}
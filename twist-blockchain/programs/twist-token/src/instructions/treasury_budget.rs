@@ -0,0 +1,214 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+
+use crate::constants::*;
+use crate::errors::TwistError;
+use crate::events::*;
+use crate::instructions::treasury_ops::withdraw_from_ops_treasury;
+use crate::state::*;
+
+#[derive(Accounts)]
+#[instruction(params: TreasuryBudgetParams)]
+pub struct CreateTreasuryBudget<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [PROGRAM_STATE_SEED],
+        bump,
+        constraint = program_state.authority == authority.key() @ TwistError::Unauthorized,
+    )]
+    pub program_state: Account<'info, ProgramState>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = TreasuryBudget::LEN,
+        seeds = [TREASURY_BUDGET_SEED, params.name.as_bytes()],
+        bump
+    )]
+    pub budget: Account<'info, TreasuryBudget>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_treasury_budget_handler(
+    ctx: Context<CreateTreasuryBudget>,
+    params: TreasuryBudgetParams,
+) -> Result<()> {
+    let budget = &mut ctx.accounts.budget;
+    let clock = Clock::get()?;
+    
+    require!(
+        !params.name.is_empty() && params.name.len() <= TreasuryBudget::MAX_NAME_LEN,
+        TwistError::InvalidAmount
+    );
+    require!(
+        params.stream_total > 0 && params.stream_end > params.stream_start,
+        TwistError::InvalidAmount
+    );
+    require!(
+        params.epoch_duration > 0 && params.cap_per_epoch > 0,
+        TwistError::InvalidAmount
+    );
+    
+    // Budgets are paid from the operations treasury
+    budget.name = params.name;
+    budget.treasury_type = TreasuryType::Operations;
+    budget.recipient = params.recipient;
+    budget.stream_total = params.stream_total;
+    budget.stream_start = params.stream_start;
+    budget.stream_end = params.stream_end;
+    budget.stream_withdrawn = 0;
+    budget.epoch_duration = params.epoch_duration;
+    budget.cap_per_epoch = params.cap_per_epoch;
+    budget.epoch_start = params.stream_start;
+    budget.epoch_withdrawn = 0;
+    budget.bump = ctx.bumps.budget;
+    
+    emit!(TreasuryBudgetCreated {
+        name: budget.name.clone(),
+        recipient: budget.recipient,
+        stream_total: budget.stream_total,
+        stream_start: budget.stream_start,
+        stream_end: budget.stream_end,
+        cap_per_epoch: budget.cap_per_epoch,
+        epoch_duration: budget.epoch_duration,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Created budget '{}' for {}", budget.name, budget.recipient);
+    msg!("Streaming {} from {} to {}, capped at {} per {} seconds",
+        budget.stream_total,
+        budget.stream_start,
+        budget.stream_end,
+        budget.cap_per_epoch,
+        budget.epoch_duration
+    );
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct WithdrawFromBudget<'info> {
+    pub recipient: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [TREASURY_BUDGET_SEED, budget.name.as_bytes()],
+        bump = budget.bump,
+        constraint = budget.recipient == recipient.key() @ TwistError::Unauthorized,
+    )]
+    pub budget: Account<'info, TreasuryBudget>,
+    
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump,
+        constraint = !program_state.emergency_pause @ TwistError::EmergencyPauseActive,
+    )]
+    pub program_state: Account<'info, ProgramState>,
+    
+    #[account(
+        mut,
+        seeds = [TREASURY_STATE_SEED, TreasuryType::Operations.seed().as_ref()],
+        bump = treasury_state.bump,
+    )]
+    pub treasury_state: Account<'info, TreasuryState>,
+    
+    #[account(
+        mut,
+        seeds = [OPS_TREASURY_SEED],
+        bump,
+        token::mint = program_state.mint,
+        token::authority = program_state,
+    )]
+    pub ops_treasury: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        token::mint = program_state.mint,
+        token::authority = recipient,
+    )]
+    pub recipient_account: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn withdraw_from_budget_handler(
+    ctx: Context<WithdrawFromBudget>,
+    amount: u64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    
+    let available = ctx.accounts.budget.available(clock.unix_timestamp);
+    require!(
+        amount > 0 && amount <= available,
+        TwistError::BudgetExhausted
+    );
+    
+    ctx.accounts.treasury_state.consume_daily_limit(amount, clock.unix_timestamp)?;
+    
+    withdraw_from_ops_treasury(
+        &ctx.accounts.program_state,
+        &ctx.accounts.ops_treasury,
+        &ctx.accounts.recipient_account,
+        &ctx.accounts.token_program,
+        ctx.accounts.recipient.key(),
+        amount,
+        format!("budget:{}", ctx.accounts.budget.name),
+    )?;
+    
+    let budget = &mut ctx.accounts.budget;
+    budget.record_withdrawal(amount, clock.unix_timestamp);
+    
+    msg!("Budget '{}': {} of {} streamed so far",
+        budget.name,
+        budget.stream_withdrawn,
+        budget.stream_total
+    );
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CloseTreasuryBudget<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [PROGRAM_STATE_SEED],
+        bump,
+        constraint = program_state.authority == authority.key() @ TwistError::Unauthorized,
+    )]
+    pub program_state: Account<'info, ProgramState>,
+    
+    #[account(
+        mut,
+        close = authority,
+        seeds = [TREASURY_BUDGET_SEED, budget.name.as_bytes()],
+        bump = budget.bump,
+    )]
+    pub budget: Account<'info, TreasuryBudget>,
+}
+
+/// Closing a budget cancels whatever has not been withdrawn yet
+pub fn close_treasury_budget_handler(ctx: Context<CloseTreasuryBudget>) -> Result<()> {
+    let budget = &ctx.accounts.budget;
+    let clock = Clock::get()?;
+    
+    emit!(ParameterUpdated {
+        parameter: format!("treasury_budget_{}", budget.name),
+        old_value: format!("withdrawn {} of {}", budget.stream_withdrawn, budget.stream_total),
+        new_value: "closed".to_string(),
+        updated_by: ctx.accounts.authority.key(),
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Closed budget '{}', {} left unstreamed",
+        budget.name,
+        budget.stream_total.saturating_sub(budget.stream_withdrawn)
+    );
+    
+    Ok(())
+}
@@ -5,6 +5,9 @@ use crate::constants::*;
 use crate::errors::TwistError;
 use crate::events::*;
 use crate::state::*;
+use crate::utils::{execute_route, oracle_min_swap_output, plan_registry_route, MEVProtection, TransactionType};
+use crate::defi::SwapEndpoints;
+use crate::instructions::dex_oracle::observe_pool;
 
 #[derive(Accounts)]
 #[instruction(amount: u64, purpose: String)]
@@ -37,6 +40,13 @@ pub struct WithdrawTreasury<'info> {
     )]
     pub recipient_account: Account<'info, TokenAccount>,
     
+    #[account(
+        mut,
+        seeds = [TREASURY_STATE_SEED, TreasuryType::Operations.seed().as_ref()],
+        bump = treasury_state.bump,
+    )]
+    pub treasury_state: Account<'info, TreasuryState>,
+    
     pub token_program: Program<'info, Token>,
 }
 
@@ -45,8 +55,37 @@ pub fn withdraw_handler(
     amount: u64,
     purpose: String,
 ) -> Result<()> {
-    let program_state = &ctx.accounts.program_state;
-    let ops_treasury = &ctx.accounts.ops_treasury;
+    // Withdrawals large enough to be delayed go through reveal_treasury_withdrawal
+    let usdc_value = ctx.accounts.program_state.twist_to_usdc_value(amount);
+    require!(
+        !MEVProtection::should_delay_transaction(usdc_value, TransactionType::Withdrawal).0,
+        TwistError::CommitRevealRequired
+    );
+    
+    ctx.accounts.treasury_state.consume_daily_limit(amount, Clock::get()?.unix_timestamp)?;
+    
+    withdraw_from_ops_treasury(
+        &ctx.accounts.program_state,
+        &ctx.accounts.ops_treasury,
+        &ctx.accounts.recipient_account,
+        &ctx.accounts.token_program,
+        ctx.accounts.authority.key(),
+        amount,
+        purpose,
+    )
+}
+
+/// Shared by direct, commit-reveal and budget withdrawals. Callers enforce the
+/// treasury's daily limit first.
+pub(crate) fn withdraw_from_ops_treasury<'info>(
+    program_state: &Account<'info, ProgramState>,
+    ops_treasury: &Account<'info, TokenAccount>,
+    recipient_account: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+    recipient: Pubkey,
+    amount: u64,
+    purpose: String,
+) -> Result<()> {
     let clock = Clock::get()?;
     
     // Validate withdrawal
@@ -81,12 +120,12 @@ pub fn withdraw_handler(
     let signer_seeds = &[&seeds[..]];
     
     let cpi_accounts = Transfer {
-        from: ctx.accounts.ops_treasury.to_account_info(),
-        to: ctx.accounts.recipient_account.to_account_info(),
+        from: ops_treasury.to_account_info(),
+        to: recipient_account.to_account_info(),
         authority: program_state.to_account_info(),
     };
     
-    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_program = token_program.to_account_info();
     let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
     
     token::transfer(cpi_ctx, amount)?;
@@ -95,7 +134,7 @@ pub fn withdraw_handler(
     emit!(TreasuryWithdrawal {
         treasury_type: "operations".to_string(),
         amount,
-        recipient: ctx.accounts.authority.key(),
+        recipient,
         purpose: purpose.clone(),
         timestamp: clock.unix_timestamp,
     });
@@ -107,19 +146,268 @@ pub fn withdraw_handler(
     Ok(())
 }
 
+#[derive(Accounts)]
+#[instruction(treasury_type: TreasuryType)]
+pub struct InitializeTreasuryState<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [PROGRAM_STATE_SEED],
+        bump,
+        constraint = program_state.authority == authority.key() @ TwistError::Unauthorized,
+    )]
+    pub program_state: Account<'info, ProgramState>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = TreasuryState::LEN,
+        seeds = [TREASURY_STATE_SEED, treasury_type.seed().as_ref()],
+        bump
+    )]
+    pub treasury_state: Account<'info, TreasuryState>,
+    
+    #[account(
+        token::mint = program_state.mint,
+        token::authority = program_state,
+    )]
+    pub token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        token::authority = program_state,
+    )]
+    pub usdc_account: Option<Account<'info, TokenAccount>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_treasury_state_handler(
+    ctx: Context<InitializeTreasuryState>,
+    treasury_type: TreasuryType,
+    withdrawal_limit_daily: u64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    
+    // Floor and operations treasuries are fixed PDAs, the reserve can be any program account
+    let expected_seed = match treasury_type {
+        TreasuryType::Floor => Some(FLOOR_TREASURY_SEED),
+        TreasuryType::Operations => Some(OPS_TREASURY_SEED),
+        TreasuryType::Reserve => None,
+    };
+    if let Some(seed) = expected_seed {
+        require_keys_eq!(
+            ctx.accounts.token_account.key(),
+            Pubkey::find_program_address(&[seed], ctx.program_id).0,
+            TwistError::InvalidAccount
+        );
+    }
+    
+    let treasury_state = &mut ctx.accounts.treasury_state;
+    treasury_state.authority = ctx.accounts.program_state.key();
+    treasury_state.treasury_type = treasury_type;
+    treasury_state.token_account = ctx.accounts.token_account.key();
+    treasury_state.usdc_account = ctx.accounts.usdc_account
+        .as_ref()
+        .map(|account| account.key())
+        .unwrap_or_default();
+    treasury_state.total_received = 0;
+    treasury_state.total_withdrawn = 0;
+    treasury_state.total_used_for_buyback = 0;
+    treasury_state.last_withdrawal = 0;
+    treasury_state.withdrawal_limit_daily = withdrawal_limit_daily;
+    treasury_state.withdrawal_used_today = 0;
+    treasury_state.last_limit_reset = clock.unix_timestamp;
+    treasury_state.bump = ctx.bumps.treasury_state;
+    
+    msg!("Initialized {} treasury state", treasury_type.label());
+    msg!("Daily withdrawal limit: {}", withdrawal_limit_daily);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateTreasuryLimit<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [PROGRAM_STATE_SEED],
+        bump,
+        constraint = program_state.authority == authority.key() @ TwistError::Unauthorized,
+    )]
+    pub program_state: Account<'info, ProgramState>,
+    
+    #[account(
+        mut,
+        seeds = [TREASURY_STATE_SEED, treasury_state.treasury_type.seed().as_ref()],
+        bump = treasury_state.bump,
+    )]
+    pub treasury_state: Account<'info, TreasuryState>,
+}
+
+pub fn update_treasury_limit_handler(
+    ctx: Context<UpdateTreasuryLimit>,
+    withdrawal_limit_daily: u64,
+) -> Result<()> {
+    let treasury_state = &mut ctx.accounts.treasury_state;
+    let clock = Clock::get()?;
+    
+    let old_limit = treasury_state.withdrawal_limit_daily;
+    treasury_state.withdrawal_limit_daily = withdrawal_limit_daily;
+    
+    emit!(ParameterUpdated {
+        parameter: format!("{}_treasury_daily_limit", treasury_state.treasury_type.label()),
+        old_value: old_limit.to_string(),
+        new_value: withdrawal_limit_daily.to_string(),
+        updated_by: ctx.accounts.authority.key(),
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Updated {} treasury daily limit to {}", treasury_state.treasury_type.label(), withdrawal_limit_daily);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeTreasuryPolicy<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [PROGRAM_STATE_SEED],
+        bump,
+        constraint = program_state.authority == authority.key() @ TwistError::Unauthorized,
+    )]
+    pub program_state: Account<'info, ProgramState>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = TreasuryPolicy::LEN,
+        seeds = [TREASURY_POLICY_SEED],
+        bump
+    )]
+    pub treasury_policy: Account<'info, TreasuryPolicy>,
+    
+    #[account(
+        constraint = floor_usdc_account.mint != program_state.mint @ TwistError::InvalidAccount,
+        token::authority = program_state,
+    )]
+    pub floor_usdc_account: Account<'info, TokenAccount>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_treasury_policy_handler(
+    ctx: Context<InitializeTreasuryPolicy>,
+    params: TreasuryPolicyParams,
+) -> Result<()> {
+    require!(
+        params.is_valid(),
+        TwistError::InvalidTreasurySplit
+    );
+    
+    let treasury_policy = &mut ctx.accounts.treasury_policy;
+    treasury_policy.bump = ctx.bumps.treasury_policy;
+    treasury_policy.apply(&params);
+    treasury_policy.usdc_mint = ctx.accounts.floor_usdc_account.mint;
+    treasury_policy.floor_usdc_account = ctx.accounts.floor_usdc_account.key();
+    treasury_policy.last_rebalance_timestamp = 0;
+    
+    msg!("Treasury policy initialized");
+    msg!("Targets: floor {}%, ops {}%, reserve {}%, floor USDC mix {}%",
+        params.floor_target_bps / 100,
+        params.ops_target_bps / 100,
+        params.reserve_target_bps / 100,
+        params.floor_usdc_target_bps / 100
+    );
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateTreasuryPolicy<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [PROGRAM_STATE_SEED],
+        bump,
+        constraint = program_state.authority == authority.key() @ TwistError::Unauthorized,
+    )]
+    pub program_state: Account<'info, ProgramState>,
+    
+    #[account(
+        mut,
+        seeds = [TREASURY_POLICY_SEED],
+        bump = treasury_policy.bump,
+    )]
+    pub treasury_policy: Account<'info, TreasuryPolicy>,
+}
+
+pub fn update_treasury_policy_handler(
+    ctx: Context<UpdateTreasuryPolicy>,
+    params: TreasuryPolicyParams,
+) -> Result<()> {
+    let treasury_policy = &mut ctx.accounts.treasury_policy;
+    let clock = Clock::get()?;
+    
+    require!(
+        params.is_valid(),
+        TwistError::InvalidTreasurySplit
+    );
+    
+    let old_value = format!("floor={}, ops={}, reserve={}, usdc_mix={}, band={}, slippage={}",
+        treasury_policy.floor_target_bps,
+        treasury_policy.ops_target_bps,
+        treasury_policy.reserve_target_bps,
+        treasury_policy.floor_usdc_target_bps,
+        treasury_policy.rebalance_band_bps,
+        treasury_policy.max_slippage_bps
+    );
+    treasury_policy.apply(&params);
+    
+    emit!(ParameterUpdated {
+        parameter: "treasury_policy".to_string(),
+        old_value,
+        new_value: format!("floor={}, ops={}, reserve={}, usdc_mix={}, band={}, slippage={}",
+            params.floor_target_bps,
+            params.ops_target_bps,
+            params.reserve_target_bps,
+            params.floor_usdc_target_bps,
+            params.rebalance_band_bps,
+            params.max_slippage_bps
+        ),
+        updated_by: ctx.accounts.authority.key(),
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Updated treasury policy");
+    
+    Ok(())
+}
+
 #[derive(Accounts)]
 pub struct RebalanceTreasury<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
     
+    // No swaps or treasury moves while the breaker is tripped
     #[account(
         mut,
         seeds = [PROGRAM_STATE_SEED],
         bump,
         constraint = program_state.authority == authority.key() @ TwistError::Unauthorized,
+        constraint = !program_state.circuit_breaker_active @ TwistError::CircuitBreakerActive,
         constraint = !program_state.emergency_pause @ TwistError::EmergencyPauseActive,
     )]
-    pub program_state: Account<'info, ProgramState>,
+    pub program_state: Box<Account<'info, ProgramState>>,
+    
+    #[account(
+        mut,
+        seeds = [TREASURY_POLICY_SEED],
+        bump = treasury_policy.bump,
+    )]
+    pub treasury_policy: Box<Account<'info, TreasuryPolicy>>,
     
     #[account(
         mut,
@@ -128,7 +416,13 @@ pub struct RebalanceTreasury<'info> {
         token::mint = program_state.mint,
         token::authority = program_state,
     )]
-    pub floor_treasury: Account<'info, TokenAccount>,
+    pub floor_treasury: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        address = treasury_policy.floor_usdc_account @ TwistError::InvalidAccount,
+    )]
+    pub floor_usdc_account: Box<Account<'info, TokenAccount>>,
     
     #[account(
         mut,
@@ -137,90 +431,184 @@ pub struct RebalanceTreasury<'info> {
         token::mint = program_state.mint,
         token::authority = program_state,
     )]
-    pub ops_treasury: Account<'info, TokenAccount>,
+    pub ops_treasury: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        seeds = [TREASURY_STATE_SEED, TreasuryType::Reserve.seed().as_ref()],
+        bump = reserve_state.bump,
+    )]
+    pub reserve_state: Box<Account<'info, TreasuryState>>,
+    
+    #[account(
+        mut,
+        address = reserve_state.token_account @ TwistError::InvalidAccount,
+    )]
+    pub reserve_treasury: Box<Account<'info, TokenAccount>>,
+    
+    // Moves out of the floor count against its daily limit
+    #[account(
+        mut,
+        seeds = [TREASURY_STATE_SEED, TreasuryType::Floor.seed().as_ref()],
+        bump = floor_state.bump,
+    )]
+    pub floor_state: Box<Account<'info, TreasuryState>>,
+    
+    // Venues the floor's mix correction may route through, needed with the swap
+    #[account(
+        seeds = [DEX_REGISTRY_SEED],
+        bump = dex_registry.bump,
+    )]
+    pub dex_registry: Option<Box<Account<'info, DexRegistry>>>,
+    
+    #[account(
+        mut,
+        seeds = [DEX_ORACLE_SEED],
+        bump = dex_oracle.bump,
+    )]
+    pub dex_oracle: Option<Box<Account<'info, DexOracle>>>,
     
     pub token_program: Program<'info, Token>,
 }
 
-pub fn rebalance_handler(ctx: Context<RebalanceTreasury>) -> Result<()> {
-    let floor_treasury = &ctx.accounts.floor_treasury;
-    let ops_treasury = &ctx.accounts.ops_treasury;
+/// Remaining accounts, needed when the floor mix is outside its band: one account group per
+/// registered venue to quote, as for a buyback
+pub fn rebalance_handler<'info>(ctx: Context<'_, '_, '_, 'info, RebalanceTreasury<'info>>) -> Result<()> {
     let clock = Clock::get()?;
     
-    // Calculate target balances based on treasury split
-    let total_treasury = floor_treasury.amount + ops_treasury.amount;
-    let target_floor_amount = (total_treasury * ctx.accounts.program_state.treasury_split_bps as u64) / 10000;
-    let target_ops_amount = total_treasury - target_floor_amount;
+    observe_pool(&mut ctx.accounts.dex_oracle, ctx.remaining_accounts, clock.unix_timestamp)?;
+    
+    let before = allocation_snapshot(ctx.accounts);
+    emit_allocation_report(ctx.accounts, "before", &before, clock.unix_timestamp);
+    
+    let plan = ctx.accounts.treasury_policy.plan(&before);
+    let program_state = &ctx.accounts.program_state;
     
     let seeds = &[
         PROGRAM_STATE_SEED,
-        &[ctx.accounts.program_state.bump],
+        &[program_state.bump],
     ];
     let signer_seeds = &[&seeds[..]];
     
-    // Rebalance if needed
-    if floor_treasury.amount < target_floor_amount {
-        // Transfer from ops to floor
-        let transfer_amount = target_floor_amount - floor_treasury.amount;
+    // Settle ops and reserve against the floor's TWIST, returns first
+    let mut moves = [
+        (plan.to_reserve, &ctx.accounts.reserve_treasury, TreasuryType::Reserve, "reserve"),
+        (plan.to_ops, &ctx.accounts.ops_treasury, TreasuryType::Operations, "ops"),
+    ];
+    moves.sort_by_key(|(value, _, _, _)| *value);
+    for (value, treasury, treasury_type, label) in moves {
+        let amount = program_state.usdc_to_twist_amount(value.unsigned_abs());
+        if amount == 0 {
+            continue;
+        }
         
-        require!(
-            ops_treasury.amount >= transfer_amount,
-            TwistError::InsufficientLiquidity
-        );
+        // Whatever leaves the floor or the reserve consumes that treasury's daily limit
+        if value > 0 {
+            ctx.accounts.floor_state.consume_daily_limit(amount, clock.unix_timestamp)?;
+        } else if treasury_type == TreasuryType::Reserve {
+            ctx.accounts.reserve_state.consume_daily_limit(amount, clock.unix_timestamp)?;
+        }
         
+        let (from, to) = if value > 0 {
+            (ctx.accounts.floor_treasury.to_account_info(), treasury.to_account_info())
+        } else {
+            (treasury.to_account_info(), ctx.accounts.floor_treasury.to_account_info())
+        };
         let cpi_accounts = Transfer {
-            from: ctx.accounts.ops_treasury.to_account_info(),
-            to: ctx.accounts.floor_treasury.to_account_info(),
-            authority: ctx.accounts.program_state.to_account_info(),
+            from,
+            to,
+            authority: program_state.to_account_info(),
         };
         
         let cpi_program = ctx.accounts.token_program.to_account_info();
         let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
         
-        token::transfer(cpi_ctx, transfer_amount)?;
-        
-        msg!("Rebalanced {} TWIST from ops to floor treasury", transfer_amount);
+        token::transfer(cpi_ctx, amount)?;
         
-    } else if ops_treasury.amount < target_ops_amount {
-        // Transfer from floor to ops
-        let transfer_amount = target_ops_amount - ops_treasury.amount;
+        if value > 0 {
+            msg!("Rebalanced {} TWIST from floor to {} treasury", amount, label);
+        } else {
+            msg!("Rebalanced {} TWIST from {} to floor treasury", amount, label);
+        }
+    }
+    
+    // Correct the floor's USDC/TWIST mix through the registered venues
+    if plan.twist_to_usdc != 0 {
+        let dex_registry = ctx.accounts.dex_registry.as_ref().ok_or(TwistError::InvalidAccount)?;
+        let a_to_b = plan.twist_to_usdc > 0;
+        let amount = if a_to_b {
+            program_state.usdc_to_twist_amount(plan.twist_to_usdc as u64)
+        } else {
+            plan.twist_to_usdc.unsigned_abs()
+        };
         
-        require!(
-            floor_treasury.amount >= transfer_amount,
-            TwistError::InsufficientLiquidity
-        );
+        // The swapped value leaves the floor in one asset, so it counts against its limit too
+        ctx.accounts.floor_state.consume_daily_limit(
+            program_state.usdc_to_twist_amount(plan.twist_to_usdc.unsigned_abs()),
+            clock.unix_timestamp,
+        )?;
         
-        let cpi_accounts = Transfer {
-            from: ctx.accounts.floor_treasury.to_account_info(),
-            to: ctx.accounts.ops_treasury.to_account_info(),
-            authority: ctx.accounts.program_state.to_account_info(),
+        let min_out = oracle_min_swap_output(
+            program_state,
+            amount,
+            a_to_b,
+            ctx.accounts.treasury_policy.max_slippage_bps,
+            clock.unix_timestamp,
+        )?;
+        let endpoints = SwapEndpoints {
+            token_program: ctx.accounts.token_program.to_account_info(),
+            token_authority: program_state.to_account_info(),
+            twist_mint: program_state.mint,
+            twist_account: ctx.accounts.floor_treasury.to_account_info(),
+            usdc_account: ctx.accounts.floor_usdc_account.to_account_info(),
         };
+        let (adapters, route) = plan_registry_route(
+            dex_registry,
+            ctx.remaining_accounts,
+            &endpoints,
+            amount,
+            a_to_b,
+        )?;
         
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
-        
-        token::transfer(cpi_ctx, transfer_amount)?;
+        let destination = if a_to_b {
+            &mut ctx.accounts.floor_usdc_account
+        } else {
+            &mut ctx.accounts.floor_treasury
+        };
+        let received = execute_route(
+            &adapters,
+            &route,
+            min_out,
+            a_to_b,
+            signer_seeds,
+            destination,
+        )?;
         
-        msg!("Rebalanced {} TWIST from floor to ops treasury", transfer_amount);
-    } else {
-        msg!("Treasuries already balanced");
+        msg!("Swapped {} {} for {} {}",
+            amount,
+            if a_to_b { "TWIST" } else { "USDC" },
+            received,
+            if a_to_b { "USDC" } else { "TWIST" }
+        );
     }
     
-    // Update floor liquidity tracking
-    ctx.accounts.program_state.floor_liquidity = ctx.accounts.floor_treasury.amount;
+    ctx.accounts.floor_treasury.reload()?;
+    ctx.accounts.floor_usdc_account.reload()?;
+    ctx.accounts.ops_treasury.reload()?;
+    ctx.accounts.reserve_treasury.reload()?;
     
-    // Emit event
-    emit!(ParameterUpdated {
-        parameter: "treasury_rebalance".to_string(),
-        old_value: format!("floor: {}, ops: {}", floor_treasury.amount, ops_treasury.amount),
-        new_value: format!("floor: {}, ops: {}", target_floor_amount, target_ops_amount),
-        updated_by: ctx.accounts.authority.key(),
-        timestamp: clock.unix_timestamp,
-    });
+    // Buybacks spend the floor's USDC
+    ctx.accounts.program_state.floor_liquidity = ctx.accounts.floor_usdc_account.amount;
+    ctx.accounts.treasury_policy.last_rebalance_timestamp = clock.unix_timestamp;
+    
+    let after = allocation_snapshot(ctx.accounts);
+    emit_allocation_report(ctx.accounts, "after", &after, clock.unix_timestamp);
     
-    msg!("Treasury rebalanced successfully");
-    msg!("Floor: {} TWIST ({}%)", target_floor_amount, ctx.accounts.program_state.treasury_split_bps / 100);
-    msg!("Ops: {} TWIST ({}%)", target_ops_amount, (10000 - ctx.accounts.program_state.treasury_split_bps) / 100);
+    msg!("Treasury rebalanced: ops {}, reserve {}, floor TWIST to USDC {} (USDC value)",
+        plan.to_ops,
+        plan.to_reserve,
+        plan.twist_to_usdc
+    );
     
     Ok(())
 }
@@ -331,7 +719,6 @@ pub fn allocate_fees_handler(ctx: Context<AllocateFees>) -> Result<()> {
     
     // Update tracking
     program_state.total_fees_collected = program_state.total_fees_collected.saturating_add(fee_amount as u128);
-    program_state.floor_liquidity = ctx.accounts.floor_treasury.amount;
     
     // Emit event
     emit!(ParameterUpdated {
@@ -348,4 +735,46 @@ pub fn allocate_fees_handler(ctx: Context<AllocateFees>) -> Result<()> {
     msg!("Ops treasury: {} TWIST", ops_allocation);
     
     Ok(())
-}
\ No newline at end of file
+}
+
+// Helper functions
+
+fn allocation_snapshot(accounts: &RebalanceTreasury) -> AllocationSnapshot {
+    let program_state = &accounts.program_state;
+    AllocationSnapshot {
+        floor_twist: program_state.twist_to_usdc_value(accounts.floor_treasury.amount),
+        floor_usdc: accounts.floor_usdc_account.amount,
+        ops: program_state.twist_to_usdc_value(accounts.ops_treasury.amount),
+        reserve: program_state.twist_to_usdc_value(accounts.reserve_treasury.amount),
+    }
+}
+
+fn emit_allocation_report(
+    accounts: &RebalanceTreasury,
+    stage: &str,
+    snapshot: &AllocationSnapshot,
+    timestamp: i64,
+) {
+    let floor = snapshot.floor_twist.saturating_add(snapshot.floor_usdc);
+    emit!(TreasuryAllocationReport {
+        stage: stage.to_string(),
+        twist_price: accounts.program_state.last_oracle_price,
+        floor_twist_value: snapshot.floor_twist,
+        floor_usdc: snapshot.floor_usdc,
+        ops_value: snapshot.ops,
+        reserve_value: snapshot.reserve,
+        floor_bps: snapshot.share_bps(floor),
+        ops_bps: snapshot.share_bps(snapshot.ops),
+        reserve_bps: snapshot.share_bps(snapshot.reserve),
+        floor_usdc_mix_bps: if floor > 0 {
+            (snapshot.floor_usdc as u128 * 10000 / floor as u128) as u64
+        } else {
+            0
+        },
+        floor_target_bps: accounts.treasury_policy.floor_target_bps,
+        ops_target_bps: accounts.treasury_policy.ops_target_bps,
+        reserve_target_bps: accounts.treasury_policy.reserve_target_bps,
+        floor_usdc_target_bps: accounts.treasury_policy.floor_usdc_target_bps,
+        timestamp,
+    });
+}
@@ -4,7 +4,8 @@ use crate::state::*;
 use crate::constants::*;
 use crate::errors::TwistError;
 use crate::events::*;
-use crate::utils::{safe_add, safe_sub, safe_div};
+use crate::instructions::fee_collector::staking_reward_per_share;
+use crate::utils::{safe_add, safe_sub};
 
 #[derive(Accounts)]
 #[instruction(stake_index: usize)]
@@ -12,12 +13,16 @@ pub struct Unstake<'info> {
     #[account(mut)]
     pub owner: Signer<'info>,
     
+    // Accounts from before the fee reward fields are grown on first use
     #[account(
         mut,
         seeds = [STAKE_STATE_SEED, owner.key().as_ref()],
         bump = stake_state.bump,
         constraint = stake_state.owner == owner.key() @ TwistError::Unauthorized,
-        constraint = stake_state.stakes.len() > stake_index @ TwistError::InvalidAmount
+        constraint = stake_state.stakes.len() > stake_index @ TwistError::InvalidAmount,
+        realloc = StakeState::LEN,
+        realloc::payer = owner,
+        realloc::zero = false,
     )]
     pub stake_state: Box<Account<'info, StakeState>>,
     
@@ -53,6 +58,13 @@ pub struct Unstake<'info> {
     )]
     pub floor_treasury_vault: Account<'info, TokenAccount>,
     
+    // Required once the fee collector is initialized
+    #[account(
+        seeds = [b"fee_collector"],
+        bump = fee_collector.bump,
+    )]
+    pub fee_collector: Option<Box<Account<'info, FeeCollectorState>>>,
+    
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
@@ -107,21 +119,18 @@ pub fn handler(ctx: Context<Unstake>, stake_index: usize) -> Result<()> {
     stake.total_earned = safe_add(stake.total_earned, pending_rewards)?;
     stake.last_claim_timestamp = clock.unix_timestamp;
     
+    // Settle fee rewards before the stake's weight is removed
+    let reward_per_share = staking_reward_per_share(&ctx.accounts.fee_collector, program_state)?;
+    stake_state.settle_fee_rewards(reward_per_share);
+    
     // Update stake state totals
     stake_state.total_staked = safe_sub(stake_state.total_staked, stake_amount)?;
+    stake_state.reset_fee_reward_debt(reward_per_share);
     stake_state.total_earned = stake_state.total_earned.saturating_add(pending_rewards as u128);
     
     // Update global program state
     program_state.total_staked = program_state.total_staked.saturating_sub(stake_amount as u128);
     
-    // Update floor liquidity if there's a penalty
-    if early_penalty > 0 {
-        program_state.floor_liquidity = safe_add(
-            program_state.floor_liquidity,
-            safe_div(early_penalty, 1_000_000_000)? // Convert from token units to USDC equivalent
-        )?;
-    }
-    
     // Get program state values before dropping mutable reference
     let program_state_bump = program_state.bump;
     
@@ -15,6 +15,13 @@ pub struct UpdatePriceAggregated<'info> {
     )]
     pub program_state: Account<'info, ProgramState>,
     
+    #[account(
+        mut,
+        seeds = [MARKET_METRICS_SEED],
+        bump = market_metrics.bump,
+    )]
+    pub market_metrics: Box<Account<'info, MarketMetrics>>,
+    
     /// CHECK: Pyth price feed account
     #[account(
         constraint = pyth_price_account.key() == program_state.pyth_price_feed @ TwistError::InvalidOracle
@@ -29,6 +36,18 @@ pub struct UpdatePriceAggregated<'info> {
     
     /// CHECK: Chainlink feed account (optional)
     pub chainlink_feed: Option<AccountInfo<'info>>,
+    
+    #[account(
+        seeds = [DEX_ORACLE_SEED],
+        bump = dex_oracle.bump,
+    )]
+    pub dex_oracle: Option<Box<Account<'info, DexOracle>>>,
+    
+    #[account(
+        seeds = [REPORTER_NETWORK_SEED],
+        bump = reporter_network.bump,
+    )]
+    pub reporter_network: Option<Box<Account<'info, ReporterNetwork>>>,
 }
 
 pub fn handler(ctx: Context<UpdatePriceAggregated>) -> Result<()> {
@@ -88,6 +107,22 @@ pub fn handler(ctx: Context<UpdatePriceAggregated>) -> Result<()> {
         }
     }
     
+    // Add the pool TWAP once its observation history covers the window
+    if let Some(dex_oracle) = &ctx.accounts.dex_oracle {
+        match dex_oracle.price_source(DEX_ORACLE_TWAP_WINDOW, clock.unix_timestamp) {
+            Ok(source) => price_sources.push(source),
+            Err(_) => msg!("DEX oracle TWAP unavailable, skipping"),
+        }
+    }
+    
+    // Add the reporter median while its last round is fresh
+    if let Some(reporter_network) = &ctx.accounts.reporter_network {
+        match reporter_network.price_source(clock.unix_timestamp) {
+            Some(source) => price_sources.push(source),
+            None => msg!("Reporter network price stale, skipping"),
+        }
+    }
+    
     // Validate all prices are recent
     for source in &price_sources {
         require!(
@@ -98,9 +133,7 @@ pub fn handler(ctx: Context<UpdatePriceAggregated>) -> Result<()> {
     
     // Calculate price divergence
     let prices: Vec<u64> = price_sources.iter().map(|s| s.price).collect();
-    let max_price = *prices.iter().max().unwrap();
-    let min_price = *prices.iter().min().unwrap();
-    let divergence_bps = ((max_price - min_price) * 10000) / min_price;
+    let divergence_bps = price_divergence_bps(&prices);
     
     require!(
         divergence_bps <= ORACLE_DIVERGENCE_THRESHOLD_BPS,
@@ -108,17 +141,7 @@ pub fn handler(ctx: Context<UpdatePriceAggregated>) -> Result<()> {
     );
     
     // Calculate weighted average price based on confidence
-    let mut weighted_sum: u128 = 0;
-    let mut weight_sum: u128 = 0;
-    
-    for source in &price_sources {
-        // Higher confidence = lower value = higher weight
-        let weight = 10000u128 / (source.confidence as u128).max(1);
-        weighted_sum += (source.price as u128) * weight;
-        weight_sum += weight;
-    }
-    
-    let aggregated_price = (weighted_sum / weight_sum) as u64;
+    let aggregated_price = weighted_average_price(&price_sources);
     
     // Calculate average confidence
     let total_confidence: u64 = price_sources.iter().map(|s| s.confidence).sum();
@@ -129,6 +152,11 @@ pub fn handler(ctx: Context<UpdatePriceAggregated>) -> Result<()> {
     program_state.last_oracle_price = aggregated_price;
     program_state.last_oracle_update = clock.unix_timestamp;
     
+    // Record the observation in the rolling metrics
+    let market_metrics = &mut ctx.accounts.market_metrics;
+    market_metrics.record_price(clock.unix_timestamp, aggregated_price);
+    market_metrics.record_liquidity(clock.unix_timestamp, program_state.floor_liquidity);
+    
     // Calculate price change
     let price_change_bps = if old_price > 0 {
         if aggregated_price > old_price {
@@ -10,7 +10,7 @@ pub mod state;
 pub mod utils;
 
 use crate::instructions::*;
-use crate::state::{PIDControllerParams, CircuitBreakerParams, CircuitBreakerSeverity, FeeCollectorParams, FeeType, MultisigParams, TransactionAccount};
+use crate::state::{PIDControllerParams, CircuitBreakerParams, CircuitBreakerSeverity, SeverityResponse, FeeCollectorParams, FeeType, DynamicFeeCurve, MultisigParams, TransactionAccount, TransactionInstruction, TreasuryType, TreasuryBudgetParams, TreasuryPolicyParams, InsuranceParams, RangeStrategyParams, DexVenueConfig, LpVaultParams, MiningScheduleParams, ReporterNetworkParams, IncidentType, GovernanceParams, VoteSide};
 
 declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
 
@@ -48,6 +48,11 @@ pub mod twist_token {
         instructions::stake::handler(ctx, amount, lock_period)
     }
 
+    /// Grow a stake account created before the fee reward fields
+    pub fn migrate_stake_state(ctx: Context<MigrateStakeState>) -> Result<()> {
+        instructions::stake::migrate_stake_state_handler(ctx)
+    }
+
     /// Unstake TWIST tokens
     pub fn unstake(
         ctx: Context<Unstake>,
@@ -65,8 +70,8 @@ pub mod twist_token {
     }
 
     /// Execute automatic market buyback
-    pub fn execute_buyback(
-        ctx: Context<ExecuteBuyback>,
+    pub fn execute_buyback<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteBuyback<'info>>,
         max_usdc_amount: u64,
     ) -> Result<()> {
         instructions::buyback::handler(ctx, max_usdc_amount)
@@ -181,7 +186,7 @@ pub mod twist_token {
         instructions::admin::transfer_authority_handler(ctx)
     }
 
-    /// Withdraw from treasury
+    /// Withdraw from treasury; amounts that warrant a delay must use commit-reveal
     pub fn withdraw_treasury(
         ctx: Context<WithdrawTreasury>,
         amount: u64,
@@ -190,9 +195,241 @@ pub mod twist_token {
         instructions::treasury_ops::withdraw_handler(ctx, amount, purpose)
     }
     
+    /// Create the limit-tracking state for a treasury
+    pub fn initialize_treasury_state(
+        ctx: Context<InitializeTreasuryState>,
+        treasury_type: TreasuryType,
+        withdrawal_limit_daily: u64,
+    ) -> Result<()> {
+        instructions::treasury_ops::initialize_treasury_state_handler(ctx, treasury_type, withdrawal_limit_daily)
+    }
+    
+    /// Update a treasury's daily withdrawal limit
+    pub fn update_treasury_limit(
+        ctx: Context<UpdateTreasuryLimit>,
+        withdrawal_limit_daily: u64,
+    ) -> Result<()> {
+        instructions::treasury_ops::update_treasury_limit_handler(ctx, withdrawal_limit_daily)
+    }
+    
+    /// Create a named, streaming budget paid from the operations treasury
+    pub fn create_treasury_budget(
+        ctx: Context<CreateTreasuryBudget>,
+        params: TreasuryBudgetParams,
+    ) -> Result<()> {
+        instructions::treasury_budget::create_treasury_budget_handler(ctx, params)
+    }
+    
+    /// Withdraw streamed funds from a budget (recipient only)
+    pub fn withdraw_from_budget(
+        ctx: Context<WithdrawFromBudget>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::treasury_budget::withdraw_from_budget_handler(ctx, amount)
+    }
+    
+    /// Close a budget, cancelling its unstreamed remainder
+    pub fn close_treasury_budget(
+        ctx: Context<CloseTreasuryBudget>,
+    ) -> Result<()> {
+        instructions::treasury_budget::close_treasury_budget_handler(ctx)
+    }
+    
+    /// Create the treasury target-allocation policy
+    pub fn initialize_treasury_policy(
+        ctx: Context<InitializeTreasuryPolicy>,
+        params: TreasuryPolicyParams,
+    ) -> Result<()> {
+        instructions::treasury_ops::initialize_treasury_policy_handler(ctx, params)
+    }
+    
+    /// Update the treasury target-allocation policy
+    pub fn update_treasury_policy(
+        ctx: Context<UpdateTreasuryPolicy>,
+        params: TreasuryPolicyParams,
+    ) -> Result<()> {
+        instructions::treasury_ops::update_treasury_policy_handler(ctx, params)
+    }
+    
+    /// Create the registry of swap venues protocol trades may route through
+    pub fn initialize_dex_registry(
+        ctx: Context<InitializeDexRegistry>,
+    ) -> Result<()> {
+        instructions::dex_registry::initialize_dex_registry_handler(ctx)
+    }
+    
+    /// Configure, replace or disable a swap venue slot
+    pub fn set_dex_venue(
+        ctx: Context<SetDexVenue>,
+        index: u8,
+        config: DexVenueConfig,
+    ) -> Result<()> {
+        instructions::dex_registry::set_dex_venue_handler(ctx, index, config)
+    }
+    
+    /// Set the single-pool price impact above which swaps are split across venues
+    pub fn set_routing_threshold(
+        ctx: Context<SetRoutingThreshold>,
+        price_impact_threshold_bps: u64,
+    ) -> Result<()> {
+        instructions::dex_registry::set_routing_threshold_handler(ctx, price_impact_threshold_bps)
+    }
+    
+    /// Open a user LP vault over an empty protocol liquidity position
+    pub fn initialize_lp_vault(
+        ctx: Context<InitializeLpVault>,
+        position_index: u8,
+        params: LpVaultParams,
+    ) -> Result<()> {
+        instructions::lp_vault::initialize_lp_vault_handler(ctx, position_index, params)
+    }
+    
+    /// Update an LP vault's deposit switch, cap and management fee
+    pub fn update_lp_vault(
+        ctx: Context<UpdateLpVault>,
+        position_index: u8,
+        params: LpVaultParams,
+    ) -> Result<()> {
+        instructions::lp_vault::update_lp_vault_handler(ctx, position_index, params)
+    }
+    
+    /// Deposit TWIST and/or USDC into an LP vault for NAV-priced shares
+    pub fn deposit_lp_vault(
+        ctx: Context<DepositLpVault>,
+        position_index: u8,
+        twist_amount: u64,
+        usdc_amount: u64,
+        min_shares: u64,
+    ) -> Result<()> {
+        instructions::lp_vault::deposit_lp_vault_handler(ctx, position_index, twist_amount, usdc_amount, min_shares)
+    }
+    
+    /// Burn LP vault shares for their pro-rata TWIST and USDC
+    pub fn withdraw_lp_vault(
+        ctx: Context<WithdrawLpVault>,
+        position_index: u8,
+        shares: u64,
+        min_twist: u64,
+        min_usdc: u64,
+    ) -> Result<()> {
+        instructions::lp_vault::withdraw_lp_vault_handler(ctx, position_index, shares, min_twist, min_usdc)
+    }
+    
+    /// Pay an LP vault's accrued management fee to the fee collector
+    pub fn collect_lp_vault_fee(
+        ctx: Context<CollectLpVaultFee>,
+        position_index: u8,
+    ) -> Result<()> {
+        instructions::lp_vault::collect_lp_vault_fee_handler(ctx, position_index)
+    }
+    
+    /// Create the liquidity mining rewards vault and its emission schedule
+    pub fn initialize_liquidity_mining(
+        ctx: Context<InitializeLiquidityMining>,
+        params: MiningScheduleParams,
+    ) -> Result<()> {
+        instructions::liquidity_mining::initialize_liquidity_mining_handler(ctx, params)
+    }
+    
+    /// Change the liquidity mining emission rate and window
+    pub fn set_mining_schedule(
+        ctx: Context<SetMiningSchedule>,
+        params: MiningScheduleParams,
+    ) -> Result<()> {
+        instructions::liquidity_mining::set_mining_schedule_handler(ctx, params)
+    }
+    
+    /// Deposit TWIST into the liquidity mining rewards vault
+    pub fn fund_liquidity_mining(ctx: Context<FundLiquidityMining>, amount: u64) -> Result<()> {
+        instructions::liquidity_mining::fund_liquidity_mining_handler(ctx, amount)
+    }
+    
+    /// Register a TWIST/USDC Whirlpool position for liquidity mining emissions
+    pub fn register_mining_position(ctx: Context<RegisterMiningPosition>) -> Result<()> {
+        instructions::liquidity_mining::register_mining_position_handler(ctx)
+    }
+    
+    /// Re-weight a mining position for its live liquidity and the current pool tick
+    pub fn refresh_mining_position(ctx: Context<RefreshMiningPosition>) -> Result<()> {
+        instructions::liquidity_mining::refresh_mining_position_handler(ctx)
+    }
+    
+    /// Claim a mining position's accrued TWIST emissions
+    pub fn claim_mining_rewards(ctx: Context<ClaimMiningRewards>) -> Result<()> {
+        instructions::liquidity_mining::claim_mining_rewards_handler(ctx)
+    }
+    
+    /// Register an LP vault share account for liquidity mining emissions
+    pub fn register_vault_mining_position(ctx: Context<RegisterVaultMiningPosition>) -> Result<()> {
+        instructions::liquidity_mining::register_vault_mining_position_handler(ctx)
+    }
+    
+    /// Re-weight registered vault shares for the current balance and pool price
+    pub fn refresh_vault_mining_position(ctx: Context<RefreshVaultMiningPosition>) -> Result<()> {
+        instructions::liquidity_mining::refresh_vault_mining_position_handler(ctx)
+    }
+    
+    /// Claim the TWIST emissions accrued to registered vault shares
+    pub fn claim_vault_mining_rewards(ctx: Context<ClaimVaultMiningRewards>) -> Result<()> {
+        instructions::liquidity_mining::claim_vault_mining_rewards_handler(ctx)
+    }
+    
+    /// Initialize the observation ring for the program's whirlpool
+    pub fn initialize_dex_oracle(ctx: Context<InitializeDexOracle>) -> Result<()> {
+        instructions::dex_oracle::initialize_dex_oracle_handler(ctx)
+    }
+    
+    /// Record a whirlpool price observation between protocol trades
+    pub fn record_dex_observation(ctx: Context<RecordDexObservation>) -> Result<()> {
+        instructions::dex_oracle::record_dex_observation_handler(ctx)
+    }
+    
+    /// Initialize the stake-weighted price reporter network
+    pub fn initialize_reporter_network(
+        ctx: Context<InitializeReporterNetwork>,
+        params: ReporterNetworkParams,
+    ) -> Result<()> {
+        instructions::price_reporter::initialize_reporter_network_handler(ctx, params)
+    }
+    
+    /// Update reporter network parameters
+    pub fn update_reporter_network(
+        ctx: Context<UpdateReporterNetwork>,
+        params: ReporterNetworkParams,
+    ) -> Result<()> {
+        instructions::price_reporter::update_reporter_network_handler(ctx, params)
+    }
+    
+    /// Register a staker as a price reporter, posting the bond
+    pub fn register_reporter(ctx: Context<RegisterReporter>) -> Result<()> {
+        instructions::price_reporter::register_reporter_handler(ctx)
+    }
+    
+    /// Restore a reporter's bond to the current bond amount
+    pub fn top_up_reporter_bond(ctx: Context<TopUpReporterBond>) -> Result<()> {
+        instructions::price_reporter::top_up_reporter_bond_handler(ctx)
+    }
+    
+    /// Submit a price for the current reporting round
+    pub fn submit_price(ctx: Context<SubmitPrice>, price: u64) -> Result<()> {
+        instructions::price_reporter::submit_price_handler(ctx, price)
+    }
+    
+    /// Settle a reporting round on its stake-weighted median and slash outliers
+    pub fn finalize_report_round<'info>(
+        ctx: Context<'_, '_, 'info, 'info, FinalizeReportRound<'info>>,
+    ) -> Result<()> {
+        instructions::price_reporter::finalize_report_round_handler(ctx)
+    }
+    
+    /// Leave the reporter network and recover the remaining bond
+    pub fn deregister_reporter(ctx: Context<DeregisterReporter>) -> Result<()> {
+        instructions::price_reporter::deregister_reporter_handler(ctx)
+    }
+    
     /// Rebalance treasury allocations
-    pub fn rebalance_treasury(
-        ctx: Context<RebalanceTreasury>,
+    pub fn rebalance_treasury<'info>(
+        ctx: Context<'_, '_, '_, 'info, RebalanceTreasury<'info>>,
     ) -> Result<()> {
         instructions::treasury_ops::rebalance_handler(ctx)
     }
@@ -222,6 +459,18 @@ pub mod twist_token {
         instructions::liquidity::add_liquidity_handler(ctx, twist_amount, usdc_amount, slippage_bps)
     }
 
+    /// Add liquidity from TWIST or USDC alone, swapping the needed share through the registered venues
+    pub fn zap_liquidity<'info>(
+        ctx: Context<'_, '_, '_, 'info, ZapLiquidity<'info>>,
+        amount_in: u64,
+        twist_in: bool,
+        min_swap_out: u64,
+        min_liquidity: u128,
+        slippage_bps: u64,
+    ) -> Result<()> {
+        instructions::liquidity::zap_liquidity_handler(ctx, amount_in, twist_in, min_swap_out, min_liquidity, slippage_bps)
+    }
+
     /// Remove liquidity from pool
     pub fn remove_liquidity(
         ctx: Context<RemoveLiquidity>,
@@ -233,8 +482,8 @@ pub mod twist_token {
     }
     
     /// Rebalance liquidity position
-    pub fn rebalance_position(
-        ctx: Context<RebalancePosition>,
+    pub fn rebalance_position<'info>(
+        ctx: Context<'_, '_, '_, 'info, RebalancePosition<'info>>,
         position_index: u8,
         params: RebalanceParams,
     ) -> Result<()> {
@@ -249,6 +498,32 @@ pub mod twist_token {
         instructions::liquidity::auto_compound_handler(ctx, position_index)
     }
     
+    /// Create the keeper range strategy for a protocol-owned position
+    pub fn initialize_range_strategy(
+        ctx: Context<InitializeRangeStrategy>,
+        position_index: u8,
+        params: RangeStrategyParams,
+    ) -> Result<()> {
+        instructions::liquidity::initialize_range_strategy_handler(ctx, position_index, params)
+    }
+    
+    /// Update range width, recenter trigger and rebalance interval
+    pub fn update_range_strategy(
+        ctx: Context<UpdateRangeStrategy>,
+        position_index: u8,
+        params: RangeStrategyParams,
+    ) -> Result<()> {
+        instructions::liquidity::update_range_strategy_handler(ctx, position_index, params)
+    }
+    
+    /// Recenter a position once its range strategy triggers (permissionless)
+    pub fn keeper_rebalance_position<'info>(
+        ctx: Context<'_, '_, '_, 'info, KeeperRebalancePosition<'info>>,
+        position_index: u8,
+    ) -> Result<()> {
+        instructions::liquidity::keeper_rebalance_position_handler(ctx, position_index)
+    }
+    
     /// Initialize PID controller for dynamic supply regulation
     pub fn initialize_pid_controller(
         ctx: Context<InitializePIDController>,
@@ -287,6 +562,11 @@ pub mod twist_token {
         instructions::circuit_breaker::initialize_handler(ctx, params)
     }
     
+    /// Grow a circuit breaker created before the response, guardian and reserve fields
+    pub fn migrate_circuit_breaker(ctx: Context<MigrateCircuitBreaker>) -> Result<()> {
+        instructions::circuit_breaker::migrate_handler(ctx)
+    }
+    
     /// Check circuit breaker conditions
     pub fn check_circuit_breaker(
         ctx: Context<CheckCircuitBreaker>,
@@ -306,8 +586,110 @@ pub mod twist_token {
     /// Manually reset circuit breaker
     pub fn manual_reset_circuit_breaker(
         ctx: Context<ManualResetCircuitBreaker>,
+        reason: String,
+    ) -> Result<()> {
+        instructions::circuit_breaker::manual_reset_handler(ctx, reason)
+    }
+    
+    /// Set the guardian keys allowed to trip the circuit breaker
+    pub fn set_circuit_breaker_guardians(
+        ctx: Context<SetCircuitBreakerGuardians>,
+        guardians: Vec<Pubkey>,
+    ) -> Result<()> {
+        instructions::circuit_breaker::set_guardians_handler(ctx, guardians)
+    }
+    
+    /// Update the automatic response for a circuit breaker severity
+    pub fn update_circuit_breaker_response(
+        ctx: Context<UpdateResponsePolicy>,
+        severity: CircuitBreakerSeverity,
+        response: SeverityResponse,
+    ) -> Result<()> {
+        instructions::circuit_breaker::update_response_policy_handler(ctx, severity, response)
+    }
+    
+    /// Set the accounting-vs-vault divergence that trips the circuit breaker
+    pub fn update_reserve_divergence_threshold(
+        ctx: Context<UpdateResponsePolicy>,
+        threshold_bps: u64,
+    ) -> Result<()> {
+        instructions::circuit_breaker::update_reserve_divergence_threshold_handler(ctx, threshold_bps)
+    }
+    
+    /// Reconcile accounting counters against vault balances (permissionless)
+    pub fn reconcile_reserves(
+        ctx: Context<ReconcileReserves>,
+    ) -> Result<()> {
+        instructions::proof_of_reserves::reconcile_reserves_handler(ctx)
+    }
+    
+    /// Create the insurance fund and its vault
+    pub fn initialize_insurance_fund(
+        ctx: Context<InitializeInsuranceFund>,
+        params: InsuranceParams,
+    ) -> Result<()> {
+        instructions::insurance::initialize_insurance_fund_handler(ctx, params)
+    }
+    
+    /// Update the insurance fee share and claim limits
+    pub fn update_insurance_parameters(
+        ctx: Context<UpdateInsuranceParameters>,
+        params: InsuranceParams,
+    ) -> Result<()> {
+        instructions::insurance::update_insurance_parameters_handler(ctx, params)
+    }
+    
+    /// File a claim against a covered incident
+    pub fn file_insurance_claim(
+        ctx: Context<FileInsuranceClaim>,
+        incident_type: IncidentType,
+        incident_timestamp: i64,
+        amount_requested: u64,
+        evidence_hash: [u8; 32],
+    ) -> Result<()> {
+        instructions::insurance::file_insurance_claim_handler(
+            ctx,
+            incident_type,
+            incident_timestamp,
+            amount_requested,
+            evidence_hash,
+        )
+    }
+    
+    /// Approve a pending claim (multisig or governance)
+    pub fn approve_insurance_claim(
+        ctx: Context<ResolveInsuranceClaim>,
+        approved_amount: u64,
+    ) -> Result<()> {
+        instructions::insurance::approve_insurance_claim_handler(ctx, approved_amount)
+    }
+    
+    /// Reject a pending claim (multisig or governance)
+    pub fn reject_insurance_claim(
+        ctx: Context<ResolveInsuranceClaim>,
+    ) -> Result<()> {
+        instructions::insurance::reject_insurance_claim_handler(ctx)
+    }
+    
+    /// Pay out an approved claim
+    pub fn pay_insurance_claim(
+        ctx: Context<PayInsuranceClaim>,
+    ) -> Result<()> {
+        instructions::insurance::pay_insurance_claim_handler(ctx)
+    }
+    
+    /// Recompute the insurance coverage ratio against TVL
+    pub fn refresh_insurance_coverage(
+        ctx: Context<RefreshInsuranceCoverage>,
     ) -> Result<()> {
-        instructions::circuit_breaker::manual_reset_handler(ctx)
+        instructions::insurance::refresh_insurance_coverage_handler(ctx)
+    }
+    
+    /// Initialize rolling market metrics
+    pub fn initialize_market_metrics(
+        ctx: Context<InitializeMarketMetrics>,
+    ) -> Result<()> {
+        instructions::market_metrics::initialize_market_metrics_handler(ctx)
     }
     
     /// Initialize fee collector
@@ -318,6 +700,11 @@ pub mod twist_token {
         instructions::fee_collector::initialize_fee_collector_handler(ctx, params)
     }
     
+    /// Grow a fee collector created before the staking, USDC, fee curve and routing fields
+    pub fn migrate_fee_collector(ctx: Context<MigrateFeeCollector>) -> Result<()> {
+        instructions::fee_collector::migrate_fee_collector_handler(ctx)
+    }
+    
     /// Collect fees from various sources
     pub fn collect_fee(
         ctx: Context<CollectFee>,
@@ -328,12 +715,50 @@ pub mod twist_token {
     }
     
     /// Distribute collected fees
-    pub fn distribute_fees(
-        ctx: Context<DistributeFees>,
+    pub fn distribute_fees<'info>(
+        ctx: Context<'_, '_, '_, 'info, DistributeFees<'info>>,
     ) -> Result<()> {
         instructions::fee_collector::distribute_fees_handler(ctx)
     }
     
+    /// Choose whether fee distributions route shares through the pool
+    pub fn set_fee_swap_routing(
+        ctx: Context<UpdateFeeParameters>,
+        swap_floor_to_usdc: bool,
+        swap_usdc_to_twist: bool,
+    ) -> Result<()> {
+        instructions::fee_collector::set_fee_swap_routing_handler(ctx, swap_floor_to_usdc, swap_usdc_to_twist)
+    }
+    
+    /// Update the dynamic fee curve applied to protocol-routed swaps
+    pub fn update_dynamic_fee_curve(
+        ctx: Context<UpdateFeeParameters>,
+        curve: DynamicFeeCurve,
+    ) -> Result<()> {
+        instructions::fee_collector::update_dynamic_fee_curve_handler(ctx, curve)
+    }
+    
+    /// Create the USDC fee vault alongside the TWIST one
+    pub fn initialize_usdc_fee_vault(
+        ctx: Context<InitializeUsdcFeeVault>,
+    ) -> Result<()> {
+        instructions::fee_collector::initialize_usdc_fee_vault_handler(ctx)
+    }
+    
+    /// Distribute collected USDC fees, optionally swapping the burn and staking shares to TWIST
+    pub fn distribute_usdc_fees<'info>(
+        ctx: Context<'_, '_, '_, 'info, DistributeUsdcFees<'info>>,
+    ) -> Result<()> {
+        instructions::fee_collector::distribute_usdc_fees_handler(ctx)
+    }
+    
+    /// Claim fee-funded staking rewards
+    pub fn claim_fee_rewards(
+        ctx: Context<ClaimFeeRewards>,
+    ) -> Result<()> {
+        instructions::fee_collector::claim_fee_rewards_handler(ctx)
+    }
+    
     /// Update fee parameters
     pub fn update_fee_parameters(
         ctx: Context<UpdateFeeParameters>,
@@ -371,6 +796,40 @@ pub mod twist_token {
         )
     }
     
+    /// Propose a multisig transaction made of several instructions executed atomically
+    pub fn propose_batch_transaction(
+        ctx: Context<ProposeBatchTransaction>,
+        instructions: Vec<TransactionInstruction>,
+        title: String,
+        description: String,
+        delay_seconds: i64,
+    ) -> Result<()> {
+        instructions::multisig::propose_batch_transaction_handler(
+            ctx,
+            instructions,
+            title,
+            description,
+            delay_seconds,
+        )
+    }
+    
+    /// Append instruction data to a batch proposal that is still being uploaded
+    pub fn upload_transaction_chunk(
+        ctx: Context<UploadTransactionChunk>,
+        instruction_index: u8,
+        offset: u32,
+        chunk: Vec<u8>,
+    ) -> Result<()> {
+        instructions::multisig::upload_transaction_chunk_handler(ctx, instruction_index, offset, chunk)
+    }
+    
+    /// Finalize an uploaded batch proposal so members can approve it
+    pub fn finalize_transaction(
+        ctx: Context<FinalizeTransaction>,
+    ) -> Result<()> {
+        instructions::multisig::finalize_transaction_handler(ctx)
+    }
+    
     /// Approve a multisig transaction
     pub fn approve_transaction(
         ctx: Context<ApproveTransaction>,
@@ -391,4 +850,168 @@ pub mod twist_token {
     ) -> Result<()> {
         instructions::multisig::cancel_transaction_handler(ctx)
     }
-}
\ No newline at end of file
+    
+    /// Close an executed, cancelled or expired multisig transaction
+    pub fn close_transaction(
+        ctx: Context<CloseTransaction>,
+    ) -> Result<()> {
+        instructions::multisig::close_transaction_handler(ctx)
+    }
+    
+    /// Propose a timelocked program upgrade from a loader buffer
+    pub fn propose_program_upgrade(
+        ctx: Context<ProposeProgramUpgrade>,
+        title: String,
+        description: String,
+    ) -> Result<()> {
+        instructions::program_upgrade::propose_program_upgrade_handler(ctx, title, description)
+    }
+    
+    /// Propose a timelocked change of a program's upgrade authority
+    pub fn propose_upgrade_authority(
+        ctx: Context<ProposeUpgradeAuthority>,
+        new_authority: Option<Pubkey>,
+        title: String,
+        description: String,
+    ) -> Result<()> {
+        instructions::program_upgrade::propose_upgrade_authority_handler(ctx, new_authority, title, description)
+    }
+    
+    /// Add a multisig member (executed by the multisig itself)
+    pub fn add_multisig_member(
+        ctx: Context<UpdateMultisigConfig>,
+        member: Pubkey,
+    ) -> Result<()> {
+        instructions::multisig::add_member_handler(ctx, member)
+    }
+    
+    /// Remove a multisig member (executed by the multisig itself)
+    pub fn remove_multisig_member(
+        ctx: Context<UpdateMultisigConfig>,
+        member: Pubkey,
+    ) -> Result<()> {
+        instructions::multisig::remove_member_handler(ctx, member)
+    }
+    
+    /// Change the multisig threshold (executed by the multisig itself)
+    pub fn change_multisig_threshold(
+        ctx: Context<UpdateMultisigConfig>,
+        threshold: u64,
+    ) -> Result<()> {
+        instructions::multisig::change_threshold_handler(ctx, threshold)
+    }
+    
+    /// Update the per-class minimum delays (executed by the multisig itself)
+    pub fn update_multisig_delays(
+        ctx: Context<UpdateMultisigConfig>,
+        parameter_update_delay: i64,
+        treasury_withdrawal_delay: i64,
+        authority_transfer_delay: i64,
+    ) -> Result<()> {
+        instructions::multisig::update_delays_handler(
+            ctx,
+            parameter_update_delay,
+            treasury_withdrawal_delay,
+            authority_transfer_delay,
+        )
+    }
+    
+    /// Initialize token-holder governance
+    pub fn initialize_governance(
+        ctx: Context<InitializeGovernance>,
+        params: GovernanceParams,
+    ) -> Result<()> {
+        instructions::governance::initialize_governance_handler(ctx, params)
+    }
+    
+    /// Create a governance proposal (requires staked TWIST)
+    pub fn create_governance_proposal(
+        ctx: Context<CreateGovernanceProposal>,
+        instructions: Vec<TransactionInstruction>,
+        title: String,
+        description: String,
+    ) -> Result<()> {
+        instructions::governance::create_governance_proposal_handler(ctx, instructions, title, description)
+    }
+    
+    /// Vote on a governance proposal with stake held at the snapshot
+    pub fn cast_vote(
+        ctx: Context<CastVote>,
+        side: VoteSide,
+    ) -> Result<()> {
+        instructions::governance::cast_vote_handler(ctx, side)
+    }
+    
+    /// Tally votes after the voting period and queue or defeat the proposal
+    pub fn finalize_governance_vote(
+        ctx: Context<FinalizeGovernanceVote>,
+    ) -> Result<()> {
+        instructions::governance::finalize_governance_vote_handler(ctx)
+    }
+    
+    /// Execute a queued governance proposal after its timelock
+    pub fn execute_governance_proposal<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteGovernanceProposal<'info>>,
+    ) -> Result<()> {
+        instructions::governance::execute_governance_proposal_handler(ctx)
+    }
+    
+    /// Veto a governance proposal (executed by the multisig)
+    pub fn veto_governance_proposal(
+        ctx: Context<VetoGovernanceProposal>,
+    ) -> Result<()> {
+        instructions::governance::veto_governance_proposal_handler(ctx)
+    }
+    
+    /// Update governance rules (executed by governance itself)
+    pub fn update_governance_config(
+        ctx: Context<UpdateGovernanceConfig>,
+        params: GovernanceParams,
+    ) -> Result<()> {
+        instructions::governance::update_governance_config_handler(ctx, params)
+    }    
+    /// Create the caller's slot-gap tracker used by commit-reveal actions
+    pub fn initialize_block_restriction(
+        ctx: Context<InitializeBlockRestriction>,
+    ) -> Result<()> {
+        instructions::commit_reveal::initialize_block_restriction_handler(ctx)
+    }
+    
+    /// Commit to a swap or treasury withdrawal without revealing its parameters
+    pub fn commit_action(
+        ctx: Context<CommitAction>,
+        commit_hash: [u8; 32],
+    ) -> Result<()> {
+        instructions::commit_reveal::commit_action_handler(ctx, commit_hash)
+    }
+    
+    /// Reveal and execute a committed swap through the program whirlpool
+    pub fn reveal_swap(
+        ctx: Context<RevealSwap>,
+        amount: u64,
+        min_amount_out: u64,
+        a_to_b: bool,
+        nonce: u64,
+        salt: [u8; 32],
+    ) -> Result<()> {
+        instructions::commit_reveal::reveal_swap_handler(ctx, amount, min_amount_out, a_to_b, nonce, salt)
+    }
+    
+    /// Reveal and execute a committed operations treasury withdrawal
+    pub fn reveal_treasury_withdrawal(
+        ctx: Context<RevealTreasuryWithdrawal>,
+        amount: u64,
+        purpose: String,
+        nonce: u64,
+        salt: [u8; 32],
+    ) -> Result<()> {
+        instructions::commit_reveal::reveal_treasury_withdrawal_handler(ctx, amount, purpose, nonce, salt)
+    }
+    
+    /// Close a commitment, cancelling it if it was never revealed
+    pub fn close_commit(
+        ctx: Context<CloseCommit>,
+    ) -> Result<()> {
+        instructions::commit_reveal::close_commit_handler(ctx)
+    }
+}
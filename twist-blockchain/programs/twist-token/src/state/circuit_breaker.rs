@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
 
+use crate::constants::MAX_TRANSACTION_SIZE_EMERGENCY;
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
 pub enum CircuitBreakerSeverity {
     Low,
@@ -17,6 +19,7 @@ pub enum TripCondition {
     LiquidityDrain,
     AbnormalDecay,
     ManualTrigger,
+    ReserveDivergence,
 }
 
 #[account]
@@ -55,8 +58,18 @@ pub struct CircuitBreakerState {
     pub supply_24h_ago: u128,
     pub liquidity_1h_ago: u64,
     
-    // Reserved space
-    pub _reserved: [u8; 64],
+    // Appended after the original layout, starting in its reserved space. Accounts
+    // created before are grown by migrate_circuit_breaker.
+    pub reserve_divergence_threshold_bps: u64, // accounting vs vault balances
+    
+    // Response policy per severity
+    pub low_response: SeverityResponse,
+    pub medium_response: SeverityResponse,
+    pub high_response: SeverityResponse,
+    pub critical_response: SeverityResponse,
+    
+    // Guardians can trip but never reset
+    pub guardians: Vec<Pubkey>,
 }
 
 impl CircuitBreakerState {
@@ -67,8 +80,56 @@ impl CircuitBreakerState {
         8 + 8 + 8 + 8 + 8 + // thresholds
         8 + 8 + 8 + 8 + // cooldowns
         8 + 8 + 16 + 16 + 16 + 8 + // historical data
-        64; // reserved
+        8 + // reserve divergence threshold
+        SeverityResponse::LEN * 4 + // response policies
+        4 + (32 * Self::MAX_GUARDIANS); // guardians
+    
+    /// Size before the appended fields, when the last 64 bytes were reserved
+    pub const ORIGINAL_LEN: usize = 8 +
+        32 + 1 +
+        8 + 1 + 1 + 8 +
+        1 + 8 +
+        8 + 8 + 8 + 8 + 8 +
+        8 + 8 + 8 + 8 +
+        8 + 8 + 16 + 16 + 16 + 8 +
+        64;
+    
+    pub const MAX_GUARDIANS: usize = 5;
+    
+    pub fn is_guardian(&self, pubkey: &Pubkey) -> bool {
+        self.guardians.contains(pubkey)
+    }
+    
+    /// Resets above Medium severity must be executed by the multisig
+    pub fn reset_requires_multisig(&self) -> bool {
+        matches!(
+            self.last_trip_severity,
+            CircuitBreakerSeverity::High | CircuitBreakerSeverity::Critical
+        )
+    }
+    
+    /// Who may lift the current trip: the multisig always, the program authority only
+    /// below High severity. `multisig` is the multisig config key when it was supplied.
+    pub fn authorize_reset(
+        &self,
+        actor: &Pubkey,
+        program_authority: &Pubkey,
+        multisig: Option<&Pubkey>,
+    ) -> Result<()> {
+        let signed_by_multisig = multisig.map(|key| key == actor).unwrap_or(false);
+        
+        if self.reset_requires_multisig() {
+            require!(signed_by_multisig, crate::errors::TwistError::MultisigRequired);
+        } else {
+            require!(
+                signed_by_multisig || program_authority == actor,
+                crate::errors::TwistError::Unauthorized
+            );
+        }
         
+        Ok(())
+    }
+    
     pub fn get_cooldown_duration(&self, severity: &CircuitBreakerSeverity) -> i64 {
         match severity {
             CircuitBreakerSeverity::Low => self.low_severity_cooldown,
@@ -78,8 +139,26 @@ impl CircuitBreakerState {
         }
     }
     
+    pub fn get_response(&self, severity: &CircuitBreakerSeverity) -> &SeverityResponse {
+        match severity {
+            CircuitBreakerSeverity::Low => &self.low_response,
+            CircuitBreakerSeverity::Medium => &self.medium_response,
+            CircuitBreakerSeverity::High => &self.high_response,
+            CircuitBreakerSeverity::Critical => &self.critical_response,
+        }
+    }
+    
+    pub fn set_response(&mut self, severity: &CircuitBreakerSeverity, response: SeverityResponse) {
+        match severity {
+            CircuitBreakerSeverity::Low => self.low_response = response,
+            CircuitBreakerSeverity::Medium => self.medium_response = response,
+            CircuitBreakerSeverity::High => self.high_response = response,
+            CircuitBreakerSeverity::Critical => self.critical_response = response,
+        }
+    }
+    
     pub fn can_reset(&self, current_timestamp: i64) -> bool {
-        if !self.auto_reset_enabled {
+        if !self.auto_reset_enabled || !self.get_response(&self.last_trip_severity).auto_recover {
             return false;
         }
         
@@ -158,6 +237,20 @@ impl CircuitBreakerState {
         }
     }
     
+    pub fn check_reserve_divergence(&self, divergence_bps: u64) -> Option<CircuitBreakerSeverity> {
+        if self.reserve_divergence_threshold_bps == 0 || divergence_bps <= self.reserve_divergence_threshold_bps {
+            return None;
+        }
+        
+        if divergence_bps > self.reserve_divergence_threshold_bps * 3 {
+            Some(CircuitBreakerSeverity::Critical)
+        } else if divergence_bps > self.reserve_divergence_threshold_bps * 2 {
+            Some(CircuitBreakerSeverity::High)
+        } else {
+            Some(CircuitBreakerSeverity::Medium)
+        }
+    }
+    
     pub fn check_liquidity_drain(&self, current_liquidity: u64) -> Option<CircuitBreakerSeverity> {
         if self.liquidity_1h_ago == 0 {
             return None;
@@ -184,6 +277,122 @@ impl CircuitBreakerState {
     }
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub enum BreakerAction {
+    Trip,
+    Reset,
+    AutoReset,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct BreakerLogEntry {
+    pub sequence: u64,
+    pub action: BreakerAction,
+    pub severity: CircuitBreakerSeverity,
+    pub condition: TripCondition,
+    pub actor: Pubkey,
+    pub reason: String,
+    pub timestamp: i64,
+}
+
+impl BreakerLogEntry {
+    pub const MAX_REASON_LEN: usize = 64;
+    pub const LEN: usize = 8 + 1 + 1 + 1 + 32 + 4 + Self::MAX_REASON_LEN + 8;
+}
+
+/// Append-only ring buffer of circuit breaker trips and resets
+#[account]
+pub struct CircuitBreakerLog {
+    pub bump: u8,
+    pub total_entries: u64,
+    pub next_index: u32,
+    pub entries: Vec<BreakerLogEntry>,
+}
+
+impl CircuitBreakerLog {
+    pub const MAX_ENTRIES: usize = 32;
+    pub const LEN: usize = 8 + // discriminator
+        1 + 8 + 4 + // bump + total_entries + next_index
+        4 + (BreakerLogEntry::LEN * Self::MAX_ENTRIES); // entries
+    
+    pub fn append(
+        &mut self,
+        action: BreakerAction,
+        severity: CircuitBreakerSeverity,
+        condition: TripCondition,
+        actor: Pubkey,
+        reason: String,
+        timestamp: i64,
+    ) -> Result<()> {
+        require!(
+            reason.len() <= BreakerLogEntry::MAX_REASON_LEN,
+            crate::errors::TwistError::InvalidAmount
+        );
+        
+        let entry = BreakerLogEntry {
+            sequence: self.total_entries,
+            action,
+            severity,
+            condition,
+            actor,
+            reason,
+            timestamp,
+        };
+        
+        // Overwrite the oldest entry once the buffer is full
+        if self.entries.len() < Self::MAX_ENTRIES {
+            self.entries.push(entry);
+        } else {
+            self.entries[self.next_index as usize] = entry;
+        }
+        
+        self.next_index = (self.next_index + 1) % Self::MAX_ENTRIES as u32;
+        self.total_entries += 1;
+        
+        Ok(())
+    }
+}
+
+/// Automatic response applied while the breaker is tripped at a given severity
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq)]
+pub struct SeverityResponse {
+    pub fee_surcharge_bps: u64,      // Added on top of protocol fees
+    pub max_transaction_size: u64,   // USDC atomic units, 0 = uncapped
+    pub halt_buyback: bool,
+    pub halt_bridge: bool,
+    pub full_pause: bool,
+    pub auto_recover: bool,          // Lift after the severity cooldown
+}
+
+impl SeverityResponse {
+    pub const LEN: usize = 8 + 8 + 1 + 1 + 1 + 1;
+    
+    pub fn describe(&self) -> Vec<String> {
+        let mut actions = Vec::new();
+        
+        if self.fee_surcharge_bps > 0 {
+            actions.push(format!("Fees raised by {} bps", self.fee_surcharge_bps));
+        }
+        if self.max_transaction_size > 0 {
+            actions.push(format!("Transaction size capped at ${}", self.max_transaction_size / 1_000_000));
+        }
+        if self.halt_buyback {
+            actions.push("Buyback disabled".to_string());
+        }
+        if self.halt_bridge {
+            actions.push("Bridge halted".to_string());
+        }
+        if self.full_pause {
+            actions.push("Emergency pause activated".to_string());
+        }
+        if actions.is_empty() {
+            actions.push("Alert sent to operators".to_string());
+        }
+        
+        actions
+    }
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
 pub struct CircuitBreakerParams {
     pub auto_reset_enabled: bool,
@@ -193,10 +402,15 @@ pub struct CircuitBreakerParams {
     pub supply_change_threshold_bps: u64,
     pub oracle_divergence_threshold_bps: u64,
     pub liquidity_drain_threshold_bps: u64,
+    pub reserve_divergence_threshold_bps: u64,
     pub low_severity_cooldown: i64,
     pub medium_severity_cooldown: i64,
     pub high_severity_cooldown: i64,
     pub critical_severity_cooldown: i64,
+    pub low_response: SeverityResponse,
+    pub medium_response: SeverityResponse,
+    pub high_response: SeverityResponse,
+    pub critical_response: SeverityResponse,
 }
 
 impl Default for CircuitBreakerParams {
@@ -209,10 +423,227 @@ impl Default for CircuitBreakerParams {
             supply_change_threshold_bps: 200, // 2%
             oracle_divergence_threshold_bps: 500, // 5%
             liquidity_drain_threshold_bps: 2000, // 20%
+            reserve_divergence_threshold_bps: 200, // 2%
             low_severity_cooldown: 900, // 15 minutes
             medium_severity_cooldown: 3600, // 1 hour
             high_severity_cooldown: 14400, // 4 hours
             critical_severity_cooldown: 86400, // 24 hours
+            low_response: SeverityResponse {
+                fee_surcharge_bps: 50, // +0.5%
+                auto_recover: true,
+                ..Default::default()
+            },
+            medium_response: SeverityResponse {
+                fee_surcharge_bps: 50,
+                max_transaction_size: MAX_TRANSACTION_SIZE_EMERGENCY,
+                auto_recover: true,
+                ..Default::default()
+            },
+            high_response: SeverityResponse {
+                fee_surcharge_bps: 100, // +1%
+                max_transaction_size: MAX_TRANSACTION_SIZE_EMERGENCY,
+                halt_buyback: true,
+                halt_bridge: true,
+                auto_recover: true,
+                ..Default::default()
+            },
+            critical_response: SeverityResponse {
+                fee_surcharge_bps: 100,
+                max_transaction_size: MAX_TRANSACTION_SIZE_EMERGENCY,
+                halt_buyback: true,
+                halt_bridge: true,
+                full_pause: true,
+                auto_recover: false, // Requires manual reset
+            },
+        }
+    }
+}
+
+/// Divergence of an accounting counter from the balance actually held, in basis points
+/// of the recorded value. A counter of zero against a non-empty balance is a full divergence.
+pub fn reserve_divergence_bps(recorded: u128, actual: u128) -> u64 {
+    let delta = recorded.abs_diff(actual);
+    if delta == 0 {
+        return 0;
+    }
+    if recorded == 0 {
+        return 10000;
+    }
+    
+    (delta * 10000 / recorded).min(u64::MAX as u128) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::ProgramState;
+
+    fn breaker(threshold_bps: u64) -> CircuitBreakerState {
+        let params = CircuitBreakerParams::default();
+        CircuitBreakerState {
+            authority: Pubkey::default(),
+            bump: 0,
+            last_trip_timestamp: 0,
+            last_trip_severity: CircuitBreakerSeverity::Low,
+            last_trip_condition: TripCondition::ManualTrigger,
+            trip_count: 0,
+            auto_reset_enabled: params.auto_reset_enabled,
+            auto_reset_duration: params.auto_reset_duration,
+            price_volatility_threshold_bps: params.price_volatility_threshold_bps,
+            volume_spike_multiplier: params.volume_spike_multiplier,
+            supply_change_threshold_bps: params.supply_change_threshold_bps,
+            oracle_divergence_threshold_bps: params.oracle_divergence_threshold_bps,
+            liquidity_drain_threshold_bps: params.liquidity_drain_threshold_bps,
+            low_severity_cooldown: params.low_severity_cooldown,
+            medium_severity_cooldown: params.medium_severity_cooldown,
+            high_severity_cooldown: params.high_severity_cooldown,
+            critical_severity_cooldown: params.critical_severity_cooldown,
+            price_1h_ago: 0,
+            price_24h_ago: 0,
+            volume_1h_ago: 0,
+            volume_24h_ago: 0,
+            supply_24h_ago: 0,
+            liquidity_1h_ago: 0,
+            reserve_divergence_threshold_bps: threshold_bps,
+            low_response: params.low_response,
+            medium_response: params.medium_response,
+            high_response: params.high_response,
+            critical_response: params.critical_response,
+            guardians: Vec::new(),
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_reserve_divergence_severity() {
+        assert_eq!(reserve_divergence_bps(1_000_000, 1_000_000), 0);
+        assert_eq!(reserve_divergence_bps(1_000_000, 990_000), 100);
+        assert_eq!(reserve_divergence_bps(1_000_000, 1_050_000), 500);
+        assert_eq!(reserve_divergence_bps(0, 5), 10000);
+        
+        let circuit_breaker = breaker(200);
+        assert_eq!(circuit_breaker.check_reserve_divergence(200), None);
+        assert_eq!(circuit_breaker.check_reserve_divergence(300), Some(CircuitBreakerSeverity::Medium));
+        assert_eq!(circuit_breaker.check_reserve_divergence(500), Some(CircuitBreakerSeverity::High));
+        assert_eq!(circuit_breaker.check_reserve_divergence(700), Some(CircuitBreakerSeverity::Critical));
+        
+        // A zero threshold disables the check
+        assert_eq!(breaker(0).check_reserve_divergence(10000), None);
+    }
+
+    fn program_state() -> ProgramState {
+        let mut state = ProgramState::deserialize(&mut &[0u8; ProgramState::LEN][..]).unwrap();
+        state.buyback_enabled = true;
+        state
+    }
+
+    #[test]
+    fn test_tiered_responses() {
+        let params = CircuitBreakerParams::default();
+        let tiers = [
+            // (response, buyback runs, bridge runs, paused)
+            (&params.low_response, true, true, false),
+            (&params.medium_response, true, true, false),
+            (&params.high_response, false, false, false),
+            (&params.critical_response, false, false, true),
+        ];
+
+        for (response, buyback, bridge, paused) in tiers {
+            let mut state = program_state();
+            state.circuit_breaker_active = true;
+            state.apply_breaker_response(response);
+            assert_eq!(state.can_buyback(), buyback);
+            assert_eq!(state.can_bridge(), bridge);
+            assert_eq!(state.emergency_pause, paused);
+            assert_eq!(state.apply_fee_surcharge(30), 30 + response.fee_surcharge_bps);
+            assert_eq!(
+                state.check_transaction_size(MAX_TRANSACTION_SIZE_EMERGENCY + 1).is_ok(),
+                response.max_transaction_size == 0
+            );
+
+            state.lift_breaker_response();
+            assert!(!state.circuit_breaker_active);
+            assert!(state.can_buyback() && state.can_bridge() && !state.emergency_pause);
+            assert_eq!(state.apply_fee_surcharge(30), 30);
+            assert!(state.check_transaction_size(u64::MAX).is_ok());
+        }
+
+        // Lifting a tier that never halted buybacks leaves an admin's disable in place
+        let mut state = program_state();
+        state.buyback_enabled = false;
+        state.apply_breaker_response(&params.low_response);
+        state.lift_breaker_response();
+        assert!(!state.can_buyback());
+
+        // So does lifting one that would have halted them, and a bridge halted beforehand
+        let mut state = program_state();
+        state.buyback_enabled = false;
+        state.bridge_halted = true;
+        state.apply_breaker_response(&params.critical_response);
+        state.lift_breaker_response();
+        assert!(!state.buyback_enabled && state.bridge_halted && !state.emergency_pause);
+
+        // An emergency pause in place before the trip outlives it
+        let mut state = program_state();
+        state.emergency_pause = true;
+        state.apply_breaker_response(&params.critical_response);
+        state.lift_breaker_response();
+        assert!(state.emergency_pause && state.buyback_enabled);
+    }
+
+    #[test]
+    fn test_admin_trip_resets_through_admin_path() {
+        use crate::instructions::circuit_breaker::{lift_circuit_breaker, trip_circuit_breaker};
+
+        let admin = Pubkey::new_unique();
+        let multisig = Pubkey::new_unique();
+        let mut state = program_state();
+        state.authority = admin;
+        let mut circuit_breaker = breaker(200);
+        let mut log = CircuitBreakerLog { bump: 0, total_entries: 0, next_index: 0, entries: Vec::new() };
+
+        // The admin trigger records a High trip
+        trip_circuit_breaker(
+            &mut state,
+            &mut circuit_breaker,
+            CircuitBreakerSeverity::High,
+            TripCondition::ManualTrigger,
+            0,
+        ).unwrap();
+        assert!(state.circuit_breaker_active && !state.can_buyback());
+
+        // The admin alone cannot lift it, the multisig signing the admin reset can
+        assert!(circuit_breaker.authorize_reset(&admin, &admin, None).is_err());
+        assert!(circuit_breaker.authorize_reset(&admin, &admin, Some(&multisig)).is_err());
+        circuit_breaker.authorize_reset(&multisig, &admin, Some(&multisig)).unwrap();
+
+        let cooldown = circuit_breaker.high_severity_cooldown;
+        lift_circuit_breaker(&mut state, &circuit_breaker, &mut log, multisig, "Reset by admin".to_string(), cooldown).unwrap();
+        assert!(!state.circuit_breaker_active && state.can_buyback());
+        assert_eq!(log.entries.last().unwrap().action, BreakerAction::Reset);
+
+        // Below High the admin resets on its own, and strangers never can
+        circuit_breaker.last_trip_severity = CircuitBreakerSeverity::Medium;
+        circuit_breaker.authorize_reset(&admin, &admin, None).unwrap();
+        assert!(circuit_breaker.authorize_reset(&Pubkey::new_unique(), &admin, Some(&multisig)).is_err());
+    }
+
+    #[test]
+    fn test_migrated_layout_reads_appended_fields_as_zero() {
+        let mut original = breaker(200);
+        original.price_1h_ago = 42;
+        original.liquidity_1h_ago = 7;
+        let data = original.try_to_vec().unwrap();
+
+        // The original fields end where the old reserved bytes began
+        let prefix = CircuitBreakerState::ORIGINAL_LEN - 8 - 64;
+        let mut migrated = data[..prefix].to_vec();
+        migrated.resize(CircuitBreakerState::LEN - 8, 0);
+
+        let state = CircuitBreakerState::deserialize(&mut &migrated[..]).unwrap();
+        assert_eq!(state.price_1h_ago, 42);
+        assert_eq!(state.liquidity_1h_ago, 7);
+        assert_eq!(state.reserve_divergence_threshold_bps, 0);
+        assert_eq!(state.critical_response, SeverityResponse::default());
+        assert!(state.guardians.is_empty());
+    }
+}
@@ -0,0 +1,289 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{DECIMALS, DEX_ORACLE_MAX_STALENESS, DEX_ORACLE_MAX_TICK_DELTA, DEX_ORACLE_MIN_INTERVAL};
+use crate::defi::{price_from_sqrt_price, sqrt_price_from_tick_index, tick_index_from_sqrt_price};
+use crate::errors::TwistError;
+use crate::state::{OracleType, PriceSource};
+
+/// Cumulative-tick observations of the program's whirlpool, sampled from its sqrt price
+/// whenever the protocol touches the pool. Each observation moves at most
+/// DEX_ORACLE_MAX_TICK_DELTA from the one before, so a transaction that pushes the pool
+/// and records it shifts the TWAP by no more than that for as long as it stays in effect.
+#[account]
+pub struct DexOracle {
+    pub bump: u8,
+    pub whirlpool: Pubkey,
+    pub last_sqrt_price: u128,
+    pub observation_index: u16, // Slot of the most recent observation
+    pub observation_count: u16, // Slots written, up to MAX_OBSERVATIONS
+    pub observations: [Observation; DexOracle::MAX_OBSERVATIONS],
+}
+
+impl DexOracle {
+    pub const MAX_OBSERVATIONS: usize = 96; // 48 minutes at the minimum interval
+    pub const LEN: usize = 8 + // discriminator
+        1 + 32 + 16 + // bump + whirlpool + last sqrt price
+        2 + 2 + // ring position
+        Observation::LEN * Self::MAX_OBSERVATIONS; // observations
+    
+    /// Record the pool's current sqrt price. The tick sampled last time is credited for
+    /// the time since, so a price only counts once it has been observed, and the new tick
+    /// is clamped to DEX_ORACLE_MAX_TICK_DELTA of the last. Returns false when the last
+    /// observation is too recent to write another.
+    pub fn record(&mut self, sqrt_price: u128, now: i64) -> Result<bool> {
+        let tick = tick_index_from_sqrt_price(sqrt_price)?;
+        
+        if self.observation_count == 0 {
+            self.observations[0] = Observation { timestamp: now, tick_cumulative: 0, tick };
+            self.observation_index = 0;
+            self.observation_count = 1;
+            self.last_sqrt_price = sqrt_price;
+            return Ok(true);
+        }
+        
+        let last = self.latest();
+        if now - last.timestamp < DEX_ORACLE_MIN_INTERVAL {
+            return Ok(false);
+        }
+        
+        // Spot is whatever the caller's transaction left the pool at, so follow it gradually
+        let tick = tick.clamp(
+            last.tick.saturating_sub(DEX_ORACLE_MAX_TICK_DELTA),
+            last.tick.saturating_add(DEX_ORACLE_MAX_TICK_DELTA),
+        );
+        
+        let index = (self.observation_index as usize + 1) % Self::MAX_OBSERVATIONS;
+        self.observations[index] = Observation {
+            timestamp: now,
+            tick_cumulative: last.tick_cumulative + last.tick as i64 * (now - last.timestamp),
+            tick,
+        };
+        self.observation_index = index as u16;
+        self.observation_count = (self.observation_count + 1).min(Self::MAX_OBSERVATIONS as u16);
+        self.last_sqrt_price = sqrt_price;
+        
+        Ok(true)
+    }
+    
+    pub fn latest(&self) -> Observation {
+        self.observations[self.observation_index as usize]
+    }
+    
+    fn oldest(&self) -> Observation {
+        if (self.observation_count as usize) < Self::MAX_OBSERVATIONS {
+            self.observations[0]
+        } else {
+            self.observations[(self.observation_index as usize + 1) % Self::MAX_OBSERVATIONS]
+        }
+    }
+    
+    /// Cumulative tick at `target`, or None outside the recorded history
+    pub fn tick_cumulative_at(&self, target: i64, now: i64) -> Option<i64> {
+        if self.observation_count == 0 || target > now || target < self.oldest().timestamp {
+            return None;
+        }
+        
+        // Each observation's tick holds until the next one, so the latest one at or
+        // before the target extends exactly to it
+        let count = self.observation_count as usize;
+        (0..count)
+            .map(|age| {
+                let index = (self.observation_index as usize + Self::MAX_OBSERVATIONS - age) % Self::MAX_OBSERVATIONS;
+                self.observations[index]
+            })
+            .find(|observation| observation.timestamp <= target)
+            .map(|observation| {
+                observation.tick_cumulative + observation.tick as i64 * (target - observation.timestamp)
+            })
+    }
+    
+    /// Mean tick over the last `window` seconds, rounded toward negative infinity
+    pub fn twap_tick(&self, window: i64, now: i64) -> Result<i32> {
+        require!(window > 0, TwistError::InvalidAmount);
+        require!(
+            self.observation_count > 0 && now - self.latest().timestamp <= DEX_ORACLE_MAX_STALENESS,
+            TwistError::OracleStale
+        );
+        
+        let start = self.tick_cumulative_at(now - window, now).ok_or(TwistError::DexOracleNotReady)?;
+        let end = self.tick_cumulative_at(now, now).ok_or(TwistError::DexOracleNotReady)?;
+        Ok((end - start).div_euclid(window) as i32)
+    }
+    
+    /// Time-weighted USDC price per whole TWIST (6 decimals) over `window` seconds
+    pub fn twap_price(&self, window: i64, now: i64) -> Result<u64> {
+        let tick = self.twap_tick(window, now)?;
+        price_from_sqrt_price(sqrt_price_from_tick_index(tick)?, DECIMALS)
+    }
+    
+    /// The pool TWAP as an aggregation source. Its confidence is the gap between the
+    /// last sampled spot price and the TWAP, so a pool being pushed around counts for less,
+    /// and never tighter than the move one clamped observation can make. A calm pool is
+    /// still one a single transaction can nudge by that much.
+    pub fn price_source(&self, window: i64, now: i64) -> Result<PriceSource> {
+        let tick = self.twap_tick(window, now)?;
+        let twap = price_from_sqrt_price(sqrt_price_from_tick_index(tick)?, DECIMALS)?;
+        let spot = price_from_sqrt_price(self.last_sqrt_price, DECIMALS)?;
+        
+        let clamped_move = price_from_sqrt_price(
+            sqrt_price_from_tick_index(tick.saturating_add(DEX_ORACLE_MAX_TICK_DELTA))?,
+            DECIMALS,
+        )?.saturating_sub(twap);
+        
+        // twap_tick already enforced DEX_ORACLE_MAX_STALENESS; the TWAP runs up to now
+        Ok(PriceSource {
+            oracle_type: OracleType::Dex,
+            price: twap,
+            confidence: spot.abs_diff(twap).max(clamped_move).max(1),
+            timestamp: now,
+        })
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default)]
+pub struct Observation {
+    pub timestamp: i64,
+    pub tick_cumulative: i64,
+    pub tick: i32, // Sampled tick, in effect until the next observation
+}
+
+impl Observation {
+    pub const LEN: usize = 8 + 8 + 4;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::DEX_ORACLE_TWAP_WINDOW;
+
+    fn empty_oracle() -> DexOracle {
+        DexOracle {
+            bump: 0,
+            whirlpool: Pubkey::default(),
+            last_sqrt_price: 0,
+            observation_index: 0,
+            observation_count: 0,
+            observations: [Observation::default(); DexOracle::MAX_OBSERVATIONS],
+        }
+    }
+
+    #[test]
+    fn test_twap_weights_ticks_by_time() {
+        let mut oracle = empty_oracle();
+        let start = 1_000;
+
+        // Tick 100 for 20 minutes, then tick 190 for 10 minutes
+        oracle.record(sqrt_price_from_tick_index(100).unwrap(), start).unwrap();
+        oracle.record(sqrt_price_from_tick_index(190).unwrap(), start + 1_200).unwrap();
+        oracle.record(sqrt_price_from_tick_index(190).unwrap(), start + 1_600).unwrap();
+        let now = start + 1_800;
+        assert_eq!(oracle.twap_tick(DEX_ORACLE_TWAP_WINDOW, now).unwrap(), 130);
+
+        // A short spike recorded at the end has not been in effect yet
+        oracle.record(sqrt_price_from_tick_index(50_000).unwrap(), now).unwrap();
+        assert_eq!(oracle.twap_tick(DEX_ORACLE_TWAP_WINDOW, now).unwrap(), 130);
+
+        // Writes closer together than the minimum interval are skipped
+        assert!(!oracle.record(sqrt_price_from_tick_index(0).unwrap(), now + 1).unwrap());
+
+        // Negative means round toward negative infinity: -2.625 becomes -3
+        let mut below = empty_oracle();
+        below.record(sqrt_price_from_tick_index(-3).unwrap(), 0).unwrap();
+        below.record(sqrt_price_from_tick_index(-3).unwrap(), 40).unwrap();
+        assert_eq!(below.twap_tick(7, 40).unwrap(), -3);
+        below.record(sqrt_price_from_tick_index(0).unwrap(), 80).unwrap();
+        assert_eq!(below.twap_tick(80, 90).unwrap(), -3);
+    }
+
+    #[test]
+    fn test_ring_history_bounds() {
+        let mut oracle = empty_oracle();
+        assert!(oracle.twap_tick(DEX_ORACLE_TWAP_WINDOW, 0).is_err());
+
+        // Wrap the ring twice at the minimum interval
+        let sqrt_price = sqrt_price_from_tick_index(-7).unwrap();
+        for step in 0..(2 * DexOracle::MAX_OBSERVATIONS as i64) {
+            assert!(oracle.record(sqrt_price, step * DEX_ORACLE_MIN_INTERVAL).unwrap());
+        }
+        let now = (2 * DexOracle::MAX_OBSERVATIONS as i64 - 1) * DEX_ORACLE_MIN_INTERVAL;
+        assert_eq!(oracle.observation_count as usize, DexOracle::MAX_OBSERVATIONS);
+        assert_eq!(oracle.twap_tick(DEX_ORACLE_TWAP_WINDOW, now).unwrap(), -7);
+
+        // Windows reaching past the oldest observation are refused
+        let covered = (DexOracle::MAX_OBSERVATIONS as i64 - 1) * DEX_ORACLE_MIN_INTERVAL;
+        assert!(oracle.twap_tick(covered, now).is_ok());
+        assert!(oracle.twap_tick(covered + 1, now).is_err());
+
+        // So are stale rings
+        assert!(oracle.twap_tick(60, now + DEX_ORACLE_MAX_STALENESS + 1).is_err());
+    }
+
+    #[test]
+    fn test_divergence_of_extreme_prices() {
+        use crate::state::price_divergence_bps;
+
+        assert_eq!(price_divergence_bps(&[50_000, 49_500]), 101);
+        // A pool at a tick that rounds to a zero price is a maximal divergence
+        assert_eq!(price_divergence_bps(&[50_000, 0]), u64::MAX);
+        // Prices near the top of the range no longer overflow the basis point scale
+        assert_eq!(price_divergence_bps(&[u64::MAX, u64::MAX / 2]), 10000);
+    }
+
+    #[test]
+    fn test_calm_pool_does_not_dominate_aggregate() {
+        use crate::state::{source_weight, weighted_average_price};
+
+        // A pool held perfectly still at about $0.05 for the whole window
+        let mut oracle = empty_oracle();
+        let sqrt_price = sqrt_price_from_tick_index(-99_040).unwrap();
+        for step in 0..=60 {
+            oracle.record(sqrt_price, step * DEX_ORACLE_MIN_INTERVAL).unwrap();
+        }
+        let now = 60 * DEX_ORACLE_MIN_INTERVAL;
+        let dex = oracle.price_source(DEX_ORACLE_TWAP_WINDOW, now).unwrap();
+
+        // Its confidence is the ~1% a clamped observation can move, not 1
+        assert!(dex.confidence >= dex.price / 101);
+
+        // Feeds quoting the same price with the mock feeds' relative confidence
+        let external = |oracle_type, confidence_bps: u64| PriceSource {
+            oracle_type,
+            price: dex.price,
+            confidence: dex.price * confidence_bps / 10000,
+            timestamp: now,
+        };
+        let pyth = external(OracleType::Pyth, 20);
+        let switchboard = external(OracleType::Switchboard, 30);
+        let total = source_weight(&pyth) + source_weight(&switchboard) + source_weight(&dex);
+        assert!(source_weight(&dex) * 5 < total);
+        assert!(source_weight(&dex) < source_weight(&switchboard));
+
+        // A TWAP pushed 1% off moves the aggregate by well under half of that
+        let pushed = PriceSource { price: dex.price * 101 / 100, ..dex };
+        let aggregate = weighted_average_price(&[pyth, switchboard, pushed]);
+        assert!(aggregate - dex.price < dex.price / 500);
+    }
+
+    #[test]
+    fn test_single_transaction_spike_is_clamped() {
+        let mut oracle = empty_oracle();
+        let sqrt_price = sqrt_price_from_tick_index(-500).unwrap();
+        for step in 0..60 {
+            oracle.record(sqrt_price, step * DEX_ORACLE_MIN_INTERVAL).unwrap();
+        }
+        let spiked_at = 60 * DEX_ORACLE_MIN_INTERVAL;
+
+        // A transaction pushes the pool 50,000 ticks and records it
+        oracle.record(sqrt_price_from_tick_index(49_500).unwrap(), spiked_at).unwrap();
+        assert_eq!(oracle.latest().tick, -500 + DEX_ORACLE_MAX_TICK_DELTA);
+
+        // Arbitrage restores the pool and the next observation records it
+        let restored_at = spiked_at + DEX_ORACLE_MIN_INTERVAL;
+        oracle.record(sqrt_price, restored_at).unwrap();
+        assert_eq!(oracle.latest().tick, -500);
+
+        // The spike counted for one interval at the clamped tick: 100 * 30 / 1800 = 1.67
+        let twap = oracle.twap_tick(DEX_ORACLE_TWAP_WINDOW, restored_at).unwrap();
+        assert_eq!(twap, -499);
+    }
+}
@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+
+pub const MAX_DEX_VENUES: usize = 4;
+
+/// Swap venues the protocol may route TWIST/USDC trades through
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DexVenue {
+    /// Orca Whirlpool concentrated liquidity pool
+    Whirlpool,
+    /// Raydium concentrated liquidity pool
+    RaydiumClmm,
+    /// Constant-product pool, such as the local mock AMM used in tests
+    ConstantProduct,
+}
+
+impl DexVenue {
+    /// Number of remaining accounts a swap on this venue consumes
+    pub fn account_count(&self) -> usize {
+        match self {
+            DexVenue::Whirlpool => 8,
+            DexVenue::RaydiumClmm => 9,
+            DexVenue::ConstantProduct => 4,
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct DexVenueConfig {
+    pub venue: DexVenue,
+    pub program_id: Pubkey,
+    pub pool: Pubkey,
+    pub enabled: bool,
+}
+
+impl DexVenueConfig {
+    pub const LEN: usize = 1 + 32 + 32 + 1;
+    
+    pub fn empty() -> Self {
+        Self {
+            venue: DexVenue::Whirlpool,
+            program_id: Pubkey::default(),
+            pool: Pubkey::default(),
+            enabled: false,
+        }
+    }
+}
+
+/// Configured venues; protocol swaps quote every venue passed in and take the best
+#[account]
+pub struct DexRegistry {
+    pub bump: u8,
+    pub venues: [DexVenueConfig; MAX_DEX_VENUES],
+    pub price_impact_threshold_bps: u64, // Orders with more impact on one pool are split
+    pub last_updated: i64,
+    
+    // Reserved space
+    pub _reserved: [u8; 32],
+}
+
+impl DexRegistry {
+    pub const LEN: usize = 8 + // discriminator
+        1 + // bump
+        DexVenueConfig::LEN * MAX_DEX_VENUES +
+        8 + // price_impact_threshold_bps
+        8 + // last_updated
+        32; // reserved
+    
+    /// Enabled venue configured for `pool`
+    pub fn find_enabled(&self, pool: &Pubkey) -> Option<&DexVenueConfig> {
+        self.venues
+            .iter()
+            .find(|config| config.enabled && config.pool == *pool)
+    }
+}
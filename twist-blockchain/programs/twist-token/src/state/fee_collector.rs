@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
 
+use crate::constants::FEE_REWARD_PRECISION;
+
 #[account]
 pub struct FeeCollectorState {
     pub authority: Pubkey,
@@ -36,8 +38,32 @@ pub struct FeeCollectorState {
     pub distributions_count: u64,
     pub unique_fee_payers: u64,
     
+    // Appended after the original layout, starting in its reserved space. Accounts
+    // created before are grown by migrate_fee_collector.
+    
+    // Staking rewards accumulator, scaled by FEE_REWARD_PRECISION
+    pub reward_per_share: u128,
+    pub unallocated_staking_rewards: u64, // Deposited but not yet in the accumulator
+    
+    // USDC fee vault, tracked separately from the TWIST amounts above
+    pub usdc_mint: Pubkey,
+    pub usdc_fee_vault: Pubkey,
+    pub pending_usdc_distribution: u64,
+    pub total_usdc_fees: u128,
+    pub total_usdc_distributed: u128,
+    
+    // Fee curve for protocol-routed swaps
+    pub dynamic_fee_curve: DynamicFeeCurve,
+    
+    // Insurance fund slice, taken before the distribution shares
+    pub insurance_share_bps: u64,
+    
+    // Governance-set routing of the floor and converted shares through the pool
+    pub swap_floor_to_usdc: bool,
+    pub swap_usdc_to_twist: bool,
+    
     // Reserved space
-    pub _reserved: [u8; 64],
+    pub _reserved: [u8; 30],
 }
 
 impl FeeCollectorState {
@@ -49,8 +75,24 @@ impl FeeCollectorState {
         8 + 8 + 8 + 8 + // distribution configuration
         8 + 8 + // accumulator accounts
         8 + 8 + // stats
-        64; // reserved
-        
+        16 + 8 + // staking rewards accumulator
+        32 + 32 + 8 + 16 + 16 + // usdc fee vault
+        DynamicFeeCurve::LEN + // dynamic fee curve
+        8 + // insurance share
+        1 + 1 + // swap routing
+        30; // reserved
+    
+    /// Size before the appended fields, when the last 64 bytes were reserved
+    pub const ORIGINAL_LEN: usize = 8 +
+        32 + 1 +
+        16 + 16 + 16 + 16 + 16 +
+        16 + 8 +
+        8 + 8 + 8 + 8 +
+        8 + 8 + 8 + 8 +
+        8 + 8 +
+        8 + 8 +
+        64;
+    
     pub fn validate_distribution_shares(&self) -> bool {
         let total = self.floor_treasury_share_bps +
             self.ops_treasury_share_bps +
@@ -60,15 +102,45 @@ impl FeeCollectorState {
         total == 10000 // Must sum to 100%
     }
     
+    pub fn insurance_amount(&self, total_amount: u64) -> u64 {
+        (total_amount as u128 * self.insurance_share_bps as u128 / 10000) as u64
+    }
+    
     pub fn calculate_distribution_amounts(&self, total_amount: u64) -> FeeDistribution {
+        let ops_treasury = (total_amount as u128 * self.ops_treasury_share_bps as u128 / 10000) as u64;
+        let staking_rewards = (total_amount as u128 * self.staking_rewards_share_bps as u128 / 10000) as u64;
+        let burn_amount = (total_amount as u128 * self.burn_share_bps as u128 / 10000) as u64;
+        
         FeeDistribution {
-            floor_treasury: (total_amount as u128 * self.floor_treasury_share_bps as u128 / 10000) as u64,
-            ops_treasury: (total_amount as u128 * self.ops_treasury_share_bps as u128 / 10000) as u64,
-            staking_rewards: (total_amount as u128 * self.staking_rewards_share_bps as u128 / 10000) as u64,
-            burn_amount: (total_amount as u128 * self.burn_share_bps as u128 / 10000) as u64,
+            // Rounding dust goes to the floor treasury so the vault is fully drained
+            floor_treasury: total_amount - ops_treasury - staking_rewards - burn_amount,
+            ops_treasury,
+            staking_rewards,
+            burn_amount,
         }
     }
     
+    /// Advance the accumulator by a staking rewards deposit. Amounts that cannot be
+    /// allocated exactly, or arrive while nothing is staked, carry over to the next deposit.
+    pub fn allocate_staking_rewards(&mut self, amount: u64, total_staked: u128) {
+        let available = self.unallocated_staking_rewards.saturating_add(amount);
+        if total_staked == 0 {
+            self.unallocated_staking_rewards = available;
+            return;
+        }
+        
+        let increment = available as u128 * FEE_REWARD_PRECISION / total_staked;
+        let allocated = (increment * total_staked / FEE_REWARD_PRECISION) as u64;
+        
+        self.reward_per_share = self.reward_per_share.saturating_add(increment);
+        self.unallocated_staking_rewards = available - allocated;
+    }
+    
+    pub fn record_usdc_fee(&mut self, amount: u64) {
+        self.total_usdc_fees = self.total_usdc_fees.saturating_add(amount as u128);
+        self.pending_usdc_distribution = self.pending_usdc_distribution.saturating_add(amount);
+    }
+    
     pub fn record_fee(&mut self, fee_type: FeeType, amount: u64) {
         match fee_type {
             FeeType::Trading => {
@@ -108,6 +180,19 @@ pub struct FeeDistribution {
     pub burn_amount: u64,
 }
 
+impl FeeDistribution {
+    /// Split TWIST bought with the staking and burn shares pro rata; rounding goes to staking
+    pub fn split_converted(&self, twist_received: u64) -> (u64, u64) {
+        let converted = self.staking_rewards as u128 + self.burn_amount as u128;
+        if converted == 0 {
+            return (0, 0);
+        }
+        
+        let burn = (twist_received as u128 * self.burn_amount as u128 / converted) as u64;
+        (twist_received - burn, burn)
+    }
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
 pub enum FeeType {
     Trading,
@@ -117,6 +202,47 @@ pub enum FeeType {
     Other,
 }
 
+/// Scales the trading fee of protocol-routed swaps by size, volatility and volume
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub struct DynamicFeeCurve {
+    pub large_trade_threshold: u64,      // USDC atomic units
+    pub large_trade_multiplier_bps: u64, // 10000 = 1x
+    pub whale_trade_threshold: u64,
+    pub whale_trade_multiplier_bps: u64,
+    pub volatility_threshold_bps: u64,   // 24h price range
+    pub volatility_surcharge_bps: u64,
+    pub congestion_multiple: u64,        // Current hour volume vs hourly average
+    pub congestion_surcharge_bps: u64,
+    pub max_fee_bps: u64,
+}
+
+impl DynamicFeeCurve {
+    pub const LEN: usize = 8 * 9;
+    
+    pub fn is_valid(&self) -> bool {
+        self.large_trade_multiplier_bps >= 10000 &&
+        self.whale_trade_multiplier_bps >= self.large_trade_multiplier_bps &&
+        self.whale_trade_threshold >= self.large_trade_threshold &&
+        self.max_fee_bps <= 1000
+    }
+}
+
+impl Default for DynamicFeeCurve {
+    fn default() -> Self {
+        Self {
+            large_trade_threshold: 50_000 * 1_000_000,  // $50k
+            large_trade_multiplier_bps: 12500,          // 1.25x
+            whale_trade_threshold: 100_000 * 1_000_000, // $100k
+            whale_trade_multiplier_bps: 15000,          // 1.5x
+            volatility_threshold_bps: 1000,             // 10% range
+            volatility_surcharge_bps: 50,               // +0.5%
+            congestion_multiple: 5,                     // 5x the hourly average
+            congestion_surcharge_bps: 100,              // +1%
+            max_fee_bps: 500,                           // 5% cap
+        }
+    }
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct FeeCollectorParams {
     pub trading_fee_bps: u64,
@@ -144,4 +270,106 @@ impl Default for FeeCollectorParams {
             min_distribution_amount: 1000 * 10u64.pow(9), // 1000 TWIST minimum
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collector() -> FeeCollectorState {
+        let params = FeeCollectorParams::default();
+        FeeCollectorState {
+            authority: Pubkey::default(),
+            bump: 0,
+            total_trading_fees: 0,
+            total_withdrawal_fees: 0,
+            total_bridge_fees: 0,
+            total_liquidity_fees: 0,
+            total_other_fees: 0,
+            total_distributed: 0,
+            last_distribution_timestamp: 0,
+            trading_fee_bps: params.trading_fee_bps,
+            withdrawal_fee_bps: params.withdrawal_fee_bps,
+            bridge_fee_bps: params.bridge_fee_bps,
+            liquidity_fee_bps: params.liquidity_fee_bps,
+            floor_treasury_share_bps: params.floor_treasury_share_bps,
+            ops_treasury_share_bps: params.ops_treasury_share_bps,
+            staking_rewards_share_bps: params.staking_rewards_share_bps,
+            burn_share_bps: params.burn_share_bps,
+            pending_distribution: 0,
+            min_distribution_amount: 0,
+            distributions_count: 0,
+            unique_fee_payers: 0,
+            reward_per_share: 0,
+            unallocated_staking_rewards: 0,
+            usdc_mint: Pubkey::default(),
+            usdc_fee_vault: Pubkey::default(),
+            pending_usdc_distribution: 0,
+            total_usdc_fees: 0,
+            total_usdc_distributed: 0,
+            dynamic_fee_curve: DynamicFeeCurve::default(),
+            insurance_share_bps: 0,
+            swap_floor_to_usdc: false,
+            swap_usdc_to_twist: false,
+            _reserved: [0; 30],
+        }
+    }
+
+    #[test]
+    fn test_distribution_dust_goes_to_floor_treasury() {
+        let distribution = collector().calculate_distribution_amounts(1_003);
+        
+        assert_eq!(distribution.ops_treasury, 300);
+        assert_eq!(distribution.staking_rewards, 200);
+        assert_eq!(distribution.burn_amount, 100);
+        assert_eq!(distribution.floor_treasury, 403);
+    }
+
+    #[test]
+    fn test_staking_rewards_carry_over() {
+        let mut fee_collector = collector();
+        
+        // Nothing staked: the whole deposit waits for stakers
+        fee_collector.allocate_staking_rewards(500, 0);
+        assert_eq!(fee_collector.reward_per_share, 0);
+        assert_eq!(fee_collector.unallocated_staking_rewards, 500);
+        
+        // 1,000 deposited across 3 staked tokens allocates 999, 1 carries over
+        fee_collector.allocate_staking_rewards(500, 3);
+        assert_eq!(fee_collector.reward_per_share * 3 / FEE_REWARD_PRECISION, 999);
+        assert_eq!(fee_collector.unallocated_staking_rewards, 1);
+    }
+
+    #[test]
+    fn test_swapped_twist_split_follows_shares() {
+        let distribution = collector().calculate_distribution_amounts(1_000);
+        
+        // Staking and burn shares are 2:1, rounding favours staking
+        assert_eq!(distribution.split_converted(3_001), (2_001, 1_000));
+        assert_eq!(distribution.split_converted(0), (0, 0));
+    }
+
+    #[test]
+    fn test_migrated_layout_reads_appended_fields_as_zero() {
+        let mut original = collector();
+        original.unique_fee_payers = 9;
+        let data = original.try_to_vec().unwrap();
+
+        // The original fields end where the old reserved bytes began
+        let prefix = FeeCollectorState::ORIGINAL_LEN - 8 - 64;
+        let mut migrated = data[..prefix].to_vec();
+        migrated.resize(FeeCollectorState::LEN - 8, 0);
+
+        let state = FeeCollectorState::deserialize(&mut &migrated[..]).unwrap();
+        assert_eq!(state.unique_fee_payers, 9);
+        assert_eq!(state.trading_fee_bps, original.trading_fee_bps);
+        assert_eq!(state.reward_per_share, 0);
+        assert_eq!(state.usdc_fee_vault, Pubkey::default());
+        assert_eq!(state.insurance_share_bps, 0);
+        assert!(!state.swap_floor_to_usdc);
+
+        // Left zeroed, the curve's 0 bps cap would waive every dynamic fee
+        assert_eq!(state.dynamic_fee_curve.max_fee_bps, 0);
+        assert!(!state.dynamic_fee_curve.is_valid());
+    }
+}
@@ -0,0 +1,235 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{StakeState, TransactionInstruction};
+
+#[account]
+pub struct GovernanceConfig {
+    pub bump: u8,
+    pub proposal_count: u64,
+    
+    // Multisig PDA allowed to veto proposals before execution
+    pub veto_authority: Pubkey,
+    
+    // Voting rules
+    pub proposal_threshold: u64,     // Minimum snapshot stake to create a proposal
+    pub quorum_bps: u64,             // Participation required, of total staked at snapshot
+    pub approval_threshold_bps: u64, // For votes required, of For + Against
+    pub voting_period: i64,
+    pub timelock_delay: i64,
+    pub execution_window: i64,
+    
+    // Reserved space
+    pub _reserved: [u8; 64],
+}
+
+impl GovernanceConfig {
+    pub const LEN: usize = 8 + // discriminator
+        1 + 8 + // bump + proposal_count
+        32 + // veto_authority
+        8 + 8 + 8 + // thresholds
+        8 + 8 + 8 + // periods
+        64; // reserved
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProposalStatus {
+    Voting,
+    Queued,
+    Defeated,
+    Executed,
+    Vetoed,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VoteSide {
+    For,
+    Against,
+    Abstain,
+}
+
+#[account]
+pub struct GovernanceProposal {
+    pub proposal_index: u64,
+    pub proposer: Pubkey,
+    pub instructions: Vec<TransactionInstruction>, // Executed in order, atomically
+    pub status: ProposalStatus,
+    
+    // Stake snapshot: only stakes opened before this timestamp carry weight
+    pub snapshot_timestamp: i64,
+    pub snapshot_total_staked: u128,
+    
+    // Tallies
+    pub for_votes: u128,
+    pub against_votes: u128,
+    pub abstain_votes: u128,
+    
+    // Schedule
+    pub voting_ends_at: i64,
+    pub eta: i64,
+    pub expires_at: i64,
+    pub executed_at: i64,
+    pub bump: u8,
+    
+    // Proposal metadata
+    pub title: String,
+    pub description: String,
+    
+    // Reserved space
+    pub _reserved: [u8; 32],
+}
+
+impl GovernanceProposal {
+    pub const BASE_LEN: usize = 8 + // discriminator
+        8 + // proposal_index
+        32 + // proposer
+        4 + // instructions vec prefix
+        1 + // status
+        8 + 16 + // snapshot
+        16 + 16 + 16 + // tallies
+        8 + 8 + 8 + 8 + // schedule
+        1 + // bump
+        4 + 64 + // title (max 64 chars)
+        4 + 256 + // description (max 256 chars)
+        32; // reserved
+    
+    pub fn space(instructions: &[TransactionInstruction]) -> usize {
+        Self::BASE_LEN + instructions.iter().map(|ix| ix.space()).sum::<usize>()
+    }
+    
+    pub fn total_votes(&self) -> u128 {
+        self.for_votes + self.against_votes + self.abstain_votes
+    }
+    
+    pub fn quorum_reached(&self, quorum_bps: u64) -> bool {
+        self.total_votes() * 10000 >= self.snapshot_total_staked * quorum_bps as u128
+    }
+    
+    pub fn approved(&self, approval_threshold_bps: u64) -> bool {
+        let decisive = self.for_votes + self.against_votes;
+        decisive > 0 && self.for_votes * 10000 >= decisive * approval_threshold_bps as u128
+    }
+    
+    pub fn can_execute(&self, current_time: i64) -> bool {
+        self.status == ProposalStatus::Queued &&
+        current_time >= self.eta &&
+        current_time < self.expires_at
+    }
+}
+
+#[account]
+pub struct VoteRecord {
+    pub proposal: Pubkey,
+    pub voter: Pubkey,
+    pub side: VoteSide,
+    pub weight: u64,
+    pub timestamp: i64,
+    pub bump: u8,
+}
+
+impl VoteRecord {
+    pub const LEN: usize = 8 + // discriminator
+        32 + 32 + // proposal + voter
+        1 + 8 + 8 + // side + weight + timestamp
+        1; // bump
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct GovernanceParams {
+    pub proposal_threshold: u64,
+    pub quorum_bps: u64,
+    pub approval_threshold_bps: u64,
+    pub voting_period: i64,
+    pub timelock_delay: i64,
+    pub execution_window: i64,
+}
+
+impl Default for GovernanceParams {
+    fn default() -> Self {
+        Self {
+            proposal_threshold: 100_000 * 10u64.pow(9), // 100k TWIST staked
+            quorum_bps: 400,                            // 4% of staked supply
+            approval_threshold_bps: 5000,               // Simple majority
+            voting_period: 5 * 86400,                   // 5 days
+            timelock_delay: 2 * 86400,                  // 48 hours
+            execution_window: 7 * 86400,                // 7 days
+        }
+    }
+}
+
+/// Voting weight of a staker at the snapshot: active stakes opened before it
+pub fn snapshot_weight(stake_state: &StakeState, snapshot_timestamp: i64) -> u64 {
+    stake_state.stakes
+        .iter()
+        .filter(|stake| !stake.withdrawn && stake.start_timestamp < snapshot_timestamp)
+        .fold(0u64, |total, stake| total.saturating_add(stake.amount))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::StakeEntry;
+
+    fn stake(amount: u64, start_timestamp: i64, withdrawn: bool) -> StakeEntry {
+        StakeEntry {
+            amount,
+            start_timestamp,
+            lock_period: 30 * 86400,
+            apy_bps: 1000,
+            last_claim_timestamp: start_timestamp,
+            total_earned: 0,
+            withdrawn,
+        }
+    }
+
+    #[test]
+    fn test_snapshot_weight_ignores_late_and_withdrawn_stakes() {
+        let stake_state = StakeState {
+            owner: Pubkey::default(),
+            bump: 0,
+            stake_index: 3,
+            total_staked: 0,
+            total_earned: 0,
+            stakes: vec![stake(100, 1_000, false), stake(50, 1_000, true), stake(25, 2_000, false)],
+            is_initialized: true,
+            fee_reward_debt: 0,
+            unclaimed_fee_rewards: 0,
+        };
+        
+        assert_eq!(snapshot_weight(&stake_state, 1_500), 100);
+        assert_eq!(snapshot_weight(&stake_state, 2_001), 125);
+    }
+
+    #[test]
+    fn test_quorum_and_approval() {
+        let mut proposal = GovernanceProposal {
+            proposal_index: 0,
+            proposer: Pubkey::default(),
+            instructions: vec![],
+            status: ProposalStatus::Voting,
+            snapshot_timestamp: 0,
+            snapshot_total_staked: 10_000,
+            for_votes: 250,
+            against_votes: 100,
+            abstain_votes: 50,
+            voting_ends_at: 0,
+            eta: 0,
+            expires_at: 0,
+            executed_at: 0,
+            bump: 0,
+            title: String::new(),
+            description: String::new(),
+            _reserved: [0; 32],
+        };
+        
+        // 400 of 10,000 staked voted: exactly 4% quorum, 71% of decisive votes in favour
+        assert!(proposal.quorum_reached(400));
+        assert!(!proposal.quorum_reached(401));
+        assert!(proposal.approved(5000));
+        assert!(!proposal.approved(7500));
+        
+        // Abstentions count towards quorum but not approval
+        proposal.for_votes = 0;
+        proposal.against_votes = 0;
+        assert!(!proposal.approved(5000));
+    }
+}
@@ -0,0 +1,212 @@
+use anchor_lang::prelude::*;
+
+/// Backstop for documented incidents, funded by a slice of distributed fees
+#[account]
+pub struct InsuranceFund {
+    pub bump: u8,
+    pub vault: Pubkey,
+    
+    // Claim limits
+    pub max_claim_amount: u64,    // TWIST per claim
+    pub max_claim_vault_bps: u64, // Per claim, of the vault balance at approval
+    pub claim_window: i64,        // Seconds after an incident a claim may be filed
+    
+    // Tracking
+    pub total_deposited: u128,
+    pub total_paid: u128,
+    pub outstanding_approved: u64, // Approved but not yet paid
+    pub claim_count: u64,
+    pub claims_paid: u64,
+    
+    // Coverage versus TVL, refreshed on payouts and on demand
+    pub coverage_ratio_bps: u64,
+    pub last_vault_value: u64, // USDC atomic units
+    pub last_tvl: u64,         // USDC atomic units
+    pub last_coverage_update: i64,
+    
+    // Reserved space
+    pub _reserved: [u8; 64],
+}
+
+impl InsuranceFund {
+    pub const LEN: usize = 8 + // discriminator
+        1 + 32 + // bump + vault
+        8 + 8 + 8 + // claim limits
+        16 + 16 + 8 + 8 + 8 + // tracking
+        8 + 8 + 8 + 8 + // coverage
+        64; // reserved
+    
+    pub fn apply(&mut self, params: &InsuranceParams) {
+        self.max_claim_amount = params.max_claim_amount;
+        self.max_claim_vault_bps = params.max_claim_vault_bps;
+        self.claim_window = params.claim_window;
+    }
+    
+    /// Cap an approved amount by the per-claim limits
+    pub fn cap_claim(&self, amount: u64, vault_balance: u64) -> u64 {
+        let vault_cap = (vault_balance as u128 * self.max_claim_vault_bps as u128 / 10000) as u64;
+        amount.min(self.max_claim_amount).min(vault_cap)
+    }
+    
+    /// When approved claims exceed the vault, each is paid the same fraction of its approval
+    pub fn pro_rated_payout(&self, approved_amount: u64, vault_balance: u64) -> u64 {
+        if self.outstanding_approved <= vault_balance {
+            return approved_amount;
+        }
+        
+        (approved_amount as u128 * vault_balance as u128 / self.outstanding_approved as u128) as u64
+    }
+    
+    pub fn record_payout(&mut self, approved_amount: u64, payout: u64) {
+        self.outstanding_approved = self.outstanding_approved.saturating_sub(approved_amount);
+        self.total_paid = self.total_paid.saturating_add(payout as u128);
+        self.claims_paid += 1;
+    }
+    
+    pub fn update_coverage(&mut self, vault_value: u64, tvl: u64, timestamp: i64) {
+        self.coverage_ratio_bps = if tvl > 0 {
+            (vault_value as u128 * 10000 / tvl as u128).min(u64::MAX as u128) as u64
+        } else {
+            0
+        };
+        self.last_vault_value = vault_value;
+        self.last_tvl = tvl;
+        self.last_coverage_update = timestamp;
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct InsuranceParams {
+    pub fee_share_bps: u64, // Taken from distributed fees before the regular split
+    pub max_claim_amount: u64,
+    pub max_claim_vault_bps: u64,
+    pub claim_window: i64,
+}
+
+impl InsuranceParams {
+    pub fn is_valid(&self) -> bool {
+        self.fee_share_bps <= 2000 &&
+        self.max_claim_amount > 0 &&
+        self.max_claim_vault_bps > 0 &&
+        self.max_claim_vault_bps <= 10000 &&
+        self.claim_window > 0
+    }
+}
+
+impl Default for InsuranceParams {
+    fn default() -> Self {
+        Self {
+            fee_share_bps: 500, // 5% of distributed fees
+            max_claim_amount: 1_000_000 * 10u64.pow(9), // 1M TWIST
+            max_claim_vault_bps: 2500, // 25% of the vault per claim
+            claim_window: 30 * 86400, // 30 days
+        }
+    }
+}
+
+/// Incidents the fund covers
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IncidentType {
+    /// Funds lost or stuck in a failed bridge transfer
+    BridgeFailure,
+    /// Losses from a stale, manipulated or diverging price feed
+    OracleIncident,
+    /// Losses on a protocol-managed liquidity position
+    LiquidityPositionLoss,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClaimStatus {
+    Pending,
+    Approved,
+    Rejected,
+    Paid,
+}
+
+#[account]
+pub struct InsuranceClaim {
+    pub claim_index: u64,
+    pub claimant: Pubkey,
+    pub recipient_account: Pubkey,
+    pub incident_type: IncidentType,
+    pub incident_timestamp: i64,
+    pub evidence_hash: [u8; 32], // Hash of the off-chain incident report
+    pub amount_requested: u64,
+    pub approved_amount: u64,
+    pub paid_amount: u64,
+    pub status: ClaimStatus,
+    pub filed_at: i64,
+    pub resolved_at: i64,
+    pub resolved_by: Pubkey,
+    pub bump: u8,
+}
+
+impl InsuranceClaim {
+    pub const LEN: usize = 8 + // discriminator
+        8 + 32 + 32 + // index + claimant + recipient
+        1 + 8 + 32 + // incident
+        8 + 8 + 8 + // amounts
+        1 + 8 + 8 + 32 + // status + resolution
+        1; // bump
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fund() -> InsuranceFund {
+        let mut fund = InsuranceFund {
+            bump: 0,
+            vault: Pubkey::default(),
+            max_claim_amount: 0,
+            max_claim_vault_bps: 0,
+            claim_window: 0,
+            total_deposited: 0,
+            total_paid: 0,
+            outstanding_approved: 0,
+            claim_count: 0,
+            claims_paid: 0,
+            coverage_ratio_bps: 0,
+            last_vault_value: 0,
+            last_tvl: 0,
+            last_coverage_update: 0,
+            _reserved: [0; 64],
+        };
+        fund.apply(&InsuranceParams {
+            fee_share_bps: 500,
+            max_claim_amount: 5_000,
+            max_claim_vault_bps: 5000,
+            claim_window: 86400,
+        });
+        fund
+    }
+
+    #[test]
+    fn test_claims_are_capped_and_pro_rated() {
+        let mut fund = fund();
+        
+        // Capped by half the vault, then by the absolute limit
+        assert_eq!(fund.cap_claim(8_000, 10_000), 5_000);
+        assert_eq!(fund.cap_claim(8_000, 20_000), 5_000);
+        assert_eq!(fund.cap_claim(3_000, 20_000), 3_000);
+        
+        // Fully covered while approvals fit in the vault
+        fund.outstanding_approved = 5_000;
+        assert_eq!(fund.pro_rated_payout(5_000, 10_000), 5_000);
+        
+        // 4k + 4k approved against a 6k vault pays 75% each
+        fund.outstanding_approved = 8_000;
+        let first = fund.pro_rated_payout(4_000, 6_000);
+        assert_eq!(first, 3_000);
+        fund.record_payout(4_000, first);
+        assert_eq!(fund.pro_rated_payout(4_000, 3_000), 3_000);
+        fund.record_payout(4_000, 3_000);
+        assert_eq!(fund.outstanding_approved, 0);
+        assert_eq!(fund.total_paid, 6_000);
+        
+        fund.update_coverage(250, 10_000, 1);
+        assert_eq!(fund.coverage_ratio_bps, 250);
+        fund.update_coverage(250, 0, 2);
+        assert_eq!(fund.coverage_ratio_bps, 0);
+    }
+}
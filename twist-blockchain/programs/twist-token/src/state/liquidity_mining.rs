@@ -0,0 +1,277 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{MAX_MINING_EMISSION_RATE, MINING_REWARD_PRECISION};
+
+/// TWIST emissions to registered TWIST/USDC positions, shared by in-range liquidity
+#[account]
+pub struct LiquidityMining {
+    pub bump: u8,
+    pub whirlpool: Pubkey,
+    pub rewards_vault: Pubkey,
+    
+    // Emission schedule
+    pub emission_rate: u64, // TWIST atomic units per second
+    pub start_time: i64,
+    pub end_time: i64,
+    
+    // Rewards accumulator, scaled by MINING_REWARD_PRECISION
+    pub reward_per_liquidity: u128,
+    pub total_weighted_liquidity: u128,
+    pub last_update_time: i64,
+    
+    // Tracking
+    pub total_emitted: u64,
+    pub total_claimed: u64,
+    pub registered_positions: u64,
+    
+    // Reserved space
+    pub _reserved: [u8; 32],
+}
+
+impl LiquidityMining {
+    pub const LEN: usize = 8 + // discriminator
+        1 + 32 + 32 + // bump + whirlpool + rewards vault
+        8 + 8 + 8 + // emission schedule
+        16 + 16 + 8 + // accumulator
+        8 + 8 + 8 + // tracking
+        32; // reserved
+    
+    pub fn apply(&mut self, params: &MiningScheduleParams) {
+        self.emission_rate = params.emission_rate;
+        self.start_time = params.start_time;
+        self.end_time = params.end_time;
+    }
+    
+    /// Emit rewards for the scheduled time since the last update. Seconds with no
+    /// in-range liquidity emit nothing.
+    pub fn update(&mut self, now: i64) {
+        let from = self.last_update_time.max(self.start_time);
+        let to = now.min(self.end_time);
+        
+        if to > from && self.total_weighted_liquidity > 0 {
+            let emitted = (self.emission_rate as u128 * (to - from) as u128).min(u64::MAX as u128);
+            let increment = emitted * MINING_REWARD_PRECISION / self.total_weighted_liquidity;
+            self.reward_per_liquidity = self.reward_per_liquidity.saturating_add(increment);
+            self.total_emitted = self.total_emitted.saturating_add(emitted as u64);
+        }
+        self.last_update_time = self.last_update_time.max(now);
+    }
+    
+    /// Settle a position's rewards, then re-weight it for its current liquidity and the
+    /// pool's current tick. Weights are cached between refreshes, so the interval since
+    /// the last one pays on no more than the position's in-range liquidity now: nothing
+    /// if the price has left its range, whenever that happened.
+    pub fn sync_position(
+        &mut self,
+        position: &mut MiningPosition,
+        liquidity: u128,
+        tick_current: i32,
+        now: i64,
+    ) {
+        self.update(now);
+        let weight = position.weight_at(liquidity, tick_current);
+        position.settle(self.reward_per_liquidity, weight);
+        
+        self.total_weighted_liquidity = self
+            .total_weighted_liquidity
+            .saturating_sub(position.weighted_liquidity)
+            .saturating_add(weight);
+        
+        position.liquidity = liquidity;
+        position.weighted_liquidity = weight;
+        position.reward_debt = accrued_rewards(weight, self.reward_per_liquidity);
+        position.last_refresh = now;
+    }
+}
+
+/// A Whirlpool position registered for emissions. Rewards follow the position NFT.
+#[account]
+pub struct MiningPosition {
+    pub owner: Pubkey, // Last registrant or claimant holding the position NFT
+    pub position: Pubkey, // Whirlpool position, or the share account for vault shares
+    pub position_mint: Pubkey, // Position NFT mint, or the vault's share mint
+    pub lp_vault: Pubkey, // Default for a directly held position
+    pub lower_tick: i32,
+    pub upper_tick: i32,
+    
+    // Weight: the position's liquidity while the pool price is inside its range
+    pub liquidity: u128,
+    pub weighted_liquidity: u128,
+    
+    // Rewards
+    pub reward_debt: u128,
+    pub unclaimed_rewards: u64,
+    pub total_claimed: u64,
+    pub last_refresh: i64,
+    pub bump: u8,
+}
+
+impl MiningPosition {
+    pub const LEN: usize = 8 + // discriminator
+        32 + 32 + 32 + 32 + 4 + 4 + // owner, position, mint, vault, range
+        16 + 16 + // weight
+        16 + 8 + 8 + 8 + 1; // rewards + bump
+    
+    /// Liquidity counted at `tick_current`; a whirlpool position is active on [lower, upper)
+    pub fn weight_at(&self, liquidity: u128, tick_current: i32) -> u128 {
+        if tick_current >= self.lower_tick && tick_current < self.upper_tick {
+            liquidity
+        } else {
+            0
+        }
+    }
+    
+    pub fn is_vault_share(&self) -> bool {
+        self.lp_vault != Pubkey::default()
+    }
+    
+    /// Pay out accrued rewards. The cached weight is capped at `weight_now`, the position's
+    /// in-range liquidity at settlement, so liquidity withdrawn or a range the price left
+    /// since the last refresh earns nothing for that interval. The forfeited share stays
+    /// in the rewards vault.
+    pub fn settle(&mut self, reward_per_liquidity: u128, weight_now: u128) {
+        let accrued = accrued_rewards(self.weighted_liquidity, reward_per_liquidity);
+        let mut pending = accrued.saturating_sub(self.reward_debt);
+        if weight_now < self.weighted_liquidity {
+            let weight = self.weighted_liquidity;
+            pending = (pending / weight).saturating_mul(weight_now)
+                .saturating_add((pending % weight).saturating_mul(weight_now) / weight)
+                .min(pending);
+        }
+        let pending = pending.min(u64::MAX as u128) as u64;
+        self.unclaimed_rewards = self.unclaimed_rewards.saturating_add(pending);
+        self.reward_debt = accrued;
+    }
+}
+
+// Split so a large accumulator times a large position stays inside u128
+fn accrued_rewards(weighted_liquidity: u128, reward_per_liquidity: u128) -> u128 {
+    (reward_per_liquidity / MINING_REWARD_PRECISION).saturating_mul(weighted_liquidity)
+        .saturating_add(reward_per_liquidity % MINING_REWARD_PRECISION * weighted_liquidity / MINING_REWARD_PRECISION)
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct MiningScheduleParams {
+    pub emission_rate: u64,
+    pub start_time: i64,
+    pub end_time: i64,
+}
+
+impl MiningScheduleParams {
+    pub fn is_valid(&self) -> bool {
+        self.end_time > self.start_time &&
+        self.emission_rate <= MAX_MINING_EMISSION_RATE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mining(emission_rate: u64) -> LiquidityMining {
+        LiquidityMining {
+            bump: 0,
+            whirlpool: Pubkey::default(),
+            rewards_vault: Pubkey::default(),
+            emission_rate,
+            start_time: 100,
+            end_time: 1_100,
+            reward_per_liquidity: 0,
+            total_weighted_liquidity: 0,
+            last_update_time: 0,
+            total_emitted: 0,
+            total_claimed: 0,
+            registered_positions: 0,
+            _reserved: [0; 32],
+        }
+    }
+
+    fn position(lower_tick: i32, upper_tick: i32) -> MiningPosition {
+        MiningPosition {
+            owner: Pubkey::default(),
+            position: Pubkey::new_unique(),
+            position_mint: Pubkey::new_unique(),
+            lp_vault: Pubkey::default(),
+            lower_tick,
+            upper_tick,
+            liquidity: 0,
+            weighted_liquidity: 0,
+            reward_debt: 0,
+            unclaimed_rewards: 0,
+            total_claimed: 0,
+            last_refresh: 0,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn test_emissions_follow_in_range_liquidity() {
+        let mut mining = mining(1_000);
+        let mut narrow = position(-10, 10);
+        let mut wide = position(-100, 100);
+
+        mining.sync_position(&mut narrow, 3_000_000, 0, 100);
+        mining.sync_position(&mut wide, 1_000_000, 0, 100);
+        assert_eq!(mining.total_weighted_liquidity, 4_000_000);
+
+        // 100 seconds with both in range split 3:1
+        mining.sync_position(&mut narrow, 3_000_000, 5, 200);
+        mining.sync_position(&mut wide, 1_000_000, 5, 200);
+        assert_eq!(narrow.unclaimed_rewards, 75_000);
+        assert_eq!(wide.unclaimed_rewards, 25_000);
+
+        // The price left the narrow range before the next refresh, so the narrow
+        // position forfeits its cached share of that interval
+        mining.sync_position(&mut narrow, 3_000_000, 50, 300);
+        mining.sync_position(&mut wide, 1_000_000, 50, 300);
+        assert_eq!(narrow.unclaimed_rewards, 75_000);
+        assert_eq!(narrow.weighted_liquidity, 0);
+        assert_eq!(wide.unclaimed_rewards, 50_000);
+
+        // From then on the wide position takes everything
+        mining.sync_position(&mut narrow, 3_000_000, 50, 400);
+        mining.sync_position(&mut wide, 1_000_000, 50, 400);
+        assert_eq!(narrow.unclaimed_rewards, 75_000);
+        assert_eq!(wide.unclaimed_rewards, 150_000);
+        assert_eq!(mining.total_emitted, 300_000);
+    }
+
+    #[test]
+    fn test_emissions_respect_schedule() {
+        let mut mining = mining(1_000);
+        let mut lp = position(-10, 10);
+
+        // Nothing before the start or after the end
+        mining.sync_position(&mut lp, 1_000, 0, 50);
+        mining.update(100);
+        assert_eq!(mining.total_emitted, 0);
+
+        mining.sync_position(&mut lp, 1_000, 0, 5_000);
+        assert_eq!(mining.total_emitted, 1_000_000);
+        assert_eq!(lp.unclaimed_rewards, 1_000_000);
+
+        // Time never runs backwards
+        mining.update(4_000);
+        assert_eq!(mining.last_update_time, 5_000);
+
+        assert!(!MiningScheduleParams { emission_rate: 1, start_time: 10, end_time: 10 }.is_valid());
+    }
+
+    #[test]
+    fn test_withdrawn_liquidity_earns_nothing_before_refresh() {
+        let mut mining = mining(1_000);
+        let mut lp = position(-10, 10);
+        mining.sync_position(&mut lp, 1_000_000, 0, 100);
+
+        // Half the liquidity was pulled right after registering, so only half the
+        // interval's emissions are paid on claim
+        mining.sync_position(&mut lp, 500_000, 0, 200);
+        assert_eq!(lp.unclaimed_rewards, 50_000);
+        assert_eq!(lp.weighted_liquidity, 500_000);
+
+        // Emptying the position stops it earning for the whole interval
+        mining.sync_position(&mut lp, 0, 0, 300);
+        assert_eq!(lp.unclaimed_rewards, 50_000);
+        assert_eq!(mining.total_weighted_liquidity, 0);
+    }
+}
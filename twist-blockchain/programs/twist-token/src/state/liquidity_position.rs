@@ -33,4 +33,131 @@ impl LiquidityPosition {
         8 + // created_timestamp
         1 + // bump
         16; // padding for future fields
-}
\ No newline at end of file
+}
+
+/// Keeper-driven range management for a protocol-owned position
+#[account]
+pub struct RangeStrategy {
+    pub position_index: u8,
+    pub liquidity_position: Pubkey,
+    pub enabled: bool,
+    
+    // Range and triggers
+    pub range_width_ticks: i32,       // Full width of a recentered range
+    pub recenter_threshold_bps: u64,  // Of the half-width the price may drift from center
+    pub min_rebalance_interval: i64,  // Seconds between keeper rebalances
+    pub max_slippage_bps: u16,
+    
+    // Tracking
+    pub last_rebalance_timestamp: i64,
+    pub keeper_rebalance_count: u64,
+    pub bump: u8,
+    
+    // Reserved space
+    pub _reserved: [u8; 32],
+}
+
+impl RangeStrategy {
+    pub const LEN: usize = 8 + // discriminator
+        1 + 32 + 1 + // position + enabled
+        4 + 8 + 8 + 2 + // range and triggers
+        8 + 8 + 1 + // tracking
+        32; // reserved
+    
+    pub fn apply(&mut self, params: &RangeStrategyParams) {
+        self.enabled = params.enabled;
+        self.range_width_ticks = params.range_width_ticks;
+        self.recenter_threshold_bps = params.recenter_threshold_bps;
+        self.min_rebalance_interval = params.min_rebalance_interval;
+        self.max_slippage_bps = params.max_slippage_bps;
+    }
+    
+    /// True once the current tick has drifted past the recenter band of the range
+    pub fn needs_recenter(&self, current_tick: i32, lower_tick: i32, upper_tick: i32) -> bool {
+        if current_tick < lower_tick || current_tick >= upper_tick {
+            return true;
+        }
+        
+        let center = (lower_tick as i64 + upper_tick as i64) / 2;
+        let half_width = (upper_tick as i64 - lower_tick as i64) / 2;
+        let drift = (current_tick as i64 - center).abs();
+        
+        drift as i128 * 10000 > half_width as i128 * self.recenter_threshold_bps as i128
+    }
+    
+    pub fn can_rebalance(&self, now: i64) -> bool {
+        now - self.last_rebalance_timestamp >= self.min_rebalance_interval
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct RangeStrategyParams {
+    pub enabled: bool,
+    pub range_width_ticks: i32,
+    pub recenter_threshold_bps: u64,
+    pub min_rebalance_interval: i64,
+    pub max_slippage_bps: u16,
+}
+
+impl RangeStrategyParams {
+    pub fn is_valid(&self) -> bool {
+        self.range_width_ticks > 0 &&
+        self.recenter_threshold_bps > 0 &&
+        self.recenter_threshold_bps <= 10000 &&
+        self.min_rebalance_interval >= 0 &&
+        self.max_slippage_bps <= 1000
+    }
+}
+
+impl Default for RangeStrategyParams {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            range_width_ticks: 4000, // About ±20% around the price
+            recenter_threshold_bps: 8000, // Recenter past 80% of the half-width
+            min_rebalance_interval: 3600, // 1 hour
+            max_slippage_bps: 100, // 1%
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recenter_triggers() {
+        let mut strategy = RangeStrategy {
+            position_index: 0,
+            liquidity_position: Pubkey::default(),
+            enabled: true,
+            range_width_ticks: 0,
+            recenter_threshold_bps: 0,
+            min_rebalance_interval: 0,
+            max_slippage_bps: 0,
+            last_rebalance_timestamp: 1_000,
+            keeper_rebalance_count: 0,
+            bump: 0,
+            _reserved: [0; 32],
+        };
+        strategy.apply(&RangeStrategyParams {
+            recenter_threshold_bps: 5000,
+            ..RangeStrategyParams::default()
+        });
+        
+        // Range [-1000, 1000): the band is ±500 ticks around 0
+        assert!(!strategy.needs_recenter(0, -1000, 1000));
+        assert!(!strategy.needs_recenter(500, -1000, 1000));
+        assert!(strategy.needs_recenter(501, -1000, 1000));
+        assert!(strategy.needs_recenter(-501, -1000, 1000));
+        assert!(strategy.needs_recenter(1000, -1000, 1000));
+        
+        // Only out of range at the full threshold
+        strategy.recenter_threshold_bps = 10000;
+        assert!(!strategy.needs_recenter(999, -1000, 1000));
+        assert!(strategy.needs_recenter(-1001, -1000, 1000));
+        
+        assert!(!strategy.can_rebalance(4_599));
+        assert!(strategy.can_rebalance(4_600));
+    }
+}
@@ -0,0 +1,348 @@
+use anchor_lang::prelude::*;
+use crate::constants::SECONDS_PER_YEAR;
+use crate::defi::{get_amounts_for_liquidity, sqrt_price_from_tick_index};
+
+/// User vault over a protocol-managed liquidity position. Shares are priced by the
+/// position's net asset value, so compounded fees accrue to every holder.
+#[account]
+pub struct LpVault {
+    pub position_index: u8,
+    pub liquidity_position: Pubkey,
+    pub share_mint: Pubkey,
+    pub twist_account: Pubkey, // Idle TWIST, owned by program state
+    pub usdc_account: Pubkey,  // Idle USDC, owned by program state
+    
+    // Limits and fees
+    pub deposits_enabled: bool,
+    pub deposit_cap: u64,        // Max NAV in USDC atomic units
+    pub management_fee_bps: u64, // Annual, charged by share dilution
+    
+    // Management fee shares accrued but not yet paid to the fee collector
+    pub fee_shares: u64,
+    pub last_fee_accrual: i64,
+    
+    // Tracking
+    pub total_fees_paid_twist: u64,
+    pub total_fees_paid_usdc: u64,
+    pub bump: u8,
+    
+    // Reserved space
+    pub _reserved: [u8; 32],
+}
+
+impl LpVault {
+    pub const LEN: usize = 8 + // discriminator
+        1 + 32 + 32 + 32 + 32 + // position, share mint, token accounts
+        1 + 8 + 8 + // limits and fees
+        8 + 8 + // fee accrual
+        8 + 8 + 1 + // tracking
+        32; // reserved
+    
+    pub fn apply(&mut self, params: &LpVaultParams) {
+        self.deposits_enabled = params.deposits_enabled;
+        self.deposit_cap = params.deposit_cap;
+        self.management_fee_bps = params.management_fee_bps;
+    }
+    
+    /// Shares outstanding, counting accrued fee shares alongside minted ones
+    pub fn total_shares(&self, share_supply: u64) -> u64 {
+        share_supply.saturating_add(self.fee_shares)
+    }
+    
+    /// Dilute holders by the management fee earned since the last accrual
+    pub fn accrue_management_fee(&mut self, share_supply: u64, now: i64) {
+        let elapsed = now.saturating_sub(self.last_fee_accrual).max(0);
+        let total_shares = self.total_shares(share_supply);
+        
+        if total_shares > 0 && self.management_fee_bps > 0 {
+            let accrued = total_shares as u128 * self.management_fee_bps as u128 * elapsed as u128
+                / (10000 * SECONDS_PER_YEAR as u128);
+            self.fee_shares = self.fee_shares.saturating_add(accrued.min(u64::MAX as u128) as u64);
+        }
+        self.last_fee_accrual = now;
+    }
+    
+    /// Shares minted for a deposit worth `deposit_value` into a vault worth `nav`
+    pub fn shares_for_deposit(&self, deposit_value: u64, nav: u64, share_supply: u64) -> Option<u64> {
+        let total_shares = self.total_shares(share_supply);
+        if total_shares == 0 {
+            return Some(deposit_value);
+        }
+        if nav == 0 {
+            return None;
+        }
+        
+        let shares = deposit_value as u128 * total_shares as u128 / nav as u128;
+        u64::try_from(shares).ok()
+    }
+}
+
+/// Holder's pro-rata portion of `amount` for `shares` out of `total_shares`
+pub fn pro_rata_amount(amount: u64, shares: u64, total_shares: u64) -> u64 {
+    if total_shares == 0 {
+        return 0;
+    }
+    
+    (amount as u128 * shares as u128 / total_shares as u128) as u64
+}
+
+pub fn pro_rata_liquidity(liquidity: u128, shares: u64, total_shares: u64) -> u128 {
+    if total_shares == 0 {
+        return 0;
+    }
+    
+    // Split to keep the product inside u128 for any position size
+    let shares = shares as u128;
+    let total_shares = total_shares as u128;
+    (liquidity / total_shares) * shares + (liquidity % total_shares) * shares / total_shares
+}
+
+/// Position liquidity and idle TWIST and USDC redeemed by `shares` out of `total_shares`
+pub fn redemption_for_shares(
+    liquidity: u128,
+    idle_twist: u64,
+    idle_usdc: u64,
+    shares: u64,
+    total_shares: u64,
+) -> (u128, u64, u64) {
+    (
+        pro_rata_liquidity(liquidity, shares, total_shares),
+        pro_rata_amount(idle_twist, shares, total_shares),
+        pro_rata_amount(idle_usdc, shares, total_shares),
+    )
+}
+
+/// TWIST and USDC behind a vault: its position at the pool price plus idle balances
+pub fn vault_holdings(
+    sqrt_price: u128,
+    lower_tick: i32,
+    upper_tick: i32,
+    liquidity: u128,
+    idle_twist: u64,
+    idle_usdc: u64,
+) -> Result<(u64, u64)> {
+    let (position_twist, position_usdc) = get_amounts_for_liquidity(
+        sqrt_price,
+        sqrt_price_from_tick_index(lower_tick)?,
+        sqrt_price_from_tick_index(upper_tick)?,
+        liquidity,
+        false,
+    )?;
+    
+    Ok((position_twist.saturating_add(idle_twist), position_usdc.saturating_add(idle_usdc)))
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct LpVaultParams {
+    pub deposits_enabled: bool,
+    pub deposit_cap: u64,
+    pub management_fee_bps: u64,
+}
+
+impl LpVaultParams {
+    pub fn is_valid(&self) -> bool {
+        self.deposit_cap > 0 &&
+        self.management_fee_bps <= 500 // At most 5% a year
+    }
+}
+
+impl Default for LpVaultParams {
+    fn default() -> Self {
+        Self {
+            deposits_enabled: true,
+            deposit_cap: 1_000_000 * 1_000_000, // $1M
+            management_fee_bps: 200, // 2% a year
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::defi::{get_liquidity_for_amounts, round_tick_to_spacing, sqrt_price_from_price, tick_index_from_sqrt_price};
+
+    fn vault() -> LpVault {
+        let mut vault = LpVault {
+            position_index: 0,
+            liquidity_position: Pubkey::default(),
+            share_mint: Pubkey::default(),
+            twist_account: Pubkey::default(),
+            usdc_account: Pubkey::default(),
+            deposits_enabled: false,
+            deposit_cap: 0,
+            management_fee_bps: 0,
+            fee_shares: 0,
+            last_fee_accrual: 0,
+            total_fees_paid_twist: 0,
+            total_fees_paid_usdc: 0,
+            bump: 0,
+            _reserved: [0; 32],
+        };
+        vault.apply(&LpVaultParams::default());
+        vault
+    }
+
+    #[test]
+    fn test_shares_track_nav() {
+        let vault = vault();
+
+        // First deposit mints one share per USDC unit of value
+        assert_eq!(vault.shares_for_deposit(1_000_000, 0, 0), Some(1_000_000));
+
+        // After the NAV doubles from compounding, the same value buys half the shares
+        assert_eq!(vault.shares_for_deposit(1_000_000, 2_000_000, 1_000_000), Some(500_000));
+        assert_eq!(vault.shares_for_deposit(1_000_000, 0, 1_000_000), None);
+
+        assert_eq!(pro_rata_amount(3_000, 250, 1_000), 750);
+        assert_eq!(pro_rata_amount(3_000, 250, 0), 0);
+        assert_eq!(pro_rata_liquidity(u128::MAX, 1, 2), u128::MAX / 2);
+    }
+
+    #[test]
+    fn test_management_fee_dilutes_holders() {
+        let mut charged = vault();
+        charged.last_fee_accrual = 1_000;
+
+        // A full year at 2% on 1M shares
+        charged.accrue_management_fee(1_000_000, 1_000 + SECONDS_PER_YEAR);
+        assert_eq!(charged.fee_shares, 20_000);
+        assert_eq!(charged.total_shares(1_000_000), 1_020_000);
+
+        // No time, no fee
+        charged.accrue_management_fee(1_000_000, 1_000 + SECONDS_PER_YEAR);
+        assert_eq!(charged.fee_shares, 20_000);
+
+        // An empty vault accrues nothing but still advances the clock
+        let mut empty = vault();
+        empty.accrue_management_fee(0, 5_000);
+        assert_eq!(empty.fee_shares, 0);
+        assert_eq!(empty.last_fee_accrual, 5_000);
+    }
+
+    // A vault over a position straddling the pool price, driven the way the deposit and
+    // withdraw handlers drive it
+    struct SimulatedVault {
+        vault: LpVault,
+        sqrt_price: u128,
+        lower_tick: i32,
+        upper_tick: i32,
+        liquidity: u128,
+        idle_twist: u64,
+        idle_usdc: u64,
+        share_supply: u64,
+    }
+
+    const TWIST_PRICE: u64 = 50_000; // $0.05
+
+    fn value(twist: u64, usdc: u64) -> u64 {
+        usdc + (twist as u128 * TWIST_PRICE as u128 / 1_000_000_000) as u64
+    }
+
+    impl SimulatedVault {
+        fn new() -> Self {
+            let mut vault = vault();
+            vault.management_fee_bps = 0;
+            let sqrt_price = sqrt_price_from_price(TWIST_PRICE, 9).unwrap();
+            let tick = tick_index_from_sqrt_price(sqrt_price).unwrap();
+            Self {
+                vault,
+                sqrt_price,
+                lower_tick: round_tick_to_spacing(tick - 4_000, 64, false).unwrap(),
+                upper_tick: round_tick_to_spacing(tick + 4_000, 64, true).unwrap(),
+                liquidity: 0,
+                idle_twist: 0,
+                idle_usdc: 0,
+                share_supply: 0,
+            }
+        }
+
+        fn nav(&self) -> u64 {
+            let (twist, usdc) = vault_holdings(
+                self.sqrt_price,
+                self.lower_tick,
+                self.upper_tick,
+                self.liquidity,
+                self.idle_twist,
+                self.idle_usdc,
+            ).unwrap();
+            value(twist, usdc)
+        }
+
+        fn deposit(&mut self, twist: u64, usdc: u64) -> u64 {
+            let shares = self.vault
+                .shares_for_deposit(value(twist, usdc), self.nav(), self.share_supply)
+                .unwrap();
+            self.idle_twist += twist;
+            self.idle_usdc += usdc;
+
+            // Idle balances go into the position; Whirlpool takes the rounded-up amounts
+            let added = get_liquidity_for_amounts(
+                self.sqrt_price,
+                sqrt_price_from_tick_index(self.lower_tick).unwrap(),
+                sqrt_price_from_tick_index(self.upper_tick).unwrap(),
+                self.idle_twist,
+                self.idle_usdc,
+            ).unwrap();
+            let (used_twist, used_usdc) = get_amounts_for_liquidity(
+                self.sqrt_price,
+                sqrt_price_from_tick_index(self.lower_tick).unwrap(),
+                sqrt_price_from_tick_index(self.upper_tick).unwrap(),
+                added,
+                true,
+            ).unwrap();
+            assert!(used_twist <= self.idle_twist && used_usdc <= self.idle_usdc);
+            self.idle_twist -= used_twist;
+            self.idle_usdc -= used_usdc;
+            self.liquidity += added;
+            self.share_supply += shares;
+            shares
+        }
+
+        fn withdraw(&mut self, shares: u64) -> (u64, u64) {
+            let (liquidity, idle_twist, idle_usdc) = redemption_for_shares(
+                self.liquidity,
+                self.idle_twist,
+                self.idle_usdc,
+                shares,
+                self.vault.total_shares(self.share_supply),
+            );
+            let (twist, usdc) = get_amounts_for_liquidity(
+                self.sqrt_price,
+                sqrt_price_from_tick_index(self.lower_tick).unwrap(),
+                sqrt_price_from_tick_index(self.upper_tick).unwrap(),
+                liquidity,
+                false,
+            ).unwrap();
+            self.liquidity -= liquidity;
+            self.idle_twist -= idle_twist;
+            self.idle_usdc -= idle_usdc;
+            self.share_supply -= shares;
+            (twist + idle_twist, usdc + idle_usdc)
+        }
+    }
+
+    #[test]
+    fn test_deposit_then_withdraw_round_trip() {
+        let mut sim = SimulatedVault::new();
+
+        // $500 of TWIST and $500 of USDC
+        let alice = sim.deposit(10_000 * 1_000_000_000, 500 * 1_000_000);
+        assert_eq!(alice, 1_000 * 1_000_000);
+        assert!(sim.liquidity > 0);
+
+        // Same value in a different mix buys the same shares, less rounding
+        let bob = sim.deposit(4_000 * 1_000_000_000, 800 * 1_000_000);
+        assert!(alice.abs_diff(bob) <= alice / 10_000);
+
+        // Redemption never pays out more than went in
+        let (twist, usdc) = sim.withdraw(alice);
+        let alice_out = value(twist, usdc);
+        assert!(alice_out <= 1_000 * 1_000_000);
+        assert!(alice_out >= 999 * 1_000_000);
+
+        let (twist, usdc) = sim.withdraw(bob);
+        assert!(value(twist, usdc) >= 999 * 1_000_000);
+        assert_eq!(sim.share_supply, 0);
+        assert_eq!(sim.liquidity, 0);
+    }
+}
@@ -0,0 +1,303 @@
+use anchor_lang::prelude::*;
+
+use crate::state::ProgramState;
+
+pub const HOUR: i64 = 3600;
+pub const DAY: i64 = 86400;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default)]
+pub struct MetricsBucket {
+    pub start_timestamp: i64,
+    pub volume: u128, // USDC atomic units
+    pub open_price: u64,
+    pub close_price: u64,
+    pub liquidity: u64,
+    pub supply: u64,
+}
+
+impl MetricsBucket {
+    pub const LEN: usize = 8 + 16 + 8 + 8 + 8 + 8;
+}
+
+#[account]
+pub struct MarketMetrics {
+    pub bump: u8,
+    pub last_update_timestamp: i64,
+    
+    // Ring buffers indexed by (timestamp / period) % len
+    pub hourly: [MetricsBucket; 24],
+    pub daily: [MetricsBucket; 30],
+    
+    // Reserved space
+    pub _reserved: [u8; 32],
+}
+
+impl MarketMetrics {
+    pub const HOURLY_BUCKETS: usize = 24;
+    pub const DAILY_BUCKETS: usize = 30;
+    pub const LEN: usize = 8 + // discriminator
+        1 + 8 + // bump + last_update_timestamp
+        MetricsBucket::LEN * Self::HOURLY_BUCKETS + // hourly buckets
+        MetricsBucket::LEN * Self::DAILY_BUCKETS + // daily buckets
+        32; // reserved
+    
+    pub fn record_volume(&mut self, timestamp: i64, usdc_volume: u64) {
+        self.roll(timestamp);
+        
+        let hourly = self.current_hourly_mut(timestamp);
+        hourly.volume = hourly.volume.saturating_add(usdc_volume as u128);
+        
+        let daily = self.current_daily_mut(timestamp);
+        daily.volume = daily.volume.saturating_add(usdc_volume as u128);
+    }
+    
+    pub fn record_price(&mut self, timestamp: i64, price: u64) {
+        self.roll(timestamp);
+        
+        let hourly = self.current_hourly_mut(timestamp);
+        if hourly.open_price == 0 {
+            hourly.open_price = price;
+        }
+        hourly.close_price = price;
+        
+        let daily = self.current_daily_mut(timestamp);
+        if daily.open_price == 0 {
+            daily.open_price = price;
+        }
+        daily.close_price = price;
+    }
+    
+    pub fn record_liquidity(&mut self, timestamp: i64, liquidity: u64) {
+        self.roll(timestamp);
+        
+        self.current_hourly_mut(timestamp).liquidity = liquidity;
+        self.current_daily_mut(timestamp).liquidity = liquidity;
+    }
+    
+    pub fn record_supply(&mut self, timestamp: i64, supply: u64) {
+        self.roll(timestamp);
+        
+        self.current_hourly_mut(timestamp).supply = supply;
+        self.current_daily_mut(timestamp).supply = supply;
+    }
+    
+    /// Most recent hourly bucket that started at or before `timestamp`
+    pub fn hourly_at(&self, timestamp: i64) -> Option<&MetricsBucket> {
+        Self::latest_at(&self.hourly, timestamp, HOUR * Self::HOURLY_BUCKETS as i64)
+    }
+    
+    /// Most recent daily bucket that started at or before `timestamp`
+    pub fn daily_at(&self, timestamp: i64) -> Option<&MetricsBucket> {
+        Self::latest_at(&self.daily, timestamp, DAY * Self::DAILY_BUCKETS as i64)
+    }
+    
+    /// Volume traded in the current hour
+    pub fn volume_1h(&self, timestamp: i64) -> u128 {
+        self.volume_since(&self.hourly, timestamp, HOUR)
+    }
+    
+    pub fn volume_24h(&self, timestamp: i64) -> u128 {
+        self.volume_since(&self.hourly, timestamp, DAY)
+    }
+    
+    pub fn volume_7d(&self, timestamp: i64) -> u128 {
+        self.volume_since(&self.daily, timestamp, 7 * DAY)
+    }
+    
+    pub fn volume_30d(&self, timestamp: i64) -> u128 {
+        self.volume_since(&self.daily, timestamp, 30 * DAY)
+    }
+    
+    /// Average hourly volume over the completed hours of the last day
+    pub fn average_hourly_volume(&self, timestamp: i64) -> u128 {
+        let current_start = Self::period_start(timestamp, HOUR);
+        let completed: Vec<&MetricsBucket> = self.hourly
+            .iter()
+            .filter(|b| b.start_timestamp > 0 &&
+                b.start_timestamp < current_start &&
+                b.start_timestamp >= current_start - DAY)
+            .collect();
+        
+        if completed.is_empty() {
+            return 0;
+        }
+        
+        let total: u128 = completed.iter().map(|b| b.volume).sum();
+        total / completed.len() as u128
+    }
+    
+    /// Range of hourly prices over the last day, in bps of the low
+    pub fn price_volatility_bps(&self, timestamp: i64) -> u64 {
+        let prices = self.hourly
+            .iter()
+            .filter(|b| b.start_timestamp > 0 &&
+                b.start_timestamp <= timestamp &&
+                b.start_timestamp > timestamp - DAY)
+            .flat_map(|b| [b.open_price, b.close_price])
+            .filter(|price| *price > 0);
+        
+        let (low, high) = prices.fold((u64::MAX, 0), |(low, high), price| {
+            (low.min(price), high.max(price))
+        });
+        if high == 0 {
+            return 0;
+        }
+        
+        ((high - low) as u128 * 10000 / low as u128) as u64
+    }
+    
+    pub fn refresh_program_volumes(&self, program_state: &mut ProgramState, timestamp: i64) {
+        program_state.volume_24h = self.volume_24h(timestamp);
+        program_state.volume_7d = self.volume_7d(timestamp);
+        program_state.volume_30d = self.volume_30d(timestamp);
+    }
+    
+    fn roll(&mut self, timestamp: i64) {
+        let hour_start = Self::period_start(timestamp, HOUR);
+        let day_start = Self::period_start(timestamp, DAY);
+        
+        // Carry the latest observations into a fresh bucket so gaps read as unchanged
+        let carried = self.latest_bucket();
+        
+        let hourly = self.current_hourly_mut(timestamp);
+        if hourly.start_timestamp != hour_start {
+            *hourly = MetricsBucket {
+                start_timestamp: hour_start,
+                volume: 0,
+                open_price: carried.close_price,
+                close_price: carried.close_price,
+                liquidity: carried.liquidity,
+                supply: carried.supply,
+            };
+        }
+        
+        let daily = self.current_daily_mut(timestamp);
+        if daily.start_timestamp != day_start {
+            *daily = MetricsBucket {
+                start_timestamp: day_start,
+                volume: 0,
+                open_price: carried.close_price,
+                close_price: carried.close_price,
+                liquidity: carried.liquidity,
+                supply: carried.supply,
+            };
+        }
+        
+        self.last_update_timestamp = timestamp;
+    }
+    
+    fn latest_bucket(&self) -> MetricsBucket {
+        self.hourly
+            .iter()
+            .max_by_key(|b| b.start_timestamp)
+            .copied()
+            .unwrap_or_default()
+    }
+    
+    fn current_hourly_mut(&mut self, timestamp: i64) -> &mut MetricsBucket {
+        let index = Self::bucket_index(timestamp, HOUR, Self::HOURLY_BUCKETS);
+        &mut self.hourly[index]
+    }
+    
+    fn current_daily_mut(&mut self, timestamp: i64) -> &mut MetricsBucket {
+        let index = Self::bucket_index(timestamp, DAY, Self::DAILY_BUCKETS);
+        &mut self.daily[index]
+    }
+    
+    fn volume_since(&self, buckets: &[MetricsBucket], timestamp: i64, window: i64) -> u128 {
+        buckets
+            .iter()
+            .filter(|b| b.start_timestamp > 0 &&
+                b.start_timestamp <= timestamp &&
+                b.start_timestamp > timestamp - window)
+            .map(|b| b.volume)
+            .sum()
+    }
+    
+    fn latest_at(buckets: &[MetricsBucket], timestamp: i64, window: i64) -> Option<&MetricsBucket> {
+        buckets
+            .iter()
+            .filter(|b| b.start_timestamp > 0 &&
+                b.start_timestamp <= timestamp &&
+                b.start_timestamp > timestamp - window)
+            .max_by_key(|b| b.start_timestamp)
+    }
+    
+    fn period_start(timestamp: i64, period: i64) -> i64 {
+        timestamp - timestamp.rem_euclid(period)
+    }
+    
+    fn bucket_index(timestamp: i64, period: i64, len: usize) -> usize {
+        (timestamp.div_euclid(period) as usize) % len
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn empty_metrics() -> MarketMetrics {
+        MarketMetrics {
+            bump: 0,
+            last_update_timestamp: 0,
+            hourly: [MetricsBucket::default(); 24],
+            daily: [MetricsBucket::default(); 30],
+            _reserved: [0; 32],
+        }
+    }
+
+    #[test]
+    fn test_hourly_snapshot_and_volume_windows() {
+        let mut metrics = empty_metrics();
+        let start = 1_700_000_000 - 1_700_000_000 % DAY;
+        
+        // Price moves from $0.05 to $0.06 over two hours with steady volume
+        metrics.record_price(start, 50_000);
+        metrics.record_volume(start, 1_000);
+        metrics.record_price(start + HOUR, 55_000);
+        metrics.record_volume(start + HOUR, 1_000);
+        metrics.record_price(start + 2 * HOUR + 10, 60_000);
+        metrics.record_volume(start + 2 * HOUR + 10, 5_000);
+        
+        let now = start + 2 * HOUR + 10;
+        assert_eq!(metrics.hourly_at(now - HOUR).unwrap().close_price, 55_000);
+        assert_eq!(metrics.volume_1h(now), 5_000);
+        assert_eq!(metrics.volume_24h(now), 7_000);
+        assert_eq!(metrics.average_hourly_volume(now), 1_000);
+    }
+
+    #[test]
+    fn test_gaps_carry_forward_and_buckets_wrap() {
+        let mut metrics = empty_metrics();
+        let start = 1_700_000_000 - 1_700_000_000 % DAY;
+        
+        metrics.record_price(start, 50_000);
+        metrics.record_volume(start, 1_000);
+        
+        // No activity for 25 hours: the old hourly bucket is reused, price carries over
+        let later = start + 25 * HOUR;
+        metrics.record_volume(later, 2_000);
+        assert_eq!(metrics.volume_24h(later), 2_000);
+        assert_eq!(metrics.hourly_at(later).unwrap().open_price, 50_000);
+        
+        // Yesterday's daily bucket is still available for the 24h delta
+        assert_eq!(metrics.daily_at(later - DAY).unwrap().volume, 1_000);
+        assert_eq!(metrics.volume_7d(later), 3_000);
+    }
+
+    #[test]
+    fn test_price_volatility_uses_last_day_range() {
+        let mut metrics = empty_metrics();
+        let start = 1_700_000_000 - 1_700_000_000 % DAY;
+        
+        metrics.record_price(start, 40_000);
+        metrics.record_price(start + 2 * HOUR, 50_000);
+        metrics.record_price(start + 3 * HOUR, 55_000);
+        
+        // 40,000 to 55,000 is a 37.5% range
+        assert_eq!(metrics.price_volatility_bps(start + 3 * HOUR), 3750);
+        
+        // Once those hours leave the 24h window there is no range
+        assert_eq!(metrics.price_volatility_bps(start + 28 * HOUR), 0);
+    }
+}
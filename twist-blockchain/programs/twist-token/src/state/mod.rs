@@ -9,6 +9,14 @@ pub mod pid_controller;
 pub mod circuit_breaker;
 pub mod fee_collector;
 pub mod multisig;
+pub mod market_metrics;
+pub mod governance;
+pub mod insurance;
+pub mod dex_registry;
+pub mod lp_vault;
+pub mod liquidity_mining;
+pub mod dex_oracle;
+pub mod price_reporter;
 
 pub use program_state::*;
 pub use token_state::*;
@@ -20,4 +28,12 @@ pub use liquidity_position::*;
 pub use pid_controller::*;
 pub use circuit_breaker::*;
 pub use fee_collector::*;
-pub use multisig::*;
\ No newline at end of file
+pub use multisig::*;
+pub use market_metrics::*;
+pub use governance::*;
+pub use insurance::*;
+pub use dex_registry::*;
+pub use lp_vault::*;
+pub use liquidity_mining::*;
+pub use dex_oracle::*;
+pub use price_reporter::*;
\ No newline at end of file
@@ -1,4 +1,10 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+use anchor_lang::Discriminator;
+
+use crate::constants::MULTISIG_PROPOSAL_LIFETIME;
+use crate::errors::TwistError;
+use crate::instruction as twist_ix;
 
 #[account]
 pub struct MultisigConfig {
@@ -42,6 +48,32 @@ impl MultisigConfig {
         self.threshold <= self.members.len() as u64 &&
         self.members.len() >= 3 // Minimum 3 members
     }
+    
+    pub fn min_delay(&self, class: OperationClass) -> i64 {
+        match class {
+            OperationClass::Emergency => 0,
+            OperationClass::ParameterUpdate => self.parameter_update_delay,
+            OperationClass::TreasuryWithdrawal => self.treasury_withdrawal_delay,
+            OperationClass::AuthorityTransfer => self.authority_transfer_delay,
+        }
+    }
+    
+    /// Class with the longest configured delay among the given instructions
+    pub fn classify(&self, instructions: &[TransactionInstruction]) -> OperationClass {
+        instructions
+            .iter()
+            .map(|ix| ix.operation_class())
+            .max_by_key(|class| self.min_delay(*class))
+            .unwrap_or(OperationClass::AuthorityTransfer)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OperationClass {
+    Emergency,          // Circuit breaker and pause actions, no delay
+    ParameterUpdate,    // Protocol parameter changes
+    TreasuryWithdrawal, // Movement of protocol funds
+    AuthorityTransfer,  // Authority, membership and threshold changes
 }
 
 #[account]
@@ -49,15 +81,18 @@ pub struct MultisigTransaction {
     pub multisig: Pubkey,
     pub transaction_index: u64,
     pub proposer: Pubkey,
-    pub instruction_data: Vec<u8>,
-    pub instruction_program_id: Pubkey,
-    pub instruction_accounts: Vec<TransactionAccount>,
+    pub instructions: Vec<TransactionInstruction>, // Executed in order, atomically
     pub signers: Vec<Pubkey>,
     pub executed: bool,
     pub cancelled: bool,
+    pub finalized: bool, // All instruction data uploaded
     pub created_at: i64,
     pub executed_at: i64,
+    pub delay_seconds: i64,
+    pub operation_class: OperationClass,
     pub eta: i64, // Estimated time of arrival (timelock)
+    pub expires_at: i64,
+    pub buffer_hash: Option<[u8; 32]>, // Hash of the bytecode for program upgrade proposals
     pub bump: u8,
     
     // Transaction metadata
@@ -69,23 +104,44 @@ pub struct MultisigTransaction {
 }
 
 impl MultisigTransaction {
-    pub const LEN: usize = 8 + // discriminator
+    pub const MAX_INSTRUCTIONS: usize = 8;
+    pub const MAX_ACCOUNTS_PER_INSTRUCTION: usize = 16;
+    pub const MAX_ACCOUNT_SIZE: usize = 10240; // Largest account init allows via CPI
+    
+    pub const BASE_LEN: usize = 8 + // discriminator
         32 + // multisig
         8 + // transaction_index
         32 + // proposer
-        4 + 1024 + // instruction_data (max 1KB)
-        32 + // instruction_program_id
-        4 + (64 * 10) + // instruction_accounts (max 10 accounts)
+        4 + // instructions vec prefix
         4 + (32 * 10) + // signers (max 10 signers)
         1 + // executed
         1 + // cancelled
+        1 + // finalized
         8 + // created_at
         8 + // executed_at
+        8 + // delay_seconds
+        1 + // operation_class
         8 + // eta
+        8 + // expires_at
+        1 + 32 + // buffer_hash
         1 + // bump
         4 + 64 + // title (max 64 chars)
         4 + 256 + // description (max 256 chars)
         32; // reserved
+    
+    // Single instruction with up to 1KB of data and 10 accounts
+    pub const LEN: usize = Self::BASE_LEN + TransactionInstruction::HEADER_LEN + (TransactionAccount::LEN * 10) + 1024;
+    
+    // Single loader instruction: Upgrade takes 7 accounts and 4 bytes of data
+    pub const PROGRAM_UPGRADE_LEN: usize = Self::BASE_LEN + TransactionInstruction::HEADER_LEN + (TransactionAccount::LEN * 7) + 4;
+    
+    pub fn space(instructions: &[TransactionInstruction]) -> usize {
+        Self::BASE_LEN + instructions.iter().map(|ix| ix.space()).sum::<usize>()
+    }
+    
+    pub fn is_fully_uploaded(&self) -> bool {
+        self.instructions.iter().all(|ix| ix.is_complete())
+    }
         
     pub fn has_signed(&self, signer: &Pubkey) -> bool {
         self.signers.contains(signer)
@@ -95,11 +151,84 @@ impl MultisigTransaction {
         self.signers.len() as u64
     }
     
-    pub fn can_execute(&self, threshold: u64, current_time: i64) -> bool {
+    /// Signatures from accounts that are still members of the multisig
+    pub fn valid_signature_count(&self, multisig: &MultisigConfig) -> u64 {
+        self.signers.iter().filter(|s| multisig.is_member(s)).count() as u64
+    }
+    
+    pub fn is_expired(&self, current_time: i64) -> bool {
+        current_time >= self.expires_at
+    }
+    
+    /// Executed, cancelled or expired; the account can be closed
+    pub fn is_settled(&self, current_time: i64) -> bool {
+        self.executed || self.cancelled || self.is_expired(current_time)
+    }
+    
+    pub fn can_execute(&self, multisig: &MultisigConfig, current_time: i64) -> bool {
         !self.executed && 
         !self.cancelled && 
-        self.signature_count() >= threshold &&
-        current_time >= self.eta
+        self.finalized &&
+        self.valid_signature_count(multisig) >= multisig.threshold &&
+        current_time >= self.eta &&
+        !self.is_expired(current_time)
+    }
+    
+    /// Append uploaded instruction data. Chunks must arrive in order so a retried
+    /// upload cannot corrupt the payload.
+    pub fn append_chunk(&mut self, instruction_index: u8, offset: u32, chunk: &[u8]) -> Result<()> {
+        let instruction = self.instructions
+            .get_mut(instruction_index as usize)
+            .ok_or(TwistError::InvalidAccount)?;
+        
+        require!(
+            offset as usize == instruction.data.len(),
+            TwistError::InvalidAmount
+        );
+        
+        require!(
+            instruction.data.len() + chunk.len() <= instruction.data_len as usize,
+            TwistError::InvalidAmount
+        );
+        
+        instruction.data.extend_from_slice(chunk);
+        Ok(())
+    }
+    
+    /// Mark a fully uploaded proposal final and start its timelock. A proposal whose
+    /// upload outlived it cannot be revived.
+    pub fn finalize(&mut self, multisig: &MultisigConfig, current_time: i64) -> Result<()> {
+        require!(
+            !self.is_expired(current_time),
+            TwistError::ProposalExpired
+        );
+        
+        require!(
+            self.is_fully_uploaded(),
+            TwistError::InvalidAmount
+        );
+        
+        // The timelock starts once the full payload is visible to signers
+        self.schedule(multisig, current_time)?;
+        self.finalized = true;
+        Ok(())
+    }
+    
+    /// Start the timelock, never shorter than the minimum for the operation class
+    pub fn schedule(&mut self, multisig: &MultisigConfig, current_time: i64) -> Result<()> {
+        self.operation_class = multisig.classify(&self.instructions);
+        let delay = self.delay_seconds.max(multisig.min_delay(self.operation_class));
+        let eta = current_time
+            .checked_add(delay)
+            .ok_or(TwistError::MathOverflow)?;
+        let expires_at = eta
+            .checked_add(MULTISIG_PROPOSAL_LIFETIME)
+            .ok_or(TwistError::MathOverflow)?;
+        
+        self.delay_seconds = delay;
+        self.eta = eta;
+        self.expires_at = expires_at;
+        Ok(())
     }
 }
 
@@ -110,6 +239,139 @@ pub struct TransactionAccount {
     pub is_writable: bool,
 }
 
+impl TransactionAccount {
+    pub const LEN: usize = 32 + 1 + 1;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct TransactionInstruction {
+    pub program_id: Pubkey,
+    pub accounts: Vec<TransactionAccount>,
+    pub data: Vec<u8>,
+    pub data_len: u32, // Declared length, data may be uploaded in chunks
+}
+
+impl TransactionInstruction {
+    pub const HEADER_LEN: usize = 32 + 4 + 4 + 4; // program_id + vec prefixes + data_len
+    
+    pub fn space(&self) -> usize {
+        Self::HEADER_LEN + (TransactionAccount::LEN * self.accounts.len()) + self.data_len as usize
+    }
+    
+    pub fn is_complete(&self) -> bool {
+        self.data.len() == self.data_len as usize
+    }
+    
+    /// Classify by target program and instruction discriminator; anything unknown is
+    /// treated as the most sensitive class
+    pub fn operation_class(&self) -> OperationClass {
+        if self.program_id == crate::ID {
+            return Self::classify_twist(&self.data);
+        }
+        
+        if self.program_id == anchor_spl::token::ID {
+            return match self.data.first() {
+                // Transfer, Approve, Burn, CloseAccount, TransferChecked, ApproveChecked, BurnChecked
+                Some(3) | Some(4) | Some(8) | Some(9) | Some(12) | Some(13) | Some(15) => {
+                    OperationClass::TreasuryWithdrawal
+                }
+                _ => OperationClass::AuthorityTransfer,
+            };
+        }
+        
+        if self.program_id == bpf_loader_upgradeable::ID {
+            // Upgrade and SetAuthority always wait for the authority transfer delay
+            return OperationClass::AuthorityTransfer;
+        }
+        
+        if self.program_id == anchor_lang::system_program::ID {
+            // SystemInstruction::Transfer
+            if self.data.get(..4) == Some(&2u32.to_le_bytes()[..]) {
+                return OperationClass::TreasuryWithdrawal;
+            }
+        }
+        
+        OperationClass::AuthorityTransfer
+    }
+    
+    /// Index of the buffer account if this is a loader `Upgrade` instruction
+    pub fn upgrade_buffer_index(&self) -> Option<usize> {
+        let is_upgrade = self.program_id == bpf_loader_upgradeable::ID &&
+            self.data.get(..4) == Some(&3u32.to_le_bytes()[..]);
+        is_upgrade.then_some(2)
+    }
+    
+    fn classify_twist(data: &[u8]) -> OperationClass {
+        let discriminator = match data.get(..8) {
+            Some(d) => d,
+            None => return OperationClass::AuthorityTransfer,
+        };
+        
+        let emergency = [
+            twist_ix::TriggerCircuitBreaker::DISCRIMINATOR,
+            twist_ix::ManualTripCircuitBreaker::DISCRIMINATOR,
+            twist_ix::ManualResetCircuitBreaker::DISCRIMINATOR,
+            twist_ix::SetEmergencyPause::DISCRIMINATOR,
+            twist_ix::VetoGovernanceProposal::DISCRIMINATOR,
+        ];
+        let treasury = [
+            twist_ix::WithdrawTreasury::DISCRIMINATOR,
+            twist_ix::RevealTreasuryWithdrawal::DISCRIMINATOR,
+            twist_ix::UpdateTreasuryLimit::DISCRIMINATOR,
+            twist_ix::CreateTreasuryBudget::DISCRIMINATOR,
+            twist_ix::UpdateTreasuryPolicy::DISCRIMINATOR,
+            twist_ix::UpdateInsuranceParameters::DISCRIMINATOR,
+            twist_ix::ApproveInsuranceClaim::DISCRIMINATOR,
+            twist_ix::RejectInsuranceClaim::DISCRIMINATOR,
+            twist_ix::RebalanceTreasury::DISCRIMINATOR,
+            twist_ix::AllocateFees::DISCRIMINATOR,
+            twist_ix::DistributeFees::DISCRIMINATOR,
+            // Settings that decide where protocol funds flow
+            twist_ix::SetDexVenue::DISCRIMINATOR,
+            twist_ix::SetRoutingThreshold::DISCRIMINATOR,
+            twist_ix::SetFeeSwapRouting::DISCRIMINATOR,
+            twist_ix::SetMiningSchedule::DISCRIMINATOR,
+            twist_ix::UpdateRangeStrategy::DISCRIMINATOR,
+            twist_ix::UpdateLpVault::DISCRIMINATOR,
+        ];
+        let authority = [
+            twist_ix::TransferAuthority::DISCRIMINATOR,
+            twist_ix::AddMultisigMember::DISCRIMINATOR,
+            twist_ix::RemoveMultisigMember::DISCRIMINATOR,
+            twist_ix::ChangeMultisigThreshold::DISCRIMINATOR,
+            twist_ix::UpdateMultisigDelays::DISCRIMINATOR,
+            twist_ix::SetCircuitBreakerGuardians::DISCRIMINATOR,
+            twist_ix::MigrateCircuitBreaker::DISCRIMINATOR,
+            twist_ix::MigrateFeeCollector::DISCRIMINATOR,
+            twist_ix::UpdateReporterNetwork::DISCRIMINATOR,
+        ];
+        let parameter = [
+            twist_ix::UpdateParameters::DISCRIMINATOR,
+            twist_ix::UpdateOracles::DISCRIMINATOR,
+            twist_ix::UpdatePidParameters::DISCRIMINATOR,
+            twist_ix::ResetPidController::DISCRIMINATOR,
+            twist_ix::UpdateCircuitBreakerResponse::DISCRIMINATOR,
+            twist_ix::UpdateReserveDivergenceThreshold::DISCRIMINATOR,
+            twist_ix::UpdateDynamicFeeCurve::DISCRIMINATOR,
+            twist_ix::UpdateFeeParameters::DISCRIMINATOR,
+            twist_ix::UpdateGovernanceConfig::DISCRIMINATOR,
+        ];
+        
+        if emergency.iter().any(|d| d[..] == *discriminator) {
+            OperationClass::Emergency
+        } else if treasury.iter().any(|d| d[..] == *discriminator) {
+            OperationClass::TreasuryWithdrawal
+        } else if authority.iter().any(|d| d[..] == *discriminator) {
+            OperationClass::AuthorityTransfer
+        } else if parameter.iter().any(|d| d[..] == *discriminator) {
+            OperationClass::ParameterUpdate
+        } else {
+            // Anything not listed waits the longest
+            OperationClass::AuthorityTransfer
+        }
+    }
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct MultisigParams {
     pub members: Vec<Pubkey>,
@@ -129,4 +391,149 @@ impl Default for MultisigParams {
             authority_transfer_delay: 259200,  // 72 hours
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn instruction(program_id: Pubkey, data: Vec<u8>) -> TransactionInstruction {
+        TransactionInstruction {
+            program_id,
+            accounts: vec![],
+            data_len: data.len() as u32,
+            data,
+        }
+    }
+
+    fn config() -> MultisigConfig {
+        MultisigConfig {
+            multisig_address: Pubkey::default(),
+            threshold: 2,
+            members: vec![],
+            transaction_count: 0,
+            initialized: true,
+            bump: 0,
+            pending_transactions: 0,
+            parameter_update_delay: 86400,
+            treasury_withdrawal_delay: 172800,
+            authority_transfer_delay: 259200,
+            _reserved: [0; 64],
+        }
+    }
+
+    fn transaction(instructions: Vec<TransactionInstruction>, created_at: i64) -> MultisigTransaction {
+        MultisigTransaction {
+            multisig: Pubkey::default(),
+            transaction_index: 0,
+            proposer: Pubkey::default(),
+            instructions,
+            signers: vec![],
+            executed: false,
+            cancelled: false,
+            finalized: false,
+            created_at,
+            executed_at: 0,
+            delay_seconds: 0,
+            operation_class: OperationClass::AuthorityTransfer,
+            eta: 0,
+            expires_at: created_at + MULTISIG_PROPOSAL_LIFETIME,
+            buffer_hash: None,
+            bump: 0,
+            title: String::new(),
+            description: String::new(),
+            _reserved: [0; 32],
+        }
+    }
+
+    #[test]
+    fn test_batch_uses_longest_class_delay() {
+        let multisig = config();
+        
+        let pause = instruction(crate::ID, twist_ix::SetEmergencyPause::DISCRIMINATOR.to_vec());
+        let update = instruction(crate::ID, twist_ix::UpdateParameters::DISCRIMINATOR.to_vec());
+        let transfer = instruction(anchor_spl::token::ID, vec![3, 0, 0, 0, 0, 0, 0, 0, 0]);
+        let unknown = instruction(Pubkey::new_unique(), vec![0; 8]);
+        
+        assert_eq!(multisig.classify(std::slice::from_ref(&pause)), OperationClass::Emergency);
+        assert_eq!(multisig.classify(&[pause.clone(), update.clone()]), OperationClass::ParameterUpdate);
+        assert_eq!(multisig.classify(&[update, transfer]), OperationClass::TreasuryWithdrawal);
+        assert_eq!(multisig.classify(&[pause, unknown]), OperationClass::AuthorityTransfer);
+    }
+
+    #[test]
+    fn test_unlisted_twist_instructions_take_the_longest_delay() {
+        let guardians = instruction(crate::ID, twist_ix::SetCircuitBreakerGuardians::DISCRIMINATOR.to_vec());
+        let venue = instruction(crate::ID, twist_ix::SetDexVenue::DISCRIMINATOR.to_vec());
+        let unlisted = instruction(crate::ID, twist_ix::InitializeFeeCollector::DISCRIMINATOR.to_vec());
+        
+        assert_eq!(guardians.operation_class(), OperationClass::AuthorityTransfer);
+        assert_eq!(venue.operation_class(), OperationClass::TreasuryWithdrawal);
+        assert_eq!(unlisted.operation_class(), OperationClass::AuthorityTransfer);
+    }
+
+    #[test]
+    fn test_chunked_upload_then_finalize() {
+        let multisig = config();
+        let mut update = instruction(crate::ID, twist_ix::UpdateParameters::DISCRIMINATOR.to_vec());
+        update.data_len = 20;
+        let mut pause = instruction(crate::ID, twist_ix::SetEmergencyPause::DISCRIMINATOR.to_vec());
+        pause.data_len = 9;
+        let mut tx = transaction(vec![update, pause], 1_000);
+
+        // Nothing is scheduled until every instruction is complete
+        assert!(tx.finalize(&multisig, 1_000).is_err());
+
+        // Chunks must continue where the last one stopped and fit the declared length
+        assert!(tx.append_chunk(0, 4, &[1; 4]).is_err());
+        tx.append_chunk(0, 8, &[1; 6]).unwrap();
+        tx.append_chunk(0, 14, &[2; 6]).unwrap();
+        assert!(tx.append_chunk(0, 20, &[3]).is_err());
+        assert!(tx.append_chunk(2, 0, &[0]).is_err());
+        assert!(!tx.is_fully_uploaded());
+
+        tx.append_chunk(1, 8, &[1]).unwrap();
+        tx.finalize(&multisig, 2_000).unwrap();
+        assert!(tx.finalized);
+        assert_eq!(tx.instructions[0].data[8..], [1, 1, 1, 1, 1, 1, 2, 2, 2, 2, 2, 2]);
+        assert_eq!(tx.operation_class, OperationClass::ParameterUpdate);
+        assert_eq!(tx.eta, 2_000 + 86400);
+        assert_eq!(tx.expires_at, tx.eta + MULTISIG_PROPOSAL_LIFETIME);
+    }
+
+    #[test]
+    fn test_finalize_rejects_expired_upload() {
+        let multisig = config();
+        let mut update = instruction(crate::ID, twist_ix::UpdateParameters::DISCRIMINATOR.to_vec());
+        update.data_len = 12;
+        let mut tx = transaction(vec![update], 1_000);
+
+        tx.append_chunk(0, 8, &[0; 4]).unwrap();
+        let expired = tx.expires_at;
+        assert_eq!(
+            tx.finalize(&multisig, expired).unwrap_err(),
+            TwistError::ProposalExpired.into()
+        );
+        assert!(!tx.finalized);
+
+        tx.finalize(&multisig, expired - 1).unwrap();
+        assert!(tx.finalized);
+    }
+
+    #[test]
+    fn test_schedule_rejects_overflowing_delay() {
+        let multisig = config();
+        let update = instruction(crate::ID, twist_ix::UpdateParameters::DISCRIMINATOR.to_vec());
+        let mut tx = transaction(vec![update], 1_000);
+
+        tx.delay_seconds = i64::MAX - 1_000;
+        assert_eq!(
+            tx.finalize(&multisig, 1_000).unwrap_err(),
+            TwistError::MathOverflow.into()
+        );
+        assert!(!tx.finalized);
+
+        tx.delay_seconds = i64::MAX;
+        assert!(tx.schedule(&multisig, 1_000).is_err());
+    }
+}
@@ -103,6 +103,8 @@ pub enum OracleType {
     Pyth,
     Switchboard,
     Chainlink,
+    Dex, // TWAP of the program's whirlpool from DexOracle
+    Reporter, // Stake-weighted median from the ReporterNetwork
 }
 
 impl OracleState {
@@ -112,10 +114,7 @@ impl OracleState {
         }
         
         let price_values: Vec<u64> = prices.iter().map(|p| p.price).collect();
-        let max_price = *price_values.iter().max().unwrap();
-        let min_price = *price_values.iter().min().unwrap();
-        
-        let divergence_bps = ((max_price - min_price) * 10000) / min_price;
+        let divergence_bps = price_divergence_bps(&price_values);
         
         require!(
             divergence_bps <= crate::constants::ORACLE_DIVERGENCE_THRESHOLD_BPS,
@@ -124,4 +123,40 @@ impl OracleState {
         
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+/// Spread between the highest and lowest price in basis points of the lowest. A zero
+/// price among the sources is a maximal divergence rather than a division by zero.
+pub fn price_divergence_bps(prices: &[u64]) -> u64 {
+    let (Some(&max_price), Some(&min_price)) = (prices.iter().max(), prices.iter().min()) else {
+        return 0;
+    };
+    if min_price == 0 {
+        return if max_price == 0 { 0 } else { u64::MAX };
+    }
+    
+    ((max_price - min_price) as u128 * 10000 / min_price as u128).min(u64::MAX as u128) as u64
+}
+
+/// Confidence-weighted mean price, where a source's weight is inversely proportional to
+/// its confidence interval
+pub fn weighted_average_price(sources: &[PriceSource]) -> u64 {
+    let mut weighted_sum: u128 = 0;
+    let mut weight_sum: u128 = 0;
+    
+    for source in sources {
+        // Higher confidence = lower value = higher weight
+        let weight = source_weight(source);
+        weighted_sum += (source.price as u128) * weight;
+        weight_sum += weight;
+    }
+    
+    if weight_sum == 0 {
+        return 0;
+    }
+    (weighted_sum / weight_sum) as u64
+}
+
+pub fn source_weight(source: &PriceSource) -> u128 {
+    10000u128 / (source.confidence as u128).max(1)
+}
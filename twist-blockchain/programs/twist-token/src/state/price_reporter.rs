@@ -0,0 +1,324 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{MIN_REPORTERS_PER_ROUND, MIN_REPORT_ROUND_DURATION, ORACLE_STALENESS_THRESHOLD};
+use crate::errors::TwistError;
+use crate::state::{OracleType, PriceSource};
+
+/// Community price reporting. Bonded stakers post a price each round, the round settles
+/// on the stake-weighted median, and reporters too far from it lose part of their bond.
+#[account]
+pub struct ReporterNetwork {
+    pub bump: u8,
+    pub bond_vault: Pubkey,
+    
+    // Parameters
+    pub min_stake: u64,
+    pub bond_amount: u64,
+    pub round_duration: i64,
+    pub min_reporters: u8,
+    pub outlier_threshold_bps: u64,
+    pub slash_bps: u64,
+    
+    // Current round
+    pub round: u64,
+    pub round_start: i64,
+    pub submissions: Vec<PriceSubmission>,
+    
+    // Last settled round
+    pub last_price: u64,
+    pub last_confidence: u64,
+    pub last_finalized: i64,
+    
+    // Tracking
+    pub active_reporters: u32,
+    pub total_bonded: u64,
+    pub total_slashed: u64,
+}
+
+impl ReporterNetwork {
+    pub const MAX_SUBMISSIONS: usize = 32;
+    pub const LEN: usize = 8 + // discriminator
+        1 + 32 + // bump + bond vault
+        8 + 8 + 8 + 1 + 8 + 8 + // parameters
+        8 + 8 + 4 + (PriceSubmission::LEN * Self::MAX_SUBMISSIONS) + // current round
+        8 + 8 + 8 + // last settled round
+        4 + 8 + 8; // tracking
+    
+    pub fn apply(&mut self, params: &ReporterNetworkParams) {
+        self.min_stake = params.min_stake;
+        self.bond_amount = params.bond_amount;
+        self.round_duration = params.round_duration;
+        self.min_reporters = params.min_reporters;
+        self.outlier_threshold_bps = params.outlier_threshold_bps;
+        self.slash_bps = params.slash_bps;
+    }
+    
+    pub fn round_open(&self, now: i64) -> bool {
+        now < self.round_start + self.round_duration
+    }
+    
+    pub fn submit(&mut self, reporter: Pubkey, price: u64, weight: u64) -> Result<()> {
+        require!(
+            self.submissions.len() < Self::MAX_SUBMISSIONS,
+            TwistError::ReportRoundFull
+        );
+        self.submissions.push(PriceSubmission { reporter, price, weight });
+        Ok(())
+    }
+    
+    /// Settle the current round's submissions. None when too few reporters took part.
+    pub fn settle(&self) -> Option<RoundResult> {
+        if self.submissions.is_empty() || self.submissions.len() < self.min_reporters as usize {
+            return None;
+        }
+        
+        let price = weighted_median(&self.submissions);
+        let total_weight: u128 = self.submissions.iter().map(|s| s.weight as u128).sum();
+        
+        // Stake-weighted mean distance from the median
+        let spread: u128 = self.submissions
+            .iter()
+            .map(|s| s.price.abs_diff(price) as u128 * s.weight as u128)
+            .sum();
+        let confidence = (spread / total_weight.max(1)) as u64;
+        
+        let outliers = self.submissions
+            .iter()
+            .filter(|s| s.price.abs_diff(price) as u128 * 10000 > price as u128 * self.outlier_threshold_bps as u128)
+            .map(|s| s.reporter)
+            .collect();
+        
+        Some(RoundResult { price, confidence, outliers })
+    }
+    
+    pub fn start_next_round(&mut self, now: i64) {
+        self.round += 1;
+        self.round_start = now;
+        self.submissions.clear();
+    }
+    
+    /// The last settled median as an aggregation source, while it is fresh. Its confidence
+    /// is never tighter than the outlier band, since reporters agreeing anywhere inside it
+    /// move the median without being slashed.
+    pub fn price_source(&self, now: i64) -> Option<PriceSource> {
+        if self.last_price == 0 || now - self.last_finalized > ORACLE_STALENESS_THRESHOLD {
+            return None;
+        }
+        
+        let outlier_band = (self.last_price as u128 * self.outlier_threshold_bps as u128 / 10000) as u64;
+        
+        Some(PriceSource {
+            oracle_type: OracleType::Reporter,
+            price: self.last_price,
+            confidence: self.last_confidence.max(outlier_band).max(1),
+            timestamp: self.last_finalized,
+        })
+    }
+}
+
+// Lowest price at which half the submitted stake has been passed
+fn weighted_median(submissions: &[PriceSubmission]) -> u64 {
+    let mut sorted: Vec<&PriceSubmission> = submissions.iter().collect();
+    sorted.sort_by_key(|s| s.price);
+    
+    let total_weight: u128 = sorted.iter().map(|s| s.weight as u128).sum();
+    let mut cumulative: u128 = 0;
+    for submission in &sorted {
+        cumulative += submission.weight as u128;
+        if cumulative * 2 >= total_weight {
+            return submission.price;
+        }
+    }
+    
+    sorted.last().map(|s| s.price).unwrap_or(0)
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default)]
+pub struct PriceSubmission {
+    pub reporter: Pubkey, // Reporter's wallet
+    pub price: u64,
+    pub weight: u64, // Stake at submission
+}
+
+impl PriceSubmission {
+    pub const LEN: usize = 32 + 8 + 8;
+}
+
+pub struct RoundResult {
+    pub price: u64,
+    pub confidence: u64,
+    pub outliers: Vec<Pubkey>,
+}
+
+/// A staker's registration and bond in the reporter network
+#[account]
+pub struct PriceReporter {
+    pub owner: Pubkey,
+    pub bond: u64,
+    pub last_round: u64, // Last round reported in
+    pub reports_submitted: u64,
+    pub times_slashed: u32,
+    pub total_slashed: u64,
+    pub registered_at: i64,
+    pub bump: u8,
+}
+
+impl PriceReporter {
+    pub const LEN: usize = 8 + // discriminator
+        32 + 8 + // owner + bond
+        8 + 8 + // reporting
+        4 + 8 + // slashing
+        8 + 1; // registered at + bump
+    
+    /// Take `slash_bps` of the bond, returning the amount taken
+    pub fn slash(&mut self, slash_bps: u64) -> u64 {
+        let amount = (self.bond as u128 * slash_bps as u128 / 10000) as u64;
+        self.bond -= amount;
+        self.times_slashed += 1;
+        self.total_slashed = self.total_slashed.saturating_add(amount);
+        amount
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct ReporterNetworkParams {
+    pub min_stake: u64,
+    pub bond_amount: u64,
+    pub round_duration: i64,
+    pub min_reporters: u8,
+    pub outlier_threshold_bps: u64,
+    pub slash_bps: u64,
+}
+
+impl ReporterNetworkParams {
+    pub fn is_valid(&self) -> bool {
+        self.bond_amount > 0 &&
+        self.round_duration >= MIN_REPORT_ROUND_DURATION &&
+        self.min_reporters >= MIN_REPORTERS_PER_ROUND &&
+        self.min_reporters as usize <= ReporterNetwork::MAX_SUBMISSIONS &&
+        self.outlier_threshold_bps > 0 &&
+        self.slash_bps <= 10000
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn network() -> ReporterNetwork {
+        ReporterNetwork {
+            bump: 0,
+            bond_vault: Pubkey::default(),
+            min_stake: 0,
+            bond_amount: 1_000,
+            round_duration: 60,
+            min_reporters: 3,
+            outlier_threshold_bps: 500,
+            slash_bps: 1_000,
+            round: 1,
+            round_start: 0,
+            submissions: Vec::new(),
+            last_price: 0,
+            last_confidence: 0,
+            last_finalized: 0,
+            active_reporters: 0,
+            total_bonded: 0,
+            total_slashed: 0,
+        }
+    }
+
+    #[test]
+    fn test_stake_weighted_median_and_outliers() {
+        let mut network = network();
+        let whale = Pubkey::new_unique();
+        let liar = Pubkey::new_unique();
+
+        network.submit(Pubkey::new_unique(), 49_000, 100).unwrap();
+        assert!(network.settle().is_none());
+
+        // The whale's stake outweighs the other honest reporters combined
+        network.submit(whale, 50_000, 500).unwrap();
+        network.submit(Pubkey::new_unique(), 51_000, 100).unwrap();
+        network.submit(liar, 80_000, 200).unwrap();
+        let result = network.settle().unwrap();
+        assert_eq!(result.price, 50_000);
+        assert_eq!(result.outliers, vec![liar]);
+
+        // (1_000 * 100 + 1_000 * 100 + 30_000 * 200) / 900
+        assert_eq!(result.confidence, 6_888);
+
+        network.start_next_round(60);
+        assert!(network.submissions.is_empty());
+        assert_eq!(network.round, 2);
+        assert!(network.round_open(119));
+        assert!(!network.round_open(120));
+    }
+
+    #[test]
+    fn test_slash_and_price_source() {
+        let mut reporter = PriceReporter {
+            owner: Pubkey::default(),
+            bond: 1_000,
+            last_round: 0,
+            reports_submitted: 0,
+            times_slashed: 0,
+            total_slashed: 0,
+            registered_at: 0,
+            bump: 0,
+        };
+        assert_eq!(reporter.slash(1_000), 100);
+        assert_eq!(reporter.slash(1_000), 90);
+        assert_eq!(reporter.bond, 810);
+        assert_eq!(reporter.times_slashed, 2);
+
+        let mut network = network();
+        assert!(network.price_source(10).is_none());
+        network.last_price = 50_000;
+        network.last_finalized = 100;
+        assert_eq!(network.price_source(100 + ORACLE_STALENESS_THRESHOLD).unwrap().price, 50_000);
+        assert!(network.price_source(101 + ORACLE_STALENESS_THRESHOLD).is_none());
+    }
+
+    #[test]
+    fn test_colluding_round_confidence_floor() {
+        use crate::state::source_weight;
+
+        // Three reporters submit the same price, so the round has no spread
+        let mut network = network();
+        for _ in 0..3 {
+            network.submit(Pubkey::new_unique(), 50_000, 100).unwrap();
+        }
+        let result = network.settle().unwrap();
+        assert_eq!(result.confidence, 0);
+        network.last_price = result.price;
+        network.last_confidence = result.confidence;
+        network.last_finalized = 100;
+
+        // The source still carries the 5% outlier band, weighing less than a 0.2% feed
+        let source = network.price_source(100).unwrap();
+        assert_eq!(source.confidence, 2_500);
+        let pyth = PriceSource { oracle_type: OracleType::Pyth, price: 50_000, confidence: 100, timestamp: 100 };
+        assert!(source_weight(&source) < source_weight(&pyth));
+
+        // A wider settled spread still counts as is
+        network.last_confidence = 4_000;
+        assert_eq!(network.price_source(100).unwrap().confidence, 4_000);
+    }
+
+    #[test]
+    fn test_params_require_three_reporters() {
+        let mut params = ReporterNetworkParams {
+            min_stake: 0,
+            bond_amount: 1_000,
+            round_duration: MIN_REPORT_ROUND_DURATION,
+            min_reporters: MIN_REPORTERS_PER_ROUND,
+            outlier_threshold_bps: 500,
+            slash_bps: 1_000,
+        };
+        assert!(params.is_valid());
+
+        // One or two reporters could set the median alone
+        params.min_reporters = 2;
+        assert!(!params.is_valid());
+    }
+}
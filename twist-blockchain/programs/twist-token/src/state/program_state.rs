@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
 
+use crate::state::SeverityResponse;
+
 #[account]
 pub struct ProgramState {
     // Authority
@@ -59,11 +61,26 @@ pub struct ProgramState {
     pub version: u8,
     pub initialized: bool,
     
+    // Circuit breaker responses, in space taken from the reserve
+    pub bridge_halted: bool,
+    pub fee_surcharge_bps: u64,
+    pub max_transaction_size: u64, // USDC atomic units, 0 = uncapped
+    
+    // Set once the fee collector exists; staking must then settle fee rewards against it
+    pub fee_collector_initialized: bool,
+    
+    // Flags the current trip's response actually changed, so lifting it restores only those
+    pub breaker_changed_flags: u8,
+    
     // Reserved space for future upgrades
-    pub _reserved: [u8; 79],
+    pub _reserved: [u8; 60],
 }
 
 impl ProgramState {
+    pub const BREAKER_HALTED_BUYBACK: u8 = 1 << 0;
+    pub const BREAKER_HALTED_BRIDGE: u8 = 1 << 1;
+    pub const BREAKER_PAUSED: u8 = 1 << 2;
+    
     pub const LEN: usize = 8 + // discriminator
         32 + 1 + // authority + bump
         32 + 1 + // mint + decimals
@@ -75,7 +92,10 @@ impl ProgramState {
         32 + 1 + // whirlpool + whirlpool_initialized
         16 + // total_fees_collected
         1 + 1 + // version + initialized
-        79; // reserved
+        1 + 8 + 8 + // circuit breaker responses
+        1 + // fee_collector_initialized
+        1 + // breaker_changed_flags
+        60; // reserved
         
     pub fn is_initialized(&self) -> bool {
         self.initialized
@@ -85,8 +105,95 @@ impl ProgramState {
         current_timestamp - self.last_decay_timestamp >= crate::constants::DECAY_INTERVAL
     }
     
+    /// Whether a tripped breaker halts buybacks is up to its severity response
     pub fn can_buyback(&self) -> bool {
-        self.buyback_enabled && !self.emergency_pause && !self.circuit_breaker_active
+        self.buyback_enabled && !self.emergency_pause
+    }
+    
+    pub fn can_bridge(&self) -> bool {
+        !self.bridge_halted && !self.emergency_pause
+    }
+    
+    /// Put a tripped breaker's severity response into effect, recording which flags it
+    /// changed. A flag already set by an admin or governance is left to them.
+    pub fn apply_breaker_response(&mut self, response: &SeverityResponse) {
+        self.fee_surcharge_bps = response.fee_surcharge_bps;
+        self.max_transaction_size = response.max_transaction_size;
+        self.breaker_changed_flags = 0;
+        
+        if response.halt_buyback && self.buyback_enabled {
+            self.buyback_enabled = false;
+            self.breaker_changed_flags |= Self::BREAKER_HALTED_BUYBACK;
+        }
+        if response.halt_bridge && !self.bridge_halted {
+            self.bridge_halted = true;
+            self.breaker_changed_flags |= Self::BREAKER_HALTED_BRIDGE;
+        }
+        if response.full_pause && !self.emergency_pause {
+            self.emergency_pause = true;
+            self.breaker_changed_flags |= Self::BREAKER_PAUSED;
+        }
+    }
+    
+    /// Clear the breaker and undo only what its severity response changed
+    pub fn lift_breaker_response(&mut self) {
+        self.circuit_breaker_active = false;
+        self.fee_surcharge_bps = 0;
+        self.max_transaction_size = 0;
+        
+        if self.breaker_changed_flags & Self::BREAKER_HALTED_BUYBACK != 0 {
+            self.buyback_enabled = true;
+        }
+        if self.breaker_changed_flags & Self::BREAKER_HALTED_BRIDGE != 0 {
+            self.bridge_halted = false;
+        }
+        if self.breaker_changed_flags & Self::BREAKER_PAUSED != 0 {
+            self.emergency_pause = false;
+        }
+        self.breaker_changed_flags = 0;
+    }
+    
+    pub fn apply_fee_surcharge(&self, base_fee_bps: u64) -> u64 {
+        base_fee_bps.saturating_add(self.fee_surcharge_bps)
+    }
+    
+    /// Value of a TWIST amount in USDC atomic units at the last oracle price
+    pub fn twist_to_usdc_value(&self, twist_amount: u64) -> u64 {
+        (twist_amount as u128 * self.last_oracle_price as u128 / 10u128.pow(self.decimals as u32)) as u64
+    }
+    
+    pub fn usdc_to_twist_amount(&self, usdc_amount: u64) -> u64 {
+        if self.last_oracle_price == 0 {
+            return 0;
+        }
+        (usdc_amount as u128 * 10u128.pow(self.decimals as u32) / self.last_oracle_price as u128) as u64
+    }
+    
+    pub fn check_transaction_size(&self, usdc_value: u64) -> Result<()> {
+        require!(
+            self.max_transaction_size == 0 || usdc_value <= self.max_transaction_size,
+            crate::errors::TwistError::TransactionSizeExceeded
+        );
+        
+        Ok(())
+    }
+    
+    /// Require a fresh oracle price and a pool price within the divergence bound of it
+    pub fn check_pool_price(&self, pool_price: u64, current_time: i64) -> Result<()> {
+        require!(
+            self.last_oracle_price > 0 &&
+            current_time - self.last_oracle_update <= crate::constants::ORACLE_STALENESS_THRESHOLD * 2,
+            crate::errors::TwistError::OracleStale
+        );
+        
+        let divergence_bps = (pool_price.abs_diff(self.last_oracle_price) as u128 * 10000
+            / self.last_oracle_price as u128) as u64;
+        require!(
+            divergence_bps <= crate::constants::ORACLE_DIVERGENCE_THRESHOLD_BPS,
+            crate::errors::TwistError::OracleDivergenceTooHigh
+        );
+        
+        Ok(())
     }
     
     pub fn reset_daily_buyback_if_needed(&mut self, current_timestamp: i64) {
@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
 
+use crate::constants::FEE_REWARD_PRECISION;
+
 #[account]
 pub struct StakeState {
     pub owner: Pubkey,
@@ -9,6 +11,11 @@ pub struct StakeState {
     pub total_earned: u128,
     pub stakes: Vec<StakeEntry>,
     pub is_initialized: bool,
+    
+    // Fee-funded staking rewards. Appended after the original layout; stake and unstake
+    // grow older accounts, and migrate_stake_state grows ones too full to load.
+    pub fee_reward_debt: u128,
+    pub unclaimed_fee_rewards: u64,
 }
 
 impl StakeState {
@@ -17,7 +24,11 @@ impl StakeState {
         32 + 1 + // owner + bump
         8 + 8 + 16 + // stake_index + total_staked + total_earned
         4 + (StakeEntry::LEN * Self::MAX_STAKES) + // vec length + entries
-        1; // is_initialized
+        1 + // is_initialized
+        16 + 8; // fee reward debt + unclaimed fee rewards
+    
+    /// Size before the fee reward fields were appended
+    pub const ORIGINAL_LEN: usize = Self::LEN - 16 - 8;
         
     pub fn add_stake(&mut self, entry: StakeEntry) -> Result<()> {
         require!(
@@ -28,6 +39,19 @@ impl StakeState {
         Ok(())
     }
     
+    /// Credit fee rewards accrued on the current stake. Call before `total_staked` changes,
+    /// then `reset_fee_reward_debt` after.
+    pub fn settle_fee_rewards(&mut self, reward_per_share: u128) {
+        let accrued = self.total_staked as u128 * reward_per_share / FEE_REWARD_PRECISION;
+        let pending = accrued.saturating_sub(self.fee_reward_debt) as u64;
+        self.unclaimed_fee_rewards = self.unclaimed_fee_rewards.saturating_add(pending);
+        self.fee_reward_debt = accrued;
+    }
+    
+    pub fn reset_fee_reward_debt(&mut self, reward_per_share: u128) {
+        self.fee_reward_debt = self.total_staked as u128 * reward_per_share / FEE_REWARD_PRECISION;
+    }
+    
     pub fn get_unlocked_stakes(&self, current_timestamp: i64) -> Vec<(usize, &StakeEntry)> {
         self.stakes
             .iter()
@@ -102,4 +126,46 @@ impl StakeEntry {
         
         ((self.amount as u128 * scaled_penalty) / 10000) as u64
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_full_original_layout_loads_once_grown() {
+        let entry = StakeEntry {
+            amount: 1_000,
+            start_timestamp: 0,
+            lock_period: 30 * 86400,
+            apy_bps: 1000,
+            last_claim_timestamp: 0,
+            total_earned: 0,
+            withdrawn: false,
+        };
+        let state = StakeState {
+            owner: Pubkey::new_unique(),
+            bump: 0,
+            stake_index: 0,
+            total_staked: 10_000,
+            total_earned: 0,
+            stakes: vec![entry; StakeState::MAX_STAKES],
+            is_initialized: true,
+            fee_reward_debt: 0,
+            unclaimed_fee_rewards: 0,
+        };
+
+        // A full account in the original layout ends at is_initialized
+        let data = state.try_to_vec().unwrap();
+        let original = &data[..StakeState::ORIGINAL_LEN - 8];
+        assert!(StakeState::deserialize(&mut &original[..]).is_err());
+
+        let mut grown = original.to_vec();
+        grown.resize(StakeState::LEN - 8, 0);
+        let loaded = StakeState::deserialize(&mut &grown[..]).unwrap();
+        assert_eq!(loaded.stakes.len(), StakeState::MAX_STAKES);
+        assert_eq!(loaded.total_staked, 10_000);
+        assert_eq!(loaded.fee_reward_debt, 0);
+        assert_eq!(loaded.unclaimed_fee_rewards, 0);
+    }
+}
@@ -31,7 +31,7 @@ impl TreasuryState {
             withdrawal_used = 0;
         }
         
-        withdrawal_used + amount <= self.withdrawal_limit_daily
+        withdrawal_used.saturating_add(amount) <= self.withdrawal_limit_daily
     }
     
     pub fn record_withdrawal(&mut self, amount: u64, current_timestamp: i64) {
@@ -39,21 +39,294 @@ impl TreasuryState {
             self.withdrawal_used_today = amount;
             self.last_limit_reset = current_timestamp;
         } else {
-            self.withdrawal_used_today += amount;
+            self.withdrawal_used_today = self.withdrawal_used_today.saturating_add(amount);
         }
         
-        self.total_withdrawn += amount as u128;
+        self.total_withdrawn = self.total_withdrawn.saturating_add(amount as u128);
         self.last_withdrawal = current_timestamp;
     }
+    
+    /// Check the daily limit and record the withdrawal against it
+    pub fn consume_daily_limit(&mut self, amount: u64, current_timestamp: i64) -> Result<()> {
+        require!(
+            self.can_withdraw(amount, current_timestamp),
+            crate::errors::TwistError::TreasuryDailyLimitExceeded
+        );
+        
+        self.record_withdrawal(amount, current_timestamp);
+        
+        Ok(())
+    }
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
 pub enum TreasuryType {
     Floor,
     Operations,
     Reserve,
 }
 
+impl TreasuryType {
+    pub fn seed(&self) -> [u8; 1] {
+        [*self as u8]
+    }
+    
+    pub fn label(&self) -> &'static str {
+        match self {
+            TreasuryType::Floor => "floor",
+            TreasuryType::Operations => "operations",
+            TreasuryType::Reserve => "reserve",
+        }
+    }
+}
+
+/// Named spending budget paid out of a treasury: a linear stream, capped per epoch
+#[account]
+pub struct TreasuryBudget {
+    pub name: String,
+    pub treasury_type: TreasuryType,
+    pub recipient: Pubkey,
+    
+    // Linear stream
+    pub stream_total: u64,
+    pub stream_start: i64,
+    pub stream_end: i64,
+    pub stream_withdrawn: u64,
+    
+    // Epoch cap
+    pub epoch_duration: i64,
+    pub cap_per_epoch: u64,
+    pub epoch_start: i64,
+    pub epoch_withdrawn: u64,
+    
+    pub bump: u8,
+    
+    // Reserved space
+    pub _reserved: [u8; 32],
+}
+
+impl TreasuryBudget {
+    pub const MAX_NAME_LEN: usize = 32;
+    pub const LEN: usize = 8 + // discriminator
+        4 + Self::MAX_NAME_LEN + // name
+        1 + 32 + // treasury_type + recipient
+        8 + 8 + 8 + 8 + // stream
+        8 + 8 + 8 + 8 + // epoch cap
+        1 + // bump
+        32; // reserved
+    
+    /// Amount of the stream unlocked by `current_timestamp`
+    pub fn vested_amount(&self, current_timestamp: i64) -> u64 {
+        if current_timestamp <= self.stream_start {
+            return 0;
+        }
+        if current_timestamp >= self.stream_end {
+            return self.stream_total;
+        }
+        
+        let elapsed = (current_timestamp - self.stream_start) as u128;
+        let duration = (self.stream_end - self.stream_start) as u128;
+        (self.stream_total as u128 * elapsed / duration) as u64
+    }
+    
+    /// Start of the epoch containing `current_timestamp`
+    pub fn current_epoch_start(&self, current_timestamp: i64) -> i64 {
+        if current_timestamp < self.epoch_start {
+            return self.epoch_start;
+        }
+        
+        let epochs = (current_timestamp - self.epoch_start) / self.epoch_duration;
+        self.epoch_start + epochs * self.epoch_duration
+    }
+    
+    /// Withdrawable now: vested but unclaimed, within what is left of this epoch's cap
+    pub fn available(&self, current_timestamp: i64) -> u64 {
+        let unclaimed = self.vested_amount(current_timestamp).saturating_sub(self.stream_withdrawn);
+        
+        let epoch_used = if self.current_epoch_start(current_timestamp) == self.epoch_start {
+            self.epoch_withdrawn
+        } else {
+            0
+        };
+        
+        unclaimed.min(self.cap_per_epoch.saturating_sub(epoch_used))
+    }
+    
+    pub fn record_withdrawal(&mut self, amount: u64, current_timestamp: i64) {
+        let epoch_start = self.current_epoch_start(current_timestamp);
+        if epoch_start != self.epoch_start {
+            self.epoch_start = epoch_start;
+            self.epoch_withdrawn = 0;
+        }
+        
+        self.epoch_withdrawn = self.epoch_withdrawn.saturating_add(amount);
+        self.stream_withdrawn = self.stream_withdrawn.saturating_add(amount);
+    }
+    
+    pub fn is_complete(&self) -> bool {
+        self.stream_withdrawn >= self.stream_total
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct TreasuryBudgetParams {
+    pub name: String,
+    pub recipient: Pubkey,
+    pub stream_total: u64,
+    pub stream_start: i64,
+    pub stream_end: i64,
+    pub epoch_duration: i64,
+    pub cap_per_epoch: u64,
+}
+
+/// Governable target allocation used by `rebalance_treasury`
+#[account]
+pub struct TreasuryPolicy {
+    pub bump: u8,
+    
+    // Targets across treasuries, by USDC value (sum to 10000)
+    pub floor_target_bps: u64,
+    pub ops_target_bps: u64,
+    pub reserve_target_bps: u64,
+    
+    // USDC share of the floor treasury; the rest is held in TWIST
+    pub floor_usdc_target_bps: u64,
+    pub usdc_mint: Pubkey,
+    pub floor_usdc_account: Pubkey,
+    
+    // Drift tolerated before acting, and the swap slippage cap
+    pub rebalance_band_bps: u64,
+    pub max_slippage_bps: u64,
+    
+    pub last_rebalance_timestamp: i64,
+    
+    // Reserved space
+    pub _reserved: [u8; 64],
+}
+
+impl TreasuryPolicy {
+    pub const LEN: usize = 8 + // discriminator
+        1 + // bump
+        8 + 8 + 8 + // treasury targets
+        8 + 32 + 32 + // floor usdc mix
+        8 + 8 + // band + slippage
+        8 + // last_rebalance_timestamp
+        64; // reserved
+    
+    pub fn apply(&mut self, params: &TreasuryPolicyParams) {
+        self.floor_target_bps = params.floor_target_bps;
+        self.ops_target_bps = params.ops_target_bps;
+        self.reserve_target_bps = params.reserve_target_bps;
+        self.floor_usdc_target_bps = params.floor_usdc_target_bps;
+        self.rebalance_band_bps = params.rebalance_band_bps;
+        self.max_slippage_bps = params.max_slippage_bps;
+    }
+    
+    /// Value moves that bring each bucket back to target. Floor TWIST is the hub: ops and
+    /// reserve settle against it first, then the floor's USDC mix is corrected with a swap.
+    pub fn plan(&self, snapshot: &AllocationSnapshot) -> RebalancePlan {
+        let total = snapshot.total();
+        let band = self.rebalance_band_bps;
+        
+        let mut to_ops = band_delta(snapshot.ops, total * self.ops_target_bps as u128 / 10000, total, band);
+        let mut to_reserve = band_delta(snapshot.reserve, total * self.reserve_target_bps as u128 / 10000, total, band);
+        
+        // Returns to the floor land first, then payouts are capped by the floor's TWIST
+        let mut floor_twist = snapshot.floor_twist as i128 - to_ops.min(0) - to_reserve.min(0);
+        if to_ops > 0 {
+            to_ops = to_ops.min(floor_twist);
+            floor_twist -= to_ops;
+        }
+        if to_reserve > 0 {
+            to_reserve = to_reserve.min(floor_twist);
+            floor_twist -= to_reserve;
+        }
+        
+        let floor = floor_twist as u128 + snapshot.floor_usdc as u128;
+        let usdc_target = floor * self.floor_usdc_target_bps as u128 / 10000;
+        let twist_to_usdc = band_delta(snapshot.floor_usdc as u128, usdc_target, floor, band).min(floor_twist);
+        
+        RebalancePlan {
+            to_ops: to_ops as i64,
+            to_reserve: to_reserve as i64,
+            twist_to_usdc: twist_to_usdc as i64,
+        }
+    }
+}
+
+/// Signed distance to target, or zero while the drift is inside the band
+fn band_delta(actual: impl Into<u128>, target: u128, total: u128, band_bps: u64) -> i128 {
+    let diff = target as i128 - actual.into() as i128;
+    if total == 0 || diff.unsigned_abs() * 10000 <= total * band_bps as u128 {
+        return 0;
+    }
+    diff
+}
+
+/// Treasury holdings valued in USDC atomic units
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct AllocationSnapshot {
+    pub floor_twist: u64,
+    pub floor_usdc: u64,
+    pub ops: u64,
+    pub reserve: u64,
+}
+
+impl AllocationSnapshot {
+    pub fn total(&self) -> u128 {
+        self.floor_twist as u128 + self.floor_usdc as u128 + self.ops as u128 + self.reserve as u128
+    }
+    
+    /// Share of the total in basis points
+    pub fn share_bps(&self, value: u64) -> u64 {
+        let total = self.total();
+        if total == 0 {
+            return 0;
+        }
+        (value as u128 * 10000 / total) as u64
+    }
+}
+
+/// USDC values to move; positive amounts leave the floor's TWIST
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct RebalancePlan {
+    pub to_ops: i64,
+    pub to_reserve: i64,
+    pub twist_to_usdc: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct TreasuryPolicyParams {
+    pub floor_target_bps: u64,
+    pub ops_target_bps: u64,
+    pub reserve_target_bps: u64,
+    pub floor_usdc_target_bps: u64,
+    pub rebalance_band_bps: u64,
+    pub max_slippage_bps: u64,
+}
+
+impl TreasuryPolicyParams {
+    pub fn is_valid(&self) -> bool {
+        self.floor_target_bps + self.ops_target_bps + self.reserve_target_bps == 10000 &&
+        self.floor_usdc_target_bps <= 10000 &&
+        self.rebalance_band_bps <= 5000 &&
+        self.max_slippage_bps <= 1000
+    }
+}
+
+impl Default for TreasuryPolicyParams {
+    fn default() -> Self {
+        Self {
+            floor_target_bps: 6000,      // 60% floor
+            ops_target_bps: 3000,        // 30% operations
+            reserve_target_bps: 1000,    // 10% reserve
+            floor_usdc_target_bps: 8000, // Floor held 80% in USDC
+            rebalance_band_bps: 500,     // Act on 5% drift
+            max_slippage_bps: 100,       // 1% below oracle price
+        }
+    }
+}
+
 #[account]
 pub struct BuybackState {
     pub authority: Pubkey,
@@ -121,4 +394,109 @@ impl BuybackState {
         
         std::cmp::min(scaled_amount, daily_remaining)
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn budget() -> TreasuryBudget {
+        TreasuryBudget {
+            name: "grants".to_string(),
+            treasury_type: TreasuryType::Operations,
+            recipient: Pubkey::default(),
+            stream_total: 1_200,
+            stream_start: 0,
+            stream_end: 120,
+            stream_withdrawn: 0,
+            epoch_duration: 30,
+            cap_per_epoch: 250,
+            epoch_start: 0,
+            epoch_withdrawn: 0,
+            bump: 0,
+            _reserved: [0; 32],
+        }
+    }
+
+    #[test]
+    fn test_budget_streams_linearly_under_epoch_cap() {
+        let mut budget = budget();
+        
+        // 10 per second vests, but only 250 may leave per 30 second epoch
+        assert_eq!(budget.vested_amount(20), 200);
+        assert_eq!(budget.available(20), 200);
+        
+        budget.record_withdrawal(200, 20);
+        assert_eq!(budget.available(29), 50);
+        
+        // New epoch: the cap resets, the stream keeps its own accounting
+        assert_eq!(budget.available(45), 250);
+        budget.record_withdrawal(250, 45);
+        assert_eq!(budget.epoch_start, 30);
+        
+        // After the end only the remainder of the stream is left
+        assert_eq!(budget.available(500), 250);
+        budget.stream_withdrawn = 1_200;
+        assert!(budget.is_complete());
+        assert_eq!(budget.available(500), 0);
+    }
+
+    #[test]
+    fn test_treasury_daily_limit_resets() {
+        let mut treasury = TreasuryState {
+            authority: Pubkey::default(),
+            treasury_type: TreasuryType::Operations,
+            token_account: Pubkey::default(),
+            usdc_account: Pubkey::default(),
+            total_received: 0,
+            total_withdrawn: 0,
+            total_used_for_buyback: 0,
+            last_withdrawal: 0,
+            withdrawal_limit_daily: 1_000,
+            withdrawal_used_today: 0,
+            last_limit_reset: 0,
+            bump: 0,
+        };
+        
+        assert!(treasury.consume_daily_limit(600, 100).is_ok());
+        assert!(treasury.consume_daily_limit(500, 200).is_err());
+        assert!(treasury.consume_daily_limit(400, 300).is_ok());
+        assert!(treasury.consume_daily_limit(1_000, 86_400).is_ok());
+        assert_eq!(treasury.total_withdrawn, 2_000);
+    }
+
+    #[test]
+    fn test_policy_plan_respects_bands_and_hub() {
+        let mut policy = TreasuryPolicy {
+            bump: 0,
+            floor_target_bps: 0,
+            ops_target_bps: 0,
+            reserve_target_bps: 0,
+            floor_usdc_target_bps: 0,
+            usdc_mint: Pubkey::default(),
+            floor_usdc_account: Pubkey::default(),
+            rebalance_band_bps: 0,
+            max_slippage_bps: 0,
+            last_rebalance_timestamp: 0,
+            _reserved: [0; 64],
+        };
+        policy.apply(&TreasuryPolicyParams::default());
+        
+        // Ops is 2% under target: inside the band, so only the floor mix moves
+        let snapshot = AllocationSnapshot { floor_twist: 6_000, floor_usdc: 0, ops: 2_800, reserve: 1_200 };
+        assert_eq!(policy.plan(&snapshot), RebalancePlan { to_ops: 0, to_reserve: 0, twist_to_usdc: 4_800 });
+        
+        // Ops 10% under and reserve 15% over: reserve returns first, ops is topped up
+        let snapshot = AllocationSnapshot { floor_twist: 1_000, floor_usdc: 4_500, ops: 2_000, reserve: 2_500 };
+        let plan = policy.plan(&snapshot);
+        assert_eq!(plan.to_ops, 1_000);
+        assert_eq!(plan.to_reserve, -1_500);
+        
+        // Floor ends at 1,500 TWIST + 4,500 USDC = 75% USDC, inside the band of 80%
+        assert_eq!(plan.twist_to_usdc, 0);
+        
+        // A floor without TWIST cannot pay out, but can buy TWIST back with surplus USDC
+        let snapshot = AllocationSnapshot { floor_twist: 0, floor_usdc: 8_000, ops: 1_000, reserve: 1_000 };
+        assert_eq!(policy.plan(&snapshot), RebalancePlan { to_ops: 0, to_reserve: 0, twist_to_usdc: -1_600 });
+    }
+}
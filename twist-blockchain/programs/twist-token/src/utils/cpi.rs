@@ -1,6 +1,11 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 
+use crate::constants::{ORACLE_STALENESS_THRESHOLD, ROUTE_SPLIT_STEPS};
+use crate::defi::{load_dex_adapters, plan_route, DexAdapter, Route, SwapEndpoints};
+use crate::errors::TwistError;
+use crate::state::{DexRegistry, ProgramState};
+
 pub fn transfer_tokens<'info>(
     from: &Account<'info, TokenAccount>,
     to: &Account<'info, TokenAccount>,
@@ -36,4 +41,97 @@ pub fn transfer_tokens_with_signer<'info>(
     let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
     token::transfer(cpi_ctx, amount)?;
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Oracle-quoted swap output less `max_slippage_bps`. `a_to_b` swaps TWIST for USDC.
+pub fn oracle_min_swap_output(
+    program_state: &ProgramState,
+    amount: u64,
+    a_to_b: bool,
+    max_slippage_bps: u64,
+    current_time: i64,
+) -> Result<u64> {
+    require!(
+        program_state.last_oracle_price > 0 &&
+        current_time - program_state.last_oracle_update <= ORACLE_STALENESS_THRESHOLD * 2,
+        TwistError::OracleStale
+    );
+    
+    let expected = if a_to_b {
+        program_state.twist_to_usdc_value(amount)
+    } else {
+        program_state.usdc_to_twist_amount(amount)
+    };
+    
+    Ok((expected as u128 * 10000u128.saturating_sub(max_slippage_bps as u128) / 10000) as u64)
+}
+
+/// Load the registered venues in `remaining_accounts` and plan an exact-input route across
+/// them, split as the registry's price impact threshold allows
+pub fn plan_registry_route<'info>(
+    registry: &DexRegistry,
+    remaining_accounts: &[AccountInfo<'info>],
+    endpoints: &SwapEndpoints<'info>,
+    amount_in: u64,
+    a_to_b: bool,
+) -> Result<(Vec<Box<dyn DexAdapter<'info> + 'info>>, Route)> {
+    let adapters = load_dex_adapters(registry, remaining_accounts, endpoints)?;
+    let route = plan_route(
+        &adapters,
+        amount_in,
+        a_to_b,
+        registry.price_impact_threshold_bps,
+        ROUTE_SPLIT_STEPS,
+    )?;
+    
+    Ok((adapters, route))
+}
+
+/// Swap an exact input on any venue and return the amount received, measured on the destination vault
+pub fn swap_exact_input<'info>(
+    adapter: &dyn DexAdapter<'info>,
+    amount: u64,
+    min_out: u64,
+    a_to_b: bool,
+    signer_seeds: &[&[&[u8]]],
+    destination: &mut Account<'info, TokenAccount>,
+) -> Result<u64> {
+    let balance_before = destination.amount;
+    
+    adapter.swap(amount, min_out, a_to_b, signer_seeds)?;
+    
+    destination.reload()?;
+    let received = destination.amount.saturating_sub(balance_before);
+    require!(
+        received >= min_out,
+        TwistError::SlippageToleranceExceeded
+    );
+    
+    Ok(received)
+}
+
+/// Execute every leg of a route, then check the combined fill against one minimum. Legs carry
+/// no minimum of their own; a shortfall anywhere fails the whole route.
+pub fn execute_route<'info>(
+    adapters: &[Box<dyn DexAdapter<'info> + 'info>],
+    route: &Route,
+    min_total_out: u64,
+    a_to_b: bool,
+    signer_seeds: &[&[&[u8]]],
+    destination: &mut Account<'info, TokenAccount>,
+) -> Result<u64> {
+    let balance_before = destination.amount;
+    
+    for leg in &route.legs {
+        adapters[leg.adapter_index].swap(leg.quote.amount_in, 0, a_to_b, signer_seeds)?;
+    }
+    
+    destination.reload()?;
+    let received = destination.amount.saturating_sub(balance_before);
+    require!(
+        received >= min_total_out,
+        TwistError::SlippageToleranceExceeded
+    );
+    
+    Ok(received)
+}
@@ -5,6 +5,7 @@ use anchor_lang::solana_program::{
 };
 
 use crate::errors::TwistError;
+use crate::state::DynamicFeeCurve;
 
 /// MEV protection mechanisms
 pub struct MEVProtection;
@@ -20,7 +21,7 @@ impl MEVProtection {
         
         require!(
             current_slot >= last_transaction_slot + min_slot_gap,
-            TwistError::InvalidAccount
+            TwistError::SlotGapNotMet
         );
         
         Ok(())
@@ -28,34 +29,36 @@ impl MEVProtection {
     
     /// Calculate dynamic fee based on transaction value and market conditions
     pub fn calculate_dynamic_fee(
+        curve: &DynamicFeeCurve,
         base_fee_bps: u64,
         transaction_value: u64,
         recent_volume: u128,
+        average_volume: u128,
         price_volatility: u64,
     ) -> Result<u64> {
         // Base fee
         let mut fee_bps = base_fee_bps;
         
         // Increase fee for large transactions (whale protection)
-        if transaction_value > 100_000 * 1_000_000 { // > $100k
-            fee_bps = fee_bps.saturating_mul(150).saturating_div(100); // 1.5x
-        } else if transaction_value > 50_000 * 1_000_000 { // > $50k
-            fee_bps = fee_bps.saturating_mul(125).saturating_div(100); // 1.25x
+        if transaction_value > curve.whale_trade_threshold {
+            fee_bps = fee_bps.saturating_mul(curve.whale_trade_multiplier_bps) / 10000;
+        } else if transaction_value > curve.large_trade_threshold {
+            fee_bps = fee_bps.saturating_mul(curve.large_trade_multiplier_bps) / 10000;
         }
         
         // Increase fee during high volatility
-        if price_volatility > 1000 { // > 10% volatility
-            fee_bps = fee_bps.saturating_add(50); // +0.5%
+        if price_volatility > curve.volatility_threshold_bps {
+            fee_bps = fee_bps.saturating_add(curve.volatility_surcharge_bps);
         }
         
-        // Increase fee during high volume (congestion pricing)
-        let avg_daily_volume: u64 = 1_000_000_000_000; // $1M baseline (in atomic units)
-        if recent_volume > (avg_daily_volume as u128 * 5) {
-            fee_bps = fee_bps.saturating_add(100); // +1%
+        // Increase fee during high volume (congestion pricing), once there is a baseline
+        if average_volume > 0 &&
+            recent_volume > average_volume.saturating_mul(curve.congestion_multiple as u128) {
+            fee_bps = fee_bps.saturating_add(curve.congestion_surcharge_bps);
         }
         
-        // Cap maximum fee at 5%
-        Ok(fee_bps.min(500))
+        // Cap maximum fee
+        Ok(fee_bps.min(curve.max_fee_bps))
     }
     
     /// Generate transaction hash for commit-reveal pattern
@@ -64,12 +67,14 @@ impl MEVProtection {
         amount: u64,
         nonce: u64,
         salt: &[u8; 32],
+        action: &CommittedAction,
     ) -> [u8; 32] {
         let mut data = Vec::new();
         data.extend_from_slice(&user.to_bytes());
         data.extend_from_slice(&amount.to_le_bytes());
         data.extend_from_slice(&nonce.to_le_bytes());
         data.extend_from_slice(salt);
+        data.extend_from_slice(&action.try_to_vec().unwrap_or_default());
         
         hash(&data).to_bytes()
     }
@@ -81,12 +86,13 @@ impl MEVProtection {
         amount: u64,
         nonce: u64,
         salt: &[u8; 32],
+        action: &CommittedAction,
     ) -> Result<()> {
-        let computed_hash = Self::generate_commit_hash(user, amount, nonce, salt);
+        let computed_hash = Self::generate_commit_hash(user, amount, nonce, salt, action);
         
         require!(
             commit_hash == &computed_hash,
-            TwistError::InvalidAccount
+            TwistError::CommitHashMismatch
         );
         
         Ok(())
@@ -169,6 +175,13 @@ pub enum TransactionType {
     Other,
 }
 
+/// Action bound into a commitment, so the reveal cannot change what is executed
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub enum CommittedAction {
+    Swap { a_to_b: bool, min_amount_out: u64 },
+    TreasuryWithdrawal { recipient: Pubkey },
+}
+
 /// Commit-reveal state for preventing front-running
 #[account]
 pub struct CommitRevealState {
@@ -196,6 +209,11 @@ impl CommitRevealState {
         !self.cancelled && 
         current_timestamp >= self.commit_timestamp + min_delay
     }
+    
+    /// The reveal window opens after `min_delay` and stays open for `window` seconds
+    pub fn is_expired(&self, current_timestamp: i64, min_delay: i64, window: i64) -> bool {
+        current_timestamp > self.commit_timestamp + min_delay + window
+    }
 }
 
 /// Single-block transaction restriction
@@ -264,4 +282,55 @@ impl Default for PriorityFeeConfig {
             whale_threshold: 100_000 * 1_000_000, // $100k
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dynamic_fee_follows_curve_and_live_baseline() {
+        let curve = DynamicFeeCurve::default();
+        
+        // $60k trade: 1.25x of 40 bps, calm market
+        assert_eq!(MEVProtection::calculate_dynamic_fee(&curve, 40, 60_000 * 1_000_000, 0, 0, 0).unwrap(), 50);
+        
+        // Congestion is measured against the hourly average, and needs one
+        assert_eq!(MEVProtection::calculate_dynamic_fee(&curve, 40, 1_000, 600, 100, 0).unwrap(), 140);
+        assert_eq!(MEVProtection::calculate_dynamic_fee(&curve, 40, 1_000, 600, 0, 0).unwrap(), 40);
+        
+        // Volatility surcharge and the governable cap
+        let capped = DynamicFeeCurve { max_fee_bps: 60, ..curve };
+        assert_eq!(MEVProtection::calculate_dynamic_fee(&capped, 40, 1_000, 0, 0, 1500).unwrap(), 60);
+    }
+
+    #[test]
+    fn test_commit_binds_action_and_reveal_window() {
+        let user = Pubkey::new_unique();
+        let salt = [7u8; 32];
+        let action = CommittedAction::Swap { a_to_b: true, min_amount_out: 900 };
+        let commit_hash = MEVProtection::generate_commit_hash(&user, 1_000, 1, &salt, &action);
+        
+        assert!(MEVProtection::verify_commit_reveal(&commit_hash, &user, 1_000, 1, &salt, &action).is_ok());
+        
+        // Flipping the direction or lowering the minimum output breaks the commitment
+        let flipped = CommittedAction::Swap { a_to_b: false, min_amount_out: 900 };
+        let looser = CommittedAction::Swap { a_to_b: true, min_amount_out: 0 };
+        assert!(MEVProtection::verify_commit_reveal(&commit_hash, &user, 1_000, 1, &salt, &flipped).is_err());
+        assert!(MEVProtection::verify_commit_reveal(&commit_hash, &user, 1_000, 1, &salt, &looser).is_err());
+        
+        let commit = CommitRevealState {
+            user,
+            commit_hash,
+            commit_timestamp: 1_000,
+            reveal_timestamp: 0,
+            revealed: false,
+            cancelled: false,
+            bump: 0,
+        };
+        assert!(!commit.can_reveal(1_029, 30));
+        assert!(commit.can_reveal(1_030, 30));
+        assert!(!commit.is_expired(1_330, 30, 300));
+        assert!(commit.is_expired(1_331, 30, 300));
+    }
+}
@@ -253,6 +253,7 @@ export interface CircuitBreakerState {
   supplyChangeThresholdBps: number;
   oracleDivergenceThresholdBps: number;
   liquidityDrainThresholdBps: number;
+  reserveDivergenceThresholdBps: number;
   
   // Cooldowns
   lowSeverityCooldown: number;
@@ -278,6 +279,7 @@ export function parseCircuitBreakerState(raw: any): CircuitBreakerState {
     supplyChangeThresholdBps: raw.supplyChangeThresholdBps.toNumber(),
     oracleDivergenceThresholdBps: raw.oracleDivergenceThresholdBps.toNumber(),
     liquidityDrainThresholdBps: raw.liquidityDrainThresholdBps.toNumber(),
+    reserveDivergenceThresholdBps: raw.reserveDivergenceThresholdBps.toNumber(),
     
     lowSeverityCooldown: raw.lowSeverityCooldown.toNumber(),
     mediumSeverityCooldown: raw.mediumSeverityCooldown.toNumber(),